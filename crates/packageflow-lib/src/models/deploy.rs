@@ -305,6 +305,9 @@ pub struct Deployment {
     /// Unique preview URL for this specific deploy
     #[serde(default)]
     pub preview_url: Option<String>,
+    /// Time Machine snapshot of the project when the deploy started
+    #[serde(default)]
+    pub snapshot_id: Option<String>,
 }
 
 impl Deployment {
@@ -325,6 +328,7 @@ impl Deployment {
             branch: None,
             site_name: None,
             preview_url: None,
+            snapshot_id: None,
         }
     }
 }
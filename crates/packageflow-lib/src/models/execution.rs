@@ -3,6 +3,9 @@
 // Feature 013: Extended to support parent-child execution tracking
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::workflow::ConcurrencyPolicy;
 
 /// Execution status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,6 +16,8 @@ pub enum ExecutionStatus {
     Failed,
     Paused,
     Cancelled,
+    /// Waiting for a concurrency slot
+    Queued,
 }
 
 impl Default for ExecutionStatus {
@@ -38,6 +43,20 @@ impl Default for NodeStatus {
     }
 }
 
+/// Why a node was skipped
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipReason {
+    /// A condition node did not select the node's branch
+    Condition,
+    /// A dependency failed
+    DependencyFailed,
+    /// The execution was cancelled before the node ran
+    Cancelled,
+    /// Left out of a resumed execution without a result to reuse
+    NotInRun,
+}
+
 /// Maximum recursion depth for child workflow executions
 pub const MAX_EXECUTION_DEPTH: u32 = 5;
 
@@ -61,6 +80,33 @@ pub struct Execution {
     /// Recursion depth (0 = top level)
     #[serde(default)]
     pub depth: u32,
+    /// Resolved workflow parameters (defaults applied)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, String>,
+    /// Past execution this one re-runs part of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<ResumeOrigin>,
+    /// Commit checked out in the project when the execution started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// Revision of the workflow that was run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+    /// 1-based position in the run queue while the execution is queued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<usize>,
+}
+
+/// Link from a resumed execution to the execution it re-runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeOrigin {
+    pub execution_id: String,
+    /// First node that was run again
+    pub from_node_id: String,
+    /// Last node of a "run X..Y" range (None = everything after `from_node_id`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_node_id: Option<String>,
 }
 
 impl Execution {
@@ -75,6 +121,11 @@ impl Execution {
             parent_execution_id: None,
             parent_node_id: None,
             depth: 0,
+            parameters: HashMap::new(),
+            resumed_from: None,
+            git_commit: None,
+            revision: None,
+            queue_position: None,
         }
     }
 
@@ -96,6 +147,11 @@ impl Execution {
             parent_execution_id: Some(parent_execution_id),
             parent_node_id: Some(parent_node_id),
             depth,
+            parameters: HashMap::new(),
+            resumed_from: None,
+            git_commit: None,
+            revision: None,
+            queue_position: None,
         }
     }
 
@@ -129,6 +185,71 @@ impl Execution {
     }
 }
 
+// ============================================================================
+// Concurrency Control
+// ============================================================================
+
+/// `ActiveExecution::source` of executions started by the app
+pub const ACTIVE_EXECUTION_SOURCE_APP: &str = "app";
+/// `ActiveExecution::source` of executions started by the MCP server
+pub const ACTIVE_EXECUTION_SOURCE_MCP: &str = "mcp";
+
+/// Top-level workflow execution registered for concurrency control.
+/// Shared through the database so the app and MCP server see each other's runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveExecution {
+    pub execution_id: String,
+    pub workflow_id: String,
+    /// Process that owns the execution ("app" or "mcp")
+    pub source: String,
+    /// Policy the execution was admitted with
+    pub policy: ConcurrencyPolicy,
+    /// Waiting for a slot (false once started)
+    pub queued: bool,
+    /// Queue order (RFC 3339)
+    pub registered_at: String,
+    /// PID of the registering process; its rows are dropped once it has exited
+    #[serde(default)]
+    pub owner_pid: Option<u32>,
+}
+
+// ============================================================================
+// Artifacts
+// ============================================================================
+
+/// File a script node produced, copied into the artifact store of its execution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionArtifact {
+    pub execution_id: String,
+    pub workflow_id: String,
+    pub node_id: String,
+    pub node_name: String,
+    /// Matrix cell the file was collected from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell_index: Option<usize>,
+    /// Path relative to the node's working directory (`/`-separated)
+    pub path: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    /// Stored copy
+    pub file_path: String,
+    pub collected_at: String,
+}
+
+/// Artifact storage used by one execution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionArtifactUsage {
+    pub execution_id: String,
+    pub workflow_id: String,
+    /// When the first artifact was collected
+    pub collected_at: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
 // ============================================================================
 // Child Execution Result (Feature 013)
 // ============================================================================
@@ -158,9 +279,16 @@ pub struct ChildExecutionResult {
 pub struct NodeResult {
     pub node_id: String,
     pub status: NodeStatus,
+    /// Captured stdout, available to later nodes as `{{steps.<id>.output}}`
     pub output: String,
+    /// `output` stopped at `STEP_OUTPUT_MAX_BYTES`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub output_truncated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Why the node was skipped (status `skipped` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<SkipReason>,
     pub started_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finished_at: Option<String>,
@@ -170,6 +298,85 @@ pub struct NodeResult {
     /// Child execution result (for trigger-workflow nodes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub child_execution_result: Option<ChildExecutionResult>,
+    /// Every attempt of the node, oldest first (only recorded for nodes with a retry policy)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<NodeAttempt>,
+    /// Key/values exported with `::set-output name=<key>::<value>` lines
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub outputs: HashMap<String, String>,
+    /// Per-cell results of a matrix node, in cell order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matrix_cells: Vec<MatrixCellResult>,
+    /// Approval request and decision of an approval node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<NodeApproval>,
+}
+
+/// Decision state of an approval node
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    TimedOut,
+    Cancelled,
+}
+
+/// Approval request of an approval node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeApproval {
+    /// Pending action request answered with `respond_to_action_request`
+    pub request_id: String,
+    pub status: ApprovalStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Comment given with the decision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub requested_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<String>,
+}
+
+/// Result of one cell of a matrix node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixCellResult {
+    pub index: usize,
+    /// Display label, e.g. `node=20, package=@acme/core`
+    pub label: String,
+    /// Dimension -> value of this cell
+    pub values: BTreeMap<String, String>,
+    pub status: NodeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// Attempts of the cell (only recorded for nodes with a retry policy)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<NodeAttempt>,
+}
+
+/// A single attempt of a node run with a retry policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeAttempt {
+    /// Attempt number (1 = first run)
+    pub attempt: u32,
+    pub status: NodeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
 }
 
 impl NodeResult {
@@ -178,11 +385,17 @@ impl NodeResult {
             node_id,
             status: NodeStatus::Pending,
             output: String::new(),
+            output_truncated: false,
             error_message: None,
+            skip_reason: None,
             started_at: chrono::Utc::now().to_rfc3339(),
             finished_at: None,
             exit_code: None,
             child_execution_result: None,
+            attempts: Vec::new(),
+            outputs: HashMap::new(),
+            matrix_cells: Vec::new(),
+            approval: None,
         }
     }
 
@@ -215,8 +428,9 @@ impl NodeResult {
         self.finished_at = Some(chrono::Utc::now().to_rfc3339());
     }
 
-    pub fn skip(&mut self) {
+    pub fn skip(&mut self, reason: SkipReason) {
         self.status = NodeStatus::Skipped;
+        self.skip_reason = Some(reason);
         self.finished_at = Some(chrono::Utc::now().to_rfc3339());
     }
 
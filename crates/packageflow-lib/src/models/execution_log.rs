@@ -0,0 +1,120 @@
+// Execution Log Models
+// Persisted stdout/stderr of workflow and script executions
+
+use serde::{Deserialize, Serialize};
+
+/// What produced a persisted log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionLogKind {
+    Workflow,
+    Script,
+}
+
+impl ExecutionLogKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionLogKind::Workflow => "workflow",
+            ExecutionLogKind::Script => "script",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "workflow" => Some(ExecutionLogKind::Workflow),
+            "script" => Some(ExecutionLogKind::Script),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata of the persisted log of one execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionLogInfo {
+    pub execution_id: String,
+    pub kind: ExecutionLogKind,
+    /// Workflow of a workflow execution
+    pub workflow_id: Option<String>,
+    /// Workflow name or script command
+    pub name: String,
+    pub project_path: Option<String>,
+    /// "running" until the execution finishes, then its final status ("interrupted" if the
+    /// app quit mid-run)
+    pub status: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub line_count: u64,
+    /// Uncompressed size of the output
+    pub size_bytes: u64,
+    /// Size of the compressed log file
+    pub compressed_bytes: u64,
+    pub file_path: String,
+}
+
+impl ExecutionLogInfo {
+    /// Log of an execution starting now; the file path is set when the log is created
+    pub fn new(execution_id: String, kind: ExecutionLogKind, name: String) -> Self {
+        Self {
+            execution_id,
+            kind,
+            workflow_id: None,
+            name,
+            project_path: None,
+            status: "running".to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            line_count: 0,
+            size_bytes: 0,
+            compressed_bytes: 0,
+            file_path: String::new(),
+        }
+    }
+}
+
+/// One line of a persisted log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionLogLine {
+    /// Position in the log, starting at 0
+    pub line_no: u64,
+    /// Workflow node that printed the line (None for scripts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// "stdout" | "stderr"
+    pub stream: String,
+    pub timestamp: String,
+    pub content: String,
+}
+
+/// A window of lines read from a persisted log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionLogPage {
+    pub info: ExecutionLogInfo,
+    pub lines: Vec<ExecutionLogLine>,
+    /// Number of lines in the log file
+    pub total_lines: u64,
+}
+
+/// A log line matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionLogSearchHit {
+    pub log: ExecutionLogInfo,
+    pub line: ExecutionLogLine,
+}
+
+/// Which persisted logs to keep
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRetentionPolicy {
+    /// Logs kept per workflow (scripts: per project and command), newest first
+    pub keep_per_workflow: usize,
+    /// Delete logs older than this many days (0 = no age limit)
+    pub retention_days: u32,
+    /// Delete the oldest logs once all log files exceed this size (0 = no limit)
+    pub max_total_bytes: u64,
+}
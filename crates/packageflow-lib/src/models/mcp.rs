@@ -117,6 +117,13 @@ pub static MCP_ALL_TOOLS: &[MCPToolDefinition] = &[
         permission_category: MCPToolPermissionCategory::Read,
         applicable_permissions: &["read"],
     },
+    MCPToolDefinition {
+        name: "plan_workflow",
+        description: "Show what a workflow run would do without running it",
+        display_category: "Workflows",
+        permission_category: MCPToolPermissionCategory::Read,
+        applicable_permissions: &["read"],
+    },
     MCPToolDefinition {
         name: "create_workflow",
         description: "Create a new workflow",
@@ -387,6 +394,13 @@ pub static MCP_ALL_TOOLS: &[MCPToolDefinition] = &[
         permission_category: MCPToolPermissionCategory::Read,
         applicable_permissions: &["read"],
     },
+    MCPToolDefinition {
+        name: "why_installed",
+        description: "Explain why a package is installed in a snapshot",
+        display_category: "Time Machine",
+        permission_category: MCPToolPermissionCategory::Read,
+        applicable_permissions: &["read"],
+    },
     MCPToolDefinition {
         name: "search_snapshots",
         description: "Search snapshots by package name, version, or date range",
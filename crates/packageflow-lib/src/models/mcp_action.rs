@@ -4,6 +4,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::models::workflow::ScriptShell;
+
 // ============================================================================
 // Enums
 // ============================================================================
@@ -220,6 +222,9 @@ pub struct ScriptConfig {
     /// Whether to use Volta for Node.js version management
     #[serde(default)]
     pub use_volta: bool,
+    /// Shell to run the command line in (same rules as workflow script nodes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ScriptShell>,
 }
 
 fn default_script_timeout() -> u64 {
@@ -409,6 +414,9 @@ pub struct ActionExecutionResponse {
     pub message: Option<String>,
 }
 
+/// `source_client` of pending requests created by workflow approval nodes
+pub const WORKFLOW_APPROVAL_SOURCE: &str = "workflow-approval";
+
 /// Pending action request for user confirmation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,6 +8,7 @@ pub mod cli_tool;
 pub mod deploy;
 pub mod mcp_action;
 pub mod execution;
+pub mod execution_log;
 pub mod git;
 pub mod incoming_webhook;
 pub mod ipa;
@@ -22,15 +23,18 @@ pub mod toolchain;
 pub mod version;
 pub mod webhook;
 pub mod workflow;
+pub mod workflow_stats;
 pub mod worktree;
 pub mod worktree_sessions;
 
 // Re-export all models for convenience
 pub use apk::*;
 pub use execution::*;
+pub use execution_log::*;
 pub use ipa::*;
 pub use project::*;
 pub use workflow::*;
+pub use workflow_stats::*;
 pub use worktree::*;
 pub use worktree_sessions::*;
 // Re-export security types except PackageManager (already exported from project)
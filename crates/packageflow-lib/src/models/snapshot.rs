@@ -3,6 +3,7 @@
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Lockfile type for a project
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -36,13 +37,37 @@ impl LockfileType {
 
     /// Get the lockfile filename for this type
     pub fn lockfile_name(&self) -> &'static str {
+        self.lockfile_names()[0]
+    }
+
+    /// Lockfile filenames for this type, in order of preference.
+    /// Bun writes the text `bun.lock` since 1.2 and the binary `bun.lockb` before that.
+    pub fn lockfile_names(&self) -> &'static [&'static str] {
         match self {
-            Self::Npm => "package-lock.json",
-            Self::Pnpm => "pnpm-lock.yaml",
-            Self::Yarn => "yarn.lock",
-            Self::Bun => "bun.lockb",
+            Self::Npm => &["package-lock.json"],
+            Self::Pnpm => &["pnpm-lock.yaml"],
+            Self::Yarn => &["yarn.lock"],
+            Self::Bun => &["bun.lock", "bun.lockb"],
         }
     }
+
+    /// Find this type's lockfile in a project
+    pub fn find_lockfile(&self, project_path: &Path) -> Option<PathBuf> {
+        self.lockfile_names()
+            .iter()
+            .map(|name| project_path.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Detect the lockfile of a project (pnpm, npm, yarn, then bun)
+    pub fn detect(project_path: &Path) -> Option<(Self, PathBuf)> {
+        [Self::Pnpm, Self::Npm, Self::Yarn, Self::Bun]
+            .into_iter()
+            .find_map(|lockfile_type| {
+                let path = lockfile_type.find_lockfile(project_path)?;
+                Some((lockfile_type, path))
+            })
+    }
 }
 
 /// Snapshot capture status
@@ -79,6 +104,8 @@ impl SnapshotStatus {
 pub enum TriggerSource {
     LockfileChange,
     Manual,
+    /// Imported from a teammate's snapshot bundle
+    Import,
 }
 
 impl TriggerSource {
@@ -86,6 +113,7 @@ impl TriggerSource {
         match self {
             Self::LockfileChange => "lockfile_change",
             Self::Manual => "manual",
+            Self::Import => "import",
         }
     }
 
@@ -93,6 +121,7 @@ impl TriggerSource {
         match s {
             "lockfile_change" => Some(Self::LockfileChange),
             "manual" => Some(Self::Manual),
+            "import" => Some(Self::Import),
             _ => None,
         }
     }
@@ -120,6 +149,80 @@ pub struct SnapshotDependency {
     pub resolved_url: Option<String>,
 }
 
+/// How a package was depended on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    Prod,
+    Dev,
+    Optional,
+    Peer,
+}
+
+impl std::str::FromStr for DependencyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prod" => Ok(Self::Prod),
+            "dev" => Ok(Self::Dev),
+            "optional" => Ok(Self::Optional),
+            "peer" => Ok(Self::Peer),
+            _ => Err(format!("Unknown dependency kind: {}", s)),
+        }
+    }
+}
+
+impl DependencyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Prod => "prod",
+            Self::Dev => "dev",
+            Self::Optional => "optional",
+            Self::Peer => "peer",
+        }
+    }
+}
+
+/// A parent -> child edge of the snapshot's dependency graph.
+/// `parent_name` is `None` when the project's package.json declares the child.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDependencyEdge {
+    pub snapshot_id: String,
+    pub parent_name: Option<String>,
+    pub parent_version: Option<String>,
+    pub child_name: String,
+    pub child_version: String,
+    pub kind: DependencyKind,
+}
+
+/// One package on a dependency path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyPathStep {
+    pub name: String,
+    pub version: String,
+    /// How the previous step (or the project, for the first step) depends on this one
+    pub kind: DependencyKind,
+}
+
+/// Why a package is installed: every path from the project's direct dependencies to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallReason {
+    pub name: String,
+    pub version: String,
+    pub is_direct: bool,
+    pub has_postinstall: bool,
+    /// Direct dependencies that pull the package in
+    pub direct_dependencies: Vec<String>,
+    /// Paths starting at a direct dependency and ending at the package
+    pub paths: Vec<Vec<DependencyPathStep>>,
+    /// More paths exist than were returned
+    pub truncated: bool,
+}
+
 /// Execution snapshot - captures dependency state at a point in time
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -141,6 +244,9 @@ pub struct ExecutionSnapshot {
     pub compressed_size: Option<i64>,
     pub error_message: Option<String>,
     pub created_at: String,
+    /// Pinned snapshots are never removed by retention
+    #[serde(default)]
+    pub is_pinned: bool,
 }
 
 /// Snapshot list item (lightweight for list views)
@@ -156,6 +262,8 @@ pub struct SnapshotListItem {
     pub security_score: Option<i32>,
     pub postinstall_count: i32,
     pub created_at: String,
+    #[serde(default)]
+    pub is_pinned: bool,
 }
 
 /// Snapshot with full dependency list
@@ -229,6 +337,9 @@ pub struct DependencyChange {
     pub postinstall_changed: bool,
     pub old_postinstall: Option<String>,
     pub new_postinstall: Option<String>,
+    /// Changed direct dependencies whose subtree contains this transitive change
+    #[serde(default)]
+    pub introduced_by: Vec<String>,
 }
 
 /// Postinstall script change
@@ -308,6 +419,8 @@ pub struct LockfileState {
 pub struct TimeMachineSettings {
     pub auto_watch_enabled: bool,
     pub debounce_ms: i32,
+    #[serde(default)]
+    pub retention: SnapshotRetentionPolicy,
     pub updated_at: String,
 }
 
@@ -316,7 +429,89 @@ impl Default for TimeMachineSettings {
         Self {
             auto_watch_enabled: true,
             debounce_ms: 2000,
+            retention: SnapshotRetentionPolicy::default(),
             updated_at: Utc::now().to_rfc3339(),
         }
     }
 }
+
+/// Which snapshots of a project survive pruning. A snapshot is kept when any rule
+/// selects it; pinned snapshots, snapshots a successful deployment was built from and
+/// the latest snapshot are always kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRetentionPolicy {
+    /// Apply the policy after every capture
+    pub enabled: bool,
+    /// Keep the N most recent snapshots
+    pub keep_recent: u32,
+    /// Keep the newest snapshot of each of the last N days with snapshots
+    pub keep_daily: u32,
+    /// Keep the newest snapshot of each of the last N ISO weeks with snapshots
+    pub keep_weekly: u32,
+    /// Keep the newest snapshot of each of the last N months with snapshots
+    pub keep_monthly: u32,
+    /// Per-project disk quota; the oldest unprotected snapshots go first when exceeded
+    pub max_project_storage_mb: Option<u64>,
+}
+
+impl Default for SnapshotRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep_recent: 10,
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 6,
+            max_project_storage_mb: None,
+        }
+    }
+}
+
+/// A snapshot as seen by retention
+#[derive(Debug, Clone)]
+pub struct SnapshotRetentionCandidate {
+    pub id: String,
+    pub status: SnapshotStatus,
+    pub created_at: String,
+    pub lockfile_hash: Option<String>,
+    pub is_pinned: bool,
+    /// A successful deployment was built from this snapshot
+    pub is_deployed: bool,
+}
+
+/// Outcome of applying the retention policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRetentionResult {
+    pub deleted_snapshot_ids: Vec<String>,
+    pub kept_count: usize,
+    /// Bytes freed by deleted snapshots and deduplicated lockfiles
+    pub reclaimed_bytes: u64,
+    /// Lockfiles moved into the shared content-addressed store
+    pub deduplicated_lockfiles: usize,
+    /// Projects still above their quota once only protected snapshots remain
+    pub over_quota_projects: Vec<String>,
+}
+
+/// A snapshot written to a bundle file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotBundleExport {
+    pub snapshot_id: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// A snapshot bundle imported for replay against a local checkout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotBundleImport {
+    /// The new local snapshot, pointing at the importing project
+    pub snapshot: ExecutionSnapshot,
+    pub original_snapshot_id: String,
+    pub original_project_path: String,
+    pub exported_at: String,
+    pub dependency_count: usize,
+    pub insight_count: usize,
+}
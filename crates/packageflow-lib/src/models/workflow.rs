@@ -1,10 +1,12 @@
 // Workflow data models
 // Represents an automation workflow
 // Feature 013: Extended to support multiple node types (trigger-workflow)
+// Extended with condition nodes for branching on previous step outcomes
 
 use crate::models::incoming_webhook::IncomingWebhookConfig;
 use crate::models::webhook::WebhookConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Represents an automation workflow
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +32,27 @@ pub struct Workflow {
     /// Incoming webhook configuration for external triggers
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub incoming_webhook: Option<IncomingWebhookConfig>,
+    /// Maximum number of nodes executed concurrently (None = engine default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallelism: Option<u32>,
+    /// Declared inputs, referenced as `{{params.<name>}}` in node commands
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<WorkflowParameter>,
+    /// Environment variables for every script node; values may reference `{{secrets.<name>}}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Cron schedule run by the background scheduler
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<WorkflowSchedule>,
+    /// Run when files matching glob patterns change in the workflow's project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_trigger: Option<WorkflowFileTrigger>,
+    /// Run on git events (commit, checkout, push) in the workflow's project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_trigger: Option<WorkflowGitTrigger>,
+    /// What happens when the workflow is started while a run is active (None = allow)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<ConcurrencyPolicy>,
 }
 
 fn default_workflow_timestamp() -> String {
@@ -50,10 +73,220 @@ impl Workflow {
             last_executed_at: None,
             webhook: None,
             incoming_webhook: None,
+            max_parallelism: None,
+            parameters: Vec::new(),
+            env: HashMap::new(),
+            schedule: None,
+            file_trigger: None,
+            git_trigger: None,
+            concurrency: None,
+        }
+    }
+}
+
+/// Behavior when a workflow is started while another run of it is active
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConcurrencyPolicy {
+    /// Runs execute side by side
+    #[default]
+    Allow,
+    /// Wait until the active runs have finished
+    Queue,
+    /// Cancel the active runs and start immediately
+    CancelPrevious,
+    /// Refuse to start
+    Reject,
+}
+
+impl std::fmt::Display for ConcurrencyPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConcurrencyPolicy::Allow => write!(f, "allow"),
+            ConcurrencyPolicy::Queue => write!(f, "queue"),
+            ConcurrencyPolicy::CancelPrevious => write!(f, "cancel-previous"),
+            ConcurrencyPolicy::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConcurrencyPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow" => Ok(ConcurrencyPolicy::Allow),
+            "queue" => Ok(ConcurrencyPolicy::Queue),
+            "cancel-previous" => Ok(ConcurrencyPolicy::CancelPrevious),
+            "reject" => Ok(ConcurrencyPolicy::Reject),
+            _ => Err(format!("Unknown concurrency policy: {}", s)),
         }
     }
 }
 
+/// App-wide limits on workflow executions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowConcurrencySettings {
+    /// Executions that may run at once; further starts are queued (0 = unlimited)
+    #[serde(default)]
+    pub max_concurrent_executions: usize,
+}
+
+/// Type of a workflow parameter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    /// One of `options`
+    Choice,
+}
+
+/// Declared workflow input
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowParameter {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub param_type: ParameterType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when the caller does not provide one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Whether a value must be provided when there is no default
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values for `choice` parameters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+/// Stored secret as listed to the frontend (the value is never returned)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowSecretInfo {
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// What to do when a scheduled run was missed (app closed or machine asleep)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MissedRunPolicy {
+    /// Drop missed runs and wait for the next scheduled time
+    #[default]
+    Skip,
+    /// Run once on wake, however many runs were missed
+    RunOnce,
+}
+
+/// Cron schedule for a workflow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowSchedule {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 5-field cron expression in local time (e.g. `0 3 * * *`) or a macro such as `@daily`
+    pub cron: String,
+    #[serde(default)]
+    pub missed_run_policy: MissedRunPolicy,
+}
+
+/// Persisted scheduler state for a workflow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowScheduleState {
+    pub workflow_id: String,
+    /// Cron expression the next run was computed from
+    pub cron: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_execution_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub updated_at: String,
+}
+
+/// File-change trigger for a workflow (paths are relative to the project root)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileTrigger {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Glob patterns that start the workflow (e.g. `src/**/*.graphql`, `prisma/schema.prisma`)
+    pub patterns: Vec<String>,
+    /// Additional glob patterns to ignore
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Quiet period before a burst of changes starts the workflow
+    #[serde(default = "default_file_trigger_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Skip paths ignored by the project's root `.gitignore`
+    #[serde(default = "default_true")]
+    pub respect_gitignore: bool,
+}
+
+fn default_file_trigger_debounce_ms() -> u64 {
+    1000
+}
+
+/// Git event that can start a workflow (named after the git hook that reports it)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitTriggerEvent {
+    /// A commit was created
+    PostCommit,
+    /// A branch was checked out
+    PostCheckout,
+    /// Commits are about to be pushed. Pushes from the app wait for these workflows
+    /// and are aborted when one fails; pushes from a terminal are not blocked.
+    PrePush,
+}
+
+impl GitTriggerEvent {
+    pub const ALL: [GitTriggerEvent; 3] = [
+        GitTriggerEvent::PostCommit,
+        GitTriggerEvent::PostCheckout,
+        GitTriggerEvent::PrePush,
+    ];
+
+    /// Name of the git hook reporting this event
+    pub fn hook_name(&self) -> &'static str {
+        match self {
+            GitTriggerEvent::PostCommit => "post-commit",
+            GitTriggerEvent::PostCheckout => "post-checkout",
+            GitTriggerEvent::PrePush => "pre-push",
+        }
+    }
+
+    pub fn from_hook_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.hook_name() == name)
+    }
+}
+
+/// Git-event trigger for a workflow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowGitTrigger {
+    #[serde(default)]
+    pub enabled: bool,
+    pub events: Vec<GitTriggerEvent>,
+    /// Branch glob patterns (e.g. `main`, `release/*`); empty matches any branch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+    /// Only run when one of these files changed (e.g. `pnpm-lock.yaml` on checkout)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+}
+
 // ============================================================================
 // Node Types (Feature 013: Workflow Trigger Workflow)
 // ============================================================================
@@ -64,6 +297,9 @@ impl Workflow {
 pub enum NodeType {
     Script,
     TriggerWorkflow,
+    Condition,
+    Approval,
+    Http,
 }
 
 impl Default for NodeType {
@@ -95,6 +331,23 @@ pub struct ScriptNodeConfig {
     pub cwd: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    /// Environment variables for this node (override workflow `env`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Retry policy for flaky steps (retries, retryDelayMs, retryBackoff, retryOn)
+    #[serde(flatten)]
+    pub retry: RetryPolicy,
+    /// Run the command once per matrix combination
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<NodeMatrix>,
+    /// Shell the command runs in. Unset runs it through `sh` (`cmd` on Windows) only
+    /// when it uses shell syntax, and executes it directly otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ScriptShell>,
+    /// Glob patterns (relative to the working directory, e.g. `dist/**`) of files kept
+    /// in the execution's artifact store after the node succeeds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
 }
 
 impl Default for ScriptNodeConfig {
@@ -103,10 +356,101 @@ impl Default for ScriptNodeConfig {
             command: String::new(),
             cwd: None,
             timeout: None,
+            env: HashMap::new(),
+            retry: RetryPolicy::default(),
+            matrix: None,
+            shell: None,
+            artifacts: Vec::new(),
+        }
+    }
+}
+
+/// Shell a script command runs in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptShell {
+    Sh,
+    Bash,
+    Zsh,
+    /// Execute the program directly; quotes are parsed but nothing is expanded
+    None,
+}
+
+impl ScriptShell {
+    /// Shell executable, or `None` for direct execution
+    pub fn program(&self) -> Option<&'static str> {
+        match self {
+            ScriptShell::Sh => Some("sh"),
+            ScriptShell::Bash => Some("bash"),
+            ScriptShell::Zsh => Some("zsh"),
+            ScriptShell::None => None,
         }
     }
 }
 
+/// Matrix a script node fans out over. Every combination of the dimensions is one
+/// cell; values are available as `{{matrix.<dimension>}}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMatrix {
+    /// Node.js versions (e.g. `18`, `20.11`) pinned with `volta run --node` (`{{matrix.node}}`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub node_versions: Vec<String>,
+    /// Glob patterns of workspace package names (e.g. `@acme/*`); each cell runs in
+    /// the package directory (`{{matrix.package}}`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<String>,
+    /// Additional dimensions: name -> values
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, Vec<String>>,
+    /// Cells run at the same time (None = one at a time)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<u32>,
+    /// Keep running the remaining cells after a cell fails
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+/// Retry policy of a script node
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt (0 = never retry)
+    #[serde(default)]
+    pub retries: u32,
+    /// Delay before the first retry (None = engine default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_delay_ms: Option<u64>,
+    /// How the delay grows between retries (None = fixed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_backoff: Option<RetryBackoff>,
+    /// Only retry failures matching one of these (empty = retry any failure)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retry_on: Vec<RetryOn>,
+}
+
+/// Delay growth between retries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RetryBackoff {
+    /// Same delay before every retry
+    Fixed,
+    /// Delay doubles after every retry
+    Exponential,
+}
+
+/// Failure that should be retried
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RetryOn {
+    /// Process exited with one of these codes
+    #[serde(rename_all = "camelCase")]
+    ExitCode { codes: Vec<i32> },
+    /// A line of output of the failed attempt matches a regex (e.g. "ECONNRESET|ETIMEDOUT")
+    #[serde(rename_all = "camelCase")]
+    OutputMatches { pattern: String },
+}
+
 /// Configuration for a trigger-workflow node
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -135,6 +479,136 @@ impl Default for TriggerWorkflowConfig {
     }
 }
 
+/// Configuration for a condition node
+/// Branches are evaluated in order and the first branch whose rules all match is taken.
+/// Targets of the other branches (and nodes that only depend on them) are skipped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionNodeConfig {
+    #[serde(default)]
+    pub branches: Vec<ConditionBranch>,
+    /// Node IDs to run when no branch matches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub otherwise: Vec<String>,
+}
+
+impl ConditionNodeConfig {
+    /// All node IDs this condition can route to (deduplicated, in declaration order)
+    pub fn all_targets(&self) -> Vec<String> {
+        let mut targets: Vec<String> = Vec::new();
+        for id in self
+            .branches
+            .iter()
+            .flat_map(|b| b.targets.iter())
+            .chain(self.otherwise.iter())
+        {
+            if !targets.contains(id) {
+                targets.push(id.clone());
+            }
+        }
+        targets
+    }
+}
+
+/// A single branch of a condition node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionBranch {
+    /// Display label (e.g. "main branch")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Rules that must all match; an empty list always matches
+    #[serde(default)]
+    pub rules: Vec<ConditionRule>,
+    /// Node IDs to run when this branch is taken
+    #[serde(default)]
+    pub targets: Vec<String>,
+}
+
+/// A single check, optionally negated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionRule {
+    #[serde(flatten)]
+    pub check: ConditionCheck,
+    /// Invert the result of the check
+    #[serde(default)]
+    pub negate: bool,
+}
+
+/// What a condition rule inspects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ConditionCheck {
+    /// Exit code of a previous node equals the expected value
+    #[serde(rename_all = "camelCase")]
+    ExitCode {
+        node_id: String,
+        #[serde(default)]
+        equals: i32,
+    },
+    /// Captured output (stdout + stderr) of a previous node matches a regex
+    #[serde(rename_all = "camelCase")]
+    OutputMatches { node_id: String, pattern: String },
+    /// Environment variable is set (and equals the value, if given)
+    #[serde(rename_all = "camelCase")]
+    EnvVar {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        equals: Option<String>,
+    },
+    /// Current git branch of the project matches a glob pattern (e.g. "main", "release/*")
+    #[serde(rename_all = "camelCase")]
+    GitBranch { pattern: String },
+}
+
+/// Configuration for an approval (manual gate) node
+/// Execution waits at the node until someone approves or rejects it; rejecting fails the node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalNodeConfig {
+    /// Shown in the approval request (e.g. "Publish 2.1.0 to npm?")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Reject automatically after this many minutes (None = wait indefinitely)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_minutes: Option<u32>,
+}
+
+/// Configuration for an HTTP request node
+/// URL, header values, body and signing secret support `{{params.*}}`, `{{steps.*}}` and
+/// `{{secrets.*}}` placeholders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpNodeConfig {
+    /// GET, POST, PUT, PATCH, DELETE or HEAD (default: GET)
+    #[serde(default = "default_http_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Request body template; sent as JSON unless a Content-Type header is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Accepted response status codes (empty = any 2xx)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_status: Vec<u16>,
+    /// Step outputs read from the JSON response: output name -> JSON path (e.g. `$.data.id`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extract: HashMap<String, String>,
+    /// Sign the body with HMAC-SHA256 (`X-Webhook-Signature` header); must be a
+    /// `{{secrets.<name>}}` reference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
+    /// Request timeout (default: 30 seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+fn default_http_method() -> String {
+    "GET".to_string()
+}
+
 /// Node configuration (Tagged Union)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -143,6 +617,12 @@ pub enum NodeConfig {
     Script(ScriptNodeConfig),
     #[serde(rename = "trigger-workflow")]
     TriggerWorkflow(TriggerWorkflowConfig),
+    #[serde(rename = "condition")]
+    Condition(ConditionNodeConfig),
+    #[serde(rename = "approval")]
+    Approval(ApprovalNodeConfig),
+    #[serde(rename = "http")]
+    Http(HttpNodeConfig),
 }
 
 impl Default for NodeConfig {
@@ -165,6 +645,10 @@ pub struct WorkflowNode {
     pub order: i32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub position: Option<NodePosition>,
+    /// IDs of nodes that must complete before this node runs.
+    /// When no node in a workflow declares dependencies, nodes run sequentially by `order`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
 }
 
 fn default_node_type() -> String {
@@ -186,6 +670,7 @@ impl WorkflowNode {
             }),
             order: 0,
             position: None,
+            depends_on: Vec::new(),
         }
     }
 
@@ -202,6 +687,46 @@ impl WorkflowNode {
             }),
             order: 0,
             position: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Create a new condition node
+    pub fn new_condition(id: String, name: String, config: ConditionNodeConfig) -> Self {
+        Self {
+            id,
+            node_type: String::from("condition"),
+            name,
+            config: serde_json::to_value(config).unwrap_or_else(|_| default_node_config()),
+            order: 0,
+            position: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Create a new approval node
+    pub fn new_approval(id: String, name: String, config: ApprovalNodeConfig) -> Self {
+        Self {
+            id,
+            node_type: String::from("approval"),
+            name,
+            config: serde_json::to_value(config).unwrap_or_else(|_| default_node_config()),
+            order: 0,
+            position: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Create a new HTTP request node
+    pub fn new_http(id: String, name: String, config: HttpNodeConfig) -> Self {
+        Self {
+            id,
+            node_type: String::from("http"),
+            name,
+            config: serde_json::to_value(config).unwrap_or_else(|_| default_node_config()),
+            order: 0,
+            position: None,
+            depends_on: Vec::new(),
         }
     }
 
@@ -215,6 +740,21 @@ impl WorkflowNode {
         self.node_type == "trigger-workflow"
     }
 
+    /// Check if this is a condition node
+    pub fn is_condition(&self) -> bool {
+        self.node_type == "condition"
+    }
+
+    /// Check if this is an approval node
+    pub fn is_approval(&self) -> bool {
+        self.node_type == "approval"
+    }
+
+    /// Check if this is an HTTP request node
+    pub fn is_http(&self) -> bool {
+        self.node_type == "http"
+    }
+
     /// Get script config (if this is a script node)
     pub fn get_script_config(&self) -> Option<ScriptNodeConfig> {
         if self.is_script() {
@@ -232,6 +772,33 @@ impl WorkflowNode {
             None
         }
     }
+
+    /// Get condition config (if this is a condition node)
+    pub fn get_condition_config(&self) -> Option<ConditionNodeConfig> {
+        if self.is_condition() {
+            serde_json::from_value(self.config.clone()).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Get approval config (if this is an approval node)
+    pub fn get_approval_config(&self) -> Option<ApprovalNodeConfig> {
+        if self.is_approval() {
+            serde_json::from_value(self.config.clone()).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Get HTTP request config (if this is an HTTP node)
+    pub fn get_http_config(&self) -> Option<HttpNodeConfig> {
+        if self.is_http() {
+            serde_json::from_value(self.config.clone()).ok()
+        } else {
+            None
+        }
+    }
 }
 
 /// Position of a node on the canvas
@@ -277,3 +844,330 @@ pub struct AvailableWorkflow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cycle_warning: Option<CycleDetectionResult>,
 }
+
+// ============================================================================
+// Workflow Files (.packageflow/workflows/*.yaml)
+// ============================================================================
+
+/// Current version of the workflow file format
+pub const WORKFLOW_FILE_VERSION: u32 = 1;
+
+/// Directory (relative to the project root) holding committed workflow files
+pub const WORKFLOW_FILES_DIR: &str = ".packageflow/workflows";
+
+/// Workflow definition as committed to a repository.
+/// Machine-local state (project, timestamps, webhooks) is not part of the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFile {
+    /// File format version (`WORKFLOW_FILE_VERSION`)
+    pub version: u32,
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallelism: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<WorkflowParameter>,
+    /// Sorted so exported files are stable
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<WorkflowSchedule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_trigger: Option<WorkflowFileTrigger>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_trigger: Option<WorkflowGitTrigger>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<ConcurrencyPolicy>,
+    #[serde(default)]
+    pub nodes: Vec<WorkflowNode>,
+}
+
+/// Serialization format of a workflow file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowFileFormat {
+    #[default]
+    Yaml,
+    Json,
+}
+
+impl WorkflowFileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            WorkflowFileFormat::Yaml => "yaml",
+            WorkflowFileFormat::Json => "json",
+        }
+    }
+
+    /// Format of a file by its extension (`.yaml`, `.yml` or `.json`)
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "yaml" | "yml" => Some(WorkflowFileFormat::Yaml),
+            "json" => Some(WorkflowFileFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Problem found while reading a workflow file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileError {
+    /// Index of the offending node in `nodes` (None for workflow-level errors)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_index: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for WorkflowFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.node_index, &self.node_id) {
+            (Some(index), Some(id)) => write!(f, "nodes[{}] ({}): {}", index, id, self.message),
+            (Some(index), None) => write!(f, "nodes[{}]: {}", index, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// What importing a workflow file would change in the stored workflow
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileDiff {
+    /// Changed workflow-level fields (camelCase names, e.g. `env`)
+    pub changed_fields: Vec<String>,
+    /// Node IDs only in the file
+    pub added_nodes: Vec<String>,
+    /// Node IDs only in the stored workflow
+    pub removed_nodes: Vec<String>,
+    /// Node IDs whose definition differs
+    pub modified_nodes: Vec<String>,
+}
+
+impl WorkflowFileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields.is_empty()
+            && self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.modified_nodes.is_empty()
+    }
+}
+
+/// Sync state of a workflow file against the database
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkflowFileState {
+    /// File defines a workflow not stored yet
+    New,
+    /// File differs from the stored workflow
+    Modified,
+    Unchanged,
+    /// File could not be parsed or validated
+    Invalid,
+    /// Stored project workflow without a file
+    NotExported,
+}
+
+/// Workflow file status for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileStatus {
+    /// Path relative to the project root (None for `NotExported`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub state: WorkflowFileState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<WorkflowFileDiff>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<WorkflowFileError>,
+}
+
+// ============================================================================
+// Execution Plans (dry run)
+// ============================================================================
+
+/// What a workflow run would do, resolved without running anything
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowPlan {
+    pub workflow_id: String,
+    pub workflow_name: String,
+    /// Executor the plan was resolved for (`app` or `mcp`)
+    pub executor: String,
+    /// Working directory of nodes without a `cwd` (the project path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_cwd: Option<String>,
+    /// Parameter values with defaults applied
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, String>,
+    /// Nodes in the order they would start
+    pub nodes: Vec<NodePlan>,
+}
+
+impl WorkflowPlan {
+    /// Whether any node (including child workflows) would fail before running
+    pub fn has_errors(&self) -> bool {
+        self.nodes.iter().any(|node| {
+            node.error.is_some()
+                || node
+                    .child_workflow
+                    .as_ref()
+                    .is_some_and(|child| child.has_errors())
+        })
+    }
+}
+
+/// Planned node of a workflow run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodePlan {
+    pub node_id: String,
+    pub node_name: String,
+    pub node_type: String,
+    /// Nodes that must finish first (linear workflows wait for the previous node)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Processes a script node would spawn: one, or one per matrix cell
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invocations: Vec<PlannedInvocation>,
+    /// Plan of the workflow a trigger-workflow node would start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub child_workflow: Option<Box<WorkflowPlan>>,
+    /// What else the node does (condition branches, approval gate, HTTP request, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Why the executor would skip the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+    /// Why the node would fail before running anything
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Process a script node (or one matrix cell) would spawn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedInvocation {
+    /// Matrix cell label, e.g. `node=20, package=@acme/core`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Variables set on top of the inherited environment; secret values are masked
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Command line with placeholders substituted; `{{steps.*}}` are only known at run time
+    pub command: String,
+    /// Program and arguments after shell selection and Volta/Corepack wrapping
+    pub program: String,
+    pub args: Vec<String>,
+    pub via_shell: bool,
+    /// Set when `rm` is intercepted: paths moved to the Trash instead of running the command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_paths: Option<Vec<String>>,
+}
+
+// ============================================================================
+// Workflow Revisions
+// ============================================================================
+
+/// What saved a workflow revision
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RevisionSource {
+    /// Workflow editor, workflow import or rollback in the app
+    Ui,
+    /// MCP server tool call
+    Mcp,
+    /// AI assistant tool call
+    AiAssistant,
+    /// Workflow file in the project (`.packageflow/workflows`)
+    File,
+    /// Saved before revision history was recorded
+    Unknown,
+}
+
+impl std::fmt::Display for RevisionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RevisionSource::Ui => write!(f, "ui"),
+            RevisionSource::Mcp => write!(f, "mcp"),
+            RevisionSource::AiAssistant => write!(f, "ai-assistant"),
+            RevisionSource::File => write!(f, "file"),
+            RevisionSource::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl std::str::FromStr for RevisionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ui" => Ok(RevisionSource::Ui),
+            "mcp" => Ok(RevisionSource::Mcp),
+            "ai-assistant" => Ok(RevisionSource::AiAssistant),
+            "file" => Ok(RevisionSource::File),
+            "unknown" => Ok(RevisionSource::Unknown),
+            _ => Err(format!("Unknown revision source: {}", s)),
+        }
+    }
+}
+
+/// Immutable copy of a workflow as it was saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRevision {
+    pub workflow_id: String,
+    /// 1-based, increasing with every saved change
+    pub revision: u32,
+    pub source: RevisionSource,
+    /// What changed it, e.g. the tool name or `Rolled back to revision 3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub created_at: String,
+    pub workflow: Workflow,
+}
+
+/// Changed value of a workflow or node field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFieldChange {
+    /// Field name, e.g. `name`, `schedule` or `config.command`
+    pub field: String,
+    /// `null` when the field was not set
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// Node present in both revisions with changed fields
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowNodeChange {
+    pub node_id: String,
+    /// Name in the newer revision
+    pub node_name: String,
+    pub changes: Vec<WorkflowFieldChange>,
+}
+
+/// Differences between two revisions of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRevisionDiff {
+    pub workflow_id: String,
+    pub from_revision: u32,
+    pub to_revision: u32,
+    /// Changed workflow-level fields (everything except nodes)
+    pub changes: Vec<WorkflowFieldChange>,
+    pub added_nodes: Vec<WorkflowNode>,
+    pub removed_nodes: Vec<WorkflowNode>,
+    pub changed_nodes: Vec<WorkflowNodeChange>,
+}
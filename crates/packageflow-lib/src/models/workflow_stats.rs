@@ -0,0 +1,76 @@
+// Workflow Statistics Models
+// Success rates, node timings, trends and flaky nodes aggregated over execution history
+
+use serde::{Deserialize, Serialize};
+
+/// Timing and reliability statistics of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStats {
+    pub workflow_id: String,
+    /// Executions the statistics are computed from
+    pub run_count: usize,
+    pub completed_count: usize,
+    pub failed_count: usize,
+    pub cancelled_count: usize,
+    /// Completed / (completed + failed); None without finished runs
+    pub success_rate: Option<f64>,
+    /// Duration percentiles of completed runs
+    pub p50_duration_ms: Option<u64>,
+    pub p95_duration_ms: Option<u64>,
+    /// Per-node timings, slowest median first
+    pub nodes: Vec<NodeTimingStats>,
+    /// One point per day with runs, oldest first
+    pub timeline: Vec<StatsTimelinePoint>,
+    /// Nodes that both passed and failed on the same commit
+    pub flaky_nodes: Vec<FlakyNode>,
+}
+
+/// Timing statistics of one node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTimingStats {
+    pub node_id: String,
+    pub node_name: String,
+    /// Runs that completed or failed
+    pub run_count: usize,
+    pub failed_count: usize,
+    pub success_rate: Option<f64>,
+    /// Duration percentiles of completed runs
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+    /// Median of the latest runs and of the runs before them
+    pub recent_p50_ms: Option<u64>,
+    pub previous_p50_ms: Option<u64>,
+    /// Recent median is well above the previous one (e.g. the build doubled in time)
+    pub slowed_down: bool,
+}
+
+/// Runs of one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsTimelinePoint {
+    /// YYYY-MM-DD (UTC)
+    pub date: String,
+    pub run_count: usize,
+    pub success_rate: Option<f64>,
+    pub p50_duration_ms: Option<u64>,
+    /// Node with the highest median duration that day
+    pub slowest_node_id: Option<String>,
+    pub slowest_node_p50_ms: Option<u64>,
+}
+
+/// A node that alternated between passing and failing on one commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlakyNode {
+    pub node_id: String,
+    pub node_name: String,
+    pub git_commit: String,
+    pub passed_count: usize,
+    pub failed_count: usize,
+    /// Status changes between consecutive runs on the commit
+    pub flip_count: usize,
+    pub last_run_at: String,
+}
@@ -0,0 +1,227 @@
+// Active Execution Repository
+// Handles database operations for the run queue shared by the app and the MCP server
+
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection, TransactionBehavior};
+
+use crate::models::ActiveExecution;
+use crate::services::workflow::is_process_alive;
+use crate::utils::database::Database;
+
+/// Repository for running and queued top-level executions
+pub struct ActiveExecutionRepository {
+    db: Database,
+}
+
+impl ActiveExecutionRepository {
+    /// Create a new ActiveExecutionRepository
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// List all active executions in queue order
+    pub fn list(&self) -> Result<Vec<ActiveExecution>, String> {
+        self.db.with_connection(list_active)
+    }
+
+    /// Register an execution (running or queued)
+    pub fn register(&self, execution: &ActiveExecution) -> Result<(), String> {
+        self.db
+            .with_connection(|conn| insert_active(conn, execution))
+    }
+
+    /// Register an execution if `admit` accepts the current active executions, in one
+    /// write transaction so that concurrent registrations (app and MCP servers) see each
+    /// other. Executions of processes that have exited are removed first.
+    /// Returns the result of `admit`.
+    pub fn admit_and_register<T, F>(
+        &self,
+        execution: &ActiveExecution,
+        admit: F,
+    ) -> Result<T, String>
+    where
+        F: FnOnce(&[ActiveExecution]) -> Result<T, String>,
+    {
+        let mut conn = self.db.lock()?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        remove_dead_owners(&tx)?;
+        let admitted = admit(&list_active(&tx)?)?;
+        insert_active(&tx, execution)?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        Ok(admitted)
+    }
+
+    /// Mark a queued execution as started
+    pub fn set_started(&self, execution_id: &str) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                "UPDATE active_executions SET queued = 0 WHERE execution_id = ?1",
+                params![execution_id],
+            )
+            .map_err(|e| format!("Failed to update execution: {}", e))?;
+
+            Ok(())
+        })
+    }
+
+    /// Remove a finished or cancelled execution
+    pub fn remove(&self, execution_id: &str) -> Result<bool, String> {
+        self.db.with_connection(|conn| {
+            let rows_affected = conn
+                .execute(
+                    "DELETE FROM active_executions WHERE execution_id = ?1",
+                    params![execution_id],
+                )
+                .map_err(|e| format!("Failed to remove execution: {}", e))?;
+
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// Remove all executions of a source (stale rows after a restart)
+    pub fn clear_source(&self, source: &str) -> Result<usize, String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                "DELETE FROM active_executions WHERE source = ?1",
+                params![source],
+            )
+            .map_err(|e| format!("Failed to clear active executions: {}", e))
+        })
+    }
+
+    /// Remove executions whose owner process has exited (e.g. a killed MCP server)
+    pub fn remove_dead_owners(&self) -> Result<usize, String> {
+        self.db.with_connection(remove_dead_owners)
+    }
+}
+
+fn list_active(conn: &Connection) -> Result<Vec<ActiveExecution>, String> {
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT execution_id, workflow_id, source, policy, queued, registered_at, owner_pid
+            FROM active_executions
+            ORDER BY registered_at, rowid
+            "#,
+        )
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let policy: String = row.get(3)?;
+            let queued: i32 = row.get(4)?;
+            Ok(ActiveExecution {
+                execution_id: row.get(0)?,
+                workflow_id: row.get(1)?,
+                source: row.get(2)?,
+                policy: policy.parse().unwrap_or_default(),
+                queued: queued != 0,
+                registered_at: row.get(5)?,
+                owner_pid: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query active executions: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read row: {}", e))
+}
+
+fn insert_active(conn: &Connection, execution: &ActiveExecution) -> Result<(), String> {
+    conn.execute(
+        r#"
+        INSERT OR REPLACE INTO active_executions
+        (execution_id, workflow_id, source, policy, queued, registered_at, owner_pid)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        params![
+            execution.execution_id,
+            execution.workflow_id,
+            execution.source,
+            execution.policy.to_string(),
+            execution.queued as i32,
+            execution.registered_at,
+            execution.owner_pid,
+        ],
+    )
+    .map_err(|e| format!("Failed to register execution: {}", e))?;
+
+    Ok(())
+}
+
+fn remove_dead_owners(conn: &Connection) -> Result<usize, String> {
+    let own_pid = std::process::id();
+    let mut alive: HashMap<u32, bool> = HashMap::new();
+    let mut removed = 0;
+
+    for execution in list_active(conn)? {
+        let Some(pid) = execution.owner_pid.filter(|pid| *pid != own_pid) else {
+            continue;
+        };
+        if *alive.entry(pid).or_insert_with(|| is_process_alive(pid)) {
+            continue;
+        }
+        removed += conn
+            .execute(
+                "DELETE FROM active_executions WHERE execution_id = ?1",
+                params![execution.execution_id],
+            )
+            .map_err(|e| format!("Failed to remove execution: {}", e))?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConcurrencyPolicy;
+    use crate::services::workflow::admit_execution;
+
+    fn execution(id: &str, owner_pid: Option<u32>) -> ActiveExecution {
+        ActiveExecution {
+            execution_id: id.to_string(),
+            workflow_id: "wf".to_string(),
+            source: "mcp".to_string(),
+            policy: ConcurrencyPolicy::Reject,
+            queued: false,
+            registered_at: chrono::Utc::now().to_rfc3339(),
+            owner_pid,
+        }
+    }
+
+    #[test]
+    fn test_admit_and_register_reaps_dead_owners() {
+        let dir = std::env::temp_dir().join(format!("packageflow-queue-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+        crate::utils::schema::migrate(&db).unwrap();
+        let repo = ActiveExecutionRepository::new(db);
+
+        // A process that no longer exists (above any PID limit) still holds a slot
+        repo.register(&execution("stale", Some(999_999_999)))
+            .unwrap();
+
+        let admit = |active: &[ActiveExecution]| {
+            admit_execution(ConcurrencyPolicy::Reject, "wf", "mcp", active)
+        };
+        let own_pid = Some(std::process::id());
+        repo.admit_and_register(&execution("e1", own_pid), admit)
+            .unwrap();
+        assert!(repo
+            .admit_and_register(&execution("e2", own_pid), admit)
+            .is_err());
+        assert_eq!(
+            repo.list()
+                .unwrap()
+                .iter()
+                .map(|e| e.execution_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["e1"]
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,133 @@
+// Artifact Repository
+// Handles database operations for files collected into execution artifact stores
+
+use rusqlite::params;
+
+use crate::models::{ExecutionArtifact, ExecutionArtifactUsage};
+use crate::utils::database::Database;
+
+/// Repository for execution artifacts
+pub struct ArtifactRepository {
+    db: Database,
+}
+
+impl ArtifactRepository {
+    /// Create a new ArtifactRepository
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Save collected artifacts
+    pub fn save(&self, artifacts: &[ExecutionArtifact]) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            for artifact in artifacts {
+                conn.execute(
+                    r#"
+                    INSERT INTO execution_artifacts
+                    (execution_id, workflow_id, node_id, node_name, cell_index, path,
+                     size_bytes, sha256, file_path, collected_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                    "#,
+                    params![
+                        artifact.execution_id,
+                        artifact.workflow_id,
+                        artifact.node_id,
+                        artifact.node_name,
+                        artifact.cell_index.map(|index| index as i64),
+                        artifact.path,
+                        artifact.size_bytes as i64,
+                        artifact.sha256,
+                        artifact.file_path,
+                        artifact.collected_at,
+                    ],
+                )
+                .map_err(|e| format!("Failed to save artifact: {}", e))?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// List the artifacts of an execution in collection order
+    pub fn list(&self, execution_id: &str) -> Result<Vec<ExecutionArtifact>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT execution_id, workflow_id, node_id, node_name, cell_index, path,
+                           size_bytes, sha256, file_path, collected_at
+                    FROM execution_artifacts
+                    WHERE execution_id = ?1
+                    ORDER BY id
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params![execution_id], |row| {
+                    let cell_index: Option<i64> = row.get(4)?;
+                    let size_bytes: i64 = row.get(6)?;
+                    Ok(ExecutionArtifact {
+                        execution_id: row.get(0)?,
+                        workflow_id: row.get(1)?,
+                        node_id: row.get(2)?,
+                        node_name: row.get(3)?,
+                        cell_index: cell_index.map(|index| index as usize),
+                        path: row.get(5)?,
+                        size_bytes: size_bytes as u64,
+                        sha256: row.get(7)?,
+                        file_path: row.get(8)?,
+                        collected_at: row.get(9)?,
+                    })
+                })
+                .map_err(|e| format!("Failed to query artifacts: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Storage used per execution, newest first
+    pub fn list_usage(&self) -> Result<Vec<ExecutionArtifactUsage>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT execution_id, workflow_id, MIN(collected_at), COUNT(*), SUM(size_bytes)
+                    FROM execution_artifacts
+                    GROUP BY execution_id
+                    ORDER BY MIN(collected_at) DESC
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map([], |row| {
+                    let file_count: i64 = row.get(3)?;
+                    let total_bytes: i64 = row.get(4)?;
+                    Ok(ExecutionArtifactUsage {
+                        execution_id: row.get(0)?,
+                        workflow_id: row.get(1)?,
+                        collected_at: row.get(2)?,
+                        file_count: file_count as usize,
+                        total_bytes: total_bytes as u64,
+                    })
+                })
+                .map_err(|e| format!("Failed to query artifact usage: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Delete the artifact records of an execution
+    pub fn delete(&self, execution_id: &str) -> Result<usize, String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                "DELETE FROM execution_artifacts WHERE execution_id = ?1",
+                params![execution_id],
+            )
+            .map_err(|e| format!("Failed to delete artifacts: {}", e))
+        })
+    }
+}
@@ -565,7 +565,7 @@ impl DeployRepository {
                     r#"
                     SELECT id, project_id, platform, status, url, created_at, completed_at,
                            commit_hash, commit_message, error_message, admin_url, deploy_time,
-                           branch, site_name, preview_url
+                           branch, site_name, preview_url, snapshot_id
                     FROM deployments
                     WHERE project_id = ?1
                     ORDER BY created_at DESC
@@ -591,6 +591,7 @@ impl DeployRepository {
                         branch: row.get(12)?,
                         site_name: row.get(13)?,
                         preview_url: row.get(14)?,
+                        snapshot_id: row.get(15)?,
                     })
                 })
                 .map_err(|e| format!("Failed to query deployments: {}", e))?;
@@ -612,7 +613,7 @@ impl DeployRepository {
                 r#"
                 SELECT id, project_id, platform, status, url, created_at, completed_at,
                        commit_hash, commit_message, error_message, admin_url, deploy_time,
-                       branch, site_name, preview_url
+                       branch, site_name, preview_url, snapshot_id
                 FROM deployments
                 WHERE id = ?1
                 "#,
@@ -634,6 +635,7 @@ impl DeployRepository {
                         branch: row.get(12)?,
                         site_name: row.get(13)?,
                         preview_url: row.get(14)?,
+                        snapshot_id: row.get(15)?,
                     })
                 },
             );
@@ -659,8 +661,8 @@ impl DeployRepository {
                 INSERT OR REPLACE INTO deployments
                 (id, project_id, platform, status, url, created_at, completed_at,
                  commit_hash, commit_message, error_message, admin_url, deploy_time,
-                 branch, site_name, preview_url)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                 branch, site_name, preview_url, snapshot_id)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
                 "#,
                 params![
                     deployment.id,
@@ -678,6 +680,7 @@ impl DeployRepository {
                     deployment.branch,
                     deployment.site_name,
                     deployment.preview_url,
+                    deployment.snapshot_id,
                 ],
             )
             .map_err(|e| format!("Failed to save deployment: {}", e))?;
@@ -976,6 +979,7 @@ struct DeploymentRow {
     branch: Option<String>,
     site_name: Option<String>,
     preview_url: Option<String>,
+    snapshot_id: Option<String>,
 }
 
 impl DeploymentRow {
@@ -1006,6 +1010,7 @@ impl DeploymentRow {
             branch: self.branch,
             site_name: self.site_name,
             preview_url: self.preview_url,
+            snapshot_id: self.snapshot_id,
         })
     }
 }
@@ -0,0 +1,247 @@
+// Execution Log Repository
+// Handles database operations for persisted execution logs and their full-text index
+
+use rusqlite::params;
+
+use crate::models::{ExecutionLogInfo, ExecutionLogKind, ExecutionLogLine};
+use crate::utils::database::Database;
+
+const LOG_COLUMNS: &str = r#"
+    execution_id, kind, workflow_id, name, project_path, status, started_at, finished_at,
+    line_count, size_bytes, compressed_bytes, file_path
+"#;
+
+/// Repository for execution log metadata and search
+pub struct ExecutionLogRepository {
+    db: Database,
+}
+
+impl ExecutionLogRepository {
+    /// Create a new ExecutionLogRepository
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Register the log of a starting execution
+    pub fn create(&self, info: &ExecutionLogInfo) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO execution_logs
+                (execution_id, kind, workflow_id, name, project_path, status, started_at,
+                 finished_at, line_count, size_bytes, compressed_bytes, file_path)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                "#,
+                params![
+                    info.execution_id,
+                    info.kind.as_str(),
+                    info.workflow_id,
+                    info.name,
+                    info.project_path,
+                    info.status,
+                    info.started_at,
+                    info.finished_at,
+                    info.line_count as i64,
+                    info.size_bytes as i64,
+                    info.compressed_bytes as i64,
+                    info.file_path,
+                ],
+            )
+            .map_err(|e| format!("Failed to save execution log: {}", e))?;
+            Ok(())
+        })
+    }
+
+    /// Record the final status and sizes of a log
+    pub fn finish(&self, info: &ExecutionLogInfo) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                UPDATE execution_logs
+                SET status = ?2, finished_at = ?3, line_count = ?4, size_bytes = ?5,
+                    compressed_bytes = ?6
+                WHERE execution_id = ?1
+                "#,
+                params![
+                    info.execution_id,
+                    info.status,
+                    info.finished_at,
+                    info.line_count as i64,
+                    info.size_bytes as i64,
+                    info.compressed_bytes as i64,
+                ],
+            )
+            .map_err(|e| format!("Failed to update execution log: {}", e))?;
+            Ok(())
+        })
+    }
+
+    /// Add lines of a log to the full-text index
+    pub fn index_lines(
+        &self,
+        execution_id: &str,
+        lines: &[ExecutionLogLine],
+    ) -> Result<(), String> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        self.db.with_transaction(|conn| {
+            let mut insert_line = conn
+                .prepare_cached(
+                    "INSERT INTO execution_log_lines (execution_id, line_no) VALUES (?1, ?2)",
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+            let mut insert_fts = conn
+                .prepare_cached("INSERT INTO execution_log_fts (rowid, content) VALUES (?1, ?2)")
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            for line in lines {
+                insert_line
+                    .execute(params![execution_id, line.line_no as i64])
+                    .map_err(|e| format!("Failed to index log line: {}", e))?;
+                insert_fts
+                    .execute(params![conn.last_insert_rowid(), line.content])
+                    .map_err(|e| format!("Failed to index log line: {}", e))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Get the log of an execution
+    pub fn get(&self, execution_id: &str) -> Result<Option<ExecutionLogInfo>, String> {
+        self.db.with_connection(|conn| {
+            let result = conn.query_row(
+                &format!(
+                    "SELECT {} FROM execution_logs WHERE execution_id = ?1",
+                    LOG_COLUMNS
+                ),
+                params![execution_id],
+                log_from_row,
+            );
+
+            match result {
+                Ok(info) => Ok(Some(info)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(format!("Failed to get execution log: {}", e)),
+            }
+        })
+    }
+
+    /// List logs (most recent first), optionally of one workflow
+    pub fn list(
+        &self,
+        workflow_id: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionLogInfo>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    r#"
+                    SELECT {} FROM execution_logs
+                    WHERE ?1 IS NULL OR workflow_id = ?1
+                    ORDER BY started_at DESC
+                    LIMIT ?2
+                    "#,
+                    LOG_COLUMNS
+                ))
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let limit = limit.map(|l| l as i64).unwrap_or(-1);
+            let rows = stmt
+                .query_map(params![workflow_id, limit], log_from_row)
+                .map_err(|e| format!("Failed to query execution logs: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Find lines containing `query` (case-insensitive substring, at least 3 characters).
+    /// Returns the matching logs with the line numbers, most recent logs first.
+    pub fn search(
+        &self,
+        query: &str,
+        workflow_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(ExecutionLogInfo, u64)>, String> {
+        // Quote the query as a single FTS5 phrase so operators are matched literally
+        let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT l.execution_id, l.kind, l.workflow_id, l.name, l.project_path,
+                           l.status, l.started_at, l.finished_at, l.line_count, l.size_bytes,
+                           l.compressed_bytes, l.file_path, ll.line_no
+                    FROM execution_log_fts f
+                    JOIN execution_log_lines ll ON ll.id = f.rowid
+                    JOIN execution_logs l ON l.execution_id = ll.execution_id
+                    WHERE execution_log_fts MATCH ?1
+                      AND (?2 IS NULL OR l.workflow_id = ?2)
+                    ORDER BY l.started_at DESC, ll.line_no
+                    LIMIT ?3
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params![phrase, workflow_id, limit as i64], |row| {
+                    let line_no: i64 = row.get(12)?;
+                    Ok((log_from_row(row)?, line_no as u64))
+                })
+                .map_err(|e| format!("Failed to search execution logs: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Delete a log and its index entries (the log file is removed by the caller)
+    pub fn delete(&self, execution_id: &str) -> Result<bool, String> {
+        self.db.with_transaction(|conn| {
+            conn.execute(
+                r#"
+                DELETE FROM execution_log_fts WHERE rowid IN (
+                    SELECT id FROM execution_log_lines WHERE execution_id = ?1
+                )
+                "#,
+                params![execution_id],
+            )
+            .map_err(|e| format!("Failed to delete log index: {}", e))?;
+            conn.execute(
+                "DELETE FROM execution_log_lines WHERE execution_id = ?1",
+                params![execution_id],
+            )
+            .map_err(|e| format!("Failed to delete log index: {}", e))?;
+            let rows_affected = conn
+                .execute(
+                    "DELETE FROM execution_logs WHERE execution_id = ?1",
+                    params![execution_id],
+                )
+                .map_err(|e| format!("Failed to delete execution log: {}", e))?;
+            Ok(rows_affected > 0)
+        })
+    }
+}
+
+fn log_from_row(row: &rusqlite::Row) -> rusqlite::Result<ExecutionLogInfo> {
+    let kind: String = row.get(1)?;
+    let line_count: i64 = row.get(8)?;
+    let size_bytes: i64 = row.get(9)?;
+    let compressed_bytes: i64 = row.get(10)?;
+    Ok(ExecutionLogInfo {
+        execution_id: row.get(0)?,
+        kind: ExecutionLogKind::parse(&kind).unwrap_or(ExecutionLogKind::Workflow),
+        workflow_id: row.get(2)?,
+        name: row.get(3)?,
+        project_path: row.get(4)?,
+        status: row.get(5)?,
+        started_at: row.get(6)?,
+        finished_at: row.get(7)?,
+        line_count: line_count as u64,
+        size_bytes: size_bytes as u64,
+        compressed_bytes: compressed_bytes as u64,
+        file_path: row.get(11)?,
+    })
+}
@@ -1,14 +1,18 @@
 // Repository Layer
 // Provides data access abstractions for SQLite database
 
+pub mod active_execution_repo;
 pub mod ai_conversation_repo;
 pub mod ai_repo;
+pub mod artifact_repo;
 pub mod deploy_repo;
+pub mod execution_log_repo;
 pub mod lockfile_validation_repo;
 pub mod mcp_action_repo;
 pub mod mcp_repo;
 pub mod notification_repo;
 pub mod project_repo;
+pub mod schedule_repo;
 pub mod security_repo;
 pub mod settings_repo;
 pub mod snapshot_repo;
@@ -16,14 +20,18 @@ pub mod template_repo;
 pub mod workflow_repo;
 
 // Re-export commonly used repositories
+pub use active_execution_repo::ActiveExecutionRepository;
 pub use ai_conversation_repo::AIConversationRepository;
 pub use ai_repo::AIRepository;
+pub use artifact_repo::ArtifactRepository;
 pub use deploy_repo::DeployRepository;
+pub use execution_log_repo::ExecutionLogRepository;
 pub use lockfile_validation_repo::LockfileValidationRepository;
 pub use mcp_action_repo::MCPActionRepository;
 pub use mcp_repo::{MCPRepository, McpLogEntry};
 pub use notification_repo::{NotificationListResponse, NotificationRecord, NotificationRepository};
 pub use project_repo::ProjectRepository;
+pub use schedule_repo::ScheduleRepository;
 pub use security_repo::SecurityRepository;
 pub use settings_repo::{
     RecentTemplateEntry, SettingsRepository, TemplatePreferences, TemplateViewMode,
@@ -0,0 +1,170 @@
+// Schedule Repository
+// Handles database operations for workflow scheduler state (next run / last run)
+
+use rusqlite::params;
+
+use crate::models::WorkflowScheduleState;
+use crate::utils::database::Database;
+
+/// Repository for workflow schedule state
+pub struct ScheduleRepository {
+    db: Database,
+}
+
+impl ScheduleRepository {
+    /// Create a new ScheduleRepository
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Get the scheduler state for a workflow
+    pub fn get_state(&self, workflow_id: &str) -> Result<Option<WorkflowScheduleState>, String> {
+        self.db.with_connection(|conn| {
+            let result = conn.query_row(
+                r#"
+                SELECT workflow_id, cron, next_run_at, last_run_at, last_execution_id,
+                       last_error, updated_at
+                FROM workflow_schedule_state
+                WHERE workflow_id = ?1
+                "#,
+                params![workflow_id],
+                state_from_row,
+            );
+
+            match result {
+                Ok(state) => Ok(Some(state)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(format!("Failed to get schedule state: {}", e)),
+            }
+        })
+    }
+
+    /// List the scheduler state of all scheduled workflows, soonest first
+    pub fn list_states(&self) -> Result<Vec<WorkflowScheduleState>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT workflow_id, cron, next_run_at, last_run_at, last_execution_id,
+                           last_error, updated_at
+                    FROM workflow_schedule_state
+                    ORDER BY next_run_at IS NULL, next_run_at
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map([], state_from_row)
+                .map_err(|e| format!("Failed to query schedule states: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Save the scheduler state for a workflow (insert or update)
+    pub fn save_state(&self, state: &WorkflowScheduleState) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT INTO workflow_schedule_state
+                (workflow_id, cron, next_run_at, last_run_at, last_execution_id, last_error, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ON CONFLICT(workflow_id) DO UPDATE SET
+                    cron = excluded.cron,
+                    next_run_at = excluded.next_run_at,
+                    last_run_at = excluded.last_run_at,
+                    last_execution_id = excluded.last_execution_id,
+                    last_error = excluded.last_error,
+                    updated_at = excluded.updated_at
+                "#,
+                params![
+                    state.workflow_id,
+                    state.cron,
+                    state.next_run_at,
+                    state.last_run_at,
+                    state.last_execution_id,
+                    state.last_error,
+                    state.updated_at,
+                ],
+            )
+            .map_err(|e| format!("Failed to save schedule state: {}", e))?;
+
+            Ok(())
+        })
+    }
+
+    /// Delete the scheduler state for a workflow (schedule removed or disabled)
+    pub fn delete_state(&self, workflow_id: &str) -> Result<bool, String> {
+        self.db.with_connection(|conn| {
+            let rows_affected = conn
+                .execute(
+                    "DELETE FROM workflow_schedule_state WHERE workflow_id = ?1",
+                    params![workflow_id],
+                )
+                .map_err(|e| format!("Failed to delete schedule state: {}", e))?;
+
+            Ok(rows_affected > 0)
+        })
+    }
+}
+
+fn state_from_row(row: &rusqlite::Row) -> rusqlite::Result<WorkflowScheduleState> {
+    Ok(WorkflowScheduleState {
+        workflow_id: row.get(0)?,
+        cron: row.get(1)?,
+        next_run_at: row.get(2)?,
+        last_run_at: row.get(3)?,
+        last_execution_id: row.get(4)?,
+        last_error: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Workflow;
+    use crate::repositories::WorkflowRepository;
+
+    #[test]
+    fn test_schedule_state_lifecycle() {
+        let dir =
+            std::env::temp_dir().join(format!("packageflow-schedule-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+
+        WorkflowRepository::new(db.clone())
+            .save(&Workflow::new(
+                "wf-1".to_string(),
+                "Nightly audit".to_string(),
+            ))
+            .unwrap();
+
+        let repo = ScheduleRepository::new(db.clone());
+        let mut state = WorkflowScheduleState {
+            workflow_id: "wf-1".to_string(),
+            cron: "0 3 * * *".to_string(),
+            next_run_at: Some("2025-03-10T03:00:00+00:00".to_string()),
+            last_run_at: None,
+            last_execution_id: None,
+            last_error: None,
+            updated_at: "2025-03-09T12:00:00+00:00".to_string(),
+        };
+        repo.save_state(&state).unwrap();
+
+        state.last_run_at = state.next_run_at.take();
+        state.next_run_at = Some("2025-03-11T03:00:00+00:00".to_string());
+        state.last_execution_id = Some("exec-1".to_string());
+        repo.save_state(&state).unwrap();
+
+        assert_eq!(repo.get_state("wf-1").unwrap(), Some(state.clone()));
+        assert_eq!(repo.list_states().unwrap(), vec![state]);
+
+        // State goes away with the workflow
+        WorkflowRepository::new(db).delete("wf-1").unwrap();
+        assert_eq!(repo.get_state("wf-1").unwrap(), None);
+        assert!(!repo.delete_state("wf-1").unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -5,8 +5,9 @@ use rusqlite::params;
 
 use crate::models::security_insight::{InsightSeverity, InsightType, InsightSummary, SecurityInsight};
 use crate::models::snapshot::{
-    ExecutionSnapshot, LockfileState, LockfileType, SnapshotDependency, SnapshotDiff,
-    SnapshotFilter, SnapshotListItem, SnapshotStatus, SnapshotWithDependencies,
+    DependencyKind, ExecutionSnapshot, LockfileState, LockfileType, SnapshotDependency,
+    SnapshotDependencyEdge, SnapshotDiff, SnapshotFilter, SnapshotListItem,
+    SnapshotRetentionCandidate, SnapshotRetentionPolicy, SnapshotStatus, SnapshotWithDependencies,
     TimeMachineSettings, TriggerSource,
 };
 use crate::utils::database::Database;
@@ -119,7 +120,7 @@ impl SnapshotRepository {
                        lockfile_type, lockfile_hash, dependency_tree_hash, package_json_hash,
                        total_dependencies, direct_dependencies, dev_dependencies,
                        security_score, postinstall_count, storage_path, compressed_size,
-                       error_message, created_at
+                       error_message, created_at, is_pinned
                 FROM execution_snapshots
                 WHERE id = ?1
                 "#,
@@ -143,6 +144,7 @@ impl SnapshotRepository {
                         compressed_size: row.get(14)?,
                         error_message: row.get(15)?,
                         created_at: row.get(16)?,
+                        is_pinned: row.get::<_, i32>(17)? != 0,
                     })
                 },
             );
@@ -179,7 +181,8 @@ impl SnapshotRepository {
             let mut sql = String::from(
                 r#"
                 SELECT id, project_path, status, trigger_source, lockfile_type,
-                       total_dependencies, security_score, postinstall_count, created_at
+                       total_dependencies, security_score, postinstall_count, created_at,
+                       is_pinned
                 FROM execution_snapshots
                 WHERE 1=1
                 "#,
@@ -247,6 +250,7 @@ impl SnapshotRepository {
                         security_score: row.get(6)?,
                         postinstall_count: row.get(7)?,
                         created_at: row.get(8)?,
+                        is_pinned: row.get::<_, i32>(9)? != 0,
                     })
                 })
                 .map_err(|e| format!("Failed to query snapshots: {}", e))?;
@@ -269,7 +273,7 @@ impl SnapshotRepository {
                        lockfile_type, lockfile_hash, dependency_tree_hash, package_json_hash,
                        total_dependencies, direct_dependencies, dev_dependencies,
                        security_score, postinstall_count, storage_path, compressed_size,
-                       error_message, created_at
+                       error_message, created_at, is_pinned
                 FROM execution_snapshots
                 WHERE project_path = ?1 AND status = 'completed'
                 ORDER BY created_at DESC
@@ -295,6 +299,7 @@ impl SnapshotRepository {
                         compressed_size: row.get(14)?,
                         error_message: row.get(15)?,
                         created_at: row.get(16)?,
+                        is_pinned: row.get::<_, i32>(17)? != 0,
                     })
                 },
             );
@@ -318,14 +323,19 @@ impl SnapshotRepository {
         })
     }
 
-    /// Delete old snapshots (keep last N per project)
+    /// Delete old snapshots (keep last N per project, plus pinned and deployed ones)
     pub fn prune_snapshots(&self, keep_per_project: usize) -> Result<usize, String> {
         self.db.with_connection(|conn| {
             let rows_affected = conn
                 .execute(
                     r#"
                     DELETE FROM execution_snapshots
-                    WHERE id NOT IN (
+                    WHERE COALESCE(is_pinned, 0) = 0
+                    AND NOT EXISTS (
+                        SELECT 1 FROM deployments d
+                        WHERE d.snapshot_id = execution_snapshots.id AND d.status = 'ready'
+                    )
+                    AND id NOT IN (
                         SELECT id FROM (
                             SELECT id, ROW_NUMBER() OVER (
                                 PARTITION BY project_path
@@ -344,6 +354,90 @@ impl SnapshotRepository {
         })
     }
 
+    /// Pin or unpin a snapshot (pinned snapshots survive retention)
+    pub fn set_snapshot_pinned(&self, id: &str, pinned: bool) -> Result<bool, String> {
+        self.db.with_connection(|conn| {
+            let rows_affected = conn
+                .execute(
+                    "UPDATE execution_snapshots SET is_pinned = ?1 WHERE id = ?2",
+                    params![pinned as i32, id],
+                )
+                .map_err(|e| format!("Failed to pin snapshot: {}", e))?;
+
+            Ok(rows_affected > 0)
+        })
+    }
+
+    // =========================================================================
+    // Retention
+    // =========================================================================
+
+    /// Projects that have snapshots
+    pub fn list_snapshot_projects(&self) -> Result<Vec<String>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT DISTINCT project_path FROM execution_snapshots ORDER BY project_path",
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map([], |row| row.get(0))
+                .map_err(|e| format!("Failed to query snapshot projects: {}", e))?;
+
+            let mut projects = Vec::new();
+            for row in rows {
+                projects.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+            }
+
+            Ok(projects)
+        })
+    }
+
+    /// Finished snapshots of a project, newest first, with what protects them from pruning
+    pub fn list_retention_candidates(
+        &self,
+        project_path: &str,
+    ) -> Result<Vec<SnapshotRetentionCandidate>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT s.id, s.status, s.created_at, s.lockfile_hash, s.is_pinned,
+                           EXISTS (
+                               SELECT 1 FROM deployments d
+                               WHERE d.snapshot_id = s.id AND d.status = 'ready'
+                           )
+                    FROM execution_snapshots s
+                    WHERE s.project_path = ?1 AND s.status != 'capturing'
+                    ORDER BY s.created_at DESC
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params![project_path], |row| {
+                    let status: String = row.get(1)?;
+                    Ok(SnapshotRetentionCandidate {
+                        id: row.get(0)?,
+                        status: SnapshotStatus::from_str(&status).unwrap_or(SnapshotStatus::Failed),
+                        created_at: row.get(2)?,
+                        lockfile_hash: row.get(3)?,
+                        is_pinned: row.get::<_, i32>(4)? != 0,
+                        is_deployed: row.get::<_, i32>(5)? != 0,
+                    })
+                })
+                .map_err(|e| format!("Failed to query retention candidates: {}", e))?;
+
+            let mut candidates = Vec::new();
+            for row in rows {
+                candidates.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+            }
+
+            Ok(candidates)
+        })
+    }
+
     // =========================================================================
     // Snapshot Dependencies
     // =========================================================================
@@ -517,6 +611,84 @@ impl SnapshotRepository {
         })
     }
 
+    // =========================================================================
+    // Snapshot Dependency Graph
+    // =========================================================================
+
+    /// Add dependency graph edges to a snapshot
+    pub fn add_dependency_edges(&self, edges: &[SnapshotDependencyEdge]) -> Result<(), String> {
+        if edges.is_empty() {
+            return Ok(());
+        }
+
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    INSERT INTO snapshot_dependency_edges (
+                        snapshot_id, parent_name, parent_version, child_name, child_version, kind
+                    )
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            for edge in edges {
+                stmt.execute(params![
+                    edge.snapshot_id,
+                    edge.parent_name,
+                    edge.parent_version,
+                    edge.child_name,
+                    edge.child_version,
+                    edge.kind.as_str(),
+                ])
+                .map_err(|e| format!("Failed to insert dependency edge: {}", e))?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// List dependency graph edges for a snapshot (empty for snapshots captured before v20)
+    pub fn list_dependency_edges(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<Vec<SnapshotDependencyEdge>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    r#"
+                    SELECT snapshot_id, parent_name, parent_version, child_name, child_version, kind
+                    FROM snapshot_dependency_edges
+                    WHERE snapshot_id = ?1
+                    ORDER BY id
+                    "#,
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params![snapshot_id], |row| {
+                    let kind: String = row.get(5)?;
+                    Ok(SnapshotDependencyEdge {
+                        snapshot_id: row.get(0)?,
+                        parent_name: row.get(1)?,
+                        parent_version: row.get(2)?,
+                        child_name: row.get(3)?,
+                        child_version: row.get(4)?,
+                        kind: kind.parse().unwrap_or(DependencyKind::Prod),
+                    })
+                })
+                .map_err(|e| format!("Failed to query dependency edges: {}", e))?;
+
+            let mut edges = Vec::new();
+            for row in rows {
+                edges.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+            }
+
+            Ok(edges)
+        })
+    }
+
     // =========================================================================
     // Security Insights
     // =========================================================================
@@ -766,7 +938,7 @@ impl SnapshotRepository {
                        lockfile_type, lockfile_hash, dependency_tree_hash, package_json_hash,
                        total_dependencies, direct_dependencies, dev_dependencies,
                        security_score, postinstall_count, storage_path, compressed_size,
-                       error_message, created_at
+                       error_message, created_at, is_pinned
                 FROM execution_snapshots
                 WHERE project_path = ?1 AND lockfile_hash = ?2 AND status = 'completed'
                 ORDER BY created_at DESC
@@ -792,6 +964,7 @@ impl SnapshotRepository {
                         compressed_size: row.get(14)?,
                         error_message: row.get(15)?,
                         created_at: row.get(16)?,
+                        is_pinned: row.get::<_, i32>(17)? != 0,
                     })
                 },
             );
@@ -890,7 +1063,9 @@ impl SnapshotRepository {
         self.db.with_connection(|conn| {
             let result = conn.query_row(
                 r#"
-                SELECT auto_watch_enabled, debounce_ms, updated_at
+                SELECT auto_watch_enabled, debounce_ms, updated_at,
+                       retention_enabled, retention_keep_recent, retention_keep_daily,
+                       retention_keep_weekly, retention_keep_monthly, retention_max_project_mb
                 FROM time_machine_settings
                 WHERE id = 1
                 "#,
@@ -899,6 +1074,16 @@ impl SnapshotRepository {
                     Ok(TimeMachineSettings {
                         auto_watch_enabled: row.get::<_, i32>(0)? != 0,
                         debounce_ms: row.get(1)?,
+                        retention: SnapshotRetentionPolicy {
+                            enabled: row.get::<_, i32>(3)? != 0,
+                            keep_recent: row.get(4)?,
+                            keep_daily: row.get(5)?,
+                            keep_weekly: row.get(6)?,
+                            keep_monthly: row.get(7)?,
+                            max_project_storage_mb: row
+                                .get::<_, Option<i64>>(8)?
+                                .map(|mb| mb.max(0) as u64),
+                        },
                         updated_at: row.get(2)?,
                     })
                 },
@@ -920,17 +1105,36 @@ impl SnapshotRepository {
         self.db.with_connection(|conn| {
             conn.execute(
                 r#"
-                INSERT INTO time_machine_settings (id, auto_watch_enabled, debounce_ms, updated_at)
-                VALUES (1, ?1, ?2, ?3)
+                INSERT INTO time_machine_settings (
+                    id, auto_watch_enabled, debounce_ms, updated_at,
+                    retention_enabled, retention_keep_recent, retention_keep_daily,
+                    retention_keep_weekly, retention_keep_monthly, retention_max_project_mb
+                )
+                VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
                 ON CONFLICT(id) DO UPDATE SET
                     auto_watch_enabled = excluded.auto_watch_enabled,
                     debounce_ms = excluded.debounce_ms,
-                    updated_at = excluded.updated_at
+                    updated_at = excluded.updated_at,
+                    retention_enabled = excluded.retention_enabled,
+                    retention_keep_recent = excluded.retention_keep_recent,
+                    retention_keep_daily = excluded.retention_keep_daily,
+                    retention_keep_weekly = excluded.retention_keep_weekly,
+                    retention_keep_monthly = excluded.retention_keep_monthly,
+                    retention_max_project_mb = excluded.retention_max_project_mb
                 "#,
                 params![
                     settings.auto_watch_enabled as i32,
                     settings.debounce_ms,
                     settings.updated_at,
+                    settings.retention.enabled as i32,
+                    settings.retention.keep_recent,
+                    settings.retention.keep_daily,
+                    settings.retention.keep_weekly,
+                    settings.retention.keep_monthly,
+                    settings
+                        .retention
+                        .max_project_storage_mb
+                        .map(|mb| mb as i64),
                 ],
             )
             .map_err(|e| format!("Failed to update Time Machine settings: {}", e))?;
@@ -959,6 +1163,7 @@ struct SnapshotRow {
     compressed_size: Option<i64>,
     error_message: Option<String>,
     created_at: String,
+    is_pinned: bool,
 }
 
 impl SnapshotRow {
@@ -982,6 +1187,7 @@ impl SnapshotRow {
             compressed_size: self.compressed_size,
             error_message: self.error_message,
             created_at: self.created_at,
+            is_pinned: self.is_pinned,
         }
     }
 }
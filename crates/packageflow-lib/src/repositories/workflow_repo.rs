@@ -1,11 +1,24 @@
 // Workflow Repository
 // Handles all database operations for workflows
 
+use std::collections::HashMap;
+
 use rusqlite::params;
 
-use crate::models::{IncomingWebhookConfig, WebhookConfig, Workflow, WorkflowNode};
+use crate::models::{
+    ConcurrencyPolicy, IncomingWebhookConfig, RevisionSource, WebhookConfig, Workflow,
+    WorkflowFileTrigger, WorkflowGitTrigger, WorkflowNode, WorkflowParameter, WorkflowRevision,
+    WorkflowSchedule, WorkflowSecretInfo,
+};
+use crate::services::crypto;
+use crate::services::workflow::is_same_definition;
 use crate::utils::database::Database;
 
+/// Columns selected for workflow rows (order must match `WorkflowRow::from_row`)
+const WORKFLOW_COLUMNS: &str = "id, name, description, project_id, nodes, webhook, \
+     incoming_webhook, created_at, updated_at, last_executed_at, max_parallelism, parameters, env, \
+     schedule, file_trigger, git_trigger, concurrency";
+
 /// Repository for workflow data access
 pub struct WorkflowRepository {
     db: Database,
@@ -22,30 +35,15 @@ impl WorkflowRepository {
         self.db.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    r#"
-                    SELECT id, name, description, project_id, nodes, webhook,
-                           incoming_webhook, created_at, updated_at, last_executed_at
-                    FROM workflows
-                    ORDER BY updated_at DESC
-                    "#,
+                    &format!(
+                        "SELECT {} FROM workflows ORDER BY updated_at DESC",
+                        WORKFLOW_COLUMNS
+                    ),
                 )
                 .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
             let rows = stmt
-                .query_map([], |row| {
-                    Ok(WorkflowRow {
-                        id: row.get(0)?,
-                        name: row.get(1)?,
-                        description: row.get(2)?,
-                        project_id: row.get(3)?,
-                        nodes: row.get(4)?,
-                        webhook: row.get(5)?,
-                        incoming_webhook: row.get(6)?,
-                        created_at: row.get(7)?,
-                        updated_at: row.get(8)?,
-                        last_executed_at: row.get(9)?,
-                    })
-                })
+                .query_map([], WorkflowRow::from_row)
                 .map_err(|e| format!("Failed to query workflows: {}", e))?;
 
             let mut workflows = Vec::new();
@@ -63,31 +61,15 @@ impl WorkflowRepository {
         self.db.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    r#"
-                    SELECT id, name, description, project_id, nodes, webhook,
-                           incoming_webhook, created_at, updated_at, last_executed_at
-                    FROM workflows
-                    WHERE project_id = ?1
-                    ORDER BY updated_at DESC
-                    "#,
+                    &format!(
+                        "SELECT {} FROM workflows WHERE project_id = ?1 ORDER BY updated_at DESC",
+                        WORKFLOW_COLUMNS
+                    ),
                 )
                 .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
             let rows = stmt
-                .query_map(params![project_id], |row| {
-                    Ok(WorkflowRow {
-                        id: row.get(0)?,
-                        name: row.get(1)?,
-                        description: row.get(2)?,
-                        project_id: row.get(3)?,
-                        nodes: row.get(4)?,
-                        webhook: row.get(5)?,
-                        incoming_webhook: row.get(6)?,
-                        created_at: row.get(7)?,
-                        updated_at: row.get(8)?,
-                        last_executed_at: row.get(9)?,
-                    })
-                })
+                .query_map(params![project_id], WorkflowRow::from_row)
                 .map_err(|e| format!("Failed to query workflows: {}", e))?;
 
             let mut workflows = Vec::new();
@@ -105,29 +87,11 @@ impl WorkflowRepository {
         self.db.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    r#"
-                    SELECT id, name, description, project_id, nodes, webhook,
-                           incoming_webhook, created_at, updated_at, last_executed_at
-                    FROM workflows
-                    WHERE id = ?1
-                    "#,
+                    &format!("SELECT {} FROM workflows WHERE id = ?1", WORKFLOW_COLUMNS),
                 )
                 .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
-            let result = stmt.query_row(params![id], |row| {
-                Ok(WorkflowRow {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description: row.get(2)?,
-                    project_id: row.get(3)?,
-                    nodes: row.get(4)?,
-                    webhook: row.get(5)?,
-                    incoming_webhook: row.get(6)?,
-                    created_at: row.get(7)?,
-                    updated_at: row.get(8)?,
-                    last_executed_at: row.get(9)?,
-                })
-            });
+            let result = stmt.query_row(params![id], WorkflowRow::from_row);
 
             match result {
                 Ok(row) => Ok(Some(row.into_workflow()?)),
@@ -163,6 +127,39 @@ impl WorkflowRepository {
             .map(|w| serde_json::to_string(w).ok())
             .flatten();
 
+        let parameters_json = if workflow.parameters.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&workflow.parameters)
+                    .map_err(|e| format!("Failed to serialize parameters: {}", e))?,
+            )
+        };
+
+        let env_json = if workflow.env.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&workflow.env)
+                    .map_err(|e| format!("Failed to serialize env: {}", e))?,
+            )
+        };
+
+        let schedule_json = workflow
+            .schedule
+            .as_ref()
+            .and_then(|s| serde_json::to_string(s).ok());
+
+        let file_trigger_json = workflow
+            .file_trigger
+            .as_ref()
+            .and_then(|t| serde_json::to_string(t).ok());
+
+        let git_trigger_json = workflow
+            .git_trigger
+            .as_ref()
+            .and_then(|t| serde_json::to_string(t).ok());
+
         // IMPORTANT: Uses ON CONFLICT DO UPDATE instead of INSERT OR REPLACE
         // to avoid triggering ON DELETE CASCADE on webhook_tokens table.
         // INSERT OR REPLACE internally does DELETE + INSERT which triggers cascades.
@@ -171,8 +168,9 @@ impl WorkflowRepository {
                 r#"
                 INSERT INTO workflows
                 (id, name, description, project_id, nodes, webhook, incoming_webhook,
-                 created_at, updated_at, last_executed_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                 created_at, updated_at, last_executed_at, max_parallelism, parameters, env,
+                 schedule, file_trigger, git_trigger, concurrency)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
                 ON CONFLICT(id) DO UPDATE SET
                     name = excluded.name,
                     description = excluded.description,
@@ -181,7 +179,14 @@ impl WorkflowRepository {
                     webhook = excluded.webhook,
                     incoming_webhook = excluded.incoming_webhook,
                     updated_at = excluded.updated_at,
-                    last_executed_at = excluded.last_executed_at
+                    last_executed_at = excluded.last_executed_at,
+                    max_parallelism = excluded.max_parallelism,
+                    parameters = excluded.parameters,
+                    env = excluded.env,
+                    schedule = excluded.schedule,
+                    file_trigger = excluded.file_trigger,
+                    git_trigger = excluded.git_trigger,
+                    concurrency = excluded.concurrency
                 "#,
                 params![
                     workflow.id,
@@ -194,6 +199,13 @@ impl WorkflowRepository {
                     workflow.created_at,
                     workflow.updated_at,
                     workflow.last_executed_at,
+                    workflow.max_parallelism,
+                    parameters_json,
+                    env_json,
+                    schedule_json,
+                    file_trigger_json,
+                    git_trigger_json,
+                    workflow.concurrency.map(|c| c.to_string()),
                 ],
             )
             .map_err(|e| format!("Failed to save workflow: {}", e))?;
@@ -412,6 +424,240 @@ impl WorkflowRepository {
             Ok(count > 0)
         })
     }
+
+    // =========================================================================
+    // Workflow Secrets (Encrypted)
+    // =========================================================================
+
+    /// Store an encrypted secret referenced as `{{secrets.<name>}}` (insert or update)
+    pub fn store_secret(&self, name: &str, ciphertext: &str, nonce: &str) -> Result<(), String> {
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT INTO workflow_secrets (name, ciphertext, nonce)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(name) DO UPDATE SET
+                    ciphertext = excluded.ciphertext,
+                    nonce = excluded.nonce,
+                    updated_at = datetime('now')
+                "#,
+                params![name, ciphertext, nonce],
+            )
+            .map_err(|e| format!("Failed to store secret: {}", e))?;
+
+            Ok(())
+        })
+    }
+
+    /// Get encrypted secret data by name
+    /// Returns (ciphertext, nonce) if found
+    pub fn get_secret(&self, name: &str) -> Result<Option<(String, String)>, String> {
+        self.db.with_connection(|conn| {
+            let result = conn.query_row(
+                "SELECT ciphertext, nonce FROM workflow_secrets WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            );
+
+            match result {
+                Ok(data) => Ok(Some(data)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(format!("Failed to get secret: {}", e)),
+            }
+        })
+    }
+
+    /// Decrypt the named secrets (referenced as `{{secrets.<name>}}`)
+    pub fn load_secrets(&self, names: &[String]) -> Result<HashMap<String, String>, String> {
+        let mut secrets = HashMap::new();
+        for name in names {
+            let (ciphertext, nonce) = self
+                .get_secret(name)?
+                .ok_or_else(|| format!("Secret '{}' is not defined", name))?;
+            let value = crypto::decrypt(&crypto::EncryptedData { ciphertext, nonce })
+                .map_err(|e| format!("Failed to decrypt secret '{}': {}", name, e))?;
+            secrets.insert(name.clone(), value);
+        }
+        Ok(secrets)
+    }
+
+    /// List stored secrets (names and timestamps only)
+    pub fn list_secrets(&self) -> Result<Vec<WorkflowSecretInfo>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT name, created_at, updated_at FROM workflow_secrets ORDER BY name")
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(WorkflowSecretInfo {
+                        name: row.get(0)?,
+                        created_at: row.get(1)?,
+                        updated_at: row.get(2)?,
+                    })
+                })
+                .map_err(|e| format!("Failed to query secrets: {}", e))?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read row: {}", e))
+        })
+    }
+
+    /// Delete a secret by name
+    pub fn delete_secret(&self, name: &str) -> Result<bool, String> {
+        self.db.with_connection(|conn| {
+            let rows_affected = conn
+                .execute(
+                    "DELETE FROM workflow_secrets WHERE name = ?1",
+                    params![name],
+                )
+                .map_err(|e| format!("Failed to delete secret: {}", e))?;
+
+            Ok(rows_affected > 0)
+        })
+    }
+
+    // =========================================================================
+    // Revisions
+    // =========================================================================
+
+    /// Save a workflow and record it as a revision
+    /// Returns the revision number of the saved workflow
+    pub fn save_with_revision(
+        &self,
+        workflow: &Workflow,
+        source: RevisionSource,
+        summary: Option<&str>,
+    ) -> Result<u32, String> {
+        self.save(workflow)?;
+        self.record_revision(workflow, source, summary)
+    }
+
+    /// Record a new revision unless the workflow matches the latest one
+    /// Returns the revision number the workflow corresponds to
+    pub fn record_revision(
+        &self,
+        workflow: &Workflow,
+        source: RevisionSource,
+        summary: Option<&str>,
+    ) -> Result<u32, String> {
+        if let Some(latest) = self.get_latest_revision(&workflow.id)? {
+            if is_same_definition(&latest.workflow, workflow) {
+                return Ok(latest.revision);
+            }
+        }
+
+        let snapshot = serde_json::to_string(workflow)
+            .map_err(|e| format!("Failed to serialize workflow: {}", e))?;
+
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT INTO workflow_revisions
+                (workflow_id, revision, source, summary, snapshot, created_at)
+                SELECT ?1, COALESCE(MAX(revision), 0) + 1, ?2, ?3, ?4, ?5
+                FROM workflow_revisions WHERE workflow_id = ?1
+                "#,
+                params![
+                    workflow.id,
+                    source.to_string(),
+                    summary,
+                    snapshot,
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| format!("Failed to save workflow revision: {}", e))?;
+
+            conn.query_row(
+                "SELECT revision FROM workflow_revisions WHERE id = ?1",
+                params![conn.last_insert_rowid()],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to read workflow revision: {}", e))
+        })
+    }
+
+    /// List the revisions of a workflow, newest first
+    pub fn list_revisions(&self, workflow_id: &str) -> Result<Vec<WorkflowRevision>, String> {
+        self.query_revisions(
+            "WHERE workflow_id = ?1 ORDER BY revision DESC",
+            params![workflow_id],
+        )
+    }
+
+    /// Get a revision of a workflow
+    pub fn get_revision(
+        &self,
+        workflow_id: &str,
+        revision: u32,
+    ) -> Result<Option<WorkflowRevision>, String> {
+        Ok(self
+            .query_revisions(
+                "WHERE workflow_id = ?1 AND revision = ?2",
+                params![workflow_id, revision],
+            )?
+            .pop())
+    }
+
+    /// Get the most recent revision of a workflow
+    pub fn get_latest_revision(
+        &self,
+        workflow_id: &str,
+    ) -> Result<Option<WorkflowRevision>, String> {
+        Ok(self
+            .query_revisions(
+                "WHERE workflow_id = ?1 ORDER BY revision DESC LIMIT 1",
+                params![workflow_id],
+            )?
+            .pop())
+    }
+
+    fn query_revisions(
+        &self,
+        filter: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<WorkflowRevision>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT workflow_id, revision, source, summary, snapshot, created_at \
+                     FROM workflow_revisions {}",
+                    filter
+                ))
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params, |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, u32>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                    ))
+                })
+                .map_err(|e| format!("Failed to query workflow revisions: {}", e))?;
+
+            let mut revisions = Vec::new();
+            for row in rows {
+                let (workflow_id, revision, source, summary, snapshot, created_at) =
+                    row.map_err(|e| format!("Failed to read row: {}", e))?;
+                let workflow = serde_json::from_str(&snapshot)
+                    .map_err(|e| format!("Failed to parse workflow revision: {}", e))?;
+                revisions.push(WorkflowRevision {
+                    workflow_id,
+                    revision,
+                    source: source.parse().unwrap_or(RevisionSource::Unknown),
+                    summary,
+                    created_at,
+                    workflow,
+                });
+            }
+
+            Ok(revisions)
+        })
+    }
 }
 
 /// Internal row structure for mapping database rows
@@ -426,9 +672,38 @@ struct WorkflowRow {
     created_at: String,
     updated_at: String,
     last_executed_at: Option<String>,
+    max_parallelism: Option<u32>,
+    parameters: Option<String>,
+    env: Option<String>,
+    schedule: Option<String>,
+    file_trigger: Option<String>,
+    git_trigger: Option<String>,
+    concurrency: Option<String>,
 }
 
 impl WorkflowRow {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            project_id: row.get(3)?,
+            nodes: row.get(4)?,
+            webhook: row.get(5)?,
+            incoming_webhook: row.get(6)?,
+            created_at: row.get(7)?,
+            updated_at: row.get(8)?,
+            last_executed_at: row.get(9)?,
+            max_parallelism: row.get(10)?,
+            parameters: row.get(11)?,
+            env: row.get(12)?,
+            schedule: row.get(13)?,
+            file_trigger: row.get(14)?,
+            git_trigger: row.get(15)?,
+            concurrency: row.get(16)?,
+        })
+    }
+
     fn into_workflow(self) -> Result<Workflow, String> {
         let nodes: Vec<WorkflowNode> = serde_json::from_str(&self.nodes)
             .map_err(|e| format!("Failed to parse nodes: {}", e))?;
@@ -445,6 +720,38 @@ impl WorkflowRow {
             .map(|json| serde_json::from_str(json).ok())
             .flatten();
 
+        let parameters: Vec<WorkflowParameter> = self
+            .parameters
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+
+        let env: HashMap<String, String> = self
+            .env
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+
+        let schedule: Option<WorkflowSchedule> = self
+            .schedule
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok());
+
+        let file_trigger: Option<WorkflowFileTrigger> = self
+            .file_trigger
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok());
+
+        let git_trigger: Option<WorkflowGitTrigger> = self
+            .git_trigger
+            .as_ref()
+            .and_then(|json| serde_json::from_str(json).ok());
+
+        let concurrency: Option<ConcurrencyPolicy> = self
+            .concurrency
+            .as_ref()
+            .and_then(|policy| policy.parse().ok());
+
         Ok(Workflow {
             id: self.id,
             name: self.name,
@@ -456,6 +763,80 @@ impl WorkflowRow {
             last_executed_at: self.last_executed_at,
             webhook,
             incoming_webhook,
+            max_parallelism: self.max_parallelism,
+            parameters,
+            env,
+            schedule,
+            file_trigger,
+            git_trigger,
+            concurrency,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_revisions() {
+        let dir =
+            std::env::temp_dir().join(format!("packageflow-revisions-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+        let repo = WorkflowRepository::new(db);
+
+        let mut workflow = Workflow::new("wf-1".to_string(), "Build".to_string());
+        let first = repo
+            .save_with_revision(&workflow, RevisionSource::Ui, None)
+            .unwrap();
+        assert_eq!(first, 1);
+
+        // Saving without changes (e.g. a new timestamp) keeps the revision
+        workflow.updated_at = "2030-01-01T00:00:00Z".to_string();
+        let unchanged = repo
+            .save_with_revision(&workflow, RevisionSource::Mcp, Some("update_workflow"))
+            .unwrap();
+        assert_eq!(unchanged, 1);
+
+        workflow.description = Some("CI build".to_string());
+        let second = repo
+            .save_with_revision(&workflow, RevisionSource::Mcp, Some("update_workflow"))
+            .unwrap();
+        assert_eq!(second, 2);
+
+        let revisions = repo.list_revisions("wf-1").unwrap();
+        assert_eq!(
+            revisions.iter().map(|r| r.revision).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(revisions[0].source, RevisionSource::Mcp);
+        assert_eq!(revisions[0].summary.as_deref(), Some("update_workflow"));
+        let original = repo.get_revision("wf-1", 1).unwrap().unwrap();
+        assert_eq!(original.workflow.description, None);
+
+        // Revisions go away with the workflow
+        repo.delete("wf-1").unwrap();
+        assert!(repo.list_revisions("wf-1").unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_load_secrets() {
+        let dir =
+            std::env::temp_dir().join(format!("packageflow-secrets-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+        let repo = WorkflowRepository::new(db);
+
+        let encrypted = crypto::encrypt("s3cret").unwrap();
+        repo.store_secret("NPM_TOKEN", &encrypted.ciphertext, &encrypted.nonce)
+            .unwrap();
+
+        let secrets = repo.load_secrets(&["NPM_TOKEN".to_string()]).unwrap();
+        assert_eq!(secrets.get("NPM_TOKEN"), Some(&"s3cret".to_string()));
+        assert!(repo.load_secrets(&[]).unwrap().is_empty());
+        assert!(repo.load_secrets(&["MISSING".to_string()]).is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
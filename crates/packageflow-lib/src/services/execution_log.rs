@@ -0,0 +1,547 @@
+// Execution Log Service
+// Streams execution output into zstd-compressed JSON-lines files, indexes every line
+// for full-text search and applies log retention
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::{
+    ExecutionLogInfo, ExecutionLogKind, ExecutionLogLine, ExecutionLogPage, ExecutionLogSearchHit,
+    LogRetentionPolicy,
+};
+use crate::repositories::ExecutionLogRepository;
+use crate::utils::database::{get_database_path, Database};
+
+/// File extension of log files
+pub const EXECUTION_LOG_EXTENSION: &str = "jsonl.zst";
+
+/// Shortest search query (the index matches trigrams)
+pub const MIN_LOG_SEARCH_LENGTH: usize = 3;
+
+/// Lines buffered before they are flushed to the file and the index
+const INDEX_BATCH_LINES: usize = 200;
+
+/// Final status of logs whose execution never finished (the app quit mid-run)
+pub const INTERRUPTED_LOG_STATUS: &str = "interrupted";
+
+/// Directory (next to the database) holding the log files
+pub fn execution_logs_dir() -> Result<PathBuf, String> {
+    get_database_path()?
+        .parent()
+        .map(|dir| dir.join("logs"))
+        .ok_or_else(|| "Could not determine application data directory".to_string())
+}
+
+/// Writes the output of one running execution to its log file and the search index
+pub struct ExecutionLogWriter {
+    info: ExecutionLogInfo,
+    encoder: zstd::Encoder<'static, BufWriter<File>>,
+    repo: ExecutionLogRepository,
+    pending: Vec<ExecutionLogLine>,
+}
+
+impl ExecutionLogWriter {
+    /// Create the log file in `dir` and register the log
+    pub fn create(db: Database, dir: &Path, mut info: ExecutionLogInfo) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create log directory: {}", e))?;
+        let path = dir.join(format!("{}.{}", info.execution_id, EXECUTION_LOG_EXTENSION));
+        let file = File::create(&path).map_err(|e| format!("Failed to create log file: {}", e))?;
+        let encoder = zstd::Encoder::new(BufWriter::new(file), 3)
+            .map_err(|e| format!("Failed to create zstd encoder: {}", e))?;
+
+        info.file_path = path.to_string_lossy().to_string();
+        let repo = ExecutionLogRepository::new(db);
+        repo.create(&info)?;
+
+        Ok(Self {
+            info,
+            encoder,
+            repo,
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn info(&self) -> &ExecutionLogInfo {
+        &self.info
+    }
+
+    /// Append output; `content` may hold several lines
+    pub fn append(
+        &mut self,
+        node: Option<(&str, &str)>,
+        stream: &str,
+        timestamp: &str,
+        content: &str,
+    ) -> Result<(), String> {
+        let content = content.strip_suffix('\n').unwrap_or(content);
+        for text in content.split('\n') {
+            let line = ExecutionLogLine {
+                line_no: self.info.line_count,
+                node_id: node.map(|(id, _)| id.to_string()),
+                node_name: node.map(|(_, name)| name.to_string()),
+                stream: stream.to_string(),
+                timestamp: timestamp.to_string(),
+                content: text.strip_suffix('\r').unwrap_or(text).to_string(),
+            };
+            let json = serde_json::to_string(&line)
+                .map_err(|e| format!("Failed to serialize log line: {}", e))?;
+            writeln!(self.encoder, "{}", json)
+                .map_err(|e| format!("Failed to write log line: {}", e))?;
+
+            self.info.line_count += 1;
+            self.info.size_bytes += line.content.len() as u64 + 1;
+            self.pending.push(line);
+        }
+
+        if self.pending.len() >= INDEX_BATCH_LINES {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered lines so they are readable and searchable
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.encoder
+            .flush()
+            .map_err(|e| format!("Failed to flush log file: {}", e))?;
+        let lines = std::mem::take(&mut self.pending);
+        self.repo.index_lines(&self.info.execution_id, &lines)
+    }
+
+    /// Move the writer to its own thread. The returned sender only queues output,
+    /// so callers holding execution locks never wait on the file or the index.
+    pub fn spawn(self) -> ExecutionLogSender {
+        let (tx, rx) = mpsc::channel();
+        let handle = thread::spawn(move || self.run(rx));
+        ExecutionLogSender { tx, handle }
+    }
+
+    fn run(mut self, rx: Receiver<LogMessage>) -> Result<ExecutionLogInfo, String> {
+        let mut failed = false;
+        // A dropped sender leaves the log unfinished, like a crash
+        while let Ok(message) = rx.recv() {
+            match message {
+                LogMessage::Append {
+                    node,
+                    stream,
+                    timestamp,
+                    content,
+                } => {
+                    if failed {
+                        continue;
+                    }
+                    let node = node.as_ref().map(|(id, name)| (id.as_str(), name.as_str()));
+                    if let Err(e) = self.append(node, &stream, &timestamp, &content) {
+                        log::warn!("Stopped writing execution log: {}", e);
+                        failed = true;
+                    }
+                }
+                LogMessage::Finish(status) => {
+                    let result = self.finish(&status);
+                    if let Err(e) = &result {
+                        log::warn!("Failed to finish execution log: {}", e);
+                    }
+                    return result;
+                }
+            }
+        }
+        self.flush()?;
+        Ok(self.info)
+    }
+
+    /// Complete the log with the final status of the execution
+    pub fn finish(mut self, status: &str) -> Result<ExecutionLogInfo, String> {
+        let lines = std::mem::take(&mut self.pending);
+        self.repo.index_lines(&self.info.execution_id, &lines)?;
+        self.encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .map_err(|e| format!("Failed to finish log file: {}", e))?;
+
+        self.info.status = status.to_string();
+        self.info.finished_at = Some(Utc::now().to_rfc3339());
+        self.info.compressed_bytes = fs::metadata(&self.info.file_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+        self.repo.finish(&self.info)?;
+        Ok(self.info)
+    }
+}
+
+enum LogMessage {
+    Append {
+        node: Option<(String, String)>,
+        stream: String,
+        timestamp: String,
+        content: String,
+    },
+    Finish(String),
+}
+
+/// Queues output to an `ExecutionLogWriter` running on its own thread
+pub struct ExecutionLogSender {
+    tx: Sender<LogMessage>,
+    handle: JoinHandle<Result<ExecutionLogInfo, String>>,
+}
+
+impl ExecutionLogSender {
+    /// Queue output; `content` may hold several lines
+    pub fn append(&self, node: Option<(&str, &str)>, stream: &str, timestamp: &str, content: &str) {
+        let _ = self.tx.send(LogMessage::Append {
+            node: node.map(|(id, name)| (id.to_string(), name.to_string())),
+            stream: stream.to_string(),
+            timestamp: timestamp.to_string(),
+            content: content.to_string(),
+        });
+    }
+
+    /// Complete the log with the final status once the queued output is written.
+    /// Join the returned handle to wait for it.
+    pub fn finish(self, status: &str) -> JoinHandle<Result<ExecutionLogInfo, String>> {
+        let _ = self.tx.send(LogMessage::Finish(status.to_string()));
+        self.handle
+    }
+}
+
+/// Mark logs left "running" by a previous session as interrupted, with the sizes of
+/// what reached the file. Call at startup, before any execution starts.
+pub fn mark_interrupted_logs(db: &Database) -> Result<usize, String> {
+    let repo = ExecutionLogRepository::new(db.clone());
+    let unfinished: Vec<ExecutionLogInfo> = repo
+        .list(None, None)?
+        .into_iter()
+        .filter(|log| log.finished_at.is_none())
+        .collect();
+
+    for mut info in unfinished.iter().cloned() {
+        let lines = read_execution_log(Path::new(&info.file_path)).unwrap_or_default();
+        info.line_count = lines.len() as u64;
+        info.size_bytes = lines.iter().map(|l| l.content.len() as u64 + 1).sum();
+        info.compressed_bytes = fs::metadata(&info.file_path).map(|m| m.len()).unwrap_or(0);
+        info.status = INTERRUPTED_LOG_STATUS.to_string();
+        info.finished_at = Some(Utc::now().to_rfc3339());
+        repo.finish(&info)?;
+    }
+    Ok(unfinished.len())
+}
+
+/// Read all lines of a log file. A file cut short (e.g. the app quit mid-run)
+/// yields the lines written before the cut.
+pub fn read_execution_log(path: &Path) -> Result<Vec<ExecutionLogLine>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open log file: {}", e))?;
+    let decoder =
+        zstd::Decoder::new(file).map_err(|e| format!("Failed to create zstd decoder: {}", e))?;
+
+    let mut lines = Vec::new();
+    for line in BufReader::new(decoder).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Ok(line) = serde_json::from_str::<ExecutionLogLine>(&line) {
+            lines.push(line);
+        }
+    }
+    Ok(lines)
+}
+
+/// Read `limit` lines of an execution log starting at line `offset`
+pub fn read_execution_log_page(
+    db: &Database,
+    execution_id: &str,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<ExecutionLogPage, String> {
+    let info = ExecutionLogRepository::new(db.clone())
+        .get(execution_id)?
+        .ok_or_else(|| format!("No log for execution {}", execution_id))?;
+    let lines = read_execution_log(Path::new(&info.file_path))?;
+    let total_lines = lines.len() as u64;
+    let lines = lines
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(ExecutionLogPage {
+        info,
+        lines,
+        total_lines,
+    })
+}
+
+/// Search the lines of all logs (optionally of one workflow) for `query`
+pub fn search_execution_logs(
+    db: &Database,
+    query: &str,
+    workflow_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<ExecutionLogSearchHit>, String> {
+    let query = query.trim();
+    if query.chars().count() < MIN_LOG_SEARCH_LENGTH {
+        return Err(format!(
+            "Search query must be at least {} characters",
+            MIN_LOG_SEARCH_LENGTH
+        ));
+    }
+
+    let matches = ExecutionLogRepository::new(db.clone()).search(query, workflow_id, limit)?;
+
+    // Each log file is decompressed once
+    let mut files: HashMap<String, Vec<ExecutionLogLine>> = HashMap::new();
+    let mut hits = Vec::new();
+    for (log, line_no) in matches {
+        let lines = files
+            .entry(log.execution_id.clone())
+            .or_insert_with(|| read_execution_log(Path::new(&log.file_path)).unwrap_or_default());
+        if let Some(line) = lines.get(line_no as usize) {
+            hits.push(ExecutionLogSearchHit {
+                line: line.clone(),
+                log,
+            });
+        }
+    }
+    Ok(hits)
+}
+
+/// Logs to delete under `policy`. `logs` must be sorted newest first.
+/// Logs still being written only expire by age.
+pub fn select_expired_logs(
+    logs: &[ExecutionLogInfo],
+    policy: &LogRetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let cutoff =
+        (policy.retention_days > 0).then(|| now - Duration::days(policy.retention_days as i64));
+
+    let mut per_group: HashMap<(ExecutionLogKind, String), usize> = HashMap::new();
+    let mut total_bytes: u64 = 0;
+    let mut expired = Vec::new();
+
+    for log in logs {
+        let too_old = cutoff.is_some_and(|cutoff| {
+            DateTime::parse_from_rfc3339(&log.started_at)
+                .map(|started| started < cutoff)
+                .unwrap_or(false)
+        });
+        if log.finished_at.is_none() {
+            if too_old {
+                expired.push(log.execution_id.clone());
+            }
+            continue;
+        }
+
+        let group = match (&log.workflow_id, &log.project_path) {
+            (Some(workflow_id), _) => workflow_id.clone(),
+            (None, project_path) => format!(
+                "{}\n{}",
+                project_path.as_deref().unwrap_or_default(),
+                log.name
+            ),
+        };
+        let count = per_group.entry((log.kind, group)).or_default();
+        *count += 1;
+        total_bytes += log.compressed_bytes;
+
+        let over_count = *count > policy.keep_per_workflow;
+        let over_size = policy.max_total_bytes > 0 && total_bytes > policy.max_total_bytes;
+        if too_old || over_count || over_size {
+            expired.push(log.execution_id.clone());
+        }
+    }
+    expired
+}
+
+/// Delete the log of an execution (file and index)
+pub fn delete_execution_log(db: &Database, execution_id: &str) -> Result<bool, String> {
+    let repo = ExecutionLogRepository::new(db.clone());
+    let Some(info) = repo.get(execution_id)? else {
+        return Ok(false);
+    };
+    repo.delete(execution_id)?;
+    if let Err(e) = fs::remove_file(&info.file_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove log file {}: {}", info.file_path, e);
+        }
+    }
+    Ok(true)
+}
+
+/// Delete the logs expired under `policy`. Returns the number deleted.
+pub fn prune_execution_logs(db: &Database, policy: &LogRetentionPolicy) -> Result<usize, String> {
+    let logs = ExecutionLogRepository::new(db.clone()).list(None, None)?;
+    let expired = select_expired_logs(&logs, policy, Utc::now());
+    for execution_id in &expired {
+        delete_execution_log(db, execution_id)?;
+    }
+    Ok(expired.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(id: &str, workflow_id: &str, started_at: &str, bytes: u64) -> ExecutionLogInfo {
+        ExecutionLogInfo {
+            workflow_id: Some(workflow_id.to_string()),
+            started_at: started_at.to_string(),
+            finished_at: Some(started_at.to_string()),
+            compressed_bytes: bytes,
+            ..ExecutionLogInfo::new(id.to_string(), ExecutionLogKind::Workflow, "wf".to_string())
+        }
+    }
+
+    #[test]
+    fn test_write_search_and_read_log() {
+        let dir = std::env::temp_dir().join(format!("packageflow-logs-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+        crate::utils::schema::migrate(&db).unwrap();
+
+        let info = ExecutionLogInfo {
+            workflow_id: Some("wf-1".to_string()),
+            ..ExecutionLogInfo::new(
+                "exec-1".to_string(),
+                ExecutionLogKind::Workflow,
+                "Build".to_string(),
+            )
+        };
+        let mut writer = ExecutionLogWriter::create(db.clone(), &dir.join("logs"), info).unwrap();
+        let ts = "2025-01-01T00:00:00Z";
+        writer
+            .append(
+                Some(("install", "Install")),
+                "stdout",
+                ts,
+                "resolving packages",
+            )
+            .unwrap();
+        // Batched script output with several lines
+        writer
+            .append(
+                Some(("install", "Install")),
+                "stderr",
+                ts,
+                "npm ERR! network\nnpm ERR! read ECONNRESET\n",
+            )
+            .unwrap();
+        let info = writer.finish("failed").unwrap();
+        assert_eq!(info.line_count, 3);
+        assert_eq!(info.status, "failed");
+        assert!(info.compressed_bytes > 0);
+
+        let hits = search_execution_logs(&db, "econnreset", None, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].log.execution_id, "exec-1");
+        assert_eq!(hits[0].line.line_no, 2);
+        assert_eq!(hits[0].line.content, "npm ERR! read ECONNRESET");
+        assert_eq!(hits[0].line.node_id.as_deref(), Some("install"));
+
+        assert!(search_execution_logs(&db, "ERR", Some("wf-2"), 10)
+            .unwrap()
+            .is_empty());
+        assert!(search_execution_logs(&db, "np", None, 10).is_err());
+
+        let page = read_execution_log_page(&db, "exec-1", 1, Some(1)).unwrap();
+        assert_eq!(page.total_lines, 3);
+        assert_eq!(page.lines[0].content, "npm ERR! network");
+        assert_eq!(page.lines[0].stream, "stderr");
+
+        // Pruning removes the file and the index entries
+        let policy = LogRetentionPolicy {
+            keep_per_workflow: 0,
+            retention_days: 0,
+            max_total_bytes: 0,
+        };
+        assert_eq!(prune_execution_logs(&db, &policy).unwrap(), 1);
+        assert!(!Path::new(&info.file_path).exists());
+        assert!(search_execution_logs(&db, "ECONNRESET", None, 10)
+            .unwrap()
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_log_sender_and_interrupted_logs() {
+        let dir = std::env::temp_dir().join(format!("packageflow-logs-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(dir.join("test.db")).unwrap();
+        crate::utils::schema::migrate(&db).unwrap();
+        let new_log = |id: &str| {
+            let info =
+                ExecutionLogInfo::new(id.to_string(), ExecutionLogKind::Script, "dev".into());
+            ExecutionLogWriter::create(db.clone(), &dir.join("logs"), info).unwrap()
+        };
+        let ts = "2025-01-01T00:00:00Z";
+
+        let sender = new_log("exec-1").spawn();
+        sender.append(None, "stdout", ts, "ready on :3000\n");
+        sender.append(None, "stderr", ts, "warn: slow");
+        let info = sender.finish("completed").join().unwrap().unwrap();
+        assert_eq!(info.line_count, 2);
+        assert_eq!(info.status, "completed");
+
+        // The app quit while this one was running
+        let mut writer = new_log("exec-2");
+        writer.append(None, "stdout", ts, "building\n").unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(mark_interrupted_logs(&db).unwrap(), 1);
+        let repo = ExecutionLogRepository::new(db.clone());
+        let interrupted = repo.get("exec-2").unwrap().unwrap();
+        assert_eq!(interrupted.status, INTERRUPTED_LOG_STATUS);
+        assert_eq!(interrupted.line_count, 1);
+        assert!(interrupted.finished_at.is_some());
+        assert_eq!(repo.get("exec-1").unwrap().unwrap().status, "completed");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_select_expired_logs() {
+        let now = DateTime::parse_from_rfc3339("2025-03-31T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let logs = vec![
+            log("a3", "a", "2025-03-30T00:00:00Z", 100),
+            log("b1", "b", "2025-03-29T00:00:00Z", 100),
+            log("a2", "a", "2025-03-20T00:00:00Z", 100),
+            log("a1", "a", "2025-01-01T00:00:00Z", 100),
+        ];
+
+        let keep_two = LogRetentionPolicy {
+            keep_per_workflow: 2,
+            retention_days: 0,
+            max_total_bytes: 0,
+        };
+        assert_eq!(select_expired_logs(&logs, &keep_two, now), vec!["a1"]);
+
+        let by_age = LogRetentionPolicy {
+            keep_per_workflow: 10,
+            retention_days: 7,
+            max_total_bytes: 0,
+        };
+        assert_eq!(select_expired_logs(&logs, &by_age, now), vec!["a2", "a1"]);
+
+        let by_size = LogRetentionPolicy {
+            keep_per_workflow: 10,
+            retention_days: 0,
+            max_total_bytes: 250,
+        };
+        assert_eq!(select_expired_logs(&logs, &by_size, now), vec!["a2", "a1"]);
+
+        // Unfinished logs are kept unless too old
+        let mut running = log("r", "a", "2025-03-30T12:00:00Z", 0);
+        running.finished_at = None;
+        let keep_none = LogRetentionPolicy {
+            keep_per_workflow: 0,
+            ..by_age
+        };
+        assert!(select_expired_logs(&[running], &keep_none, now).is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+// Bun Lockfile Parser
+// Parses Bun's JSONC text lockfile (bun.lock) and decodes the binary bun.lockb via the bun CLI
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::{yarn, DependencyEdge, DependencyGraph, DependencyKind, LockfilePackage};
+use crate::models::snapshot::LockfileType;
+use crate::utils::path_resolver;
+
+/// Header every binary bun.lockb starts with
+pub const BUN_LOCKB_HEADER: &[u8] = b"#!/usr/bin/env bun\nbun-lockfile-format-v0\n";
+
+/// Dependency fields of a workspace or package and the kind of edge they produce
+const DEPENDENCY_FIELDS: [(&str, DependencyKind); 4] = [
+    ("dependencies", DependencyKind::Prod),
+    ("devDependencies", DependencyKind::Dev),
+    ("optionalDependencies", DependencyKind::Optional),
+    ("peerDependencies", DependencyKind::Peer),
+];
+
+/// Whether lockfile content is the binary bun.lockb format
+pub fn is_binary_lockfile(content: &[u8]) -> bool {
+    content.starts_with(BUN_LOCKB_HEADER)
+}
+
+/// Parse a Bun lockfile of either format. `lockfile_path` is only used to decode
+/// binary lockfiles, whose direct dependencies come from `manifest` (package.json).
+pub fn parse(
+    lockfile_path: &Path,
+    content: &[u8],
+    manifest: Option<&Value>,
+) -> Result<DependencyGraph, String> {
+    if !is_binary_lockfile(content) {
+        return parse_bun_lock(&String::from_utf8_lossy(content));
+    }
+
+    let yarn_lock = decode_bun_lockb(lockfile_path)?;
+    Ok(parse_bun_lockb_output(&yarn_lock, manifest))
+}
+
+// =============================================================================
+// bun.lock (JSONC)
+// =============================================================================
+
+/// Parse the text bun.lock format
+pub fn parse_bun_lock(content: &str) -> Result<DependencyGraph, String> {
+    let lockfile: Value = serde_json::from_str(&strip_jsonc(content))
+        .map_err(|e| format!("Failed to parse bun.lock: {}", e))?;
+
+    let trusted: HashSet<&str> = lockfile
+        .get("trustedDependencies")
+        .and_then(|t| t.as_array())
+        .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+        .unwrap_or_default();
+
+    let mut packages = Vec::new();
+    let mut package_infos = Vec::new();
+
+    if let Some(entries) = lockfile.get("packages").and_then(|p| p.as_object()) {
+        for (key, entry) in entries {
+            let Some(fields) = entry.as_array() else {
+                continue;
+            };
+            let Some((name, spec)) = fields
+                .first()
+                .and_then(|ident| ident.as_str())
+                .and_then(super::split_package_spec)
+            else {
+                continue;
+            };
+
+            // Workspace packages are the project's own code, not installed dependencies
+            if spec.starts_with("workspace:") {
+                continue;
+            }
+
+            let mut package = LockfilePackage::new(name, spec, key.as_str());
+            package.has_install_script = trusted.contains(name);
+            // npm packages: [ident, tarball url ("" for the default registry), info, integrity]
+            // Other sources (git, github, tarball, file, link) resolve to the spec itself
+            package.resolved = if spec.starts_with(|c: char| c.is_ascii_digit()) {
+                fields
+                    .get(1)
+                    .and_then(|r| r.as_str())
+                    .filter(|r| !r.is_empty())
+                    .map(|r| r.to_string())
+            } else {
+                Some(spec.to_string())
+            };
+            package.integrity = fields
+                .iter()
+                .skip(1)
+                .filter_map(|f| f.as_str())
+                .find(|f| is_integrity(f))
+                .map(|f| f.to_string());
+
+            packages.push(package);
+            package_infos.push(fields.iter().find(|f| f.is_object()));
+        }
+    }
+
+    // Resolve each dependency the way Bun installs it: nested under the package first,
+    // then under each ancestor, then at the root
+    let index: HashMap<&str, usize> = packages
+        .iter()
+        .enumerate()
+        .map(|(i, pkg)| (pkg.path.as_str(), i))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (parent, info) in package_infos.iter().enumerate() {
+        for (name, kind) in dependency_names(*info) {
+            // devDependencies of installed packages are never installed
+            if kind == DependencyKind::Dev {
+                continue;
+            }
+            if let Some(child) = resolve_nested(&index, &packages[parent].path, name) {
+                edges.push(DependencyEdge {
+                    parent: Some(parent),
+                    child,
+                    kind,
+                });
+            }
+        }
+    }
+
+    // Every workspace's manifest counts as the project's own
+    if let Some(workspaces) = lockfile.get("workspaces").and_then(|w| w.as_object()) {
+        for workspace in workspaces.values() {
+            for (name, kind) in dependency_names(Some(workspace)) {
+                if let Some(&child) = index.get(name) {
+                    edges.push(DependencyEdge {
+                        parent: None,
+                        child,
+                        kind,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(DependencyGraph::new(LockfileType::Bun, packages, edges))
+}
+
+/// `(name, kind)` of the dependencies a workspace or package info object lists
+fn dependency_names(info: Option<&Value>) -> Vec<(&str, DependencyKind)> {
+    let mut names = Vec::new();
+    for (field, kind) in DEPENDENCY_FIELDS {
+        if let Some(deps) = info.and_then(|i| i.get(field)).and_then(|d| d.as_object()) {
+            names.extend(deps.keys().map(|name| (name.as_str(), kind)));
+        }
+    }
+    names
+}
+
+/// Strip comments and trailing commas so JSONC can be read as JSON
+pub fn strip_jsonc(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    let mut i = 0;
+
+    // Next character after `from` that is not whitespace or a comment
+    let next_significant = |mut from: usize| -> Option<char> {
+        while from < chars.len() {
+            match chars[from] {
+                c if c.is_whitespace() => from += 1,
+                '/' if chars.get(from + 1) == Some(&'/') => {
+                    while from < chars.len() && chars[from] != '\n' {
+                        from += 1;
+                    }
+                }
+                '/' if chars.get(from + 1) == Some(&'*') => {
+                    from += 2;
+                    while from < chars.len()
+                        && !(chars[from] == '*' && chars.get(from + 1) == Some(&'/'))
+                    {
+                        from += 1;
+                    }
+                    from += 2;
+                }
+                c => return Some(c),
+            }
+        }
+        None
+    };
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => {
+                // Copy strings verbatim, including escaped quotes
+                output.push('"');
+                i += 1;
+                while i < chars.len() {
+                    output.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        output.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == '"' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' if matches!(next_significant(i + 1), Some('}') | Some(']')) => {
+                i += 1;
+            }
+            c => {
+                output.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    output
+}
+
+/// Find the package a dependency of `parent_key` resolves to
+fn resolve_nested(index: &HashMap<&str, usize>, parent_key: &str, name: &str) -> Option<usize> {
+    let segments = key_segments(parent_key);
+    (0..=segments.len()).rev().find_map(|depth| {
+        let mut key = segments[..depth].join("/");
+        if !key.is_empty() {
+            key.push('/');
+        }
+        key.push_str(name);
+        index.get(key.as_str()).copied()
+    })
+}
+
+/// Split an install key into package names (`a/@scope/b` -> [`a`, `@scope/b`])
+fn key_segments(key: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    let mut parts = key.split('/');
+    while let Some(part) = parts.next() {
+        if part.starts_with('@') {
+            segments.push(format!("{}/{}", part, parts.next().unwrap_or_default()));
+        } else {
+            segments.push(part.to_string());
+        }
+    }
+    segments
+}
+
+// =============================================================================
+// bun.lockb (binary)
+// =============================================================================
+
+/// Decode a binary bun.lockb into yarn.lock text with the bun CLI
+pub fn decode_bun_lockb(lockfile_path: &Path) -> Result<String, String> {
+    let mut cmd = path_resolver::create_command("bun");
+    cmd.arg(lockfile_path);
+    if let Some(dir) = lockfile_path.parent() {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run bun to decode bun.lockb: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to decode bun.lockb: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parse the yarn.lock text `bun bun.lockb` prints; `trustedDependencies` in the
+/// manifest marks packages whose lifecycle scripts Bun runs
+pub fn parse_bun_lockb_output(content: &str, manifest: Option<&Value>) -> DependencyGraph {
+    let (mut packages, edges) = yarn::parse_classic(content, manifest);
+
+    let trusted: HashSet<&str> = manifest
+        .and_then(|m| m.get("trustedDependencies"))
+        .and_then(|t| t.as_array())
+        .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+        .unwrap_or_default();
+    for package in &mut packages {
+        package.has_install_script = trusted.contains(package.name.as_str());
+    }
+
+    DependencyGraph::new(LockfileType::Bun, packages, edges)
+}
+
+/// Whether a string is a Subresource Integrity hash
+fn is_integrity(value: &str) -> bool {
+    ["sha512-", "sha384-", "sha256-", "sha1-"]
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_fixture_graph, find, manifest};
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let stripped = strip_jsonc(r#"{ "a": "x // not a comment", /* c */ "b": [1, 2,], }"#);
+        let value: Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value["a"], "x // not a comment");
+        assert_eq!(value["b"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_parse_bun_lock_fixture() {
+        let graph = parse_bun_lock(include_str!("fixtures/bun.lock")).unwrap();
+        assert_fixture_graph(&graph);
+
+        let (_, fsevents) = find(&graph, "fsevents");
+        assert!(fsevents.has_install_script);
+        assert_eq!(fsevents.resolved, None);
+    }
+
+    #[test]
+    fn test_parse_bun_lock_nested_and_non_registry() {
+        let content = r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": { "name": "app", "dependencies": { "react": "^18.2.0", "esbuild": "github:evanw/esbuild" } },
+    "packages/ui": { "name": "@app/ui" },
+  },
+  "packages": {
+    "@app/ui": ["@app/ui@workspace:packages/ui"],
+    "esbuild": ["esbuild@github:evanw/esbuild#abc123", {}, "evanw-esbuild-abc123"],
+    "js-tokens": ["js-tokens@4.0.0", "", {}, "sha512-jst"],
+    "react": ["react@18.2.0", "https://npm.example.com/react/-/react-18.2.0.tgz", { "dependencies": { "js-tokens": "^3.0.0" } }, "sha512-react"],
+    // nested install of an older version
+    "react/js-tokens": ["js-tokens@3.0.2", "", {}, "sha512-old"],
+  },
+}"#;
+        let graph = parse_bun_lock(content).unwrap();
+        assert_eq!(graph.packages.len(), 4);
+
+        let (react, package) = find(&graph, "react");
+        assert!(package
+            .resolved
+            .as_deref()
+            .unwrap()
+            .starts_with("https://npm.example.com/"));
+        let children: Vec<_> = graph
+            .children(react)
+            .map(|e| &graph.packages[e.child])
+            .collect();
+        assert_eq!(children.len(), 1);
+        assert_eq!(
+            (children[0].path.as_str(), children[0].version.as_str()),
+            ("react/js-tokens", "3.0.2")
+        );
+
+        let (_, esbuild) = find(&graph, "esbuild");
+        assert!(esbuild.is_direct);
+        assert_eq!(esbuild.integrity, None);
+        assert_eq!(
+            esbuild.resolved.as_deref(),
+            Some("github:evanw/esbuild#abc123")
+        );
+    }
+
+    #[test]
+    fn test_parse_bun_lockb_output_fixture() {
+        let graph =
+            parse_bun_lockb_output(include_str!("fixtures/bun.lockb.txt"), Some(&manifest()));
+        assert_fixture_graph(&graph);
+        assert_eq!(graph.lockfile_type, LockfileType::Bun);
+        assert!(find(&graph, "fsevents").1.has_install_script);
+        assert!(is_binary_lockfile(
+            b"#!/usr/bin/env bun\nbun-lockfile-format-v0\n\x02\x00"
+        ));
+    }
+}
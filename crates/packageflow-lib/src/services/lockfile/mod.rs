@@ -0,0 +1,313 @@
+// Lockfile Parsing
+// Normalizes npm, pnpm, Yarn and Bun lockfiles into one dependency graph shared by
+// snapshot capture, replay and dependency integrity checks
+
+pub mod bun;
+pub mod npm;
+pub mod pnpm;
+pub mod yarn;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use crate::models::snapshot::DependencyKind;
+use crate::models::snapshot::LockfileType;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A package installed from a lockfile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockfilePackage {
+    pub name: String,
+    pub version: String,
+    /// Unique install path: `node_modules/a/node_modules/b` (npm), `a/b` (Bun),
+    /// `name@version` (pnpm, Yarn)
+    pub path: String,
+    pub is_direct: bool,
+    /// Only needed by dev dependencies
+    pub is_dev: bool,
+    /// Only needed through optional dependencies
+    pub is_optional: bool,
+    /// Only needed through peer dependencies
+    pub is_peer: bool,
+    pub has_install_script: bool,
+    pub integrity: Option<String>,
+    pub resolved: Option<String>,
+}
+
+impl LockfilePackage {
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            path: path.into(),
+            is_direct: false,
+            is_dev: false,
+            is_optional: false,
+            is_peer: false,
+            has_install_script: false,
+            integrity: None,
+            resolved: None,
+        }
+    }
+}
+
+/// A parent -> child dependency; `parent` is `None` for the project's own manifests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub parent: Option<usize>,
+    pub child: usize,
+    pub kind: DependencyKind,
+}
+
+/// Packages of a lockfile and the edges between them (indexes into `packages`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub lockfile_type: LockfileType,
+    pub packages: Vec<LockfilePackage>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Build a graph, deriving the direct, dev, optional and peer flags from the edges
+    pub fn new(
+        lockfile_type: LockfileType,
+        mut packages: Vec<LockfilePackage>,
+        edges: Vec<DependencyEdge>,
+    ) -> Self {
+        let mut seen = HashSet::new();
+        let edges: Vec<DependencyEdge> = edges.into_iter().filter(|e| seen.insert(*e)).collect();
+
+        let graph = Self {
+            lockfile_type,
+            packages: Vec::new(),
+            edges,
+        };
+        let all = graph.reachable(|_| true);
+        let without_dev = graph.reachable(|kind| kind != DependencyKind::Dev);
+        let without_optional = graph.reachable(|kind| kind != DependencyKind::Optional);
+        let without_peer = graph.reachable(|kind| kind != DependencyKind::Peer);
+
+        for (i, package) in packages.iter_mut().enumerate() {
+            package.is_direct = graph
+                .edges
+                .iter()
+                .any(|e| e.parent.is_none() && e.child == i);
+            if all.contains(&i) {
+                package.is_dev = !without_dev.contains(&i);
+                package.is_optional = !without_optional.contains(&i);
+                package.is_peer = !without_peer.contains(&i);
+            }
+        }
+
+        Self { packages, ..graph }
+    }
+
+    /// Edges pointing at a package
+    pub fn parents(&self, index: usize) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges.iter().filter(move |e| e.child == index)
+    }
+
+    /// Edges leaving a package
+    pub fn children(&self, index: usize) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges.iter().filter(move |e| e.parent == Some(index))
+    }
+
+    /// Packages reachable from the project following only edges of the given kinds
+    fn reachable(&self, follow: impl Fn(DependencyKind) -> bool) -> HashSet<usize> {
+        let mut children: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+        for edge in self.edges.iter().filter(|e| follow(e.kind)) {
+            children.entry(edge.parent).or_default().push(edge.child);
+        }
+
+        let mut seen = HashSet::new();
+        let mut queue: VecDeque<usize> = children.get(&None).cloned().unwrap_or_default().into();
+        while let Some(i) = queue.pop_front() {
+            if seen.insert(i) {
+                queue.extend(children.get(&Some(i)).into_iter().flatten());
+            }
+        }
+        seen
+    }
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// Parse a lockfile into a dependency graph. The package.json next to the lockfile
+/// supplies what some formats don't record (direct dependencies of npm v1, Yarn and
+/// bun.lockb, dev dependencies of Yarn Berry).
+pub fn parse_lockfile(
+    lockfile_type: &LockfileType,
+    lockfile_path: &Path,
+    content: &[u8],
+) -> Result<DependencyGraph, String> {
+    let manifest = lockfile_path
+        .parent()
+        .and_then(|dir| std::fs::read(dir.join("package.json")).ok())
+        .and_then(|content| serde_json::from_slice::<Value>(&content).ok());
+
+    match lockfile_type {
+        LockfileType::Npm => npm::parse(&String::from_utf8_lossy(content), manifest.as_ref()),
+        LockfileType::Pnpm => pnpm::parse(&String::from_utf8_lossy(content)),
+        LockfileType::Yarn => yarn::parse(&String::from_utf8_lossy(content), manifest.as_ref()),
+        LockfileType::Bun => bun::parse(lockfile_path, content, manifest.as_ref()),
+    }
+}
+
+/// Split `name@spec` into name and spec (handles `@scope/name@spec`)
+pub(crate) fn split_package_spec(spec: &str) -> Option<(&str, &str)> {
+    let at = spec.get(1..)?.find('@')? + 1;
+    Some((&spec[..at], &spec[at + 1..]))
+}
+
+/// `(name, range, kind)` of the dependencies a package.json declares
+pub(crate) fn manifest_dependencies(manifest: &Value) -> Vec<(String, String, DependencyKind)> {
+    let fields = [
+        ("dependencies", DependencyKind::Prod),
+        ("devDependencies", DependencyKind::Dev),
+        ("optionalDependencies", DependencyKind::Optional),
+        ("peerDependencies", DependencyKind::Peer),
+    ];
+    let mut dependencies = Vec::new();
+    for (field, kind) in fields {
+        if let Some(deps) = manifest.get(field).and_then(|d| d.as_object()) {
+            for (name, range) in deps {
+                let range = range.as_str().unwrap_or_default().to_string();
+                dependencies.push((name.clone(), range, kind));
+            }
+        }
+    }
+    dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const MANIFEST: &str = include_str!("fixtures/package.json");
+
+    pub(super) fn manifest() -> Value {
+        serde_json::from_str(MANIFEST).unwrap()
+    }
+
+    pub(super) fn find<'a>(graph: &'a DependencyGraph, name: &str) -> (usize, &'a LockfilePackage) {
+        graph
+            .packages
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.name == name)
+            .unwrap_or_else(|| panic!("{} not in graph", name))
+    }
+
+    /// Every fixture locks the same project (see fixtures/package.json)
+    pub(super) fn assert_fixture_graph(graph: &DependencyGraph) {
+        let mut versions: Vec<_> = graph
+            .packages
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                "@types/react@18.2.45",
+                "csstype@3.1.3",
+                "fsevents@2.3.3",
+                "js-tokens@4.0.0",
+                "loose-envify@1.4.0",
+                "react@18.2.0",
+                "typescript@5.3.3",
+            ]
+        );
+
+        let flags = |name: &str| {
+            let (_, p) = find(graph, name);
+            (p.is_direct, p.is_dev, p.is_optional)
+        };
+        assert_eq!(flags("react"), (true, false, false));
+        assert_eq!(flags("loose-envify"), (false, false, false));
+        assert_eq!(flags("js-tokens"), (false, false, false));
+        assert_eq!(flags("typescript"), (true, true, false));
+        assert_eq!(flags("@types/react"), (true, true, false));
+        assert_eq!(flags("csstype"), (false, true, false));
+        assert_eq!(flags("fsevents"), (true, false, true));
+
+        let (react, _) = find(graph, "react");
+        let (loose_envify, _) = find(graph, "loose-envify");
+        let (js_tokens, _) = find(graph, "js-tokens");
+        assert!(graph.children(react).any(|e| e.child == loose_envify));
+        assert!(graph
+            .parents(js_tokens)
+            .any(|e| e.parent == Some(loose_envify)));
+        assert!(find(graph, "react").1.integrity.is_some());
+    }
+
+    #[test]
+    fn test_graph_flags() {
+        let packages = [
+            "app-dep",
+            "dev-tool",
+            "shared",
+            "peer-only",
+            "optional-native",
+        ]
+        .iter()
+        .map(|name| LockfilePackage::new(*name, "1.0.0", *name))
+        .collect();
+        let edge = |parent, child, kind| DependencyEdge {
+            parent,
+            child,
+            kind,
+        };
+        let graph = DependencyGraph::new(
+            LockfileType::Npm,
+            packages,
+            vec![
+                edge(None, 0, DependencyKind::Prod),
+                edge(None, 1, DependencyKind::Dev),
+                edge(Some(0), 2, DependencyKind::Prod),
+                edge(Some(1), 2, DependencyKind::Prod),
+                edge(Some(0), 3, DependencyKind::Peer),
+                edge(Some(1), 4, DependencyKind::Optional),
+                edge(Some(1), 4, DependencyKind::Optional),
+            ],
+        );
+
+        assert_eq!(graph.edges.len(), 6);
+        let p = &graph.packages;
+        assert!(p[0].is_direct && !p[0].is_dev);
+        assert!(p[1].is_direct && p[1].is_dev);
+        assert!(!p[2].is_direct && !p[2].is_dev);
+        assert!(p[3].is_peer && !p[3].is_dev);
+        assert!(p[4].is_optional && p[4].is_dev);
+        assert_eq!(graph.parents(2).count(), 2);
+    }
+
+    #[test]
+    fn test_split_package_spec() {
+        assert_eq!(
+            split_package_spec("react@18.2.0"),
+            Some(("react", "18.2.0"))
+        );
+        assert_eq!(
+            split_package_spec("@types/react@npm:^18.2.0"),
+            Some(("@types/react", "npm:^18.2.0"))
+        );
+        assert_eq!(split_package_spec("react"), None);
+        assert_eq!(split_package_spec(""), None);
+    }
+}
@@ -0,0 +1,287 @@
+// npm Lockfile Parser
+// package-lock.json / npm-shrinkwrap.json: v1 (`dependencies` tree) and v2-v3 (`packages` map)
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use super::{
+    manifest_dependencies, DependencyEdge, DependencyGraph, DependencyKind, LockfilePackage,
+};
+use crate::models::snapshot::LockfileType;
+
+/// Dependency fields of a lockfile entry and the kind of edge they produce
+const DEPENDENCY_FIELDS: [(&str, DependencyKind); 4] = [
+    ("dependencies", DependencyKind::Prod),
+    ("devDependencies", DependencyKind::Dev),
+    ("optionalDependencies", DependencyKind::Optional),
+    ("peerDependencies", DependencyKind::Peer),
+];
+
+/// Parse package-lock.json. `manifest` (package.json) supplies the direct
+/// dependencies of v1 lockfiles.
+pub fn parse(content: &str, manifest: Option<&Value>) -> Result<DependencyGraph, String> {
+    let lockfile: Value = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse package-lock.json: {}", e))?;
+
+    match lockfile.get("packages").and_then(|p| p.as_object()) {
+        Some(packages) => Ok(parse_packages(packages)),
+        None => Ok(parse_v1(&lockfile, manifest)),
+    }
+}
+
+/// v2-v3: flat map of install paths (`node_modules/a/node_modules/b`)
+fn parse_packages(entries: &Map<String, Value>) -> DependencyGraph {
+    let mut packages = Vec::new();
+    let mut index = HashMap::new();
+    for (path, entry) in entries {
+        // The root, workspace folders and links to them are the project's own code
+        if !path.contains("node_modules/") || flag(entry, "link") {
+            continue;
+        }
+        let name = entry
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_else(|| package_name(path));
+
+        let mut package =
+            LockfilePackage::new(name, string(entry, "version").unwrap_or("unknown"), path);
+        package.has_install_script = flag(entry, "hasInstallScript");
+        package.integrity = string(entry, "integrity").map(|s| s.to_string());
+        package.resolved = string(entry, "resolved").map(|s| s.to_string());
+        index.insert(path.as_str(), packages.len());
+        packages.push(package);
+    }
+
+    let mut edges = Vec::new();
+    for (path, entry) in entries {
+        if flag(entry, "link") {
+            continue;
+        }
+        // The root and workspace folders are the project's manifests
+        let parent = if path.contains("node_modules/") {
+            match index.get(path.as_str()) {
+                Some(&i) => Some(i),
+                None => continue,
+            }
+        } else {
+            None
+        };
+
+        for (field, kind) in DEPENDENCY_FIELDS {
+            // devDependencies of installed packages are never installed
+            if parent.is_some() && kind == DependencyKind::Dev {
+                continue;
+            }
+            for name in object_keys(entry.get(field)) {
+                if let Some(child) = resolve(&index, path, name) {
+                    edges.push(DependencyEdge {
+                        parent,
+                        child,
+                        kind,
+                    });
+                }
+            }
+        }
+    }
+
+    DependencyGraph::new(LockfileType::Npm, packages, edges)
+}
+
+/// v1: nested `dependencies` tree with `requires` ranges
+fn parse_v1(lockfile: &Value, manifest: Option<&Value>) -> DependencyGraph {
+    let mut packages = Vec::new();
+    let mut requires = Vec::new();
+    let mut queue: Vec<(String, &Map<String, Value>)> = Vec::new();
+    if let Some(deps) = lockfile.get("dependencies").and_then(|d| d.as_object()) {
+        queue.push((String::new(), deps));
+    }
+
+    while let Some((base, deps)) = queue.pop() {
+        for (name, entry) in deps {
+            let path = if base.is_empty() {
+                format!("node_modules/{}", name)
+            } else {
+                format!("{}/node_modules/{}", base, name)
+            };
+
+            let mut package = LockfilePackage::new(
+                name.as_str(),
+                string(entry, "version").unwrap_or("unknown"),
+                path.as_str(),
+            );
+            package.integrity = string(entry, "integrity").map(|s| s.to_string());
+            package.resolved = string(entry, "resolved").map(|s| s.to_string());
+            packages.push(package);
+            requires.push((
+                object_keys(entry.get("requires")),
+                flag(entry, "dev"),
+                flag(entry, "optional"),
+            ));
+
+            if let Some(nested) = entry.get("dependencies").and_then(|d| d.as_object()) {
+                queue.push((path, nested));
+            }
+        }
+    }
+
+    let index: HashMap<&str, usize> = packages
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.path.as_str(), i))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (i, (names, _, _)) in requires.iter().enumerate() {
+        for name in names {
+            if let Some(child) = resolve(&index, &packages[i].path, name) {
+                edges.push(DependencyEdge {
+                    parent: Some(i),
+                    child,
+                    kind: DependencyKind::Prod,
+                });
+            }
+        }
+    }
+
+    match manifest {
+        Some(manifest) => {
+            for (name, _, kind) in manifest_dependencies(manifest) {
+                if let Some(child) = resolve(&index, "", &name) {
+                    edges.push(DependencyEdge {
+                        parent: None,
+                        child,
+                        kind,
+                    });
+                }
+            }
+        }
+        // Without package.json, top-level packages nothing requires are the direct ones
+        None => {
+            for (i, (_, dev, optional)) in requires.iter().enumerate() {
+                let top_level = packages[i].path.matches("node_modules/").count() == 1;
+                if top_level && !edges.iter().any(|e| e.child == i) {
+                    let kind = match (dev, optional) {
+                        (true, _) => DependencyKind::Dev,
+                        (_, true) => DependencyKind::Optional,
+                        _ => DependencyKind::Prod,
+                    };
+                    edges.push(DependencyEdge {
+                        parent: None,
+                        child: i,
+                        kind,
+                    });
+                }
+            }
+        }
+    }
+
+    DependencyGraph::new(LockfileType::Npm, packages, edges)
+}
+
+/// Resolve a dependency the way Node does: the nearest `node_modules/<name>` walking
+/// up from the dependent's install path
+fn resolve(index: &HashMap<&str, usize>, from: &str, name: &str) -> Option<usize> {
+    let mut base = from;
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{}", name)
+        } else {
+            format!("{}/node_modules/{}", base, name)
+        };
+        if let Some(&i) = index.get(candidate.as_str()) {
+            return Some(i);
+        }
+        if base.is_empty() {
+            return None;
+        }
+        // Top-level packages and workspace folders fall back to the root's node_modules
+        base = base.rfind("/node_modules/").map_or("", |pos| &base[..pos]);
+    }
+}
+
+/// Package name from an install path (`node_modules/a/node_modules/@s/b` -> `@s/b`)
+fn package_name(path: &str) -> &str {
+    path.rsplit_once("node_modules/")
+        .map(|(_, name)| name)
+        .unwrap_or(path)
+}
+
+fn string<'a>(entry: &'a Value, field: &str) -> Option<&'a str> {
+    entry.get(field).and_then(|v| v.as_str())
+}
+
+fn flag(entry: &Value, field: &str) -> bool {
+    entry.get(field).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+fn object_keys(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(|v| v.as_object())
+        .map(|o| o.keys().map(|k| k.as_str()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_fixture_graph, find, manifest};
+    use super::*;
+
+    #[test]
+    fn test_parse_v3_fixture() {
+        let graph = parse(include_str!("fixtures/package-lock.v3.json"), None).unwrap();
+        assert_fixture_graph(&graph);
+
+        let (_, fsevents) = find(&graph, "fsevents");
+        assert!(fsevents.has_install_script);
+        assert_eq!(fsevents.path, "node_modules/fsevents");
+        assert_eq!(
+            fsevents.resolved.as_deref(),
+            Some("https://registry.npmjs.org/fsevents/-/fsevents-2.3.3.tgz")
+        );
+    }
+
+    #[test]
+    fn test_parse_v1_fixture() {
+        let content = include_str!("fixtures/package-lock.v1.json");
+        assert_fixture_graph(&parse(content, Some(&manifest())).unwrap());
+
+        // Without package.json the lockfile's own flags pick the direct dependencies
+        let graph = parse(content, None).unwrap();
+        let (_, typescript) = find(&graph, "typescript");
+        assert!(typescript.is_direct && typescript.is_dev);
+        assert!(!find(&graph, "loose-envify").1.is_direct);
+    }
+
+    #[test]
+    fn test_nested_packages_resolve_nearest_install() {
+        let content = r#"{
+          "lockfileVersion": 3,
+          "packages": {
+            "": { "dependencies": { "a": "^1.0.0", "b": "^2.0.0" } },
+            "node_modules/a": { "version": "1.0.0", "dependencies": { "b": "^1.0.0" } },
+            "node_modules/a/node_modules/b": { "version": "1.0.0" },
+            "node_modules/b": { "version": "2.0.0" },
+            "packages/web": { "name": "web", "dependencies": { "b": "^2.0.0" } },
+            "node_modules/web": { "resolved": "packages/web", "link": true }
+          }
+        }"#;
+        let graph = parse(content, None).unwrap();
+        assert_eq!(graph.packages.len(), 3);
+
+        let nested = graph
+            .packages
+            .iter()
+            .position(|p| p.path == "node_modules/a/node_modules/b")
+            .unwrap();
+        assert_eq!(graph.packages[nested].name, "b");
+        assert_eq!(graph.packages[nested].version, "1.0.0");
+        assert!(!graph.packages[nested].is_direct);
+
+        let (a, _) = find(&graph, "a");
+        assert_eq!(
+            graph.children(a).map(|e| e.child).collect::<Vec<_>>(),
+            vec![nested]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+// pnpm Lockfile Parser
+// pnpm-lock.yaml v5-v9. v9 splits package metadata (`packages`) from the resolved
+// dependency graph (`snapshots`); earlier versions keep both in `packages`.
+
+use std::collections::HashMap;
+
+use serde_yaml::Value;
+
+use super::{split_package_spec, DependencyEdge, DependencyGraph, DependencyKind, LockfilePackage};
+use crate::models::snapshot::LockfileType;
+
+/// Dependency fields of an importer or snapshot and the kind of edge they produce
+const DEPENDENCY_FIELDS: [(&str, DependencyKind); 3] = [
+    ("dependencies", DependencyKind::Prod),
+    ("devDependencies", DependencyKind::Dev),
+    ("optionalDependencies", DependencyKind::Optional),
+];
+
+/// Parse pnpm-lock.yaml
+pub fn parse(content: &str) -> Result<DependencyGraph, String> {
+    let lockfile: Value = serde_yaml::from_str(content)
+        .map_err(|e| format!("Failed to parse pnpm-lock.yaml: {}", e))?;
+
+    let version = match lockfile.get("lockfileVersion") {
+        Some(Value::String(v)) => v.parse::<f64>().unwrap_or(0.0),
+        Some(Value::Number(v)) => v.as_f64().unwrap_or(0.0),
+        _ => 0.0,
+    };
+    let format = if version >= 9.0 {
+        KeyFormat::V9
+    } else if version >= 6.0 {
+        KeyFormat::V6
+    } else {
+        KeyFormat::V5
+    };
+
+    let infos = lockfile.get("packages").and_then(|p| p.as_mapping());
+    let nodes = match format {
+        KeyFormat::V9 => lockfile
+            .get("snapshots")
+            .and_then(|s| s.as_mapping())
+            .or(infos),
+        _ => infos,
+    };
+
+    let mut packages = Vec::new();
+    let mut index = HashMap::new();
+    for (key, node) in nodes.into_iter().flatten() {
+        let Some(path) = key.as_str().map(|k| k.trim_start_matches('/')) else {
+            continue;
+        };
+        let (name, version) = format.split_key(path);
+
+        // v9 keeps metadata under the key without the peer suffix
+        let info = match format {
+            KeyFormat::V9 => infos.and_then(|i| i.get(strip_peer_suffix(path))),
+            _ => Some(node),
+        };
+        let field = |name: &str| info.and_then(|i| i.get(name));
+
+        let mut package = LockfilePackage::new(
+            field("name").and_then(|n| n.as_str()).unwrap_or(name),
+            field("version").and_then(|v| v.as_str()).unwrap_or(version),
+            path,
+        );
+        package.has_install_script = field("requiresBuild")
+            .and_then(|r| r.as_bool())
+            .unwrap_or(false);
+        let resolution = field("resolution");
+        package.integrity = resolution
+            .and_then(|r| r.get("integrity"))
+            .and_then(|i| i.as_str())
+            .map(|i| i.to_string());
+        package.resolved = resolution.and_then(resolved_url);
+        index.insert(path.to_string(), packages.len());
+        packages.push(package);
+    }
+
+    let mut edges = Vec::new();
+    for (key, node) in nodes.into_iter().flatten() {
+        let Some(&parent) = key
+            .as_str()
+            .and_then(|k| index.get(k.trim_start_matches('/')))
+        else {
+            continue;
+        };
+        for (name, reference, kind) in dependencies(node) {
+            if let Some(child) = format.resolve(&index, &name, &reference) {
+                edges.push(DependencyEdge {
+                    parent: Some(parent),
+                    child,
+                    kind,
+                });
+            }
+        }
+    }
+
+    // Workspaces list each project under `importers`; single projects use top-level fields
+    let importers: Vec<&Value> = match lockfile.get("importers").and_then(|i| i.as_mapping()) {
+        Some(importers) => importers.values().collect(),
+        None => vec![&lockfile],
+    };
+    for importer in importers {
+        for (name, reference, kind) in dependencies(importer) {
+            if let Some(child) = format.resolve(&index, &name, &reference) {
+                edges.push(DependencyEdge {
+                    parent: None,
+                    child,
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(DependencyGraph::new(LockfileType::Pnpm, packages, edges))
+}
+
+/// Package key layout per lockfile version
+#[derive(Clone, Copy)]
+enum KeyFormat {
+    /// `/name/1.0.0_peer@1.0.0`
+    V5,
+    /// `/name@1.0.0(peer@1.0.0)`
+    V6,
+    /// `name@1.0.0(peer@1.0.0)`
+    V9,
+}
+
+impl KeyFormat {
+    /// Name and version of a package key
+    fn split_key(self, key: &str) -> (&str, &str) {
+        let split = match self {
+            Self::V5 => key
+                .rsplit_once('/')
+                .map(|(name, version)| (name, version.split('_').next().unwrap_or(version))),
+            _ => split_package_spec(strip_peer_suffix(key)),
+        };
+        split.unwrap_or((key, "unknown"))
+    }
+
+    /// Package a dependency reference (`1.0.0`, `1.0.0(peer@1.0.0)`, an alias or a
+    /// full key) points at. Linked workspace packages resolve to nothing.
+    fn resolve(self, index: &HashMap<String, usize>, name: &str, reference: &str) -> Option<usize> {
+        if reference.starts_with("link:") {
+            return None;
+        }
+        let key = if !reference.starts_with(|c: char| c.is_ascii_digit()) {
+            reference.trim_start_matches('/').to_string()
+        } else {
+            match self {
+                Self::V5 => format!("{}/{}", name, reference),
+                _ => format!("{}@{}", name, reference),
+            }
+        };
+        index.get(&key).copied()
+    }
+}
+
+/// `(name, reference, kind)` of a node's dependencies. Importers list
+/// `{specifier, version}` since v6 and plain versions before.
+fn dependencies(node: &Value) -> Vec<(String, String, DependencyKind)> {
+    let mut dependencies = Vec::new();
+    for (field, kind) in DEPENDENCY_FIELDS {
+        let Some(deps) = node.get(field).and_then(|d| d.as_mapping()) else {
+            continue;
+        };
+        for (name, value) in deps {
+            let reference = value
+                .get("version")
+                .unwrap_or(value)
+                .as_str()
+                .map(|r| r.to_string());
+            if let (Some(name), Some(reference)) = (name.as_str(), reference) {
+                dependencies.push((name.to_string(), reference, kind));
+            }
+        }
+    }
+    dependencies
+}
+
+/// Drop the peer dependency suffix of a key (`a@1.0.0(b@2.0.0)` -> `a@1.0.0`)
+fn strip_peer_suffix(key: &str) -> &str {
+    key.split('(').next().unwrap_or(key)
+}
+
+/// Tarball URL or git repository a package was resolved from
+fn resolved_url(resolution: &Value) -> Option<String> {
+    if let Some(tarball) = resolution.get("tarball").and_then(|t| t.as_str()) {
+        return Some(tarball.to_string());
+    }
+    let repo = resolution.get("repo").and_then(|r| r.as_str())?;
+    match resolution.get("commit").and_then(|c| c.as_str()) {
+        Some(commit) => Some(format!("{}#{}", repo, commit)),
+        None => Some(repo.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_fixture_graph, find};
+    use super::*;
+
+    #[test]
+    fn test_parse_v6_fixture() {
+        let graph = parse(include_str!("fixtures/pnpm-lock.v6.yaml")).unwrap();
+        assert_fixture_graph(&graph);
+
+        let (_, fsevents) = find(&graph, "fsevents");
+        assert!(fsevents.has_install_script);
+        assert_eq!(fsevents.path, "fsevents@2.3.3");
+        assert_eq!(fsevents.integrity.as_deref(), Some("sha512-fsevents"));
+    }
+
+    #[test]
+    fn test_parse_v9_fixture() {
+        let graph = parse(include_str!("fixtures/pnpm-lock.v9.yaml")).unwrap();
+        assert_fixture_graph(&graph);
+        assert_eq!(find(&graph, "@types/react").1.path, "@types/react@18.2.45");
+    }
+
+    #[test]
+    fn test_peer_suffixes_and_v5_keys() {
+        let v9 = r#"
+lockfileVersion: '9.0'
+importers:
+  .:
+    dependencies:
+      react-dom:
+        specifier: ^18.2.0
+        version: 18.2.0(react@18.2.0)
+packages:
+  react@18.2.0:
+    resolution: {integrity: sha512-react}
+  react-dom@18.2.0:
+    resolution: {integrity: sha512-react-dom}
+snapshots:
+  react@18.2.0: {}
+  react-dom@18.2.0(react@18.2.0):
+    dependencies:
+      react: 18.2.0
+"#;
+        let graph = parse(v9).unwrap();
+        let (react_dom, package) = find(&graph, "react-dom");
+        assert_eq!(package.version, "18.2.0");
+        assert_eq!(package.integrity.as_deref(), Some("sha512-react-dom"));
+        assert!(package.is_direct);
+        assert_eq!(graph.children(react_dom).count(), 1);
+
+        let v5 = r#"
+lockfileVersion: 5.4
+dependencies:
+  '@scope/pkg': 1.2.0_react@18.2.0
+packages:
+  /@scope/pkg/1.2.0_react@18.2.0:
+    resolution: {tarball: https://example.com/pkg-1.2.0.tgz}
+"#;
+        let graph = parse(v5).unwrap();
+        let (_, package) = find(&graph, "@scope/pkg");
+        assert_eq!(package.version, "1.2.0");
+        assert!(package.is_direct);
+        assert_eq!(
+            package.resolved.as_deref(),
+            Some("https://example.com/pkg-1.2.0.tgz")
+        );
+    }
+}
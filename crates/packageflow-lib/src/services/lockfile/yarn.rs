@@ -0,0 +1,280 @@
+// Yarn Lockfile Parser
+// yarn.lock for Yarn classic (v1, its own format) and Berry (v2+, YAML with `__metadata`)
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{
+    manifest_dependencies, split_package_spec, DependencyEdge, DependencyGraph, DependencyKind,
+    LockfilePackage,
+};
+use crate::models::snapshot::LockfileType;
+
+/// Parse yarn.lock of either generation. Neither records which dependencies are
+/// direct, so those come from `manifest` (package.json).
+pub fn parse(content: &str, manifest: Option<&Value>) -> Result<DependencyGraph, String> {
+    let (packages, edges) = if content.lines().any(|line| line.starts_with("__metadata:")) {
+        parse_berry(content, manifest)?
+    } else {
+        parse_classic(content, manifest)
+    };
+    Ok(DependencyGraph::new(LockfileType::Yarn, packages, edges))
+}
+
+/// Yarn classic: `"name@range", "name@other-range":` headers with indented fields
+pub fn parse_classic(
+    content: &str,
+    manifest: Option<&Value>,
+) -> (Vec<LockfilePackage>, Vec<DependencyEdge>) {
+    let mut packages: Vec<LockfilePackage> = Vec::new();
+    let mut specs: HashMap<String, usize> = HashMap::new();
+    let mut dependencies: Vec<Vec<(String, DependencyKind)>> = Vec::new();
+    let mut section = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if indent == 0 {
+            let header = trimmed.trim_end_matches(':');
+            let entry_specs: Vec<&str> = header.split(", ").map(|s| s.trim_matches('"')).collect();
+            let name = entry_specs
+                .first()
+                .and_then(|spec| split_package_spec(spec))
+                .map_or(header, |(name, _)| name);
+
+            for spec in &entry_specs {
+                specs.insert(spec.to_string(), packages.len());
+            }
+            packages.push(LockfilePackage::new(name, "unknown", ""));
+            dependencies.push(Vec::new());
+            section = None;
+            continue;
+        }
+
+        let Some(package) = packages.last_mut() else {
+            continue;
+        };
+        if indent <= 2 {
+            section = match trimmed {
+                "dependencies:" => Some(DependencyKind::Prod),
+                "optionalDependencies:" => Some(DependencyKind::Optional),
+                _ => None,
+            };
+            if let Some((field, value)) = trimmed.split_once(' ') {
+                let value = value.trim_matches('"').to_string();
+                match field {
+                    "version" => package.version = value,
+                    "resolved" => package.resolved = Some(value),
+                    "integrity" => package.integrity = Some(value),
+                    _ => {}
+                }
+            }
+        } else if let Some(kind) = section {
+            if let Some((name, range)) = trimmed.split_once(' ') {
+                let spec = format!("{}@{}", name.trim_matches('"'), range.trim_matches('"'));
+                if let Some(deps) = dependencies.last_mut() {
+                    deps.push((spec, kind));
+                }
+            }
+        }
+    }
+
+    for package in &mut packages {
+        package.path = format!("{}@{}", package.name, package.version);
+    }
+
+    let mut edges = Vec::new();
+    for (parent, deps) in dependencies.iter().enumerate() {
+        for (spec, kind) in deps {
+            if let Some(&child) = specs.get(spec) {
+                edges.push(DependencyEdge {
+                    parent: Some(parent),
+                    child,
+                    kind: *kind,
+                });
+            }
+        }
+    }
+    for (name, range, kind) in manifest.map(manifest_dependencies).unwrap_or_default() {
+        if let Some(&child) = specs.get(&format!("{}@{}", name, range)) {
+            edges.push(DependencyEdge {
+                parent: None,
+                child,
+                kind,
+            });
+        }
+    }
+
+    (packages, edges)
+}
+
+/// Yarn Berry: YAML keyed by descriptors (`name@npm:^1.0.0`). Workspaces are entries
+/// of their own (`app@workspace:.`) listing dependencies and devDependencies together.
+fn parse_berry(
+    content: &str,
+    manifest: Option<&Value>,
+) -> Result<(Vec<LockfilePackage>, Vec<DependencyEdge>), String> {
+    let lockfile: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("Failed to parse yarn.lock: {}", e))?;
+    let entries = lockfile
+        .as_mapping()
+        .ok_or_else(|| "Failed to parse yarn.lock: not a mapping".to_string())?;
+
+    let str_field = |entry: &serde_yaml::Value, field: &str| {
+        entry
+            .get(field)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+
+    let mut packages = Vec::new();
+    let mut specs: HashMap<String, usize> = HashMap::new();
+    let mut nodes = Vec::new();
+    let mut workspaces = Vec::new();
+    for (header, entry) in entries {
+        let Some(header) = header.as_str().filter(|h| *h != "__metadata") else {
+            continue;
+        };
+        let resolution = str_field(entry, "resolution").unwrap_or_else(|| header.to_string());
+        if resolution.contains("@workspace:") {
+            workspaces.push((resolution, entry));
+            continue;
+        }
+
+        let Some((name, reference)) = split_package_spec(&resolution) else {
+            continue;
+        };
+        let version = match entry.get("version") {
+            Some(serde_yaml::Value::String(v)) => v.clone(),
+            Some(serde_yaml::Value::Number(v)) => v.to_string(),
+            _ => "unknown".to_string(),
+        };
+
+        let mut package = LockfilePackage::new(name, version, resolution.as_str());
+        package.integrity = str_field(entry, "checksum");
+        // Registry packages resolve through the configured registry; record other sources
+        if !reference.starts_with("npm:") {
+            package.resolved = Some(reference.to_string());
+        }
+
+        for spec in header.split(", ") {
+            specs.insert(spec.to_string(), packages.len());
+        }
+        nodes.push(entry);
+        packages.push(package);
+    }
+
+    // Descriptors in `dependencies` omit the `npm:` protocol when it's the default
+    let lookup = |name: &str, range: &str| {
+        specs
+            .get(&format!("{}@{}", name, range))
+            .or_else(|| specs.get(&format!("{}@npm:{}", name, range)))
+            .copied()
+    };
+
+    let mut edges = Vec::new();
+    for (parent, entry) in nodes.iter().enumerate() {
+        for (name, range, kind) in berry_dependencies(entry) {
+            if let Some(child) = lookup(&name, &range) {
+                edges.push(DependencyEdge {
+                    parent: Some(parent),
+                    child,
+                    kind,
+                });
+            }
+        }
+    }
+
+    // The root workspace's package.json tells dev dependencies apart
+    let dev_names: Vec<String> = manifest
+        .map(manifest_dependencies)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, _, kind)| *kind == DependencyKind::Dev)
+        .map(|(name, _, _)| name)
+        .collect();
+    for (resolution, entry) in workspaces {
+        let is_root = resolution.ends_with("@workspace:.");
+        for (name, range, kind) in berry_dependencies(entry) {
+            let kind = if is_root && dev_names.contains(&name) {
+                DependencyKind::Dev
+            } else {
+                kind
+            };
+            if let Some(child) = lookup(&name, &range) {
+                edges.push(DependencyEdge {
+                    parent: None,
+                    child,
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok((packages, edges))
+}
+
+/// `(name, range, kind)` of a Berry entry's dependencies; `dependenciesMeta` marks optional ones
+fn berry_dependencies(entry: &serde_yaml::Value) -> Vec<(String, String, DependencyKind)> {
+    let optional = |name: &str| {
+        entry
+            .get("dependenciesMeta")
+            .and_then(|m| m.get(name))
+            .and_then(|m| m.get("optional"))
+            .and_then(|o| o.as_bool())
+            .unwrap_or(false)
+    };
+
+    let mut dependencies = Vec::new();
+    for (field, kind) in [
+        ("dependencies", DependencyKind::Prod),
+        ("optionalDependencies", DependencyKind::Optional),
+    ] {
+        let Some(deps) = entry.get(field).and_then(|d| d.as_mapping()) else {
+            continue;
+        };
+        for (name, range) in deps {
+            if let (Some(name), Some(range)) = (name.as_str(), range.as_str()) {
+                let kind = if optional(name) {
+                    DependencyKind::Optional
+                } else {
+                    kind
+                };
+                dependencies.push((name.to_string(), range.to_string(), kind));
+            }
+        }
+    }
+    dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_fixture_graph, find, manifest};
+    use super::*;
+
+    #[test]
+    fn test_parse_classic_fixture() {
+        let graph = parse(include_str!("fixtures/yarn.v1.lock"), Some(&manifest())).unwrap();
+        assert_fixture_graph(&graph);
+
+        let (_, js_tokens) = find(&graph, "js-tokens");
+        assert_eq!(js_tokens.path, "js-tokens@4.0.0");
+        assert_eq!(js_tokens.integrity.as_deref(), Some("sha512-js-tokens"));
+    }
+
+    #[test]
+    fn test_parse_berry_fixture() {
+        let graph = parse(include_str!("fixtures/yarn.berry.lock"), Some(&manifest())).unwrap();
+        assert_fixture_graph(&graph);
+
+        let (_, react) = find(&graph, "react");
+        assert_eq!(react.path, "react@npm:18.2.0");
+        assert_eq!(react.integrity.as_deref(), Some("10c0/react"));
+        assert_eq!(react.resolved, None);
+    }
+}
@@ -10,6 +10,7 @@ use tokio::time::{timeout, Duration};
 
 use crate::models::mcp_action::{MCPActionType, ScriptConfig, ScriptExecutionResult};
 use crate::utils::path_resolver;
+use crate::utils::shell::{join_shell_words, resolve_command};
 
 use super::ActionExecutor;
 
@@ -35,23 +36,41 @@ impl ScriptExecutor {
             .map_err(|e| format!("Invalid script config: {}", e))
     }
 
+    /// Command line of the configuration: `command` alone is a full command line,
+    /// with `args` it is the program
+    fn command_line(config: &ScriptConfig) -> Result<String, String> {
+        if config.args.is_empty() {
+            Ok(config.command.clone())
+        } else {
+            join_shell_words(
+                &std::iter::once(&config.command)
+                    .chain(&config.args)
+                    .collect::<Vec<_>>(),
+                config.shell,
+            )
+        }
+    }
+
     /// Run a command with the specified configuration
     async fn run_command(&self, config: &ScriptConfig, cwd_override: Option<&str>) -> Result<ScriptExecutionResult, String> {
         let start = Instant::now();
 
+        // Same shell selection as workflow script nodes
+        let invocation = resolve_command(&Self::command_line(config)?, config.shell)?;
+
         // Build the command using path_resolver for proper macOS GUI app support
         let mut cmd = if config.use_volta {
             // Use volta run for Volta-managed projects
             let volta_path = path_resolver::get_tool_path("volta");
             let mut c = Command::new(&volta_path);
-            c.arg("run").arg(&config.command);
-            c.args(&config.args);
+            c.arg("run").arg(&invocation.program);
+            c.args(&invocation.args);
             c
         } else {
             // Use path_resolver to find the command
-            let tool_path = path_resolver::get_tool_path(&config.command);
+            let tool_path = path_resolver::get_tool_path(&invocation.program);
             let mut c = Command::new(&tool_path);
-            c.args(&config.args);
+            c.args(&invocation.args);
             c
         };
 
@@ -239,6 +258,22 @@ mod tests {
         assert!(result.unwrap_err().contains("timed out"));
     }
 
+    #[tokio::test]
+    async fn test_script_executor_command_line() {
+        let executor = ScriptExecutor::new();
+
+        let params = serde_json::json!({
+            "config": {
+                "command": "echo \"a  b\" | tr a-z A-Z",
+                "timeoutMs": 5000
+            }
+        });
+
+        let result = executor.execute(params).await.unwrap();
+        assert_eq!(result["exitCode"], 0);
+        assert_eq!(result["stdout"].as_str().unwrap().trim(), "A  B");
+    }
+
     #[test]
     fn test_action_type() {
         let executor = ScriptExecutor::new();
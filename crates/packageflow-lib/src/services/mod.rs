@@ -3,9 +3,12 @@
 
 // Core services (shared between Tauri app and MCP)
 pub mod crypto;
+pub mod execution_log;
+pub mod lockfile;
 pub mod mcp_action;
 pub mod security_guardian;
 pub mod snapshot;
+pub mod workflow;
 
 pub use crypto::*;
 
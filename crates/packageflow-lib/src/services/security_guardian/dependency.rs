@@ -10,6 +10,7 @@ use crate::models::snapshot::{
     DependencyChange, DependencyChangeType, ExecutionSnapshot, LockfileType, SnapshotDependency,
 };
 use crate::repositories::SnapshotRepository;
+use crate::services::lockfile;
 use crate::utils::database::Database;
 
 use super::patterns::{check_typosquatting, PatternAlert, PatternAlertType, AlertSeverity};
@@ -170,148 +171,36 @@ impl DependencyIntegrityService {
     fn scan_current_dependencies(&self, project_path: &str) -> Result<CurrentDependencyState, String> {
         let path = Path::new(project_path);
 
-        // Try to find and parse lockfile
-        let (lockfile_type, lockfile_hash, dependencies) = if path.join("package-lock.json").exists() {
-            self.parse_npm_lockfile(&path.join("package-lock.json"))?
-        } else if path.join("pnpm-lock.yaml").exists() {
-            self.parse_pnpm_lockfile(&path.join("pnpm-lock.yaml"))?
-        } else if path.join("yarn.lock").exists() {
-            // Basic yarn support - just hash the lockfile
-            let content = std::fs::read_to_string(path.join("yarn.lock"))
-                .map_err(|e| format!("Failed to read yarn.lock: {}", e))?;
-            let hash = compute_hash(&content);
-            (Some(LockfileType::Yarn), Some(hash), Vec::new())
-        } else {
-            return Err("No supported lockfile found (package-lock.json, pnpm-lock.yaml, yarn.lock)".to_string());
-        };
+        let (lockfile_type, lockfile_path) = LockfileType::detect(path).ok_or_else(|| {
+            "No supported lockfile found (package-lock.json, pnpm-lock.yaml, yarn.lock, bun.lock, bun.lockb)".to_string()
+        })?;
+        let content = std::fs::read(&lockfile_path)
+            .map_err(|e| format!("Failed to read {}: {}", lockfile_path.display(), e))?;
+
+        let dependencies: Vec<CurrentDependency> =
+            lockfile::parse_lockfile(&lockfile_type, &lockfile_path, &content)?
+                .packages
+                .into_iter()
+                .map(|pkg| CurrentDependency {
+                    name: pkg.name,
+                    version: pkg.version,
+                    is_direct: pkg.is_direct,
+                    is_dev: pkg.is_dev,
+                    has_postinstall: false, // Will be filled by postinstall scan
+                    postinstall_script: None,
+                })
+                .collect();
 
         let total_count = dependencies.len();
 
         Ok(CurrentDependencyState {
-            lockfile_type,
-            lockfile_hash,
+            lockfile_type: Some(lockfile_type),
+            lockfile_hash: Some(compute_hash(&content)),
             dependencies,
             total_count,
         })
     }
 
-    /// Parse npm package-lock.json
-    fn parse_npm_lockfile(
-        &self,
-        lockfile_path: &Path,
-    ) -> Result<(Option<LockfileType>, Option<String>, Vec<CurrentDependency>), String> {
-        let content = std::fs::read_to_string(lockfile_path)
-            .map_err(|e| format!("Failed to read package-lock.json: {}", e))?;
-
-        let hash = compute_hash(&content);
-
-        let lockfile: serde_json::Value = serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse package-lock.json: {}", e))?;
-
-        let mut dependencies = Vec::new();
-
-        // Parse packages (npm v3 format)
-        if let Some(packages) = lockfile.get("packages").and_then(|p| p.as_object()) {
-            for (path, pkg_data) in packages {
-                // Skip root package
-                if path.is_empty() {
-                    continue;
-                }
-
-                // Extract package name from path (e.g., "node_modules/lodash" -> "lodash")
-                let name = path.trim_start_matches("node_modules/").to_string();
-
-                // Skip nested dependencies for now (they have multiple node_modules in path)
-                if name.contains("node_modules/") {
-                    continue;
-                }
-
-                let version = pkg_data.get("version")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-
-                let is_dev = pkg_data.get("dev").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                dependencies.push(CurrentDependency {
-                    name,
-                    version,
-                    is_direct: true, // Simplified - all direct for now
-                    is_dev,
-                    has_postinstall: false, // Will be filled by postinstall scan
-                    postinstall_script: None,
-                });
-            }
-        }
-
-        Ok((Some(LockfileType::Npm), Some(hash), dependencies))
-    }
-
-    /// Parse pnpm pnpm-lock.yaml
-    fn parse_pnpm_lockfile(
-        &self,
-        lockfile_path: &Path,
-    ) -> Result<(Option<LockfileType>, Option<String>, Vec<CurrentDependency>), String> {
-        let content = std::fs::read_to_string(lockfile_path)
-            .map_err(|e| format!("Failed to read pnpm-lock.yaml: {}", e))?;
-
-        let hash = compute_hash(&content);
-
-        let lockfile: serde_yaml::Value = serde_yaml::from_str(&content)
-            .map_err(|e| format!("Failed to parse pnpm-lock.yaml: {}", e))?;
-
-        let mut dependencies = Vec::new();
-
-        // Parse importers.'.'.dependencies and devDependencies (pnpm v9 format)
-        if let Some(importers) = lockfile.get("importers") {
-            if let Some(root) = importers.get(".") {
-                // Direct dependencies
-                if let Some(deps) = root.get("dependencies").and_then(|d| d.as_mapping()) {
-                    for (name, info) in deps {
-                        if let (Some(name), Some(info)) = (name.as_str(), info.as_mapping()) {
-                            let version = info.get("version")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string();
-
-                            dependencies.push(CurrentDependency {
-                                name: name.to_string(),
-                                version,
-                                is_direct: true,
-                                is_dev: false,
-                                has_postinstall: false,
-                                postinstall_script: None,
-                            });
-                        }
-                    }
-                }
-
-                // Dev dependencies
-                if let Some(deps) = root.get("devDependencies").and_then(|d| d.as_mapping()) {
-                    for (name, info) in deps {
-                        if let (Some(name), Some(info)) = (name.as_str(), info.as_mapping()) {
-                            let version = info.get("version")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string();
-
-                            dependencies.push(CurrentDependency {
-                                name: name.to_string(),
-                                version,
-                                is_direct: true,
-                                is_dev: true,
-                                has_postinstall: false,
-                                postinstall_script: None,
-                            });
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok((Some(LockfileType::Pnpm), Some(hash), dependencies))
-    }
-
     /// Compare current state against reference snapshot
     fn compare_against_reference(
         &self,
@@ -357,6 +246,7 @@ impl DependencyIntegrityService {
                         postinstall_changed: ref_dep.has_postinstall != cur_dep.has_postinstall,
                         old_postinstall: ref_dep.postinstall_script.clone(),
                         new_postinstall: cur_dep.postinstall_script.clone(),
+                        introduced_by: Vec::new(),
                     });
 
                     // Check for postinstall changes
@@ -375,7 +265,7 @@ impl DependencyIntegrityService {
                             },
                             old_script: ref_dep.postinstall_script.clone(),
                             new_script: cur_dep.postinstall_script.clone(),
-                            script_hash: cur_dep.postinstall_script.as_ref().map(|s| compute_hash(s)),
+                            script_hash: cur_dep.postinstall_script.as_ref().map(compute_hash),
                         });
                     }
                 }
@@ -393,6 +283,7 @@ impl DependencyIntegrityService {
                     postinstall_changed: ref_dep.has_postinstall,
                     old_postinstall: ref_dep.postinstall_script.clone(),
                     new_postinstall: None,
+                    introduced_by: Vec::new(),
                 });
             }
         }
@@ -412,6 +303,7 @@ impl DependencyIntegrityService {
                     postinstall_changed: cur_dep.has_postinstall,
                     old_postinstall: None,
                     new_postinstall: cur_dep.postinstall_script.clone(),
+                    introduced_by: Vec::new(),
                 });
 
                 // Check for typosquatting on new packages
@@ -440,7 +332,7 @@ impl DependencyIntegrityService {
                         change_type: PostinstallChangeType::Added,
                         old_script: None,
                         new_script: cur_dep.postinstall_script.clone(),
-                        script_hash: cur_dep.postinstall_script.as_ref().map(|s| compute_hash(s)),
+                        script_hash: cur_dep.postinstall_script.as_ref().map(compute_hash),
                     });
                 }
             }
@@ -548,9 +440,9 @@ impl DependencyIntegrityService {
 // =============================================================================
 
 /// Compute SHA-256 hash of content
-fn compute_hash(content: &str) -> String {
+fn compute_hash(content: impl AsRef<[u8]>) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(content.as_bytes());
+    hasher.update(content.as_ref());
     format!("{:x}", hasher.finalize())
 }
 
@@ -0,0 +1,486 @@
+// Snapshot Bundle Service
+// Exports a snapshot as a signed, zstd-compressed bundle and imports it on another machine
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::models::security_insight::SecurityInsight;
+use crate::models::snapshot::{
+    ExecutionSnapshot, LockfileType, PostinstallEntry, SnapshotBundleExport, SnapshotBundleImport,
+    SnapshotDependency, SnapshotDependencyEdge, SnapshotStatus, TriggerSource,
+};
+use crate::repositories::SnapshotRepository;
+use crate::services::snapshot::storage::SnapshotStorage;
+use crate::utils::database::Database;
+
+/// Marks a file as a snapshot bundle
+const BUNDLE_FORMAT: &str = "packageflow-snapshot";
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+/// Largest bundle read or decompressed, so a crafted file cannot exhaust memory
+/// before its signature is checked
+const MAX_BUNDLE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// First line of a bundle; the signed contents follow
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleHeader {
+    format: String,
+    format_version: u32,
+    /// HMAC-SHA256 of the contents bytes ("sha256=<hex>")
+    signature: String,
+}
+
+/// Everything needed to replay a snapshot elsewhere
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleContents {
+    exported_at: String,
+    snapshot: ExecutionSnapshot,
+    lockfile_name: String,
+    /// Base64, since bun.lockb is binary
+    lockfile: String,
+    package_json: Option<String>,
+    dependencies: Vec<SnapshotDependency>,
+    edges: Vec<SnapshotDependencyEdge>,
+    postinstall_manifest: Vec<PostinstallEntry>,
+    insights: Vec<SecurityInsight>,
+}
+
+/// Service for sharing snapshots between machines
+pub struct SnapshotBundleService {
+    storage: SnapshotStorage,
+    db: Database,
+}
+
+impl SnapshotBundleService {
+    /// Create a new SnapshotBundleService
+    pub fn new(storage: SnapshotStorage, db: Database) -> Self {
+        Self { storage, db }
+    }
+
+    /// Write a snapshot to `output_path` as a bundle signed with the team's shared secret
+    pub fn export_bundle(
+        &self,
+        snapshot_id: &str,
+        output_path: &Path,
+        signing_secret: &str,
+    ) -> Result<SnapshotBundleExport, String> {
+        let repo = SnapshotRepository::new(self.db.clone());
+        let snapshot = repo
+            .get_snapshot(snapshot_id)?
+            .ok_or_else(|| format!("Snapshot not found: {}", snapshot_id))?;
+        if snapshot.status != SnapshotStatus::Completed {
+            return Err(format!("Snapshot {} is not completed", snapshot_id));
+        }
+
+        let lockfile_type = snapshot.lockfile_type.clone().unwrap_or(LockfileType::Npm);
+        let (lockfile_name, lockfile) = lockfile_type
+            .lockfile_names()
+            .iter()
+            .find_map(|name| {
+                self.storage
+                    .read_lockfile(snapshot_id, name)
+                    .ok()
+                    .map(|content| (*name, content))
+            })
+            .ok_or_else(|| format!("Lockfile not found in snapshot: {}", snapshot_id))?;
+        let package_json = self.storage.read_package_json(snapshot_id).ok();
+
+        let dependencies = repo.list_dependencies(snapshot_id)?;
+        let postinstall_manifest = dependencies
+            .iter()
+            .filter_map(|dep| {
+                let script = dep.postinstall_script.as_ref()?;
+                Some(PostinstallEntry {
+                    package_name: dep.name.clone(),
+                    version: dep.version.clone(),
+                    script: script.clone(),
+                    script_hash: sha256_hex(script.as_bytes()),
+                })
+            })
+            .collect();
+
+        let contents = BundleContents {
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            lockfile_name: lockfile_name.to_string(),
+            lockfile: BASE64.encode(&lockfile),
+            package_json: package_json.map(|content| BASE64.encode(content)),
+            edges: repo.list_dependency_edges(snapshot_id)?,
+            insights: repo.list_insights(snapshot_id)?,
+            dependencies,
+            postinstall_manifest,
+            snapshot,
+        };
+
+        let bundle = encode_bundle(&contents, signing_secret)?;
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create bundle directory: {}", e))?;
+        }
+        fs::write(output_path, &bundle)
+            .map_err(|e| format!("Failed to write snapshot bundle: {}", e))?;
+
+        Ok(SnapshotBundleExport {
+            snapshot_id: snapshot_id.to_string(),
+            path: output_path.to_string_lossy().to_string(),
+            size_bytes: bundle.len() as u64,
+        })
+    }
+
+    /// Import a bundle as a new pinned snapshot of `project_path`, ready for
+    /// `prepare_replay` / `restore_lockfile` against that checkout
+    pub fn import_bundle(
+        &self,
+        bundle_path: &Path,
+        project_path: &str,
+        signing_secret: &str,
+    ) -> Result<SnapshotBundleImport, String> {
+        let bundle = fs::File::open(bundle_path)
+            .and_then(|file| read_capped(file, MAX_BUNDLE_BYTES))
+            .map_err(|e| format!("Failed to read snapshot bundle: {}", e))?;
+        let contents = decode_bundle(&bundle, signing_secret)?;
+
+        let lockfile = BASE64
+            .decode(&contents.lockfile)
+            .map_err(|e| format!("Failed to decode bundled lockfile: {}", e))?;
+        let package_json = contents
+            .package_json
+            .as_ref()
+            .map(|content| BASE64.decode(content))
+            .transpose()
+            .map_err(|e| format!("Failed to decode bundled package.json: {}", e))?;
+
+        // The lockfile is what gets restored, so it must match the recorded hash
+        let lockfile_hash = sha256_hex(&lockfile);
+        if contents.snapshot.lockfile_hash.as_deref() != Some(lockfile_hash.as_str()) {
+            return Err("Bundled lockfile does not match the snapshot's lockfile hash".to_string());
+        }
+        if !valid_lockfile_name(&contents) {
+            return Err(format!(
+                "Unexpected lockfile name in bundle: {}",
+                contents.lockfile_name
+            ));
+        }
+
+        let original = &contents.snapshot;
+        let snapshot_id = uuid::Uuid::new_v4().to_string();
+        let mut snapshot = ExecutionSnapshot {
+            id: snapshot_id.clone(),
+            project_path: project_path.to_string(),
+            trigger_source: TriggerSource::Import,
+            storage_path: Some(
+                self.storage
+                    .get_snapshot_path(&snapshot_id)
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            error_message: None,
+            is_pinned: true,
+            ..original.clone()
+        };
+
+        let (_, compressed_size) = self.storage.store_lockfile_blob(
+            &snapshot_id,
+            &contents.lockfile_name,
+            &lockfile,
+            &lockfile_hash,
+        )?;
+        snapshot.compressed_size = Some(compressed_size as i64);
+        if let Some(package_json) = &package_json {
+            self.storage
+                .store_package_json(&snapshot_id, package_json)?;
+        }
+
+        let repo = SnapshotRepository::new(self.db.clone());
+        repo.create_snapshot(&snapshot)?;
+        // Imported snapshots are kept until removed by hand
+        repo.set_snapshot_pinned(&snapshot_id, true)?;
+
+        let dependencies: Vec<SnapshotDependency> = contents
+            .dependencies
+            .iter()
+            .map(|dep| SnapshotDependency {
+                id: None,
+                snapshot_id: snapshot_id.clone(),
+                ..dep.clone()
+            })
+            .collect();
+        let edges: Vec<SnapshotDependencyEdge> = contents
+            .edges
+            .iter()
+            .map(|edge| SnapshotDependencyEdge {
+                snapshot_id: snapshot_id.clone(),
+                ..edge.clone()
+            })
+            .collect();
+        repo.add_dependencies(&dependencies)?;
+        repo.add_dependency_edges(&edges)?;
+        for insight in &contents.insights {
+            repo.create_insight(&SecurityInsight {
+                id: uuid::Uuid::new_v4().to_string(),
+                snapshot_id: snapshot_id.clone(),
+                ..insight.clone()
+            })?;
+        }
+
+        Ok(SnapshotBundleImport {
+            original_snapshot_id: original.id.clone(),
+            original_project_path: original.project_path.clone(),
+            exported_at: contents.exported_at.clone(),
+            dependency_count: dependencies.len(),
+            insight_count: contents.insights.len(),
+            snapshot,
+        })
+    }
+}
+
+/// Sign and compress bundle contents
+fn encode_bundle(contents: &BundleContents, signing_secret: &str) -> Result<Vec<u8>, String> {
+    let payload = serde_json::to_vec(contents)
+        .map_err(|e| format!("Failed to serialize snapshot bundle: {}", e))?;
+    let header = BundleHeader {
+        format: BUNDLE_FORMAT.to_string(),
+        format_version: BUNDLE_FORMAT_VERSION,
+        signature: sign(&payload, signing_secret)?,
+    };
+
+    let mut data = serde_json::to_vec(&header)
+        .map_err(|e| format!("Failed to serialize bundle header: {}", e))?;
+    data.push(b'\n');
+    data.extend_from_slice(&payload);
+
+    let mut encoder = zstd::Encoder::new(Vec::new(), 19)
+        .map_err(|e| format!("Failed to create zstd encoder: {}", e))?;
+    encoder
+        .write_all(&data)
+        .map_err(|e| format!("Failed to compress snapshot bundle: {}", e))?;
+    encoder
+        .finish()
+        .map_err(|e| format!("Failed to finish compression: {}", e))
+}
+
+/// Decompress a bundle and verify its signature before parsing the contents
+fn decode_bundle(bundle: &[u8], signing_secret: &str) -> Result<BundleContents, String> {
+    let data = zstd::Decoder::new(bundle)
+        .and_then(|decoder| read_capped(decoder, MAX_BUNDLE_BYTES))
+        .map_err(|e| format!("Not a snapshot bundle: {}", e))?;
+
+    let split = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| "Not a snapshot bundle: missing header".to_string())?;
+    let (header, payload) = (&data[..split], &data[split + 1..]);
+
+    let header: BundleHeader =
+        serde_json::from_slice(header).map_err(|e| format!("Not a snapshot bundle: {}", e))?;
+    if header.format != BUNDLE_FORMAT {
+        return Err(format!("Not a snapshot bundle: {}", header.format));
+    }
+    if header.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Snapshot bundle format {} is newer than supported ({})",
+            header.format_version, BUNDLE_FORMAT_VERSION
+        ));
+    }
+    verify(payload, &header.signature, signing_secret)?;
+
+    serde_json::from_slice(payload).map_err(|e| format!("Failed to parse snapshot bundle: {}", e))
+}
+
+/// Read everything from `reader`, failing once it exceeds `limit` bytes
+fn read_capped(reader: impl Read, limit: u64) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(limit + 1).read_to_end(&mut data)?;
+    if data.len() as u64 > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("contents exceed {} MB", limit / (1024 * 1024)),
+        ));
+    }
+    Ok(data)
+}
+
+fn mac(signing_secret: &str) -> Result<Hmac<Sha256>, String> {
+    if signing_secret.is_empty() {
+        return Err("A signing secret is required for snapshot bundles".to_string());
+    }
+    Hmac::<Sha256>::new_from_slice(signing_secret.as_bytes())
+        .map_err(|e| format!("Invalid signing secret: {}", e))
+}
+
+/// HMAC-SHA256 signature in the same "sha256=<hex>" form as webhook signatures
+fn sign(payload: &[u8], signing_secret: &str) -> Result<String, String> {
+    let mut mac = mac(signing_secret)?;
+    mac.update(payload);
+    Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+}
+
+fn verify(payload: &[u8], signature: &str, signing_secret: &str) -> Result<(), String> {
+    let expected = signature
+        .strip_prefix("sha256=")
+        .filter(|hex| hex.len() == 64)
+        .and_then(|hex| {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+                .collect::<Option<Vec<u8>>>()
+        })
+        .ok_or_else(|| "Invalid snapshot bundle signature".to_string())?;
+
+    let mut mac = mac(signing_secret)?;
+    mac.update(payload);
+    // Constant-time comparison
+    mac.verify_slice(&expected).map_err(|_| {
+        "Snapshot bundle signature does not match; check the signing secret".to_string()
+    })
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// The lockfile name becomes a path in the importing project, so only known names are accepted
+fn valid_lockfile_name(contents: &BundleContents) -> bool {
+    contents
+        .snapshot
+        .lockfile_type
+        .clone()
+        .unwrap_or(LockfileType::Npm)
+        .lockfile_names()
+        .contains(&contents.lockfile_name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn capture_sample(storage: &SnapshotStorage, db: &Database) -> ExecutionSnapshot {
+        let lockfile = br#"{"name": "app", "lockfileVersion": 3}"#;
+        let snapshot = ExecutionSnapshot {
+            id: "snap-1".to_string(),
+            project_path: "/home/alice/app".to_string(),
+            status: SnapshotStatus::Completed,
+            trigger_source: TriggerSource::Manual,
+            lockfile_type: Some(LockfileType::Npm),
+            lockfile_hash: Some(sha256_hex(lockfile)),
+            dependency_tree_hash: None,
+            package_json_hash: None,
+            total_dependencies: 1,
+            direct_dependencies: 1,
+            dev_dependencies: 0,
+            security_score: Some(90),
+            postinstall_count: 1,
+            storage_path: None,
+            compressed_size: None,
+            error_message: None,
+            created_at: "2024-03-05T18:00:00Z".to_string(),
+            is_pinned: false,
+        };
+        let repo = SnapshotRepository::new(db.clone());
+        repo.create_snapshot(&snapshot).unwrap();
+        repo.add_dependencies(&[SnapshotDependency {
+            id: None,
+            snapshot_id: snapshot.id.clone(),
+            name: "esbuild".to_string(),
+            version: "0.20.0".to_string(),
+            is_direct: true,
+            is_dev: false,
+            has_postinstall: true,
+            postinstall_script: Some("node install.js".to_string()),
+            integrity_hash: None,
+            resolved_url: None,
+        }])
+        .unwrap();
+        storage
+            .store_lockfile(&snapshot.id, "package-lock.json", lockfile)
+            .unwrap();
+        snapshot
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(temp_dir.path().join("test.db")).unwrap();
+        let storage = SnapshotStorage::new(temp_dir.path().join("time-machine"));
+        let original = capture_sample(&storage, &db);
+        let service = SnapshotBundleService::new(storage.clone(), db.clone());
+
+        let bundle_path = temp_dir.path().join("bug-repro.pfsnap");
+        let export = service
+            .export_bundle(&original.id, &bundle_path, "team-secret")
+            .unwrap();
+        assert!(export.size_bytes > 0);
+
+        let imported = service
+            .import_bundle(&bundle_path, "/home/bob/app", "team-secret")
+            .unwrap();
+        assert_eq!(imported.original_snapshot_id, "snap-1");
+        assert_eq!(imported.dependency_count, 1);
+
+        let snapshot = imported.snapshot;
+        assert_ne!(snapshot.id, original.id);
+        assert_eq!(snapshot.project_path, "/home/bob/app");
+        assert_eq!(snapshot.trigger_source, TriggerSource::Import);
+        assert_eq!(snapshot.lockfile_hash, original.lockfile_hash);
+        assert_eq!(
+            storage
+                .read_lockfile(&snapshot.id, "package-lock.json")
+                .unwrap(),
+            storage
+                .read_lockfile(&original.id, "package-lock.json")
+                .unwrap()
+        );
+
+        let repo = SnapshotRepository::new(db);
+        let stored = repo.get_snapshot(&snapshot.id).unwrap().unwrap();
+        assert!(stored.is_pinned);
+        assert_eq!(repo.list_dependencies(&snapshot.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tampered_or_wrongly_signed_bundle_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(temp_dir.path().join("test.db")).unwrap();
+        let storage = SnapshotStorage::new(temp_dir.path().join("time-machine"));
+        let original = capture_sample(&storage, &db);
+        let service = SnapshotBundleService::new(storage, db.clone());
+
+        let bundle_path = temp_dir.path().join("bundle.pfsnap");
+        service
+            .export_bundle(&original.id, &bundle_path, "team-secret")
+            .unwrap();
+
+        let err = service
+            .import_bundle(&bundle_path, "/home/bob/app", "other-secret")
+            .unwrap_err();
+        assert!(err.contains("signature does not match"));
+
+        // Re-sign a modified lockfile with the wrong contents hash
+        let mut contents = decode_bundle(&fs::read(&bundle_path).unwrap(), "team-secret").unwrap();
+        contents.lockfile = BASE64.encode(b"{\"tampered\": true}");
+        fs::write(
+            &bundle_path,
+            encode_bundle(&contents, "team-secret").unwrap(),
+        )
+        .unwrap();
+        let err = service
+            .import_bundle(&bundle_path, "/home/bob/app", "team-secret")
+            .unwrap_err();
+        assert!(err.contains("lockfile hash"));
+    }
+
+    #[test]
+    fn test_read_capped() {
+        assert_eq!(read_capped(&b"abc"[..], 3).unwrap(), b"abc");
+        assert!(read_capped(std::io::repeat(0), 1024).is_err());
+    }
+}
@@ -3,19 +3,30 @@
 
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::models::security_insight::{InsightType, SecurityInsight};
 use crate::models::snapshot::{
     CreateSnapshotRequest, ExecutionSnapshot, LockfileType, PostinstallEntry, SecurityContext,
-    SnapshotDependency, SnapshotStatus, TriggerSource, TyposquattingAlert,
+    SnapshotDependency, SnapshotDependencyEdge, SnapshotStatus, TriggerSource,
+    TyposquattingAlert,
 };
 use crate::repositories::{LockfileValidationRepository, SnapshotRepository};
+use crate::services::lockfile::{self, DependencyGraph};
+use crate::services::snapshot::retention::SnapshotRetentionService;
 use crate::services::snapshot::storage::SnapshotStorage;
 use crate::services::snapshot::validation::{ValidationEngine, ValidationFailure};
 use crate::utils::database::Database;
 
+/// Dependencies, their edges and package.json read while capturing a snapshot
+struct CapturedData {
+    dependencies: Vec<SnapshotDependency>,
+    edges: Vec<SnapshotDependencyEdge>,
+    package_json: Option<serde_json::Value>,
+}
+
 /// Service for capturing execution snapshots
 pub struct SnapshotCaptureService {
     storage: SnapshotStorage,
@@ -55,6 +66,7 @@ impl SnapshotCaptureService {
             compressed_size: None,
             error_message: None,
             created_at: now,
+            is_pinned: false,
         };
 
         let repo = SnapshotRepository::new(self.db.clone());
@@ -62,16 +74,23 @@ impl SnapshotCaptureService {
 
         // Capture the snapshot data
         match self.capture_snapshot_data(&mut snapshot) {
-            Ok((dependencies, package_json)) => {
+            Ok(CapturedData {
+                dependencies,
+                edges,
+                package_json,
+            }) => {
                 snapshot.status = SnapshotStatus::Completed;
                 repo.update_snapshot(&snapshot)?;
 
-                // Store dependencies
+                // Store dependencies and the graph between them
                 repo.add_dependencies(&dependencies)?;
+                repo.add_dependency_edges(&edges)?;
 
                 // Run lockfile validation if enabled
                 self.run_validation_and_store_insights(&snapshot.id, &dependencies, package_json.as_ref())?;
 
+                self.apply_retention(&snapshot.project_path);
+
                 Ok(snapshot)
             }
             Err(e) => {
@@ -108,25 +127,34 @@ impl SnapshotCaptureService {
     }
 
     /// Capture snapshot data from the project
-    /// Returns (dependencies, package_json) for validation
+    /// Returns the dependency graph and package_json for validation
     fn capture_snapshot_data(
         &self,
         snapshot: &mut ExecutionSnapshot,
-    ) -> Result<(Vec<SnapshotDependency>, Option<serde_json::Value>), String> {
+    ) -> Result<CapturedData, String> {
         let project_path = Path::new(&snapshot.project_path);
 
         // Detect lockfile type and read lockfile
-        let (lockfile_type, lockfile_content) = self.detect_and_read_lockfile(project_path)?;
+        let (lockfile_type, lockfile_path, lockfile_content) =
+            self.detect_and_read_lockfile(project_path)?;
         snapshot.lockfile_type = Some(lockfile_type.clone());
 
         // Compute lockfile hash
-        snapshot.lockfile_hash = Some(self.compute_hash(&lockfile_content));
-
-        // Store compressed lockfile
-        let lockfile_name = lockfile_type.lockfile_name();
-        let (_, compressed_size) = self
-            .storage
-            .store_lockfile(&snapshot.id, lockfile_name, &lockfile_content)?;
+        let lockfile_hash = self.compute_hash(&lockfile_content);
+        snapshot.lockfile_hash = Some(lockfile_hash.clone());
+
+        // Store compressed lockfile under its own filename (bun.lock or bun.lockb for Bun),
+        // shared with earlier snapshots that captured identical content
+        let lockfile_name = lockfile_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(lockfile_type.lockfile_name());
+        let (_, compressed_size) = self.storage.store_lockfile_blob(
+            &snapshot.id,
+            lockfile_name,
+            &lockfile_content,
+            &lockfile_hash,
+        )?;
         snapshot.compressed_size = Some(compressed_size as i64);
         snapshot.storage_path = Some(self.storage.get_snapshot_path(&snapshot.id).to_string_lossy().to_string());
 
@@ -143,7 +171,8 @@ impl SnapshotCaptureService {
         }
 
         // Parse lockfile and extract dependencies
-        let dependencies = self.parse_lockfile(&lockfile_type, &lockfile_content, &snapshot.id)?;
+        let (dependencies, edges) =
+            self.parse_lockfile(&lockfile_type, &lockfile_path, &lockfile_content, &snapshot.id)?;
 
         // Compute dependency statistics
         snapshot.total_dependencies = dependencies.len() as i32;
@@ -158,7 +187,36 @@ impl SnapshotCaptureService {
         // Compute security score (simplified)
         snapshot.security_score = Some(self.compute_security_score(&dependencies));
 
-        Ok((dependencies, package_json))
+        Ok(CapturedData {
+            dependencies,
+            edges,
+            package_json,
+        })
+    }
+
+    /// Prune the project's snapshots if automatic retention is enabled
+    fn apply_retention(&self, project_path: &str) {
+        let settings = match SnapshotRepository::new(self.db.clone()).get_time_machine_settings() {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::warn!("[SnapshotCapture] Failed to load retention settings: {}", e);
+                return;
+            }
+        };
+        if !settings.retention.enabled {
+            return;
+        }
+
+        let service = SnapshotRetentionService::new(self.storage.clone(), self.db.clone());
+        match service.apply_retention(&settings.retention, Some(project_path)) {
+            Ok(result) if !result.deleted_snapshot_ids.is_empty() => log::info!(
+                "[SnapshotCapture] Retention removed {} snapshots, reclaimed {} bytes",
+                result.deleted_snapshot_ids.len(),
+                result.reclaimed_bytes
+            ),
+            Ok(_) => {}
+            Err(e) => log::warn!("[SnapshotCapture] Failed to apply retention: {}", e),
+        }
     }
 
     /// Run lockfile validation and store insights
@@ -250,37 +308,25 @@ impl SnapshotCaptureService {
     }
 
     /// Detect lockfile type and read content
-    fn detect_and_read_lockfile(&self, project_path: &Path) -> Result<(LockfileType, Vec<u8>), String> {
+    fn detect_and_read_lockfile(
+        &self,
+        project_path: &Path,
+    ) -> Result<(LockfileType, PathBuf, Vec<u8>), String> {
         log::info!(
             "[SnapshotCapture] Detecting lockfile in: {}",
             project_path.display()
         );
 
         // Try each lockfile type in order of preference
-        let lockfile_checks = [
-            (LockfileType::Pnpm, "pnpm-lock.yaml"),
-            (LockfileType::Npm, "package-lock.json"),
-            (LockfileType::Yarn, "yarn.lock"),
-            (LockfileType::Bun, "bun.lockb"),
-        ];
-
-        for (lockfile_type, filename) in lockfile_checks {
-            let path = project_path.join(filename);
-            log::debug!(
-                "[SnapshotCapture] Checking {} - exists: {}",
-                path.display(),
-                path.exists()
+        if let Some((lockfile_type, path)) = LockfileType::detect(project_path) {
+            log::info!(
+                "[SnapshotCapture] Found {} lockfile: {}",
+                lockfile_type.as_str(),
+                path.display()
             );
-            if path.exists() {
-                log::info!(
-                    "[SnapshotCapture] Found {} lockfile: {}",
-                    lockfile_type.as_str(),
-                    path.display()
-                );
-                let content = fs::read(&path)
-                    .map_err(|e| format!("Failed to read {}: {}", filename, e))?;
-                return Ok((lockfile_type, content));
-            }
+            let content = fs::read(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            return Ok((lockfile_type, path, content));
         }
 
         log::error!(
@@ -290,246 +336,66 @@ impl SnapshotCaptureService {
         Err(format!("No lockfile found in project: {}", project_path.display()))
     }
 
-    /// Parse lockfile and extract dependencies
+    /// Parse lockfile and extract dependencies and the edges between them
     fn parse_lockfile(
         &self,
         lockfile_type: &LockfileType,
+        lockfile_path: &Path,
         content: &[u8],
         snapshot_id: &str,
-    ) -> Result<Vec<SnapshotDependency>, String> {
-        match lockfile_type {
-            LockfileType::Npm => self.parse_npm_lockfile(content, snapshot_id),
-            LockfileType::Pnpm => self.parse_pnpm_lockfile(content, snapshot_id),
-            LockfileType::Yarn => self.parse_yarn_lockfile(content, snapshot_id),
-            LockfileType::Bun => self.parse_bun_lockfile(content, snapshot_id),
-        }
-    }
-
-    /// Parse npm package-lock.json (v3 format)
-    fn parse_npm_lockfile(
-        &self,
-        content: &[u8],
-        snapshot_id: &str,
-    ) -> Result<Vec<SnapshotDependency>, String> {
-        let lockfile: serde_json::Value = serde_json::from_slice(content)
-            .map_err(|e| format!("Failed to parse package-lock.json: {}", e))?;
-
-        let mut dependencies = Vec::new();
-
-        // npm v3 format uses "packages" key
-        if let Some(packages) = lockfile.get("packages").and_then(|p| p.as_object()) {
-            for (path, pkg) in packages {
-                // Skip the root package (empty path)
-                if path.is_empty() {
-                    continue;
-                }
-
-                // Extract package name from path (node_modules/name or node_modules/@scope/name)
-                let name = path
-                    .strip_prefix("node_modules/")
-                    .unwrap_or(path)
-                    .to_string();
-
-                let version = pkg
-                    .get("version")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-
-                let is_dev = pkg.get("dev").and_then(|v| v.as_bool()).unwrap_or(false);
-
-                // Check for postinstall script
-                let has_postinstall = pkg
-                    .get("hasInstallScript")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-
-                let integrity_hash = pkg
-                    .get("integrity")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-
-                let resolved_url = pkg
-                    .get("resolved")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-
-                dependencies.push(SnapshotDependency {
-                    id: None,
-                    snapshot_id: snapshot_id.to_string(),
-                    name,
-                    version,
-                    is_direct: !path.contains("/node_modules/"),
-                    is_dev,
-                    has_postinstall,
-                    postinstall_script: None, // Would need to read from node_modules
-                    integrity_hash,
-                    resolved_url,
-                });
+    ) -> Result<(Vec<SnapshotDependency>, Vec<SnapshotDependencyEdge>), String> {
+        let graph = match lockfile::parse_lockfile(lockfile_type, lockfile_path, content) {
+            Ok(graph) => graph,
+            // A bun.lockb without a working bun CLI to decode it still gets a snapshot
+            Err(e) if lockfile::bun::is_binary_lockfile(content) => {
+                log::warn!("[SnapshotCapture] {}, returning empty dependency list", e);
+                return Ok((Vec::new(), Vec::new()));
             }
-        }
-
-        Ok(dependencies)
-    }
-
-    /// Parse pnpm-lock.yaml
-    fn parse_pnpm_lockfile(
-        &self,
-        content: &[u8],
-        snapshot_id: &str,
-    ) -> Result<Vec<SnapshotDependency>, String> {
-        let content_str = String::from_utf8_lossy(content);
-        let lockfile: serde_yaml::Value = serde_yaml::from_str(&content_str)
-            .map_err(|e| format!("Failed to parse pnpm-lock.yaml: {}", e))?;
-
-        let mut dependencies = Vec::new();
-
-        // pnpm uses "packages" key
-        if let Some(packages) = lockfile.get("packages").and_then(|p| p.as_mapping()) {
-            for (path, pkg) in packages {
-                let path_str = path.as_str().unwrap_or_default();
-
-                // Parse package name and version from path
-                // Format: /name@version or /@scope/name@version
-                let (name, version) = self.parse_pnpm_package_path(path_str);
-
-                let is_dev = pkg
-                    .get("dev")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-
-                // Check for scripts
-                let has_postinstall = pkg
-                    .get("hasBin")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false)
-                    || pkg.get("scripts").is_some();
-
-                let integrity_hash = pkg
-                    .get("resolution")
-                    .and_then(|r| r.get("integrity"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-
-                dependencies.push(SnapshotDependency {
-                    id: None,
-                    snapshot_id: snapshot_id.to_string(),
-                    name,
-                    version,
-                    is_direct: false, // Would need to check importers
-                    is_dev,
-                    has_postinstall,
-                    postinstall_script: None,
-                    integrity_hash,
-                    resolved_url: None,
-                });
-            }
-        }
-
-        Ok(dependencies)
-    }
+            Err(e) => return Err(e),
+        };
 
-    /// Parse pnpm package path to extract name and version
-    fn parse_pnpm_package_path(&self, path: &str) -> (String, String) {
-        // Remove leading slash
-        let path = path.strip_prefix('/').unwrap_or(path);
-
-        // Find the last @ that separates name from version
-        if let Some(at_pos) = path.rfind('@') {
-            if at_pos > 0 {
-                let name = &path[..at_pos];
-                let version = &path[at_pos + 1..];
-                // Handle additional path components after version
-                let version = version.split('/').next().unwrap_or(version);
-                return (name.to_string(), version.to_string());
-            }
-        }
+        let edges = Self::graph_edges(&graph, snapshot_id);
+        let dependencies = graph
+            .packages
+            .into_iter()
+            .map(|pkg| SnapshotDependency {
+                id: None,
+                snapshot_id: snapshot_id.to_string(),
+                name: pkg.name,
+                version: pkg.version,
+                is_direct: pkg.is_direct,
+                is_dev: pkg.is_dev,
+                has_postinstall: pkg.has_install_script,
+                postinstall_script: None, // Would need to read from node_modules
+                integrity_hash: pkg.integrity,
+                resolved_url: pkg.resolved,
+            })
+            .collect();
 
-        (path.to_string(), "unknown".to_string())
+        Ok((dependencies, edges))
     }
 
-    /// Parse yarn.lock (v1 format - simplified)
-    fn parse_yarn_lockfile(
-        &self,
-        content: &[u8],
-        snapshot_id: &str,
-    ) -> Result<Vec<SnapshotDependency>, String> {
-        let content_str = String::from_utf8_lossy(content);
-        let mut dependencies = Vec::new();
-        let mut current_name = String::new();
-        let mut current_version = String::new();
-        let mut current_integrity = Option::<String>::None;
-
-        for line in content_str.lines() {
-            let line = line.trim();
-
-            // Package declaration line
-            if !line.starts_with(' ') && !line.starts_with('#') && line.ends_with(':') {
-                // Parse package name from line like "package-name@version:"
-                let spec = line.trim_end_matches(':');
-                if let Some(at_pos) = spec.find('@') {
-                    // Handle scoped packages (@scope/name)
-                    if spec.starts_with('@') {
-                        if let Some(second_at) = spec[1..].find('@') {
-                            current_name = spec[..second_at + 1].to_string();
-                        }
-                    } else {
-                        current_name = spec[..at_pos].to_string();
-                    }
-                }
-            }
-
-            // Version line
-            if line.starts_with("version ") {
-                current_version = line
-                    .strip_prefix("version ")
-                    .unwrap_or("")
-                    .trim_matches('"')
-                    .to_string();
-            }
-
-            // Integrity line
-            if line.starts_with("integrity ") {
-                current_integrity = Some(
-                    line.strip_prefix("integrity ")
-                        .unwrap_or("")
-                        .trim_matches('"')
-                        .to_string(),
-                );
-            }
-
-            // Empty line indicates end of entry
-            if line.is_empty() && !current_name.is_empty() && !current_version.is_empty() {
-                dependencies.push(SnapshotDependency {
-                    id: None,
+    /// Snapshot edges keyed by package name and version. Copies of the same version
+    /// installed at several paths collapse into one node.
+    fn graph_edges(graph: &DependencyGraph, snapshot_id: &str) -> Vec<SnapshotDependencyEdge> {
+        let mut seen = HashSet::new();
+        graph
+            .edges
+            .iter()
+            .map(|edge| {
+                let parent = edge.parent.map(|i| &graph.packages[i]);
+                let child = &graph.packages[edge.child];
+                SnapshotDependencyEdge {
                     snapshot_id: snapshot_id.to_string(),
-                    name: current_name.clone(),
-                    version: current_version.clone(),
-                    is_direct: false,
-                    is_dev: false,
-                    has_postinstall: false,
-                    postinstall_script: None,
-                    integrity_hash: current_integrity.take(),
-                    resolved_url: None,
-                });
-                current_name.clear();
-                current_version.clear();
-            }
-        }
-
-        Ok(dependencies)
-    }
-
-    /// Parse bun.lockb (binary format - returns empty for now)
-    fn parse_bun_lockfile(
-        &self,
-        _content: &[u8],
-        _snapshot_id: &str,
-    ) -> Result<Vec<SnapshotDependency>, String> {
-        // Bun lockfile is binary format, would need special handling
-        // For now, return empty list
-        log::warn!("Bun lockfile parsing not implemented, returning empty dependency list");
-        Ok(Vec::new())
+                    parent_name: parent.map(|p| p.name.clone()),
+                    parent_version: parent.map(|p| p.version.clone()),
+                    child_name: child.name.clone(),
+                    child_version: child.version.clone(),
+                    kind: edge.kind,
+                }
+            })
+            .filter(|edge| seen.insert(edge.clone()))
+            .collect()
     }
 
     /// Compute SHA-256 hash of data
@@ -1,7 +1,7 @@
 // Snapshot Diff Service
 // Compares two snapshots and generates detailed diff information
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::models::snapshot::{
     DependencyChange, DependencyChangeType, DiffSummary, ExecutionSnapshot, PostinstallChange,
@@ -9,6 +9,7 @@ use crate::models::snapshot::{
 };
 use crate::repositories::SnapshotRepository;
 use crate::services::security_guardian::patterns::{analyze_dependency_changes, PatternAnalysisResult};
+use crate::services::snapshot::graph::SnapshotGraph;
 use crate::utils::database::Database;
 
 /// Service for comparing execution snapshots
@@ -45,7 +46,14 @@ impl SnapshotDiffService {
             .ok_or_else(|| format!("Snapshot {} not found", snapshot_b_id))?;
 
         // Build the diff
-        let diff = self.build_diff(&snapshot_a.snapshot, &snapshot_a.dependencies, &snapshot_b.snapshot, &snapshot_b.dependencies);
+        let mut diff = self.build_diff(&snapshot_a.snapshot, &snapshot_a.dependencies, &snapshot_b.snapshot, &snapshot_b.dependencies);
+
+        // Attribute transitive changes to the direct dependency changes that caused them
+        let edges_a = repo.list_dependency_edges(snapshot_a_id)?;
+        let edges_b = repo.list_dependency_edges(snapshot_b_id)?;
+        let graph_a = SnapshotGraph::new(&snapshot_a.dependencies, &edges_a);
+        let graph_b = SnapshotGraph::new(&snapshot_b.dependencies, &edges_b);
+        Self::attribute_transitive_changes(&mut diff.dependency_changes, &graph_a, &graph_b);
 
         // Cache the result
         repo.cache_diff(&diff)?;
@@ -116,6 +124,7 @@ impl SnapshotDiffService {
                         postinstall_changed: postinstall_changed_flag,
                         old_postinstall: dep_a.postinstall_script.clone(),
                         new_postinstall: dep_b.postinstall_script.clone(),
+                        introduced_by: Vec::new(),
                     });
                 } else {
                     unchanged_count += 1;
@@ -144,6 +153,7 @@ impl SnapshotDiffService {
                     postinstall_changed: dep_a.has_postinstall,
                     old_postinstall: dep_a.postinstall_script.clone(),
                     new_postinstall: None,
+                    introduced_by: Vec::new(),
                 });
             }
         }
@@ -173,6 +183,7 @@ impl SnapshotDiffService {
                     postinstall_changed: dep_b.has_postinstall,
                     old_postinstall: None,
                     new_postinstall: dep_b.postinstall_script.clone(),
+                    introduced_by: Vec::new(),
                 });
             }
         }
@@ -208,6 +219,36 @@ impl SnapshotDiffService {
         }
     }
 
+    /// Fill `introduced_by` of each transitive change with the changed direct dependencies
+    /// that depend on it: in the new snapshot for additions and updates, in the old one
+    /// for removals. Snapshots captured without a dependency graph leave it empty.
+    fn attribute_transitive_changes(
+        changes: &mut [DependencyChange],
+        graph_a: &SnapshotGraph,
+        graph_b: &SnapshotGraph,
+    ) {
+        let changed_direct: HashSet<String> = changes
+            .iter()
+            .filter(|c| c.is_direct)
+            .map(|c| c.name.clone())
+            .collect();
+
+        for change in changes.iter_mut().filter(|c| !c.is_direct) {
+            let (graph, version) = match change.change_type {
+                DependencyChangeType::Removed => (graph_a, &change.old_version),
+                _ => (graph_b, &change.new_version),
+            };
+            let Some(version) = version else {
+                continue;
+            };
+            change.introduced_by = graph
+                .direct_dependents(&change.name, version)
+                .into_iter()
+                .filter(|name| changed_direct.contains(name))
+                .collect();
+        }
+    }
+
     /// Generate AI-friendly prompt for diff analysis
     pub fn generate_ai_prompt(&self, diff: &SnapshotDiff) -> String {
         let mut prompt = String::from("Analyze this dependency change between two workflow executions:\n\n");
@@ -280,6 +321,10 @@ impl SnapshotDiffService {
                     DependencyChangeType::Unchanged => {}
                 }
 
+                if !change.introduced_by.is_empty() {
+                    prompt.push_str(&format!("    via {}\n", change.introduced_by.join(", ")));
+                }
+
                 if change.postinstall_changed {
                     prompt.push_str("    ⚠️ Postinstall script changed\n");
                 }
@@ -336,3 +381,100 @@ impl SnapshotDiffService {
         Ok((diff, patterns))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::snapshot::{DependencyKind, SnapshotDependencyEdge};
+
+    fn change(
+        name: &str,
+        change_type: DependencyChangeType,
+        old: Option<&str>,
+        new: Option<&str>,
+        is_direct: bool,
+    ) -> DependencyChange {
+        DependencyChange {
+            name: name.to_string(),
+            change_type,
+            old_version: old.map(|v| v.to_string()),
+            new_version: new.map(|v| v.to_string()),
+            is_direct,
+            is_dev: false,
+            postinstall_changed: false,
+            old_postinstall: None,
+            new_postinstall: None,
+            introduced_by: Vec::new(),
+        }
+    }
+
+    fn graph(edges: &[(Option<(&str, &str)>, (&str, &str))]) -> SnapshotGraph {
+        let edges: Vec<SnapshotDependencyEdge> = edges
+            .iter()
+            .map(|(parent, child)| SnapshotDependencyEdge {
+                snapshot_id: "s".to_string(),
+                parent_name: parent.map(|(n, _)| n.to_string()),
+                parent_version: parent.map(|(_, v)| v.to_string()),
+                child_name: child.0.to_string(),
+                child_version: child.1.to_string(),
+                kind: DependencyKind::Prod,
+            })
+            .collect();
+        SnapshotGraph::new(&[], &edges)
+    }
+
+    #[test]
+    fn test_transitive_changes_attributed_to_direct_bumps() {
+        let graph_a = graph(&[
+            (None, ("express", "4.17.0")),
+            (None, ("react", "18.2.0")),
+            (Some(("express", "4.17.0")), ("qs", "6.9.0")),
+            (Some(("express", "4.17.0")), ("old-dep", "1.0.0")),
+            (Some(("react", "18.2.0")), ("loose-envify", "1.4.0")),
+        ]);
+        let graph_b = graph(&[
+            (None, ("express", "4.18.2")),
+            (None, ("react", "18.2.0")),
+            (Some(("express", "4.18.2")), ("qs", "6.11.0")),
+            (Some(("react", "18.2.0")), ("loose-envify", "1.4.1")),
+        ]);
+        let mut changes = vec![
+            change(
+                "express",
+                DependencyChangeType::Updated,
+                Some("4.17.0"),
+                Some("4.18.2"),
+                true,
+            ),
+            change(
+                "qs",
+                DependencyChangeType::Updated,
+                Some("6.9.0"),
+                Some("6.11.0"),
+                false,
+            ),
+            change(
+                "old-dep",
+                DependencyChangeType::Removed,
+                Some("1.0.0"),
+                None,
+                false,
+            ),
+            // react itself didn't change, so nothing is blamed for this refresh
+            change(
+                "loose-envify",
+                DependencyChangeType::Updated,
+                Some("1.4.0"),
+                Some("1.4.1"),
+                false,
+            ),
+        ];
+
+        SnapshotDiffService::attribute_transitive_changes(&mut changes, &graph_a, &graph_b);
+
+        assert!(changes[0].introduced_by.is_empty());
+        assert_eq!(changes[1].introduced_by, vec!["express"]);
+        assert_eq!(changes[2].introduced_by, vec!["express"]);
+        assert!(changes[3].introduced_by.is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+// Snapshot Dependency Graph
+// Answers "why is this package installed" from the edges stored with a snapshot
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::models::snapshot::{
+    DependencyKind, DependencyPathStep, InstallReason, SnapshotDependency, SnapshotDependencyEdge,
+};
+use crate::repositories::SnapshotRepository;
+use crate::utils::database::Database;
+
+/// Paths returned per package; popular transitive packages can have thousands
+const MAX_PATHS: usize = 50;
+
+/// A snapshot's dependency graph with packages keyed by name and version
+pub struct SnapshotGraph {
+    nodes: Vec<(String, String)>,
+    index: HashMap<(String, String), usize>,
+    /// Incoming edges per node; `None` is the project's package.json
+    parents: Vec<Vec<(Option<usize>, DependencyKind)>>,
+    /// Flags from the dependency list (snapshots without edges still know these)
+    direct: HashSet<usize>,
+    postinstall: HashSet<usize>,
+}
+
+impl SnapshotGraph {
+    /// Build the graph of a snapshot from its dependencies and edges
+    pub fn new(dependencies: &[SnapshotDependency], edges: &[SnapshotDependencyEdge]) -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            parents: Vec::new(),
+            direct: HashSet::new(),
+            postinstall: HashSet::new(),
+        };
+
+        for dep in dependencies {
+            let node = graph.node(&dep.name, &dep.version);
+            if dep.is_direct {
+                graph.direct.insert(node);
+            }
+            if dep.has_postinstall {
+                graph.postinstall.insert(node);
+            }
+        }
+        for edge in edges {
+            let child = graph.node(&edge.child_name, &edge.child_version);
+            let parent = match (&edge.parent_name, &edge.parent_version) {
+                (Some(name), Some(version)) => Some(graph.node(name, version)),
+                _ => {
+                    graph.direct.insert(child);
+                    None
+                }
+            };
+            if !graph.parents[child].contains(&(parent, edge.kind)) {
+                graph.parents[child].push((parent, edge.kind));
+            }
+        }
+        // Deterministic path order: project edges first, then by parent name
+        for parents in &mut graph.parents {
+            parents.sort_by_key(|(parent, _)| parent.map(|p| graph.nodes[p].clone()));
+        }
+
+        graph
+    }
+
+    /// Why each installed version of `name` (or only `version`) is in the snapshot
+    pub fn why(&self, name: &str, version: Option<&str>) -> Vec<InstallReason> {
+        let mut targets: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| {
+                let (n, v) = &self.nodes[i];
+                n == name && version.is_none_or(|version| v == version)
+            })
+            .collect();
+        targets.sort_by(|a, b| self.nodes[*a].1.cmp(&self.nodes[*b].1));
+        targets.into_iter().map(|i| self.reason(i)).collect()
+    }
+
+    /// Why each package with a postinstall script is in the snapshot
+    pub fn postinstall_reasons(&self) -> Vec<InstallReason> {
+        let mut targets: Vec<usize> = self.postinstall.iter().copied().collect();
+        targets.sort_by(|a, b| self.nodes[*a].cmp(&self.nodes[*b]));
+        targets.into_iter().map(|i| self.reason(i)).collect()
+    }
+
+    /// Names of the direct dependencies whose subtree contains `name@version`
+    pub fn direct_dependents(&self, name: &str, version: &str) -> BTreeSet<String> {
+        let Some(&node) = self.index.get(&(name.to_string(), version.to_string())) else {
+            return BTreeSet::new();
+        };
+
+        let mut seen = HashSet::new();
+        let mut stack = vec![node];
+        let mut direct = BTreeSet::new();
+        while let Some(i) = stack.pop() {
+            if !seen.insert(i) {
+                continue;
+            }
+            if self.direct.contains(&i) {
+                direct.insert(self.nodes[i].0.clone());
+            }
+            stack.extend(self.parents[i].iter().filter_map(|(parent, _)| *parent));
+        }
+        direct
+    }
+
+    fn node(&mut self, name: &str, version: &str) -> usize {
+        let key = (name.to_string(), version.to_string());
+        if let Some(&i) = self.index.get(&key) {
+            return i;
+        }
+        self.nodes.push(key.clone());
+        self.parents.push(Vec::new());
+        self.index.insert(key, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    fn reason(&self, node: usize) -> InstallReason {
+        let (name, version) = &self.nodes[node];
+        let mut paths = Vec::new();
+        let truncated = !self.collect_paths(node, &mut Vec::new(), &mut paths);
+        paths.sort_by_key(|path| path.len());
+
+        InstallReason {
+            name: name.clone(),
+            version: version.clone(),
+            is_direct: self.direct.contains(&node),
+            has_postinstall: self.postinstall.contains(&node),
+            direct_dependencies: self.direct_dependents(name, version).into_iter().collect(),
+            paths,
+            truncated,
+        }
+    }
+
+    /// Walk up from `node` to the project, collecting each path top-down.
+    /// `below` holds the nodes already on the path; returns false once MAX_PATHS is hit.
+    fn collect_paths(
+        &self,
+        node: usize,
+        below: &mut Vec<(usize, DependencyKind)>,
+        paths: &mut Vec<Vec<DependencyPathStep>>,
+    ) -> bool {
+        for &(parent, kind) in &self.parents[node] {
+            below.push((node, kind));
+            let complete = match parent {
+                None if paths.len() >= MAX_PATHS => false,
+                None => {
+                    paths.push(
+                        below
+                            .iter()
+                            .rev()
+                            .map(|&(i, kind)| self.step(i, kind))
+                            .collect(),
+                    );
+                    true
+                }
+                // Skip cycles
+                Some(p) if below.iter().any(|&(i, _)| i == p) => true,
+                Some(p) => self.collect_paths(p, below, paths),
+            };
+            below.pop();
+            if !complete {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn step(&self, node: usize, kind: DependencyKind) -> DependencyPathStep {
+        let (name, version) = &self.nodes[node];
+        DependencyPathStep {
+            name: name.clone(),
+            version: version.clone(),
+            kind,
+        }
+    }
+}
+
+/// Service for dependency graph queries on stored snapshots
+pub struct SnapshotGraphService {
+    db: Database,
+}
+
+impl SnapshotGraphService {
+    /// Create a new SnapshotGraphService
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Load the dependency graph of a snapshot
+    pub fn load_graph(&self, snapshot_id: &str) -> Result<SnapshotGraph, String> {
+        let repo = SnapshotRepository::new(self.db.clone());
+        repo.get_snapshot(snapshot_id)?
+            .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+
+        let dependencies = repo.list_dependencies(snapshot_id)?;
+        let edges = repo.list_dependency_edges(snapshot_id)?;
+        Ok(SnapshotGraph::new(&dependencies, &edges))
+    }
+
+    /// Why a package is in a snapshot: all paths from direct dependencies to it
+    pub fn why_installed(
+        &self,
+        snapshot_id: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<InstallReason>, String> {
+        Ok(self.load_graph(snapshot_id)?.why(name, version))
+    }
+
+    /// What pulled in each package with a postinstall script
+    pub fn postinstall_origins(&self, snapshot_id: &str) -> Result<Vec<InstallReason>, String> {
+        Ok(self.load_graph(snapshot_id)?.postinstall_reasons())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(
+        name: &str,
+        version: &str,
+        is_direct: bool,
+        has_postinstall: bool,
+    ) -> SnapshotDependency {
+        SnapshotDependency {
+            id: None,
+            snapshot_id: "s1".to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            is_direct,
+            is_dev: false,
+            has_postinstall,
+            postinstall_script: None,
+            integrity_hash: None,
+            resolved_url: None,
+        }
+    }
+
+    fn edge(
+        parent: Option<(&str, &str)>,
+        child: (&str, &str),
+        kind: DependencyKind,
+    ) -> SnapshotDependencyEdge {
+        SnapshotDependencyEdge {
+            snapshot_id: "s1".to_string(),
+            parent_name: parent.map(|(n, _)| n.to_string()),
+            parent_version: parent.map(|(_, v)| v.to_string()),
+            child_name: child.0.to_string(),
+            child_version: child.1.to_string(),
+            kind,
+        }
+    }
+
+    /// app -> {express, jest(dev)}; express -> body-parser -> lodash; jest -> lodash;
+    /// jest -> fsevents (optional, postinstall)
+    fn sample_graph() -> SnapshotGraph {
+        let deps = vec![
+            dep("express", "4.18.2", true, false),
+            dep("jest", "29.7.0", true, false),
+            dep("body-parser", "1.20.1", false, false),
+            dep("lodash", "4.17.20", false, false),
+            dep("fsevents", "2.3.3", false, true),
+        ];
+        let edges = vec![
+            edge(None, ("express", "4.18.2"), DependencyKind::Prod),
+            edge(None, ("jest", "29.7.0"), DependencyKind::Dev),
+            edge(
+                Some(("express", "4.18.2")),
+                ("body-parser", "1.20.1"),
+                DependencyKind::Prod,
+            ),
+            edge(
+                Some(("body-parser", "1.20.1")),
+                ("lodash", "4.17.20"),
+                DependencyKind::Prod,
+            ),
+            edge(
+                Some(("jest", "29.7.0")),
+                ("lodash", "4.17.20"),
+                DependencyKind::Prod,
+            ),
+            edge(
+                Some(("jest", "29.7.0")),
+                ("fsevents", "2.3.3"),
+                DependencyKind::Optional,
+            ),
+            // Cycle back up the tree must not loop
+            edge(
+                Some(("lodash", "4.17.20")),
+                ("body-parser", "1.20.1"),
+                DependencyKind::Peer,
+            ),
+        ];
+        SnapshotGraph::new(&deps, &edges)
+    }
+
+    #[test]
+    fn test_why_installed_lists_all_paths() {
+        let graph = sample_graph();
+        let reasons = graph.why("lodash", Some("4.17.20"));
+        assert_eq!(reasons.len(), 1);
+
+        let reason = &reasons[0];
+        assert!(!reason.is_direct);
+        assert!(!reason.truncated);
+        assert_eq!(reason.direct_dependencies, vec!["express", "jest"]);
+
+        let paths: Vec<Vec<&str>> = reason
+            .paths
+            .iter()
+            .map(|path| path.iter().map(|step| step.name.as_str()).collect())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["jest", "lodash"],
+                vec!["express", "body-parser", "lodash"]
+            ]
+        );
+        assert_eq!(reason.paths[0][0].kind, DependencyKind::Dev);
+
+        assert!(graph.why("lodash", Some("3.0.0")).is_empty());
+    }
+
+    #[test]
+    fn test_postinstall_reasons() {
+        let reasons = sample_graph().postinstall_reasons();
+        assert_eq!(reasons.len(), 1);
+        assert_eq!(reasons[0].name, "fsevents");
+        assert_eq!(reasons[0].direct_dependencies, vec!["jest"]);
+        assert_eq!(reasons[0].paths[0][1].kind, DependencyKind::Optional);
+    }
+}
@@ -1,16 +1,23 @@
 // Time Machine - Snapshot Services
-// Provides snapshot capture, storage, diff, replay, search, and validation functionality
+// Provides snapshot capture, storage, diff, replay, retention, bundle sharing, search, and
+// validation functionality
 
+pub mod bundle;
 pub mod capture;
 pub mod diff;
+pub mod graph;
 pub mod replay;
+pub mod retention;
 pub mod search;
 pub mod storage;
 pub mod validation;
 
+pub use bundle::SnapshotBundleService;
 pub use capture::SnapshotCaptureService;
 pub use diff::SnapshotDiffService;
+pub use graph::{SnapshotGraph, SnapshotGraphService};
 pub use replay::SnapshotReplayService;
+pub use retention::SnapshotRetentionService;
 pub use search::SnapshotSearchService;
 pub use storage::SnapshotStorage;
 pub use validation::{
@@ -8,6 +8,7 @@ use std::path::Path;
 
 use crate::models::snapshot::{ExecutionSnapshot, LockfileType, SnapshotDependency};
 use crate::repositories::SnapshotRepository;
+use crate::services::lockfile;
 use crate::services::snapshot::storage::SnapshotStorage;
 use crate::utils::database::Database;
 
@@ -154,15 +155,14 @@ impl SnapshotReplayService {
     ) -> Result<Option<ReplayMismatch>, String> {
         // Try to read current lockfile
         let lockfile_type = snapshot.lockfile_type.clone().unwrap_or(LockfileType::Npm);
-        let lockfile_name = lockfile_type.lockfile_name();
-        let lockfile_path = project_path.join(lockfile_name);
 
-        let current_lockfile_hash = if lockfile_path.exists() {
-            let content = fs::read(&lockfile_path)
-                .map_err(|e| format!("Failed to read lockfile: {}", e))?;
-            Some(compute_hash(&content))
-        } else {
-            None
+        let current_lockfile_hash = match lockfile_type.find_lockfile(project_path) {
+            Some(lockfile_path) => {
+                let content = fs::read(&lockfile_path)
+                    .map_err(|e| format!("Failed to read lockfile: {}", e))?;
+                Some(compute_hash(&content))
+            }
+            None => None,
         };
 
         // Check if lockfile changed
@@ -253,87 +253,25 @@ impl SnapshotReplayService {
         }))
     }
 
-    /// Parse current dependencies from lockfile (simplified)
+    /// Parse current dependencies from lockfile
     fn parse_current_dependencies(
         &self,
         project_path: &Path,
         lockfile_type: &LockfileType,
     ) -> Result<Vec<(String, String)>, String> {
-        let lockfile_path = project_path.join(lockfile_type.lockfile_name());
-
-        if !lockfile_path.exists() {
+        let Some(lockfile_path) = lockfile_type.find_lockfile(project_path) else {
             return Ok(Vec::new());
-        }
+        };
 
-        let content = fs::read_to_string(&lockfile_path)
+        let content = fs::read(&lockfile_path)
             .map_err(|e| format!("Failed to read lockfile: {}", e))?;
 
-        match lockfile_type {
-            LockfileType::Npm => self.parse_npm_lockfile_deps(&content),
-            LockfileType::Pnpm => self.parse_pnpm_lockfile_deps(&content),
-            LockfileType::Yarn => Ok(Vec::new()), // Simplified - yarn parsing is complex
-            LockfileType::Bun => Ok(Vec::new()),  // Simplified - bun uses binary format
-        }
-    }
-
-    /// Parse npm package-lock.json dependencies
-    fn parse_npm_lockfile_deps(&self, content: &str) -> Result<Vec<(String, String)>, String> {
-        let lockfile: serde_json::Value = serde_json::from_str(content)
-            .map_err(|e| format!("Failed to parse package-lock.json: {}", e))?;
-
-        let mut deps = Vec::new();
-
-        if let Some(packages) = lockfile.get("packages").and_then(|p| p.as_object()) {
-            for (path, pkg_data) in packages {
-                if path.is_empty() {
-                    continue;
-                }
-                let name = path.trim_start_matches("node_modules/").to_string();
-                if name.contains("node_modules/") {
-                    continue;
-                }
-                if let Some(version) = pkg_data.get("version").and_then(|v| v.as_str()) {
-                    deps.push((name, version.to_string()));
-                }
-            }
-        }
-
-        Ok(deps)
-    }
-
-    /// Parse pnpm-lock.yaml dependencies
-    fn parse_pnpm_lockfile_deps(&self, content: &str) -> Result<Vec<(String, String)>, String> {
-        let lockfile: serde_yaml::Value = serde_yaml::from_str(content)
-            .map_err(|e| format!("Failed to parse pnpm-lock.yaml: {}", e))?;
-
-        let mut deps = Vec::new();
-
-        if let Some(importers) = lockfile.get("importers") {
-            if let Some(root) = importers.get(".") {
-                // Direct dependencies
-                if let Some(dependencies) = root.get("dependencies").and_then(|d| d.as_mapping()) {
-                    for (name, info) in dependencies {
-                        if let (Some(name), Some(info)) = (name.as_str(), info.as_mapping()) {
-                            if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
-                                deps.push((name.to_string(), version.to_string()));
-                            }
-                        }
-                    }
-                }
-                // Dev dependencies
-                if let Some(dev_deps) = root.get("devDependencies").and_then(|d| d.as_mapping()) {
-                    for (name, info) in dev_deps {
-                        if let (Some(name), Some(info)) = (name.as_str(), info.as_mapping()) {
-                            if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
-                                deps.push((name.to_string(), version.to_string()));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(deps)
+        // Same packages the snapshot captured, so both sides compare like for like
+        Ok(lockfile::parse_lockfile(lockfile_type, &lockfile_path, &content)?
+            .packages
+            .into_iter()
+            .map(|pkg| (pkg.name, pkg.version))
+            .collect())
     }
 
     /// Restore lockfile from snapshot
@@ -346,13 +284,22 @@ impl SnapshotReplayService {
             .ok_or_else(|| format!("Snapshot not found: {}", snapshot_id))?;
 
         let lockfile_type = snapshot.lockfile_type.clone().unwrap_or(LockfileType::Npm);
-        let lockfile_name = lockfile_type.lockfile_name();
 
-        // Read lockfile from storage
-        let lockfile_content = self.storage.read_lockfile(snapshot_id, lockfile_name)?;
+        // Read lockfile from storage (Bun snapshots hold either bun.lock or bun.lockb)
+        let (lockfile_name, lockfile_content) = lockfile_type
+            .lockfile_names()
+            .iter()
+            .find_map(|name| {
+                self.storage
+                    .read_lockfile(snapshot_id, name)
+                    .ok()
+                    .map(|content| (*name, content))
+            })
+            .ok_or_else(|| format!("Lockfile not found in snapshot: {}", snapshot_id))?;
 
         // Write to project
         let project_path = Path::new(&snapshot.project_path);
+        check_restorable_lockfile(project_path, lockfile_name)?;
         let lockfile_path = project_path.join(lockfile_name);
 
         // Backup current lockfile
@@ -439,6 +386,19 @@ fn compute_hash(content: &[u8]) -> String {
 // Tests
 // =============================================================================
 
+/// Bun reads `bun.lock` whenever it exists, so a `bun.lockb` restored next to one
+/// would be silently ignored
+fn check_restorable_lockfile(project_path: &Path, lockfile_name: &str) -> Result<(), String> {
+    if lockfile_name == "bun.lockb" && project_path.join("bun.lock").exists() {
+        return Err(
+            "Snapshot holds a binary bun.lockb but the project now has a bun.lock, which Bun \
+             reads instead. Remove bun.lock to restore this snapshot."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -456,4 +416,18 @@ mod tests {
         let json = serde_json::to_string(&option).unwrap();
         assert_eq!(json, "\"restore_lockfile\"");
     }
+
+    #[test]
+    fn test_check_restorable_lockfile() {
+        let project =
+            std::env::temp_dir().join(format!("packageflow-restore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&project).unwrap();
+        assert!(check_restorable_lockfile(&project, "bun.lockb").is_ok());
+
+        fs::write(project.join("bun.lock"), "{}").unwrap();
+        assert!(check_restorable_lockfile(&project, "bun.lockb").is_err());
+        assert!(check_restorable_lockfile(&project, "bun.lock").is_ok());
+
+        fs::remove_dir_all(&project).unwrap();
+    }
 }
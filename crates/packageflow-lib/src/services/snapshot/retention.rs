@@ -0,0 +1,371 @@
+// Snapshot Retention Service
+// Prunes snapshots by keep-recent and grandfather-father-son rules, enforces per-project
+// disk quotas and deduplicates identical lockfiles
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::models::snapshot::{
+    SnapshotRetentionCandidate, SnapshotRetentionPolicy, SnapshotRetentionResult, SnapshotStatus,
+};
+use crate::repositories::SnapshotRepository;
+use crate::services::snapshot::storage::SnapshotStorage;
+use crate::utils::database::Database;
+
+/// Disk usage of one snapshot; the lockfile blob is shared with other snapshots
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotUsage {
+    pub dir_bytes: u64,
+    pub blob: Option<(String, u64)>,
+}
+
+/// Formats the period (day, week, month) a snapshot falls in
+type PeriodKey = fn(&DateTime<Utc>) -> String;
+
+/// Snapshots retention never deletes: pinned, deployed, or still being captured
+/// (possibly by a concurrent capture of the same project)
+fn is_protected(candidate: &SnapshotRetentionCandidate) -> bool {
+    candidate.is_pinned || candidate.is_deployed || candidate.status == SnapshotStatus::Capturing
+}
+
+/// IDs of the snapshots the policy keeps. `candidates` are one project's snapshots.
+pub fn select_snapshots_to_keep(
+    candidates: &[SnapshotRetentionCandidate],
+    policy: &SnapshotRetentionPolicy,
+) -> HashSet<String> {
+    let mut keep: HashSet<String> = candidates
+        .iter()
+        .filter(|c| is_protected(c))
+        .map(|c| c.id.clone())
+        .collect();
+
+    let mut completed: Vec<(&SnapshotRetentionCandidate, Option<DateTime<Utc>>)> = candidates
+        .iter()
+        .filter(|c| c.status == SnapshotStatus::Completed)
+        .map(|c| (c, parse_time(&c.created_at)))
+        .collect();
+    completed.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
+
+    // The latest snapshot is what replay and diff compare against
+    let recent = (policy.keep_recent as usize).max(1);
+    keep.extend(completed.iter().take(recent).map(|(c, _)| c.id.clone()));
+
+    let periods: [(u32, PeriodKey); 3] = [
+        (policy.keep_daily, |t| t.format("%Y-%m-%d").to_string()),
+        (policy.keep_weekly, |t| {
+            let week = t.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }),
+        (policy.keep_monthly, |t| t.format("%Y-%m").to_string()),
+    ];
+    for (count, period_of) in periods {
+        // Newest snapshot of each of the last `count` periods that have snapshots
+        let mut seen = HashSet::new();
+        for (candidate, time) in &completed {
+            let Some(time) = time else {
+                continue;
+            };
+            if seen.len() >= count as usize {
+                break;
+            }
+            if seen.insert(period_of(time)) {
+                keep.insert(candidate.id.clone());
+            }
+        }
+    }
+
+    keep
+}
+
+/// Kept snapshots to drop, oldest first, until the project fits in `limit_bytes`.
+/// `kept` is newest first; protected snapshots are never dropped.
+pub fn select_over_quota(
+    kept: &[&SnapshotRetentionCandidate],
+    protected: &HashSet<String>,
+    usage: &HashMap<String, SnapshotUsage>,
+    limit_bytes: u64,
+) -> Vec<String> {
+    let mut remaining: Vec<&SnapshotRetentionCandidate> = kept.to_vec();
+    let mut dropped = Vec::new();
+
+    while total_usage(&remaining, usage) > limit_bytes {
+        let Some(index) = remaining.iter().rposition(|c| !protected.contains(&c.id)) else {
+            break;
+        };
+        dropped.push(remaining.remove(index).id.clone());
+    }
+
+    dropped
+}
+
+/// Bytes used by a set of snapshots, counting each shared blob once
+pub fn total_usage(
+    snapshots: &[&SnapshotRetentionCandidate],
+    usage: &HashMap<String, SnapshotUsage>,
+) -> u64 {
+    let mut blobs = HashMap::new();
+    let mut total = 0;
+    for snapshot in snapshots {
+        if let Some(entry) = usage.get(&snapshot.id) {
+            total += entry.dir_bytes;
+            if let Some((hash, size)) = &entry.blob {
+                blobs.insert(hash.as_str(), *size);
+            }
+        }
+    }
+    total + blobs.values().sum::<u64>()
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Service applying the retention policy to stored snapshots
+pub struct SnapshotRetentionService {
+    storage: SnapshotStorage,
+    db: Database,
+}
+
+impl SnapshotRetentionService {
+    /// Create a new SnapshotRetentionService
+    pub fn new(storage: SnapshotStorage, db: Database) -> Self {
+        Self { storage, db }
+    }
+
+    /// Apply `policy` to one project, or to every project with snapshots
+    pub fn apply_retention(
+        &self,
+        policy: &SnapshotRetentionPolicy,
+        project_path: Option<&str>,
+    ) -> Result<SnapshotRetentionResult, String> {
+        let repo = SnapshotRepository::new(self.db.clone());
+        let projects = match project_path {
+            Some(path) => vec![path.to_string()],
+            None => repo.list_snapshot_projects()?,
+        };
+
+        let mut result = SnapshotRetentionResult::default();
+        for project in projects {
+            self.apply_to_project(&repo, policy, &project, &mut result)?;
+        }
+        result.reclaimed_bytes += self.storage.prune_blobs()?;
+
+        Ok(result)
+    }
+
+    fn apply_to_project(
+        &self,
+        repo: &SnapshotRepository,
+        policy: &SnapshotRetentionPolicy,
+        project_path: &str,
+        result: &mut SnapshotRetentionResult,
+    ) -> Result<(), String> {
+        let candidates = repo.list_retention_candidates(project_path)?;
+        let keep = select_snapshots_to_keep(&candidates, policy);
+
+        let (mut kept, pruned): (Vec<_>, Vec<_>) =
+            candidates.iter().partition(|c| keep.contains(&c.id));
+        for candidate in pruned {
+            self.delete(repo, &candidate.id, result)?;
+        }
+
+        // Point identical lockfiles at one shared blob
+        for candidate in &kept {
+            let Some(hash) = &candidate.lockfile_hash else {
+                continue;
+            };
+            if candidate.status == SnapshotStatus::Capturing {
+                continue;
+            }
+            if self.storage.lockfile_blob_hash(&candidate.id).is_none() {
+                match self.storage.deduplicate_lockfile(&candidate.id, hash) {
+                    Ok(reclaimed) => {
+                        result.reclaimed_bytes += reclaimed;
+                        result.deduplicated_lockfiles += 1;
+                    }
+                    Err(e) => {
+                        log::warn!("[SnapshotRetention] Failed to deduplicate lockfile: {}", e)
+                    }
+                }
+            }
+        }
+
+        if let Some(limit_mb) = policy.max_project_storage_mb {
+            let limit_bytes = limit_mb.saturating_mul(1024 * 1024);
+            let usage: HashMap<String, SnapshotUsage> = kept
+                .iter()
+                .map(|c| (c.id.clone(), self.usage_of(&c.id)))
+                .collect();
+            let latest = kept
+                .iter()
+                .find(|c| c.status == SnapshotStatus::Completed)
+                .map(|c| c.id.clone());
+            let protected: HashSet<String> = kept
+                .iter()
+                .filter(|c| is_protected(c))
+                .map(|c| c.id.clone())
+                .chain(latest)
+                .collect();
+
+            let dropped = select_over_quota(&kept, &protected, &usage, limit_bytes);
+            for id in &dropped {
+                self.delete(repo, id, result)?;
+            }
+            kept.retain(|c| !dropped.contains(&c.id));
+
+            if total_usage(&kept, &usage) > limit_bytes {
+                result.over_quota_projects.push(project_path.to_string());
+            }
+        }
+
+        result.kept_count += kept.len();
+        Ok(())
+    }
+
+    fn usage_of(&self, snapshot_id: &str) -> SnapshotUsage {
+        SnapshotUsage {
+            dir_bytes: self.storage.get_snapshot_size(snapshot_id).unwrap_or(0),
+            blob: self.storage.lockfile_blob_hash(snapshot_id).map(|hash| {
+                let size = self.storage.blob_size(&hash);
+                (hash, size)
+            }),
+        }
+    }
+
+    fn delete(
+        &self,
+        repo: &SnapshotRepository,
+        snapshot_id: &str,
+        result: &mut SnapshotRetentionResult,
+    ) -> Result<(), String> {
+        result.reclaimed_bytes += self.storage.get_snapshot_size(snapshot_id).unwrap_or(0);
+        self.storage.delete_snapshot(snapshot_id)?;
+        repo.delete_snapshot(snapshot_id)?;
+        result.deleted_snapshot_ids.push(snapshot_id.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, created_at: &str) -> SnapshotRetentionCandidate {
+        SnapshotRetentionCandidate {
+            id: id.to_string(),
+            status: SnapshotStatus::Completed,
+            created_at: created_at.to_string(),
+            lockfile_hash: None,
+            is_pinned: false,
+            is_deployed: false,
+        }
+    }
+
+    fn policy(recent: u32, daily: u32, weekly: u32, monthly: u32) -> SnapshotRetentionPolicy {
+        SnapshotRetentionPolicy {
+            enabled: true,
+            keep_recent: recent,
+            keep_daily: daily,
+            keep_weekly: weekly,
+            keep_monthly: monthly,
+            max_project_storage_mb: None,
+        }
+    }
+
+    #[test]
+    fn test_grandfather_father_son() {
+        let mut old_failed = candidate("failed", "2024-03-04T09:00:00Z");
+        old_failed.status = SnapshotStatus::Failed;
+        let mut pinned = candidate("pinned", "2023-06-01T09:00:00Z");
+        pinned.is_pinned = true;
+        let mut deployed = candidate("deployed", "2023-07-01T09:00:00Z");
+        deployed.is_deployed = true;
+
+        let candidates = vec![
+            candidate("mar-05-b", "2024-03-05T18:00:00Z"),
+            candidate("mar-05-a", "2024-03-05T09:00:00Z"),
+            old_failed,
+            candidate("mar-04", "2024-03-04T08:00:00Z"),
+            // Same ISO week as mar-04 (Monday), previous day
+            candidate("mar-03", "2024-03-03T08:00:00Z"),
+            candidate("feb-20", "2024-02-20T08:00:00Z"),
+            candidate("jan-10", "2024-01-10T08:00:00Z"),
+            deployed,
+            pinned,
+        ];
+
+        let keep = select_snapshots_to_keep(&candidates, &policy(1, 2, 2, 2));
+        let mut keep: Vec<&str> = keep.iter().map(|id| id.as_str()).collect();
+        keep.sort();
+
+        // daily: mar-05-b, mar-04; weekly: mar-05-b, mar-03; monthly: mar-05-b, feb-20
+        assert_eq!(
+            keep,
+            vec!["deployed", "feb-20", "mar-03", "mar-04", "mar-05-b", "pinned"]
+        );
+    }
+
+    #[test]
+    fn test_capturing_snapshots_are_kept() {
+        let mut capturing = candidate("capturing", "2024-03-01T18:00:00Z");
+        capturing.status = SnapshotStatus::Capturing;
+        let candidates = vec![
+            candidate("new", "2024-03-05T18:00:00Z"),
+            capturing,
+            candidate("old", "2024-02-01T18:00:00Z"),
+        ];
+
+        let keep = select_snapshots_to_keep(&candidates, &policy(1, 0, 0, 0));
+        assert_eq!(
+            keep,
+            HashSet::from(["new".to_string(), "capturing".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_latest_snapshot_always_kept() {
+        let candidates = vec![
+            candidate("new", "2024-03-05T18:00:00Z"),
+            candidate("old", "2024-03-01T18:00:00Z"),
+        ];
+
+        let keep = select_snapshots_to_keep(&candidates, &policy(0, 0, 0, 0));
+        assert_eq!(keep, HashSet::from(["new".to_string()]));
+    }
+
+    #[test]
+    fn test_quota_drops_oldest_and_counts_shared_blobs_once() {
+        let snapshots = [
+            candidate("new", "2024-03-05T18:00:00Z"),
+            candidate("mid", "2024-03-04T18:00:00Z"),
+            candidate("old", "2024-03-03T18:00:00Z"),
+        ];
+        let kept: Vec<&SnapshotRetentionCandidate> = snapshots.iter().collect();
+        let usage: HashMap<String, SnapshotUsage> = snapshots
+            .iter()
+            .map(|c| {
+                let usage = SnapshotUsage {
+                    dir_bytes: 10,
+                    blob: Some(("abc".to_string(), 100)),
+                };
+                (c.id.clone(), usage)
+            })
+            .collect();
+        let protected = HashSet::from(["new".to_string()]);
+
+        assert_eq!(total_usage(&kept, &usage), 130);
+        assert!(select_over_quota(&kept, &protected, &usage, 130).is_empty());
+        assert_eq!(
+            select_over_quota(&kept, &protected, &usage, 115),
+            vec!["old", "mid"]
+        );
+        // Protected snapshots stay even when the quota cannot be met
+        assert_eq!(
+            select_over_quota(&kept, &protected, &usage, 50),
+            vec!["old", "mid"]
+        );
+    }
+}
@@ -1,11 +1,28 @@
 // Snapshot Storage Service
 // Handles file-based storage for snapshot data with zstd compression
 
+use std::collections::HashSet;
 use std::fs;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::models::snapshot::LockfileType;
+
+/// Lockfile types whose files can be stored in a snapshot
+const LOCKFILE_TYPES: [LockfileType; 4] = [
+    LockfileType::Npm,
+    LockfileType::Pnpm,
+    LockfileType::Yarn,
+    LockfileType::Bun,
+];
+
+/// Unreferenced blobs younger than this are kept by `prune_blobs`: a snapshot being
+/// stored (possibly by another process) may not have written its reference yet
+const BLOB_PRUNE_GRACE: Duration = Duration::from_secs(10 * 60);
 
 /// Snapshot storage service for file operations
+#[derive(Clone)]
 pub struct SnapshotStorage {
     base_path: PathBuf,
 }
@@ -49,7 +66,30 @@ impl SnapshotStorage {
         Ok((path, size))
     }
 
-    /// Store package.json
+    /// Store a lockfile in the shared blob store, keyed by its content hash.
+    /// The snapshot keeps a `<lockfile>.ref` pointer so identical lockfiles are stored once.
+    pub fn store_lockfile_blob(
+        &self,
+        snapshot_id: &str,
+        lockfile_name: &str,
+        content: &[u8],
+        hash: &str,
+    ) -> Result<(PathBuf, u64), String> {
+        let blob_path = self.get_blob_path(hash)?;
+        // Reference first, so a concurrent prune never sees the blob as unused
+        self.write_lockfile_ref(snapshot_id, lockfile_name, hash)?;
+        if !touch_blob(&blob_path) {
+            fs::create_dir_all(self.base_path.join("blobs"))
+                .map_err(|e| format!("Failed to create blob directory: {}", e))?;
+            let compressed = self.compress(content)?;
+            fs::write(&blob_path, &compressed)
+                .map_err(|e| format!("Failed to write lockfile blob: {}", e))?;
+        }
+
+        let size = self.blob_size(hash);
+        Ok((blob_path, size))
+    }
+
     pub fn store_package_json(
         &self,
         snapshot_id: &str,
@@ -116,9 +156,14 @@ impl SnapshotStorage {
 
     /// Read lockfile from snapshot
     pub fn read_lockfile(&self, snapshot_id: &str, lockfile_name: &str) -> Result<Vec<u8>, String> {
-        let path = self
-            .get_snapshot_path(snapshot_id)
-            .join(format!("{}.zst", lockfile_name));
+        let dir = self.get_snapshot_path(snapshot_id);
+        let path = dir.join(format!("{}.zst", lockfile_name));
+        let ref_path = dir.join(format!("{}.ref", lockfile_name));
+        if !path.exists() && ref_path.exists() {
+            let hash = fs::read_to_string(&ref_path)
+                .map_err(|e| format!("Failed to read lockfile reference: {}", e))?;
+            return self.read_file(&self.get_blob_path(hash.trim())?);
+        }
         self.read_file(&path)
     }
 
@@ -171,6 +216,131 @@ impl SnapshotStorage {
         Ok(total_size)
     }
 
+    /// Hash of the shared lockfile blob a snapshot points to, if any
+    pub fn lockfile_blob_hash(&self, snapshot_id: &str) -> Option<String> {
+        let dir = self.get_snapshot_path(snapshot_id);
+        LOCKFILE_TYPES
+            .iter()
+            .flat_map(|lockfile_type| lockfile_type.lockfile_names())
+            .find_map(|name| fs::read_to_string(dir.join(format!("{}.ref", name))).ok())
+            .map(|hash| hash.trim().to_string())
+    }
+
+    /// Size of a stored lockfile blob (0 if missing)
+    pub fn blob_size(&self, hash: &str) -> u64 {
+        self.get_blob_path(hash)
+            .ok()
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .unwrap_or(0)
+    }
+
+    /// Move a snapshot's own lockfile copy into the blob store.
+    /// Returns the bytes reclaimed (0 when the snapshot was the first with this content).
+    pub fn deduplicate_lockfile(&self, snapshot_id: &str, hash: &str) -> Result<u64, String> {
+        let blob_path = self.get_blob_path(hash)?;
+        let dir = self.get_snapshot_path(snapshot_id);
+
+        let mut reclaimed = 0;
+        for lockfile_type in &LOCKFILE_TYPES {
+            for name in lockfile_type.lockfile_names() {
+                let path = dir.join(format!("{}.zst", name));
+                if !path.exists() {
+                    continue;
+                }
+
+                self.write_lockfile_ref(snapshot_id, name, hash)?;
+                if touch_blob(&blob_path) {
+                    reclaimed += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    fs::remove_file(&path)
+                        .map_err(|e| format!("Failed to remove duplicate lockfile: {}", e))?;
+                } else {
+                    fs::create_dir_all(self.base_path.join("blobs"))
+                        .map_err(|e| format!("Failed to create blob directory: {}", e))?;
+                    fs::rename(&path, &blob_path)
+                        .map_err(|e| format!("Failed to move lockfile to blob store: {}", e))?;
+                }
+            }
+        }
+
+        Ok(reclaimed)
+    }
+
+    /// Delete lockfile blobs no snapshot points to, except those stored or reused
+    /// within `BLOB_PRUNE_GRACE`. Returns the bytes freed.
+    pub fn prune_blobs(&self) -> Result<u64, String> {
+        let blobs_dir = self.base_path.join("blobs");
+        if !blobs_dir.exists() {
+            return Ok(0);
+        }
+
+        let mut referenced = HashSet::new();
+        let snapshots_dir = self.base_path.join("snapshots");
+        if snapshots_dir.exists() {
+            for entry in fs::read_dir(&snapshots_dir)
+                .map_err(|e| format!("Failed to read snapshots directory: {}", e))?
+            {
+                let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+                let Ok(files) = fs::read_dir(entry.path()) else {
+                    continue;
+                };
+                for file in files.flatten() {
+                    let path = file.path();
+                    if path.extension().is_some_and(|ext| ext == "ref") {
+                        if let Ok(hash) = fs::read_to_string(&path) {
+                            referenced.insert(format!("{}.zst", hash.trim()));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut freed = 0;
+        for entry in
+            fs::read_dir(&blobs_dir).map_err(|e| format!("Failed to read blob directory: {}", e))?
+        {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if referenced.contains(&file_name) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let recent = metadata.modified().map_or(true, |modified| {
+                modified
+                    .elapsed()
+                    .map_or(true, |age| age < BLOB_PRUNE_GRACE)
+            });
+            if !recent {
+                freed += metadata.len();
+                fs::remove_file(entry.path())
+                    .map_err(|e| format!("Failed to remove lockfile blob: {}", e))?;
+            }
+        }
+
+        Ok(freed)
+    }
+
+    /// Path of a lockfile blob; the hash must be hex so it is a safe file name
+    fn get_blob_path(&self, hash: &str) -> Result<PathBuf, String> {
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid lockfile hash: {}", hash));
+        }
+        Ok(self.base_path.join("blobs").join(format!("{}.zst", hash)))
+    }
+
+    fn write_lockfile_ref(
+        &self,
+        snapshot_id: &str,
+        lockfile_name: &str,
+        hash: &str,
+    ) -> Result<(), String> {
+        let dir = self.ensure_snapshot_dir(snapshot_id)?;
+        fs::write(dir.join(format!("{}.ref", lockfile_name)), hash)
+            .map_err(|e| format!("Failed to write lockfile reference: {}", e))
+    }
+
     /// Compress data using zstd
     fn compress(&self, data: &[u8]) -> Result<Vec<u8>, String> {
         let mut encoder = zstd::Encoder::new(Vec::new(), 3)
@@ -226,6 +396,15 @@ impl SnapshotStorage {
     }
 }
 
+/// Mark an existing blob as just used so `prune_blobs` keeps it. False if it is missing.
+fn touch_blob(path: &Path) -> bool {
+    fs::File::options()
+        .append(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+        .is_ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +443,55 @@ mod tests {
         assert_eq!(content.as_slice(), read_content.as_slice());
     }
 
+    #[test]
+    fn test_lockfile_blobs_are_shared() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SnapshotStorage::new(temp_dir.path().to_path_buf());
+        let content = b"{\"name\": \"test\", \"lockfileVersion\": 3}";
+
+        storage
+            .store_lockfile_blob("a", "package-lock.json", content, "abc123")
+            .unwrap();
+        // Legacy per-snapshot copy of the same lockfile
+        storage
+            .store_lockfile("b", "package-lock.json", content)
+            .unwrap();
+
+        let reclaimed = storage.deduplicate_lockfile("b", "abc123").unwrap();
+        assert!(reclaimed > 0);
+        assert_eq!(storage.lockfile_blob_hash("b").as_deref(), Some("abc123"));
+        assert_eq!(
+            storage.read_lockfile("b", "package-lock.json").unwrap(),
+            content.as_slice()
+        );
+
+        // The blob survives until the last snapshot pointing to it is gone
+        storage.delete_snapshot("a").unwrap();
+        assert_eq!(storage.prune_blobs().unwrap(), 0);
+        storage.delete_snapshot("b").unwrap();
+        // Recently used blobs are kept in case a snapshot is still being stored
+        assert_eq!(storage.prune_blobs().unwrap(), 0);
+        let blob = storage.get_blob_path("abc123").unwrap();
+        fs::File::options()
+            .append(true)
+            .open(&blob)
+            .unwrap()
+            .set_modified(SystemTime::now() - BLOB_PRUNE_GRACE * 2)
+            .unwrap();
+        assert!(storage.prune_blobs().unwrap() > 0);
+        assert_eq!(storage.blob_size("abc123"), 0);
+    }
+
+    #[test]
+    fn test_blob_hash_must_be_hex() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SnapshotStorage::new(temp_dir.path().to_path_buf());
+
+        assert!(storage
+            .store_lockfile_blob("a", "package-lock.json", b"{}", "../escape")
+            .is_err());
+    }
+
     #[test]
     fn test_delete_snapshot() {
         let temp_dir = TempDir::new().unwrap();
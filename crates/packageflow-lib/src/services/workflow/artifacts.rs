@@ -0,0 +1,446 @@
+// Workflow Artifacts
+// Collects files matching a script node's artifact globs into the execution's artifact
+// store, exports them and applies artifact retention
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::models::{ExecutionArtifact, ExecutionArtifactUsage, LogRetentionPolicy};
+use crate::repositories::ArtifactRepository;
+use crate::utils::database::{get_database_path, Database};
+
+/// Largest total size of the files one node run may collect as artifacts (1 GiB)
+pub const MAX_NODE_ARTIFACT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Directory (next to the database) holding the artifact stores of all executions
+pub fn execution_artifacts_dir() -> Result<PathBuf, String> {
+    get_database_path()?
+        .parent()
+        .map(|dir| dir.join("artifacts"))
+        .ok_or_else(|| "Could not determine application data directory".to_string())
+}
+
+/// Node run the artifacts are collected for
+#[derive(Debug, Clone)]
+pub struct ArtifactOwner {
+    pub execution_id: String,
+    pub workflow_id: String,
+    pub node_id: String,
+    pub node_name: String,
+    pub cell_index: Option<usize>,
+}
+
+/// Result of collecting a node's artifacts
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactCollection {
+    pub artifacts: Vec<ExecutionArtifact>,
+    /// Patterns that matched no file
+    pub unmatched: Vec<String>,
+}
+
+/// Check an artifact pattern: relative and inside the working directory
+pub fn validate_artifact_pattern(pattern: &str) -> Result<(), String> {
+    let path = Path::new(pattern);
+    if pattern.trim().is_empty() {
+        return Err("Artifact pattern is empty".to_string());
+    }
+    if path.has_root() || path.is_absolute() {
+        return Err(format!(
+            "Artifact pattern '{}' must be relative to the working directory",
+            pattern
+        ));
+    }
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Err(format!(
+            "Artifact pattern '{}' must not leave the working directory",
+            pattern
+        ));
+    }
+    glob::Pattern::new(pattern)
+        .map_err(|e| format!("Invalid artifact pattern '{}': {}", pattern, e))?;
+    Ok(())
+}
+
+/// Files under `cwd` matching `pattern`; a matched directory contributes all its files.
+/// Symlinks are never followed, so a link can neither pull in files from outside the
+/// working directory nor make the walk loop.
+fn match_files(cwd: &Path, pattern: &str) -> Result<BTreeSet<PathBuf>, String> {
+    let parts: Vec<&str> = pattern
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+    // A trailing `**` only yields subdirectories; `**/*` also yields the files
+    let mut pattern = parts.join("/");
+    if pattern.ends_with("**") {
+        pattern.push_str("/*");
+    }
+    let matcher = glob::Pattern::new(&pattern)
+        .map_err(|e| format!("Invalid artifact pattern '{}': {}", pattern, e))?;
+
+    // Only walk below the literal leading directories of the pattern
+    let mut base = cwd.to_path_buf();
+    for part in parts
+        .iter()
+        .take_while(|part| !part.contains(['*', '?', '[']))
+    {
+        base.push(part);
+        match fs::symlink_metadata(&base) {
+            Ok(metadata) if !metadata.is_symlink() => {}
+            _ => return Ok(BTreeSet::new()),
+        }
+    }
+
+    // Without `**` nothing deeper than the pattern can match
+    let max_depth = (!pattern.contains("**")).then(|| pattern.split('/').count());
+    let mut files = BTreeSet::new();
+    walk_matches(cwd, &base, &matcher, max_depth, &mut files)
+        .map_err(|e| format!("Failed to read {}: {}", base.display(), e))?;
+    Ok(files)
+}
+
+fn walk_matches(
+    cwd: &Path,
+    path: &Path,
+    matcher: &glob::Pattern,
+    max_depth: Option<usize>,
+    files: &mut BTreeSet<PathBuf>,
+) -> std::io::Result<()> {
+    let Ok(relative) = path.strip_prefix(cwd) else {
+        return Ok(());
+    };
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    if matcher.matches_path_with(relative, options) {
+        return add_files(path, files);
+    }
+    let depth = relative.components().count();
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() && max_depth.is_none_or(|max| depth < max) {
+        for entry in fs::read_dir(path)? {
+            walk_matches(cwd, &entry?.path(), matcher, max_depth, files)?;
+        }
+    }
+    Ok(())
+}
+
+fn add_files(path: &Path, files: &mut BTreeSet<PathBuf>) -> std::io::Result<()> {
+    // A symlink is neither a file nor a directory here and is skipped
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_file() {
+        files.insert(path.to_path_buf());
+    } else if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            add_files(&entry?.path(), files)?;
+        }
+    }
+    Ok(())
+}
+
+fn file_sha256(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copy the files matching `patterns` (relative to `cwd`) into
+/// `store_dir/<execution>/<node>[/cell-<n>]`, keeping their relative paths.
+/// Nothing is copied when the matched files add up to more than `max_bytes`.
+pub fn collect_artifacts(
+    store_dir: &Path,
+    cwd: &Path,
+    patterns: &[String],
+    owner: &ArtifactOwner,
+    max_bytes: u64,
+) -> Result<ArtifactCollection, String> {
+    let mut collection = ArtifactCollection::default();
+    let mut files = BTreeSet::new();
+    for pattern in patterns {
+        validate_artifact_pattern(pattern)?;
+        let matched = match_files(cwd, pattern)?;
+        if matched.is_empty() {
+            collection.unmatched.push(pattern.clone());
+        }
+        files.extend(matched);
+    }
+
+    let mut total_bytes: u64 = 0;
+    for file in &files {
+        let metadata = fs::symlink_metadata(file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        total_bytes += metadata.len();
+    }
+    if total_bytes > max_bytes {
+        return Err(format!(
+            "Artifacts add up to {} bytes, more than the limit of {} bytes",
+            total_bytes, max_bytes
+        ));
+    }
+
+    let mut node_dir = store_dir.join(&owner.execution_id).join(&owner.node_id);
+    if let Some(index) = owner.cell_index {
+        node_dir = node_dir.join(format!("cell-{}", index));
+    }
+    let collected_at = Utc::now().to_rfc3339();
+
+    for file in files {
+        let Ok(relative) = file.strip_prefix(cwd) else {
+            continue;
+        };
+        let target = node_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create artifact directory: {}", e))?;
+        }
+        let size_bytes = fs::copy(&file, &target)
+            .map_err(|e| format!("Failed to copy artifact {}: {}", relative.display(), e))?;
+        let sha256 = file_sha256(&target)
+            .map_err(|e| format!("Failed to hash artifact {}: {}", relative.display(), e))?;
+
+        collection.artifacts.push(ExecutionArtifact {
+            execution_id: owner.execution_id.clone(),
+            workflow_id: owner.workflow_id.clone(),
+            node_id: owner.node_id.clone(),
+            node_name: owner.node_name.clone(),
+            cell_index: owner.cell_index,
+            path: relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            size_bytes,
+            sha256,
+            file_path: target.to_string_lossy().to_string(),
+            collected_at: collected_at.clone(),
+        });
+    }
+    Ok(collection)
+}
+
+/// Copy stored artifacts into `destination`, keeping their relative paths.
+/// Artifacts of matrix cells go under `cell-<n>/`. Returns the number of files copied.
+pub fn export_artifacts(
+    artifacts: &[ExecutionArtifact],
+    destination: &Path,
+) -> Result<usize, String> {
+    for artifact in artifacts {
+        let mut target = destination.to_path_buf();
+        if let Some(index) = artifact.cell_index {
+            target.push(format!("cell-{}", index));
+        }
+        target.push(&artifact.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        fs::copy(&artifact.file_path, &target)
+            .map_err(|e| format!("Failed to export artifact {}: {}", artifact.path, e))?;
+    }
+    Ok(artifacts.len())
+}
+
+/// Delete the artifacts of an execution (stored files and records)
+pub fn delete_execution_artifacts(
+    db: &Database,
+    store_dir: &Path,
+    execution_id: &str,
+) -> Result<usize, String> {
+    let deleted = ArtifactRepository::new(db.clone()).delete(execution_id)?;
+    if let Err(e) = fs::remove_dir_all(store_dir.join(execution_id)) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove artifacts of {}: {}", execution_id, e);
+        }
+    }
+    Ok(deleted)
+}
+
+/// Executions whose artifacts expire under `policy`. `usage` must be sorted newest first.
+pub fn select_expired_artifacts(
+    usage: &[ExecutionArtifactUsage],
+    policy: &LogRetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let cutoff =
+        (policy.retention_days > 0).then(|| now - Duration::days(policy.retention_days as i64));
+
+    let mut per_workflow: HashMap<&str, usize> = HashMap::new();
+    let mut total_bytes: u64 = 0;
+    let mut expired = Vec::new();
+
+    for execution in usage {
+        let count = per_workflow.entry(&execution.workflow_id).or_default();
+        *count += 1;
+        total_bytes += execution.total_bytes;
+
+        let too_old = cutoff.is_some_and(|cutoff| {
+            DateTime::parse_from_rfc3339(&execution.collected_at)
+                .map(|collected| collected < cutoff)
+                .unwrap_or(false)
+        });
+        let over_count = *count > policy.keep_per_workflow;
+        let over_size = policy.max_total_bytes > 0 && total_bytes > policy.max_total_bytes;
+        if too_old || over_count || over_size {
+            expired.push(execution.execution_id.clone());
+        }
+    }
+    expired
+}
+
+/// Delete the artifacts expired under `policy`. Returns the number of executions pruned.
+pub fn prune_execution_artifacts(
+    db: &Database,
+    store_dir: &Path,
+    policy: &LogRetentionPolicy,
+) -> Result<usize, String> {
+    let usage = ArtifactRepository::new(db.clone()).list_usage()?;
+    let expired = select_expired_artifacts(&usage, policy, Utc::now());
+    for execution_id in &expired {
+        delete_execution_artifacts(db, store_dir, execution_id)?;
+    }
+    Ok(expired.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_and_export_artifacts() {
+        let root =
+            std::env::temp_dir().join(format!("packageflow-artifacts-{}", uuid::Uuid::new_v4()));
+        let cwd = root.join("project");
+        fs::create_dir_all(cwd.join("dist/assets")).unwrap();
+        fs::write(cwd.join("dist/index.js"), "console.log(1)").unwrap();
+        fs::write(cwd.join("dist/assets/app.css"), "body{}").unwrap();
+        fs::write(cwd.join("pkg-1.0.0.tgz"), "tgz").unwrap();
+        fs::write(cwd.join("README.md"), "readme").unwrap();
+        fs::create_dir_all(root.join("outside")).unwrap();
+        fs::write(root.join("outside/secret"), "secret").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::symlink;
+            symlink(root.join("outside"), cwd.join("dist/linked")).unwrap();
+            symlink(root.join("outside/secret"), cwd.join("dist/secret")).unwrap();
+            symlink(&cwd, cwd.join("dist/assets/loop")).unwrap();
+        }
+
+        let owner = ArtifactOwner {
+            execution_id: "exec-1".to_string(),
+            workflow_id: "wf-1".to_string(),
+            node_id: "build".to_string(),
+            node_name: "Build".to_string(),
+            cell_index: None,
+        };
+        let patterns = vec![
+            "dist/**".to_string(),
+            "*.tgz".to_string(),
+            "coverage/lcov.info".to_string(),
+        ];
+        let collection =
+            collect_artifacts(&root.join("store"), &cwd, &patterns, &owner, 1024).unwrap();
+
+        let paths: Vec<&str> = collection
+            .artifacts
+            .iter()
+            .map(|a| a.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["dist/assets/app.css", "dist/index.js", "pkg-1.0.0.tgz"]
+        );
+        assert_eq!(collection.unmatched, vec!["coverage/lcov.info"]);
+        assert_eq!(collection.artifacts[2].size_bytes, 3);
+        assert!(root.join("store/exec-1/build/dist/index.js").is_file());
+
+        let exported = export_artifacts(&collection.artifacts, &root.join("out")).unwrap();
+        assert_eq!(exported, 3);
+        assert_eq!(
+            fs::read_to_string(root.join("out/dist/assets/app.css")).unwrap(),
+            "body{}"
+        );
+
+        assert!(collect_artifacts(
+            &root.join("store"),
+            &cwd,
+            &["../secrets".to_string()],
+            &owner,
+            1024
+        )
+        .is_err());
+        assert!(validate_artifact_pattern("/etc/passwd").is_err());
+        #[cfg(unix)]
+        assert!(collect_artifacts(
+            &root.join("store"),
+            &cwd,
+            &["dist/linked/secret".to_string()],
+            &owner,
+            1024
+        )
+        .unwrap()
+        .artifacts
+        .is_empty());
+
+        // dist/** is 20 bytes
+        let err = collect_artifacts(
+            &root.join("store"),
+            &cwd,
+            &["dist/**".to_string()],
+            &owner,
+            16,
+        )
+        .unwrap_err();
+        assert!(err.contains("more than the limit of 16 bytes"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_select_expired_artifacts() {
+        let usage =
+            |id: &str, workflow: &str, collected_at: &str, bytes: u64| ExecutionArtifactUsage {
+                execution_id: id.to_string(),
+                workflow_id: workflow.to_string(),
+                collected_at: collected_at.to_string(),
+                file_count: 1,
+                total_bytes: bytes,
+            };
+        let now = DateTime::parse_from_rfc3339("2025-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let all = vec![
+            usage("e4", "a", "2025-02-28T00:00:00Z", 10),
+            usage("e3", "a", "2025-02-27T00:00:00Z", 10),
+            usage("e2", "b", "2025-02-26T00:00:00Z", 10),
+            usage("e1", "a", "2025-01-01T00:00:00Z", 10),
+        ];
+        let policy = LogRetentionPolicy {
+            keep_per_workflow: 2,
+            retention_days: 30,
+            max_total_bytes: 0,
+        };
+        assert_eq!(select_expired_artifacts(&all, &policy, now), vec!["e1"]);
+
+        let policy = LogRetentionPolicy {
+            keep_per_workflow: 10,
+            retention_days: 0,
+            max_total_bytes: 25,
+        };
+        assert_eq!(
+            select_expired_artifacts(&all, &policy, now),
+            vec!["e2", "e1"]
+        );
+    }
+}
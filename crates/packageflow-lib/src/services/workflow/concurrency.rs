@@ -0,0 +1,170 @@
+// Workflow Concurrency
+// Admission of new runs under per-workflow concurrency policies and the global
+// limit, and selection of queued runs that may start
+
+use std::collections::HashSet;
+
+use crate::models::{ActiveExecution, ConcurrencyPolicy};
+
+/// Settings key of `WorkflowConcurrencySettings`
+pub const WORKFLOW_CONCURRENCY_SETTINGS_KEY: &str = "workflow_concurrency_settings";
+
+/// Interval at which queued runs check whether they may start
+pub const QUEUE_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Decide how a new run of `workflow_id` is admitted.
+/// Returns the executions to cancel first (`cancel-previous` only cancels runs of the
+/// same `source`), or an error when the policy rejects the run. Whether the run starts
+/// right away or waits is decided by `startable_executions` once it is registered.
+pub fn admit_execution(
+    policy: ConcurrencyPolicy,
+    workflow_id: &str,
+    source: &str,
+    active: &[ActiveExecution],
+) -> Result<Vec<String>, String> {
+    let same_workflow = active.iter().filter(|e| e.workflow_id == workflow_id);
+    match policy {
+        ConcurrencyPolicy::Reject => match same_workflow.count() {
+            0 => Ok(Vec::new()),
+            count => Err(format!(
+                "Workflow is already running ({} active run{}); its concurrency policy rejects new runs",
+                count,
+                if count == 1 { "" } else { "s" }
+            )),
+        },
+        ConcurrencyPolicy::CancelPrevious => Ok(same_workflow
+            .filter(|e| e.source == source)
+            .map(|e| e.execution_id.clone())
+            .collect()),
+        ConcurrencyPolicy::Allow | ConcurrencyPolicy::Queue => Ok(Vec::new()),
+    }
+}
+
+/// Queued executions that may start now, in queue order.
+/// `active` must be in queue order. Queued runs whose policy waits for their own
+/// workflow start once no earlier run of it is active; every start needs a free slot
+/// under `max_concurrent` (0 = unlimited).
+pub fn startable_executions(active: &[ActiveExecution], max_concurrent: usize) -> Vec<String> {
+    let mut running = active.iter().filter(|e| !e.queued).count();
+    let mut busy_workflows: HashSet<&str> = active
+        .iter()
+        .filter(|e| !e.queued)
+        .map(|e| e.workflow_id.as_str())
+        .collect();
+
+    let mut startable = Vec::new();
+    for execution in active.iter().filter(|e| e.queued) {
+        let waits_for_workflow = matches!(
+            execution.policy,
+            ConcurrencyPolicy::Queue | ConcurrencyPolicy::CancelPrevious
+        );
+        let blocked = waits_for_workflow && busy_workflows.contains(execution.workflow_id.as_str());
+        // Later runs of the workflow stay behind this one
+        busy_workflows.insert(execution.workflow_id.as_str());
+        if blocked || (max_concurrent > 0 && running >= max_concurrent) {
+            continue;
+        }
+        running += 1;
+        startable.push(execution.execution_id.clone());
+    }
+    startable
+}
+
+/// Whether a process with this PID is still running
+pub fn is_process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    let output = std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map(|o| o.status.success());
+    #[cfg(not(unix))]
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()));
+
+    // When the check itself fails, keep the process's runs
+    output.unwrap_or(true)
+}
+
+/// 1-based position of each queued execution, in queue order
+pub fn queue_positions(active: &[ActiveExecution]) -> Vec<(String, usize)> {
+    active
+        .iter()
+        .filter(|e| e.queued)
+        .enumerate()
+        .map(|(index, e)| (e.execution_id.clone(), index + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(
+        id: &str,
+        workflow: &str,
+        policy: ConcurrencyPolicy,
+        queued: bool,
+    ) -> ActiveExecution {
+        ActiveExecution {
+            execution_id: id.to_string(),
+            workflow_id: workflow.to_string(),
+            source: "app".to_string(),
+            policy,
+            queued,
+            registered_at: String::new(),
+            owner_pid: None,
+        }
+    }
+
+    #[test]
+    fn test_admit_execution() {
+        let running = vec![active("e1", "wf", ConcurrencyPolicy::Allow, false)];
+
+        assert!(admit_execution(ConcurrencyPolicy::Reject, "wf", "app", &running).is_err());
+        assert!(
+            admit_execution(ConcurrencyPolicy::Reject, "other", "app", &running)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            admit_execution(ConcurrencyPolicy::CancelPrevious, "wf", "app", &running).unwrap(),
+            vec!["e1"]
+        );
+        // Runs of another process cannot be cancelled; the new run waits for them instead
+        assert!(
+            admit_execution(ConcurrencyPolicy::CancelPrevious, "wf", "mcp", &running)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_startable_executions() {
+        use ConcurrencyPolicy::*;
+        let active = vec![
+            active("e1", "a", Queue, false),
+            active("e2", "a", Queue, true),
+            active("e3", "b", Allow, true),
+            active("e4", "c", Queue, true),
+            active("e5", "c", Queue, true),
+        ];
+
+        // e2 waits for e1; e5 waits for e4
+        assert_eq!(startable_executions(&active, 0), vec!["e3", "e4"]);
+        // One slot left besides e1
+        assert_eq!(startable_executions(&active, 2), vec!["e3"]);
+        assert!(startable_executions(&active, 1).is_empty());
+
+        assert_eq!(
+            queue_positions(&active),
+            vec![
+                ("e2".to_string(), 1),
+                ("e3".to_string(), 2),
+                ("e4".to_string(), 3),
+                ("e5".to_string(), 4)
+            ]
+        );
+    }
+}
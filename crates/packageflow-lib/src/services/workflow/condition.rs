@@ -0,0 +1,322 @@
+// Workflow Conditions
+// Evaluates condition nodes against the outcomes of previous steps
+// and decides which downstream nodes run
+
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::models::workflow::{ConditionCheck, ConditionNodeConfig, ConditionRule};
+use crate::utils::path_resolver;
+
+/// Data a condition can inspect, collected by the executor
+#[derive(Debug, Clone, Default)]
+pub struct ConditionContext {
+    /// node_id -> exit code of finished nodes
+    pub exit_codes: HashMap<String, i32>,
+    /// node_id -> captured output of finished nodes
+    pub outputs: HashMap<String, String>,
+    /// Environment visible to the workflow
+    pub env: HashMap<String, String>,
+    /// Current git branch of the project (None when not a git repository)
+    pub git_branch: Option<String>,
+}
+
+/// Result of evaluating a condition node
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionOutcome {
+    /// Index of the matched branch (None = `otherwise`)
+    pub branch_index: Option<usize>,
+    /// Human-readable name of the selected branch
+    pub branch_label: String,
+    /// Node IDs selected to run
+    pub taken: Vec<String>,
+    /// Node IDs routed by this condition that will not run
+    pub not_taken: Vec<String>,
+}
+
+/// Validate patterns of a condition config before it is saved or run
+pub fn validate_condition_config(config: &ConditionNodeConfig) -> Result<(), String> {
+    for rule in config.branches.iter().flat_map(|b| b.rules.iter()) {
+        match &rule.check {
+            ConditionCheck::OutputMatches { pattern, .. } => {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid output pattern '{}': {}", pattern, e))?;
+            }
+            ConditionCheck::GitBranch { pattern } => {
+                glob::Pattern::new(pattern)
+                    .map_err(|e| format!("Invalid branch pattern '{}': {}", pattern, e))?;
+            }
+            ConditionCheck::ExitCode { .. } | ConditionCheck::EnvVar { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether any rule needs the current git branch (lets the executor skip the git call)
+pub fn uses_git_branch(config: &ConditionNodeConfig) -> bool {
+    config
+        .branches
+        .iter()
+        .flat_map(|b| b.rules.iter())
+        .any(|r| matches!(r.check, ConditionCheck::GitBranch { .. }))
+}
+
+/// Evaluate a single rule
+pub fn evaluate_rule(rule: &ConditionRule, ctx: &ConditionContext) -> Result<bool, String> {
+    let matched = match &rule.check {
+        ConditionCheck::ExitCode { node_id, equals } => {
+            ctx.exit_codes.get(node_id) == Some(equals)
+        }
+        ConditionCheck::OutputMatches { node_id, pattern } => {
+            let re = Regex::new(pattern)
+                .map_err(|e| format!("Invalid output pattern '{}': {}", pattern, e))?;
+            ctx.outputs
+                .get(node_id)
+                .map(|output| re.is_match(output))
+                .unwrap_or(false)
+        }
+        ConditionCheck::EnvVar { name, equals } => match (ctx.env.get(name), equals) {
+            (Some(value), Some(expected)) => value == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        },
+        ConditionCheck::GitBranch { pattern } => {
+            let glob = glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid branch pattern '{}': {}", pattern, e))?;
+            ctx.git_branch
+                .as_deref()
+                .map(|branch| glob.matches(branch))
+                .unwrap_or(false)
+        }
+    };
+
+    Ok(matched != rule.negate)
+}
+
+/// Evaluate a condition node: the first branch whose rules all match is taken
+pub fn evaluate_condition(
+    config: &ConditionNodeConfig,
+    ctx: &ConditionContext,
+) -> Result<ConditionOutcome, String> {
+    let mut selected: Option<usize> = None;
+    for (index, branch) in config.branches.iter().enumerate() {
+        let mut all_match = true;
+        for rule in &branch.rules {
+            if !evaluate_rule(rule, ctx)? {
+                all_match = false;
+                break;
+            }
+        }
+        if all_match {
+            selected = Some(index);
+            break;
+        }
+    }
+
+    let (taken, branch_label) = match selected {
+        Some(index) => {
+            let branch = &config.branches[index];
+            (
+                branch.targets.clone(),
+                branch
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| format!("branch {}", index + 1)),
+            )
+        }
+        None => (config.otherwise.clone(), "otherwise".to_string()),
+    };
+
+    let not_taken = config
+        .all_targets()
+        .into_iter()
+        .filter(|id| !taken.contains(id))
+        .collect();
+
+    Ok(ConditionOutcome {
+        branch_index: selected,
+        branch_label,
+        taken,
+        not_taken,
+    })
+}
+
+/// Read the current git branch of a directory (None if it is not a git repository)
+pub fn current_git_branch(cwd: &str) -> Option<String> {
+    let output = path_resolver::create_command("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if branch.is_empty() {
+        None
+    } else {
+        Some(branch)
+    }
+}
+
+/// Read the commit checked out in a directory (None if it is not a git repository)
+pub fn current_git_commit(cwd: &str) -> Option<String> {
+    let output = path_resolver::create_command("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if commit.is_empty() {
+        None
+    } else {
+        Some(commit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workflow::ConditionBranch;
+
+    fn rule(check: ConditionCheck) -> ConditionRule {
+        ConditionRule {
+            check,
+            negate: false,
+        }
+    }
+
+    /// "Only publish if on main and tests passed", otherwise notify
+    fn publish_config() -> ConditionNodeConfig {
+        ConditionNodeConfig {
+            branches: vec![ConditionBranch {
+                label: Some("release".to_string()),
+                rules: vec![
+                    rule(ConditionCheck::GitBranch {
+                        pattern: "main".to_string(),
+                    }),
+                    rule(ConditionCheck::ExitCode {
+                        node_id: "test".to_string(),
+                        equals: 0,
+                    }),
+                ],
+                targets: vec!["publish".to_string()],
+            }],
+            otherwise: vec!["notify".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_first_matching_branch_is_taken() {
+        let ctx = ConditionContext {
+            exit_codes: HashMap::from([("test".to_string(), 0)]),
+            git_branch: Some("main".to_string()),
+            ..Default::default()
+        };
+
+        let outcome = evaluate_condition(&publish_config(), &ctx).unwrap();
+        assert_eq!(outcome.branch_index, Some(0));
+        assert_eq!(outcome.branch_label, "release");
+        assert_eq!(outcome.taken, vec!["publish"]);
+        assert_eq!(outcome.not_taken, vec!["notify"]);
+    }
+
+    #[test]
+    fn test_otherwise_when_no_branch_matches() {
+        let ctx = ConditionContext {
+            exit_codes: HashMap::from([("test".to_string(), 0)]),
+            git_branch: Some("feature/login".to_string()),
+            ..Default::default()
+        };
+
+        let outcome = evaluate_condition(&publish_config(), &ctx).unwrap();
+        assert_eq!(outcome.branch_index, None);
+        assert_eq!(outcome.taken, vec!["notify"]);
+        assert_eq!(outcome.not_taken, vec!["publish"]);
+    }
+
+    #[test]
+    fn test_output_env_and_negate_rules() {
+        let ctx = ConditionContext {
+            outputs: HashMap::from([(
+                "changed".to_string(),
+                "apps/web/src/index.ts\npackages/ui/button.tsx".to_string(),
+            )]),
+            env: HashMap::from([("CI".to_string(), "true".to_string())]),
+            ..Default::default()
+        };
+
+        let web_changed = rule(ConditionCheck::OutputMatches {
+            node_id: "changed".to_string(),
+            pattern: r"(?m)^apps/web/".to_string(),
+        });
+        assert!(evaluate_rule(&web_changed, &ctx).unwrap());
+
+        let ci = rule(ConditionCheck::EnvVar {
+            name: "CI".to_string(),
+            equals: Some("true".to_string()),
+        });
+        assert!(evaluate_rule(&ci, &ctx).unwrap());
+
+        let not_set = ConditionRule {
+            check: ConditionCheck::EnvVar {
+                name: "SKIP_E2E".to_string(),
+                equals: None,
+            },
+            negate: true,
+        };
+        assert!(evaluate_rule(&not_set, &ctx).unwrap());
+
+        // Missing node output never matches
+        let missing = rule(ConditionCheck::OutputMatches {
+            node_id: "unknown".to_string(),
+            pattern: ".*".to_string(),
+        });
+        assert!(!evaluate_rule(&missing, &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_config_deserializes_and_validates() {
+        let config: ConditionNodeConfig = serde_json::from_value(serde_json::json!({
+            "branches": [{
+                "rules": [
+                    { "kind": "gitBranch", "pattern": "release/*" },
+                    { "kind": "exitCode", "nodeId": "test", "negate": true }
+                ],
+                "targets": ["publish"]
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            config.branches[0].rules[1].check,
+            ConditionCheck::ExitCode {
+                node_id: "test".to_string(),
+                equals: 0
+            }
+        );
+        assert!(config.branches[0].rules[1].negate);
+        assert!(uses_git_branch(&config));
+        assert!(validate_condition_config(&config).is_ok());
+
+        let invalid = ConditionNodeConfig {
+            branches: vec![ConditionBranch {
+                rules: vec![rule(ConditionCheck::OutputMatches {
+                    node_id: "a".to_string(),
+                    pattern: "(".to_string(),
+                })],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(validate_condition_config(&invalid).is_err());
+    }
+}
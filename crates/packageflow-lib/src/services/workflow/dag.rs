@@ -0,0 +1,607 @@
+// Workflow DAG
+// Builds the dependency graph between workflow nodes, validates it and
+// decides which nodes are ready to run while an execution is in progress
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::models::execution::NodeStatus;
+use crate::models::workflow::WorkflowNode;
+use crate::services::workflow::condition::validate_condition_config;
+use crate::services::workflow::matrix::validate_matrix;
+use crate::services::workflow::retry::validate_retry_policy;
+
+/// Default number of nodes allowed to run at the same time
+pub const DEFAULT_MAX_PARALLELISM: usize = 4;
+
+/// Find a path from `start` to `target` using DFS.
+/// Returns the visited path (both endpoints included) if `target` is reachable.
+/// Passing the same ID as `start` and `target` finds a cycle through that node.
+pub fn find_path(
+    adjacency: &HashMap<String, Vec<String>>,
+    start: &str,
+    target: &str,
+) -> Option<Vec<String>> {
+    fn dfs(
+        node: &str,
+        target: &str,
+        adjacency: &HashMap<String, Vec<String>>,
+        visited: &mut HashSet<String>,
+        path: &mut Vec<String>,
+    ) -> bool {
+        visited.insert(node.to_string());
+        path.push(node.to_string());
+
+        if let Some(neighbors) = adjacency.get(node) {
+            for neighbor in neighbors {
+                // Found the target
+                if neighbor == target {
+                    path.push(neighbor.to_string());
+                    return true;
+                }
+
+                if !visited.contains(neighbor) && dfs(neighbor, target, adjacency, visited, path) {
+                    return true;
+                }
+            }
+        }
+
+        path.pop();
+        false
+    }
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut path: Vec<String> = Vec::new();
+
+    if dfs(start, target, adjacency, &mut visited, &mut path) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Minimal node description needed to build a DAG
+/// (lets callers with their own node types, e.g. the MCP server, reuse the graph logic)
+#[derive(Debug, Clone)]
+pub struct DagNodeSpec {
+    pub id: String,
+    pub name: String,
+    pub order: i32,
+    pub depends_on: Vec<String>,
+    /// Nodes a condition node routes to (each implicitly depends on the condition)
+    pub branch_targets: Vec<String>,
+}
+
+impl From<&WorkflowNode> for DagNodeSpec {
+    fn from(node: &WorkflowNode) -> Self {
+        Self {
+            id: node.id.clone(),
+            name: node.name.clone(),
+            order: node.order,
+            depends_on: node.depends_on.clone(),
+            branch_targets: node
+                .get_condition_config()
+                .map(|c| c.all_targets())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Validated dependency graph of a workflow
+#[derive(Debug, Clone)]
+pub struct WorkflowDag {
+    /// Node IDs sorted by `order` (tie-breaker when several nodes are ready)
+    order: Vec<String>,
+    /// node_id -> IDs of nodes it depends on
+    dependencies: HashMap<String, Vec<String>>,
+    /// node_id -> IDs of nodes depending on it
+    dependents: HashMap<String, Vec<String>>,
+}
+
+impl WorkflowDag {
+    /// Build the DAG for workflow nodes
+    /// Also validates node configs (condition rules, retry patterns, matrices)
+    pub fn new(nodes: &[WorkflowNode]) -> Result<Self, String> {
+        for node in nodes {
+            if node.is_condition() {
+                let config = node
+                    .get_condition_config()
+                    .ok_or_else(|| format!("Invalid condition config for node '{}'", node.name))?;
+                validate_condition_config(&config)
+                    .map_err(|e| format!("Condition node '{}': {}", node.name, e))?;
+            } else if let Some(config) = node.get_script_config() {
+                validate_retry_policy(&config.retry)
+                    .map_err(|e| format!("Node '{}': {}", node.name, e))?;
+                if let Some(ref matrix) = config.matrix {
+                    validate_matrix(matrix).map_err(|e| format!("Node '{}': {}", node.name, e))?;
+                }
+            }
+        }
+        Self::from_specs(nodes.iter().map(DagNodeSpec::from).collect())
+    }
+
+    /// Build the DAG from node specs.
+    /// Workflows where no node declares `dependsOn` (and no condition routes) are chained
+    /// by `order` so that existing linear workflows keep running one step after another.
+    pub fn from_specs(mut specs: Vec<DagNodeSpec>) -> Result<Self, String> {
+        specs.sort_by_key(|s| s.order);
+
+        let mut names: HashMap<String, String> = HashMap::new();
+        for spec in &specs {
+            if names.insert(spec.id.clone(), spec.name.clone()).is_some() {
+                return Err(format!("Duplicate node ID: {}", spec.id));
+            }
+        }
+
+        let is_linear = specs
+            .iter()
+            .all(|s| s.depends_on.is_empty() && s.branch_targets.is_empty());
+
+        let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
+        for (index, spec) in specs.iter().enumerate() {
+            let deps = if is_linear {
+                if index > 0 {
+                    vec![specs[index - 1].id.clone()]
+                } else {
+                    Vec::new()
+                }
+            } else {
+                let mut deps: Vec<String> = Vec::new();
+                for dep in &spec.depends_on {
+                    if dep == &spec.id {
+                        return Err(format!("Node '{}' cannot depend on itself", spec.name));
+                    }
+                    if !names.contains_key(dep) {
+                        return Err(format!(
+                            "Node '{}' depends on unknown node '{}'",
+                            spec.name, dep
+                        ));
+                    }
+                    if !deps.contains(dep) {
+                        deps.push(dep.clone());
+                    }
+                }
+                deps
+            };
+            dependencies.insert(spec.id.clone(), deps);
+        }
+
+        // Condition targets depend on the condition node
+        for spec in &specs {
+            for target in &spec.branch_targets {
+                if target == &spec.id {
+                    return Err(format!("Condition '{}' cannot route to itself", spec.name));
+                }
+                let deps = dependencies.get_mut(target).ok_or_else(|| {
+                    format!(
+                        "Condition '{}' routes to unknown node '{}'",
+                        spec.name, target
+                    )
+                })?;
+                if !deps.contains(&spec.id) {
+                    deps.push(spec.id.clone());
+                }
+            }
+        }
+
+        // Cycle detection: a node whose dependency can reach it again forms a cycle
+        for spec in &specs {
+            if let Some(path) = find_path(&dependencies, &spec.id, &spec.id) {
+                let cycle_names: Vec<String> = path
+                    .iter()
+                    .map(|id| names.get(id).cloned().unwrap_or_else(|| id.clone()))
+                    .collect();
+                return Err(format!(
+                    "Dependency cycle detected: {}",
+                    cycle_names.join(" → ")
+                ));
+            }
+        }
+
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        for spec in &specs {
+            for dep in &dependencies[&spec.id] {
+                dependents
+                    .entry(dep.clone())
+                    .or_default()
+                    .push(spec.id.clone());
+            }
+        }
+
+        Ok(Self {
+            order: specs.into_iter().map(|s| s.id).collect(),
+            dependencies,
+            dependents,
+        })
+    }
+
+    /// Number of nodes in the graph
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Node IDs sorted by `order`
+    pub fn node_ids(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Direct dependencies of a node
+    pub fn dependencies_of(&self, node_id: &str) -> &[String] {
+        self.dependencies
+            .get(node_id)
+            .map(|d| d.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Direct dependents of a node
+    pub fn dependents_of(&self, node_id: &str) -> &[String] {
+        self.dependents
+            .get(node_id)
+            .map(|d| d.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Topological order (Kahn's algorithm, ties broken by `order`)
+    pub fn topological_order(&self) -> Vec<String> {
+        let mut remaining: HashMap<&str, usize> = self
+            .order
+            .iter()
+            .map(|id| (id.as_str(), self.dependencies_of(id).len()))
+            .collect();
+        let mut result: Vec<String> = Vec::with_capacity(self.order.len());
+
+        while result.len() < self.order.len() {
+            let next = self
+                .order
+                .iter()
+                .find(|id| remaining.get(id.as_str()) == Some(&0));
+
+            let Some(next) = next else {
+                // Unreachable for a validated graph
+                break;
+            };
+
+            remaining.remove(next.as_str());
+            for dependent in self.dependents_of(next) {
+                if let Some(count) = remaining.get_mut(dependent.as_str()) {
+                    *count -= 1;
+                }
+            }
+            result.push(next.clone());
+        }
+
+        result
+    }
+}
+
+/// Tracks node statuses during an execution and hands out ready nodes
+#[derive(Debug, Clone)]
+pub struct DagScheduler {
+    dag: WorkflowDag,
+    statuses: HashMap<String, NodeStatus>,
+    /// Nodes skipped because a condition did not select them (as opposed to failure skips)
+    branch_skipped: HashSet<String>,
+}
+
+impl DagScheduler {
+    pub fn new(dag: WorkflowDag) -> Self {
+        let statuses = dag
+            .node_ids()
+            .iter()
+            .map(|id| (id.clone(), NodeStatus::Pending))
+            .collect();
+        Self {
+            dag,
+            statuses,
+            branch_skipped: HashSet::new(),
+        }
+    }
+
+    pub fn dag(&self) -> &WorkflowDag {
+        &self.dag
+    }
+
+    pub fn status(&self, node_id: &str) -> Option<&NodeStatus> {
+        self.statuses.get(node_id)
+    }
+
+    /// Pending nodes whose dependencies have all completed, sorted by `order`.
+    /// Dependencies skipped by a condition count as satisfied as long as at least
+    /// one dependency completed (so branches can join again).
+    pub fn ready_nodes(&self) -> Vec<String> {
+        self.dag
+            .node_ids()
+            .iter()
+            .filter(|id| self.statuses.get(id.as_str()) == Some(&NodeStatus::Pending))
+            .filter(|id| {
+                let deps = self.dag.dependencies_of(id);
+                deps.iter().all(|dep| {
+                    self.statuses.get(dep) == Some(&NodeStatus::Completed)
+                        || self.branch_skipped.contains(dep)
+                }) && (deps.is_empty()
+                    || deps
+                        .iter()
+                        .any(|dep| self.statuses.get(dep) == Some(&NodeStatus::Completed)))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn mark_running(&mut self, node_id: &str) {
+        self.set_status(node_id, NodeStatus::Running);
+    }
+
+    pub fn mark_completed(&mut self, node_id: &str) {
+        self.set_status(node_id, NodeStatus::Completed);
+    }
+
+    /// Mark a node as failed.
+    /// Returns the downstream nodes that can no longer run and were marked skipped.
+    pub fn mark_failed(&mut self, node_id: &str) -> Vec<String> {
+        self.set_status(node_id, NodeStatus::Failed);
+        self.skip_dependents(node_id)
+    }
+
+    /// Mark a condition node as completed and skip the targets it did not select.
+    /// Returns every node skipped as a result (including downstream nodes left without
+    /// any dependency that ran).
+    pub fn mark_branch_result(&mut self, node_id: &str, not_taken: &[String]) -> Vec<String> {
+        self.set_status(node_id, NodeStatus::Completed);
+        self.skip_branch(not_taken)
+    }
+
+    /// Skip nodes not selected by a condition and propagate to nodes whose
+    /// dependencies were all skipped this way
+    pub fn skip_branch(&mut self, node_ids: &[String]) -> Vec<String> {
+        let mut skipped: Vec<String> = Vec::new();
+        for id in node_ids {
+            if self.statuses.get(id) == Some(&NodeStatus::Pending) {
+                self.set_status(id, NodeStatus::Skipped);
+                self.branch_skipped.insert(id.clone());
+                skipped.push(id.clone());
+            }
+        }
+
+        loop {
+            let newly_skipped: Vec<String> = self
+                .dag
+                .node_ids()
+                .iter()
+                .filter(|id| self.statuses.get(id.as_str()) == Some(&NodeStatus::Pending))
+                .filter(|id| {
+                    let deps = self.dag.dependencies_of(id);
+                    !deps.is_empty() && deps.iter().all(|dep| self.branch_skipped.contains(dep))
+                })
+                .cloned()
+                .collect();
+
+            if newly_skipped.is_empty() {
+                break;
+            }
+            for id in newly_skipped {
+                self.set_status(&id, NodeStatus::Skipped);
+                self.branch_skipped.insert(id.clone());
+                skipped.push(id);
+            }
+        }
+
+        skipped
+    }
+
+    /// Mark every pending node as skipped (used on cancellation)
+    pub fn skip_pending(&mut self) -> Vec<String> {
+        let pending: Vec<String> = self
+            .dag
+            .node_ids()
+            .iter()
+            .filter(|id| self.statuses.get(id.as_str()) == Some(&NodeStatus::Pending))
+            .cloned()
+            .collect();
+        for id in &pending {
+            self.set_status(id, NodeStatus::Skipped);
+        }
+        pending
+    }
+
+    /// Number of nodes currently running
+    pub fn running_count(&self) -> usize {
+        self.count(NodeStatus::Running)
+    }
+
+    /// Number of nodes that completed successfully
+    pub fn completed_count(&self) -> usize {
+        self.count(NodeStatus::Completed)
+    }
+
+    /// Whether any node failed
+    pub fn has_failures(&self) -> bool {
+        self.count(NodeStatus::Failed) > 0
+    }
+
+    /// Whether no node is pending or running
+    pub fn is_finished(&self) -> bool {
+        self.statuses
+            .values()
+            .all(|s| !matches!(s, NodeStatus::Pending | NodeStatus::Running))
+    }
+
+    fn count(&self, status: NodeStatus) -> usize {
+        self.statuses.values().filter(|s| **s == status).count()
+    }
+
+    fn set_status(&mut self, node_id: &str, status: NodeStatus) {
+        if let Some(current) = self.statuses.get_mut(node_id) {
+            *current = status;
+        }
+    }
+
+    /// Transitively skip pending dependents of a node (fan-in: one failed parent is enough)
+    fn skip_dependents(&mut self, node_id: &str) -> Vec<String> {
+        let mut skipped: Vec<String> = Vec::new();
+        let mut queue: VecDeque<String> = VecDeque::from([node_id.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            for dependent in self.dag.dependents_of(&current).to_vec() {
+                if self.statuses.get(&dependent) == Some(&NodeStatus::Pending) {
+                    self.set_status(&dependent, NodeStatus::Skipped);
+                    skipped.push(dependent.clone());
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(id: &str, order: i32, deps: &[&str]) -> DagNodeSpec {
+        DagNodeSpec {
+            id: id.to_string(),
+            name: id.to_string(),
+            order,
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            branch_targets: Vec::new(),
+        }
+    }
+
+    /// install -> (lint, typecheck, test) -> build
+    fn ci_dag() -> WorkflowDag {
+        WorkflowDag::from_specs(vec![
+            spec("install", 0, &[]),
+            spec("lint", 1, &["install"]),
+            spec("typecheck", 2, &["install"]),
+            spec("test", 3, &["install"]),
+            spec("build", 4, &["lint", "typecheck", "test"]),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_linear_workflow_chains_by_order() {
+        let dag = WorkflowDag::from_specs(vec![
+            spec("b", 1, &[]),
+            spec("a", 0, &[]),
+            spec("c", 2, &[]),
+        ])
+        .unwrap();
+
+        assert_eq!(dag.topological_order(), vec!["a", "b", "c"]);
+        assert_eq!(dag.dependencies_of("c"), &["b".to_string()]);
+
+        let scheduler = DagScheduler::new(dag);
+        assert_eq!(scheduler.ready_nodes(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_independent_branches_are_ready_together() {
+        let mut scheduler = DagScheduler::new(ci_dag());
+        assert_eq!(scheduler.ready_nodes(), vec!["install"]);
+
+        scheduler.mark_running("install");
+        assert!(scheduler.ready_nodes().is_empty());
+        scheduler.mark_completed("install");
+        assert_eq!(scheduler.ready_nodes(), vec!["lint", "typecheck", "test"]);
+
+        scheduler.mark_running("test");
+        scheduler.mark_completed("lint");
+        scheduler.mark_completed("typecheck");
+        assert!(scheduler.ready_nodes().is_empty());
+        scheduler.mark_completed("test");
+        assert_eq!(scheduler.ready_nodes(), vec!["build"]);
+    }
+
+    #[test]
+    fn test_failed_branch_skips_fan_in() {
+        let mut scheduler = DagScheduler::new(ci_dag());
+        scheduler.mark_completed("install");
+        scheduler.mark_running("lint");
+        scheduler.mark_running("typecheck");
+
+        let skipped = scheduler.mark_failed("lint");
+        assert_eq!(skipped, vec!["build"]);
+        assert_eq!(scheduler.status("build"), Some(&NodeStatus::Skipped));
+
+        // Independent siblings still run to completion
+        assert_eq!(scheduler.ready_nodes(), vec!["test"]);
+        scheduler.mark_completed("typecheck");
+        scheduler.mark_completed("test");
+        assert!(scheduler.is_finished());
+        assert!(scheduler.has_failures());
+    }
+
+    #[test]
+    fn test_cycle_is_rejected() {
+        let result = WorkflowDag::from_specs(vec![
+            spec("a", 0, &["c"]),
+            spec("b", 1, &["a"]),
+            spec("c", 2, &["b"]),
+        ]);
+        let error = result.unwrap_err();
+        assert!(error.contains("cycle"), "unexpected error: {}", error);
+    }
+
+    #[test]
+    fn test_unknown_and_self_dependency_are_rejected() {
+        assert!(WorkflowDag::from_specs(vec![spec("a", 0, &["missing"])]).is_err());
+        assert!(WorkflowDag::from_specs(vec![spec("a", 0, &["a"])]).is_err());
+    }
+
+    #[test]
+    fn test_condition_skips_branch_and_joins() {
+        // test -> gate -> (publish | notify) -> report
+        let mut gate = spec("gate", 1, &["test"]);
+        gate.branch_targets = vec!["publish".to_string(), "notify".to_string()];
+        let dag = WorkflowDag::from_specs(vec![
+            spec("test", 0, &[]),
+            gate,
+            spec("publish", 2, &[]),
+            spec("notify", 3, &[]),
+            spec("changelog", 4, &["publish"]),
+            spec("report", 5, &["publish", "notify"]),
+        ])
+        .unwrap();
+        assert_eq!(dag.dependencies_of("publish"), &["gate".to_string()]);
+
+        let mut scheduler = DagScheduler::new(dag);
+        scheduler.mark_completed("test");
+        assert_eq!(scheduler.ready_nodes(), vec!["gate"]);
+
+        let skipped = scheduler.mark_branch_result("gate", &["publish".to_string()]);
+        assert_eq!(skipped, vec!["publish", "changelog"]);
+        assert_eq!(scheduler.ready_nodes(), vec!["notify"]);
+
+        scheduler.mark_completed("notify");
+        assert_eq!(scheduler.ready_nodes(), vec!["report"]);
+        scheduler.mark_completed("report");
+        assert!(scheduler.is_finished());
+        assert!(!scheduler.has_failures());
+    }
+
+    #[test]
+    fn test_condition_to_unknown_node_is_rejected() {
+        let mut gate = spec("gate", 0, &[]);
+        gate.branch_targets = vec!["missing".to_string()];
+        assert!(WorkflowDag::from_specs(vec![gate]).is_err());
+    }
+
+    #[test]
+    fn test_find_path() {
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+        adjacency.insert("a".to_string(), vec!["b".to_string()]);
+        adjacency.insert("b".to_string(), vec!["c".to_string()]);
+
+        assert_eq!(
+            find_path(&adjacency, "a", "c"),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(find_path(&adjacency, "c", "a"), None);
+    }
+}
@@ -0,0 +1,187 @@
+// Workflow Environment
+// Per-workflow and per-node environment variables, secret references
+// (`{{secrets.<name>}}`) and masking of secret values in output
+
+use std::collections::{BTreeSet, HashMap};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::template::{interpolate, InterpolationContext};
+
+/// Matches `{{secrets.<name>}}` references
+static SECRET_REF_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*secrets\.([\w\-]+)\s*\}\}").unwrap());
+
+/// Name suffixes of variables that must come from a secret instead of a plaintext value
+const CREDENTIAL_SUFFIXES: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "API_KEY",
+    "APIKEY",
+    "PRIVATE_KEY",
+];
+
+/// Secret values shorter than this are not masked (they would mangle unrelated output)
+pub const MIN_MASKED_SECRET_LEN: usize = 4;
+
+/// Replacement for secret values in output
+const MASK: &str = "***";
+
+/// Validate an env map before a workflow is saved.
+/// Credential-like variables (e.g. `NPM_TOKEN`) must reference a secret so that the
+/// value is never stored in plaintext in the workflows table.
+pub fn validate_env(env: &HashMap<String, String>) -> Result<(), String> {
+    for (name, value) in env {
+        let valid_name = name
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_alphabetic() || c == '_')
+            .unwrap_or(false)
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(format!("Invalid environment variable name '{}'", name));
+        }
+
+        let upper = name.to_ascii_uppercase();
+        let is_credential = CREDENTIAL_SUFFIXES.iter().any(|s| upper.ends_with(s));
+        if is_credential && !value.is_empty() && !SECRET_REF_RE.is_match(value) {
+            return Err(format!(
+                "Environment variable '{}' looks like a credential; store it as a secret and reference it as {{{{secrets.<name>}}}}",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Names of the secrets referenced by env values (sorted, without duplicates)
+pub fn secret_references<'a, I>(values: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    values
+        .into_iter()
+        .flat_map(|value| SECRET_REF_RE.captures_iter(value))
+        .map(|caps| caps[1].to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Merge workflow and node env (node wins)
+pub fn merge_env(
+    workflow_env: &HashMap<String, String>,
+    node_env: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut env = workflow_env.clone();
+    env.extend(node_env.iter().map(|(k, v)| (k.clone(), v.clone())));
+    env
+}
+
+/// Render env values: `{{params.*}}`, `{{steps.*}}` and `{{secrets.*}}` are substituted
+pub fn resolve_env(
+    env: &HashMap<String, String>,
+    ctx: &InterpolationContext,
+    secrets: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let ctx = InterpolationContext {
+        secrets: Some(secrets.clone()),
+        ..ctx.clone()
+    };
+    env.iter()
+        .map(|(name, value)| {
+            interpolate(value, &ctx)
+                .map(|value| (name.clone(), value))
+                .map_err(|e| format!("Environment variable '{}': {}", name, e))
+        })
+        .collect()
+}
+
+/// Replace secret values in output with `***` (longest first, so overlapping values are fully masked)
+pub fn mask_secret_values(output: &str, secrets: &[String]) -> String {
+    let mut values: Vec<&String> = secrets
+        .iter()
+        .filter(|s| s.len() >= MIN_MASKED_SECRET_LEN)
+        .collect();
+    if values.is_empty() {
+        return output.to_string();
+    }
+    values.sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+    let mut masked = output.to_string();
+    for value in values {
+        if masked.contains(value.as_str()) {
+            masked = masked.replace(value.as_str(), MASK);
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_env_requires_secrets_for_credentials() {
+        assert!(validate_env(&env(&[
+            ("NODE_ENV", "production"),
+            ("NPM_TOKEN", "{{secrets.NPM_TOKEN}}"),
+        ]))
+        .is_ok());
+
+        assert!(validate_env(&env(&[("NPM_TOKEN", "npm_abc123")])).is_err());
+        assert!(validate_env(&env(&[("github_token", "ghp_xxx")])).is_err());
+        assert!(validate_env(&env(&[("1BAD", "x")])).is_err());
+        assert!(validate_env(&env(&[("BAD-NAME", "x")])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_env_with_secrets() {
+        let workflow_env = env(&[("NODE_ENV", "production"), ("REGISTRY", "npmjs")]);
+        let node_env = env(&[
+            ("NODE_ENV", "{{params.env}}"),
+            ("NPM_TOKEN", "{{secrets.NPM_TOKEN}}"),
+        ]);
+        let merged = merge_env(&workflow_env, &node_env);
+        assert_eq!(secret_references(merged.values()), vec!["NPM_TOKEN"]);
+
+        let mut ctx = InterpolationContext::default();
+        ctx.params.insert("env".to_string(), "staging".to_string());
+        let secrets = HashMap::from([("NPM_TOKEN".to_string(), "npm_s3cr3t".to_string())]);
+
+        let resolved = resolve_env(&merged, &ctx, &secrets).unwrap();
+        assert_eq!(resolved.get("NODE_ENV"), Some(&"staging".to_string()));
+        assert_eq!(resolved.get("REGISTRY"), Some(&"npmjs".to_string()));
+        assert_eq!(resolved.get("NPM_TOKEN"), Some(&"npm_s3cr3t".to_string()));
+
+        let missing = resolve_env(&merged, &ctx, &HashMap::new()).unwrap_err();
+        assert!(missing.contains("NPM_TOKEN"));
+    }
+
+    #[test]
+    fn test_mask_secret_values() {
+        let secrets = vec![
+            "npm_s3cr3t".to_string(),
+            "npm_s3cr3t_long".to_string(),
+            "ab".to_string(),
+        ];
+        assert_eq!(
+            mask_secret_values("//registry/:_authToken=npm_s3cr3t_long ab", &secrets),
+            "//registry/:_authToken=*** ab"
+        );
+        assert_eq!(
+            mask_secret_values("token npm_s3cr3t", &secrets),
+            "token ***"
+        );
+        assert_eq!(mask_secret_values("nothing here", &[]), "nothing here");
+    }
+}
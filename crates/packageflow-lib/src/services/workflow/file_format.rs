@@ -0,0 +1,689 @@
+// Workflow Files
+// Versioned YAML/JSON workflow definitions committed under `.packageflow/workflows`,
+// with validation errors that point at the offending node and diffing against
+// the stored workflow
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::models::workflow::{
+    Workflow, WorkflowFile, WorkflowFileDiff, WorkflowFileError, WorkflowFileFormat,
+    WorkflowFileState, WorkflowFileStatus, WorkflowNode, WORKFLOW_FILES_DIR, WORKFLOW_FILE_VERSION,
+};
+use crate::services::workflow::condition::validate_condition_config;
+use crate::services::workflow::dag::WorkflowDag;
+use crate::services::workflow::env::validate_env;
+use crate::services::workflow::file_trigger::validate_file_trigger;
+use crate::services::workflow::git_trigger::validate_git_trigger;
+use crate::services::workflow::http::validate_http_config;
+use crate::services::workflow::matrix::validate_matrix;
+use crate::services::workflow::retry::validate_retry_policy;
+use crate::services::workflow::schedule::validate_schedule;
+use crate::services::workflow::template::validate_parameters;
+
+impl WorkflowFileError {
+    fn workflow(message: impl Into<String>) -> Self {
+        Self {
+            node_index: None,
+            node_id: None,
+            message: message.into(),
+        }
+    }
+
+    fn node(index: usize, node_id: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            node_index: Some(index),
+            node_id: node_id.filter(|id| !id.is_empty()).map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+/// File representation of a stored workflow
+pub fn workflow_to_file(workflow: &Workflow) -> WorkflowFile {
+    WorkflowFile {
+        version: WORKFLOW_FILE_VERSION,
+        id: workflow.id.clone(),
+        name: workflow.name.clone(),
+        description: workflow.description.clone(),
+        max_parallelism: workflow.max_parallelism,
+        parameters: workflow.parameters.clone(),
+        env: workflow
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        schedule: workflow.schedule.clone(),
+        file_trigger: workflow.file_trigger.clone(),
+        git_trigger: workflow.git_trigger.clone(),
+        concurrency: workflow.concurrency,
+        nodes: workflow.nodes.clone(),
+    }
+}
+
+/// Workflow to store for an imported file. Machine-local fields (timestamps,
+/// webhooks) are kept from `existing`.
+pub fn apply_workflow_file(
+    file: WorkflowFile,
+    existing: Option<&Workflow>,
+    project_id: Option<String>,
+) -> Workflow {
+    let mut workflow = existing
+        .cloned()
+        .unwrap_or_else(|| Workflow::new(file.id.clone(), file.name.clone()));
+    workflow.id = file.id;
+    workflow.name = file.name;
+    workflow.description = file.description;
+    workflow.project_id = project_id;
+    workflow.max_parallelism = file.max_parallelism;
+    workflow.parameters = file.parameters;
+    workflow.env = file.env.into_iter().collect();
+    workflow.schedule = file.schedule;
+    workflow.file_trigger = file.file_trigger;
+    workflow.git_trigger = file.git_trigger;
+    workflow.concurrency = file.concurrency;
+    workflow.nodes = file.nodes;
+    workflow.updated_at = chrono::Utc::now().to_rfc3339();
+    workflow
+}
+
+/// Serialize a workflow into the file format
+pub fn serialize_workflow_file(
+    workflow: &Workflow,
+    format: WorkflowFileFormat,
+) -> Result<String, String> {
+    let file = workflow_to_file(workflow);
+    match format {
+        WorkflowFileFormat::Yaml => serde_yaml::to_string(&file)
+            .map_err(|e| format!("Failed to serialize workflow to YAML: {}", e)),
+        WorkflowFileFormat::Json => serde_json::to_string_pretty(&file)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to serialize workflow to JSON: {}", e)),
+    }
+}
+
+/// Parse and validate a workflow file
+pub fn parse_workflow_file(
+    content: &str,
+    format: WorkflowFileFormat,
+) -> Result<WorkflowFile, Vec<WorkflowFileError>> {
+    let mut value: Value = match format {
+        WorkflowFileFormat::Yaml => serde_yaml::from_str(content)
+            .map_err(|e| vec![WorkflowFileError::workflow(format!("Invalid YAML: {}", e))])?,
+        WorkflowFileFormat::Json => serde_json::from_str(content)
+            .map_err(|e| vec![WorkflowFileError::workflow(format!("Invalid JSON: {}", e))])?,
+    };
+    let Some(object) = value.as_object_mut() else {
+        return Err(vec![WorkflowFileError::workflow(
+            "Workflow file must be a mapping of fields",
+        )]);
+    };
+
+    let max_version = u64::from(WORKFLOW_FILE_VERSION);
+    match object.get("version").map(Value::as_u64) {
+        Some(Some(version)) if (1..=max_version).contains(&version) => {}
+        Some(Some(version)) if version > max_version => {
+            return Err(vec![WorkflowFileError::workflow(format!(
+                "Workflow file version {} is newer than the supported version {}; update PackageFlow",
+                version, WORKFLOW_FILE_VERSION
+            ))]);
+        }
+        Some(_) => {
+            return Err(vec![WorkflowFileError::workflow(
+                "Invalid `version`: expected a positive integer",
+            )]);
+        }
+        None => {
+            return Err(vec![WorkflowFileError::workflow(format!(
+                "Missing `version` field (current version is {})",
+                WORKFLOW_FILE_VERSION
+            ))]);
+        }
+    }
+
+    let raw_nodes = match object.remove("nodes") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(nodes)) => nodes,
+        Some(_) => return Err(vec![WorkflowFileError::workflow("`nodes` must be a list")]),
+    };
+
+    // Nodes are deserialized one by one so errors name the node
+    let mut errors = Vec::new();
+    let mut nodes = Vec::new();
+    for (index, mut raw) in raw_nodes.into_iter().enumerate() {
+        let node_id = raw.get("id").and_then(Value::as_str).map(str::to_string);
+        // `order` defaults to the node's position in the list
+        if let Some(fields) = raw.as_object_mut() {
+            fields
+                .entry("order")
+                .or_insert_with(|| Value::from(index as i64));
+        }
+        match serde_json::from_value::<WorkflowNode>(raw) {
+            Ok(node) => nodes.push(node),
+            Err(e) => errors.push(WorkflowFileError::node(
+                index,
+                node_id.as_deref(),
+                e.to_string(),
+            )),
+        }
+    }
+
+    let mut file: WorkflowFile = match serde_json::from_value(value) {
+        Ok(file) => file,
+        Err(e) => {
+            errors.insert(0, WorkflowFileError::workflow(e.to_string()));
+            return Err(errors);
+        }
+    };
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    file.nodes = nodes;
+
+    let errors = validate_workflow_file(&file);
+    if errors.is_empty() {
+        Ok(file)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validate a parsed workflow file; node problems carry the node's index and ID
+pub fn validate_workflow_file(file: &WorkflowFile) -> Vec<WorkflowFileError> {
+    let mut errors = Vec::new();
+    let mut workflow_check = |result: Result<(), String>| {
+        if let Err(e) = result {
+            errors.push(WorkflowFileError::workflow(e));
+        }
+    };
+
+    if file.id.trim().is_empty() {
+        workflow_check(Err("Workflow `id` must not be empty".to_string()));
+    }
+    if file.name.trim().is_empty() {
+        workflow_check(Err("Workflow `name` must not be empty".to_string()));
+    }
+    workflow_check(validate_parameters(&file.parameters));
+    workflow_check(validate_env(&file.env.clone().into_iter().collect()));
+    if let Some(ref schedule) = file.schedule {
+        workflow_check(validate_schedule(schedule));
+    }
+    if let Some(ref trigger) = file.file_trigger {
+        workflow_check(validate_file_trigger(trigger));
+    }
+    if let Some(ref trigger) = file.git_trigger {
+        workflow_check(validate_git_trigger(trigger));
+    }
+
+    let ids: HashSet<&str> = file.nodes.iter().map(|n| n.id.as_str()).collect();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut node_errors = Vec::new();
+    for (index, node) in file.nodes.iter().enumerate() {
+        let mut fail = |message: String| {
+            node_errors.push(WorkflowFileError::node(index, Some(&node.id), message));
+        };
+
+        if node.id.trim().is_empty() {
+            fail("Node `id` must not be empty".to_string());
+        } else if !seen.insert(node.id.as_str()) {
+            fail(format!("Duplicate node ID '{}'", node.id));
+        }
+        if node.name.trim().is_empty() {
+            fail("Node `name` must not be empty".to_string());
+        }
+
+        if node.is_script() {
+            match node.get_script_config() {
+                Some(config) => {
+                    if config.command.trim().is_empty() {
+                        fail("Script node needs a `command`".to_string());
+                    }
+                    if let Err(e) = validate_retry_policy(&config.retry) {
+                        fail(e);
+                    }
+                    if let Err(e) = validate_env(&config.env) {
+                        fail(e);
+                    }
+                    if let Some(Err(e)) = config.matrix.as_ref().map(validate_matrix) {
+                        fail(e);
+                    }
+                }
+                None => fail("Invalid script config".to_string()),
+            }
+        } else if node.is_trigger_workflow() {
+            if node.get_trigger_workflow_config().is_none() {
+                fail("Invalid trigger-workflow config".to_string());
+            }
+        } else if node.is_condition() {
+            match node.get_condition_config() {
+                Some(config) => {
+                    if let Err(e) = validate_condition_config(&config) {
+                        fail(e);
+                    }
+                }
+                None => fail("Invalid condition config".to_string()),
+            }
+        } else if node.is_approval() {
+            match node.get_approval_config() {
+                Some(config) if config.timeout_minutes == Some(0) => {
+                    fail("Approval `timeoutMinutes` must be at least 1".to_string())
+                }
+                Some(_) => {}
+                None => fail("Invalid approval config".to_string()),
+            }
+        } else if node.is_http() {
+            match node.get_http_config() {
+                Some(config) => {
+                    if let Err(e) = validate_http_config(&config) {
+                        fail(e);
+                    }
+                }
+                None => fail("Invalid HTTP config".to_string()),
+            }
+        } else {
+            fail(format!("Unknown node type '{}'", node.node_type));
+        }
+
+        for dep in &node.depends_on {
+            if dep == &node.id {
+                fail("A node cannot depend on itself".to_string());
+            } else if !ids.contains(dep.as_str()) {
+                fail(format!("`dependsOn` references unknown node '{}'", dep));
+            }
+        }
+    }
+    errors.extend(node_errors);
+
+    // Cycles and condition routes span several nodes; checked once nodes are valid
+    if errors.is_empty() {
+        if let Err(e) = WorkflowDag::new(&file.nodes) {
+            errors.push(WorkflowFileError::workflow(e));
+        }
+    }
+
+    errors
+}
+
+/// Compare a workflow file with the stored workflow
+pub fn diff_workflow_file(file: &WorkflowFile, workflow: &Workflow) -> WorkflowFileDiff {
+    let to_value = |file: &WorkflowFile| serde_json::to_value(file).unwrap_or_default();
+    let incoming = to_value(file);
+    let stored = to_value(&workflow_to_file(workflow));
+    let empty = serde_json::Map::new();
+    let incoming_fields = incoming.as_object().unwrap_or(&empty);
+    let stored_fields = stored.as_object().unwrap_or(&empty);
+
+    let mut changed_fields: Vec<String> = incoming_fields
+        .keys()
+        .chain(stored_fields.keys())
+        .filter(|key| *key != "version" && *key != "nodes")
+        .filter(|key| incoming_fields.get(*key) != stored_fields.get(*key))
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    changed_fields.sort();
+
+    let node_values = |nodes: &[WorkflowNode]| -> Vec<(String, Value)> {
+        nodes
+            .iter()
+            .map(|n| (n.id.clone(), serde_json::to_value(n).unwrap_or_default()))
+            .collect()
+    };
+    let incoming_nodes = node_values(&file.nodes);
+    let stored_nodes: HashMap<String, Value> = node_values(&workflow.nodes).into_iter().collect();
+    let incoming_ids: HashSet<&str> = incoming_nodes.iter().map(|(id, _)| id.as_str()).collect();
+
+    let mut diff = WorkflowFileDiff {
+        changed_fields,
+        ..Default::default()
+    };
+    for (id, value) in &incoming_nodes {
+        match stored_nodes.get(id) {
+            None => diff.added_nodes.push(id.clone()),
+            Some(stored) if stored != value => diff.modified_nodes.push(id.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.removed_nodes = workflow
+        .nodes
+        .iter()
+        .filter(|n| !incoming_ids.contains(n.id.as_str()))
+        .map(|n| n.id.clone())
+        .collect();
+    diff
+}
+
+/// File name for a workflow (slug of its name)
+pub fn workflow_file_name(name: &str, format: WorkflowFileFormat) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let slug = if slug.is_empty() { "workflow" } else { slug };
+    format!("{}.{}", slug, format.extension())
+}
+
+/// Workflow files in a project's `.packageflow/workflows` directory, sorted by name
+pub fn list_workflow_files(project_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(project_path.join(WORKFLOW_FILES_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && WorkflowFileFormat::from_path(path).is_some())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Read and validate a workflow file; the format follows the file extension
+pub fn read_workflow_file(path: &Path) -> Result<WorkflowFile, Vec<WorkflowFileError>> {
+    let format = WorkflowFileFormat::from_path(path).ok_or_else(|| {
+        vec![WorkflowFileError::workflow(
+            "Unsupported workflow file extension (use .yaml, .yml or .json)",
+        )]
+    })?;
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        vec![WorkflowFileError::workflow(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))]
+    })?;
+    parse_workflow_file(&content, format)
+}
+
+/// Path of the file defining `workflow_id` in a project, if any
+pub fn find_workflow_file(project_path: &Path, workflow_id: &str) -> Option<PathBuf> {
+    list_workflow_files(project_path)
+        .into_iter()
+        .find(|path| read_workflow_file(path).is_ok_and(|file| file.id == workflow_id))
+}
+
+/// Write a workflow into the project's workflow directory. An existing file for
+/// the workflow is overwritten in its own format; `format` applies to new files.
+pub fn export_workflow_file(
+    project_path: &Path,
+    workflow: &Workflow,
+    format: Option<WorkflowFileFormat>,
+) -> Result<PathBuf, String> {
+    let path = match find_workflow_file(project_path, &workflow.id) {
+        Some(path) => path,
+        None => {
+            let dir = project_path.join(WORKFLOW_FILES_DIR);
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create {}: {}", WORKFLOW_FILES_DIR, e))?;
+            let format = format.unwrap_or_default();
+            let path = dir.join(workflow_file_name(&workflow.name, format));
+            if path.exists() {
+                // Name taken by another workflow's file
+                let short_id: String = workflow.id.chars().take(8).collect();
+                let stem = workflow_file_name(&workflow.name, format);
+                let stem = stem.trim_end_matches(&format!(".{}", format.extension()));
+                dir.join(format!("{}-{}.{}", stem, short_id, format.extension()))
+            } else {
+                path
+            }
+        }
+    };
+
+    let format = WorkflowFileFormat::from_path(&path).unwrap_or_default();
+    let content = serialize_workflow_file(workflow, format)?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write workflow file: {}", e))?;
+    Ok(path)
+}
+
+/// Compare a project's workflow files with its stored workflows
+pub fn workflow_file_statuses(
+    project_path: &Path,
+    workflows: &[Workflow],
+) -> Vec<WorkflowFileStatus> {
+    let mut statuses = Vec::new();
+    let mut defined: HashMap<String, String> = HashMap::new();
+
+    for path in list_workflow_files(project_path) {
+        let relative = path
+            .strip_prefix(project_path)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut status = WorkflowFileStatus {
+            path: Some(relative.clone()),
+            workflow_id: None,
+            name: None,
+            state: WorkflowFileState::Invalid,
+            diff: None,
+            errors: Vec::new(),
+        };
+
+        match read_workflow_file(&path) {
+            Err(errors) => status.errors = errors,
+            Ok(file) => {
+                status.workflow_id = Some(file.id.clone());
+                status.name = Some(file.name.clone());
+                if let Some(other) = defined.get(&file.id) {
+                    status.errors.push(WorkflowFileError::workflow(format!(
+                        "Workflow ID '{}' is also defined in {}",
+                        file.id, other
+                    )));
+                } else {
+                    defined.insert(file.id.clone(), relative);
+                    match workflows.iter().find(|w| w.id == file.id) {
+                        None => status.state = WorkflowFileState::New,
+                        Some(workflow) => {
+                            let diff = diff_workflow_file(&file, workflow);
+                            if diff.is_empty() {
+                                status.state = WorkflowFileState::Unchanged;
+                            } else {
+                                status.state = WorkflowFileState::Modified;
+                                status.diff = Some(diff);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        statuses.push(status);
+    }
+
+    for workflow in workflows {
+        if !defined.contains_key(&workflow.id) {
+            statuses.push(WorkflowFileStatus {
+                path: None,
+                workflow_id: Some(workflow.id.clone()),
+                name: Some(workflow.name.clone()),
+                state: WorkflowFileState::NotExported,
+                diff: None,
+                errors: Vec::new(),
+            });
+        }
+    }
+
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_workflow() -> Workflow {
+        let mut workflow = Workflow::new("wf-1".to_string(), "Build & Test".to_string());
+        workflow.project_id = Some("project-1".to_string());
+        workflow
+            .env
+            .insert("NODE_ENV".to_string(), "production".to_string());
+        let install = WorkflowNode::new(
+            "install".to_string(),
+            "Install".to_string(),
+            "pnpm install".to_string(),
+        );
+        let mut test = WorkflowNode::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "pnpm test".to_string(),
+        );
+        test.order = 1;
+        test.depends_on = vec!["install".to_string()];
+        workflow.nodes = vec![install, test];
+        workflow
+    }
+
+    #[test]
+    fn test_round_trip_yaml_and_json() {
+        let workflow = sample_workflow();
+        for format in [WorkflowFileFormat::Yaml, WorkflowFileFormat::Json] {
+            let content = serialize_workflow_file(&workflow, format).unwrap();
+            let file = parse_workflow_file(&content, format).unwrap();
+            assert_eq!(file.version, WORKFLOW_FILE_VERSION);
+            assert!(diff_workflow_file(&file, &workflow).is_empty());
+
+            // Machine-local fields never reach the file
+            assert!(!content.contains("project-1"));
+            assert!(!content.contains("createdAt"));
+        }
+
+        // Hand-written files may omit `order`
+        let file = parse_workflow_file(
+            "version: 1\nid: wf-2\nname: Lint\nnodes:\n  - id: a\n    name: A\n    config: { command: pnpm lint }\n  - id: b\n    name: B\n    config: { command: pnpm format }\n",
+            WorkflowFileFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(file.nodes[1].order, 1);
+        assert_eq!(file.nodes[0].node_type, "script");
+
+        // build -> approve -> publish
+        let file = parse_workflow_file(
+            "version: 1\nid: wf-3\nname: Deploy\nnodes:\n  - id: build\n    name: Build\n    config: { command: pnpm build }\n  - id: approve\n    name: Approve\n    type: approval\n    config: { message: Publish?, timeoutMinutes: 30 }\n  - id: publish\n    name: Publish\n    config: { command: pnpm publish }\n",
+            WorkflowFileFormat::Yaml,
+        )
+        .unwrap();
+        let config = file.nodes[1].get_approval_config().unwrap();
+        assert_eq!(config.message.as_deref(), Some("Publish?"));
+        assert_eq!(config.timeout_minutes, Some(30));
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_nodes() {
+        let errors =
+            parse_workflow_file("id: wf\nname: x\n", WorkflowFileFormat::Yaml).unwrap_err();
+        assert!(errors[0].message.contains("Missing `version`"));
+
+        let errors =
+            parse_workflow_file("version: 99\nid: wf\nname: x\n", WorkflowFileFormat::Yaml)
+                .unwrap_err();
+        assert!(errors[0].message.contains("newer"));
+
+        // Shape errors name the node that failed to deserialize
+        let errors = parse_workflow_file(
+            "version: 1\nid: wf\nname: x\nnodes:\n  - id: ok\n    name: Ok\n    config: { command: ls }\n  - id: broken\n    config: { command: ls }\n",
+            WorkflowFileFormat::Yaml,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].node_index, Some(1));
+        assert_eq!(errors[0].node_id.as_deref(), Some("broken"));
+        assert!(errors[0].to_string().starts_with("nodes[1] (broken): "));
+
+        // Semantic errors too
+        let errors = parse_workflow_file(
+            r#"{"version": 1, "id": "wf", "name": "x", "nodes": [
+                {"id": "a", "name": "A", "config": {"command": ""}},
+                {"id": "b", "name": "B", "config": {"command": "ls"}, "dependsOn": ["missing"]},
+                {"id": "c", "name": "C", "type": "unknown"}
+            ]}"#,
+            WorkflowFileFormat::Json,
+        )
+        .unwrap_err();
+        let located: Vec<(Option<usize>, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.node_index, e.node_id.as_deref()))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                (Some(0), Some("a")),
+                (Some(1), Some("b")),
+                (Some(2), Some("c"))
+            ]
+        );
+
+        let errors = parse_workflow_file(
+            "version: 1\nid: wf\nname: x\nnodes:\n  - id: a\n    name: A\n    config: { command: ls }\n    dependsOn: [b]\n  - id: b\n    name: B\n    config: { command: ls }\n    dependsOn: [a]\n",
+            WorkflowFileFormat::Yaml,
+        )
+        .unwrap_err();
+        assert!(errors[0].message.contains("cycle"));
+        assert_eq!(errors[0].node_index, None);
+    }
+
+    #[test]
+    fn test_export_diff_and_status() {
+        let root =
+            std::env::temp_dir().join(format!("packageflow-wf-files-{}", uuid::Uuid::new_v4()));
+        let workflow = sample_workflow();
+
+        let path = export_workflow_file(&root, &workflow, None).unwrap();
+        assert!(path.ends_with(".packageflow/workflows/build-test.yaml"));
+        // Re-exporting overwrites the same file
+        assert_eq!(
+            export_workflow_file(&root, &workflow, Some(WorkflowFileFormat::Json)).unwrap(),
+            path
+        );
+
+        // Another workflow with the same name gets a distinct file
+        let mut other = sample_workflow();
+        other.id = "abcdef123456".to_string();
+        let other_path = export_workflow_file(&root, &other, None).unwrap();
+        assert!(other_path.ends_with("build-test-abcdef12.yaml"));
+
+        let mut stored = workflow.clone();
+        stored.nodes[1].config = serde_json::json!({ "command": "pnpm vitest" });
+        stored.nodes.push(WorkflowNode::new(
+            "lint".to_string(),
+            "Lint".to_string(),
+            "pnpm lint".to_string(),
+        ));
+        stored.description = Some("local edit".to_string());
+
+        let statuses = workflow_file_statuses(&root, &[stored]);
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].state, WorkflowFileState::New);
+        assert_eq!(statuses[1].state, WorkflowFileState::Modified);
+        let diff = statuses[1].diff.clone().unwrap();
+        assert_eq!(diff.changed_fields, vec!["description".to_string()]);
+        assert_eq!(diff.modified_nodes, vec!["test".to_string()]);
+        assert_eq!(diff.removed_nodes, vec!["lint".to_string()]);
+        assert!(diff.added_nodes.is_empty());
+
+        let statuses = workflow_file_statuses(
+            &root,
+            &[
+                workflow.clone(),
+                Workflow::new("local".to_string(), "Local".to_string()),
+            ],
+        );
+        assert_eq!(statuses[1].state, WorkflowFileState::Unchanged);
+        assert_eq!(statuses[2].state, WorkflowFileState::NotExported);
+
+        // Duplicate IDs across files are reported
+        std::fs::copy(&path, root.join(WORKFLOW_FILES_DIR).join("copy.yaml")).unwrap();
+        let statuses = workflow_file_statuses(&root, &[workflow]);
+        let invalid: Vec<&WorkflowFileStatus> = statuses
+            .iter()
+            .filter(|s| s.state == WorkflowFileState::Invalid)
+            .collect();
+        assert_eq!(invalid.len(), 1);
+        assert!(invalid[0].errors[0].message.contains("also defined"));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,370 @@
+// Workflow File Triggers
+// Glob matching with .gitignore rules for file-change triggered workflows, and
+// the guard that keeps a workflow from re-triggering itself through its own writes
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use glob::{MatchOptions, Pattern};
+
+use crate::models::workflow::WorkflowFileTrigger;
+
+/// Directories never watched for triggers
+const ALWAYS_IGNORED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// Events are ignored for this long after a triggered run finishes, so trailing
+/// writes of the run itself do not start it again
+pub const RETRIGGER_COOLDOWN_MS: u64 = 2000;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// One rule of a `.gitignore` file
+#[derive(Debug, Clone)]
+struct GitignoreRule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Contains a slash: matched against the path from the root instead of the file name
+    anchored: bool,
+}
+
+/// Rules from a `.gitignore` file (last matching rule wins)
+#[derive(Debug, Clone, Default)]
+pub struct GitignoreRules {
+    rules: Vec<GitignoreRule>,
+}
+
+impl GitignoreRules {
+    /// Parse `.gitignore` content; invalid patterns are skipped
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line.strip_prefix('\\').unwrap_or(line)),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let line = line.trim_start_matches('/');
+                Pattern::new(line).ok().map(|pattern| GitignoreRule {
+                    pattern,
+                    negated,
+                    dir_only,
+                    anchored,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Load the root `.gitignore` of a project (empty when missing)
+    pub fn load(project_root: &Path) -> Self {
+        std::fs::read_to_string(project_root.join(".gitignore"))
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    /// Whether a path relative to the project root is ignored.
+    /// A file inside an ignored directory is ignored, as in git.
+    pub fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        let components: Vec<&str> = relative.split('/').filter(|c| !c.is_empty()).collect();
+        for end in 1..=components.len() {
+            let path = components[..end].join("/");
+            let last = end == components.len();
+            if self.matches(&path, components[end - 1], !last || is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn matches(&self, path: &str, name: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let target = if rule.anchored { path } else { name };
+            if rule.pattern.matches_with(target, MATCH_OPTIONS) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Decides which changed paths start a workflow
+#[derive(Debug, Clone)]
+pub struct FileTriggerMatcher {
+    root: PathBuf,
+    patterns: Vec<Pattern>,
+    ignore: Vec<Pattern>,
+    gitignore: GitignoreRules,
+}
+
+impl FileTriggerMatcher {
+    pub fn new(project_root: &Path, trigger: &WorkflowFileTrigger) -> Result<Self, String> {
+        validate_file_trigger(trigger)?;
+        Ok(Self {
+            root: project_root.to_path_buf(),
+            patterns: compile_patterns(&trigger.patterns)?,
+            ignore: compile_patterns(&trigger.ignore)?,
+            gitignore: if trigger.respect_gitignore {
+                GitignoreRules::load(project_root)
+            } else {
+                GitignoreRules::default()
+            },
+        })
+    }
+
+    /// Relative path (with `/` separators) if `path` should start the workflow
+    pub fn matches(&self, path: &Path) -> Option<String> {
+        let relative = relative_path(&self.root, path)?;
+        if relative
+            .split('/')
+            .any(|c| ALWAYS_IGNORED_DIRS.contains(&c))
+        {
+            return None;
+        }
+        if self
+            .ignore
+            .iter()
+            .any(|p| p.matches_with(&relative, MATCH_OPTIONS))
+            || self.gitignore.is_ignored(&relative, false)
+        {
+            return None;
+        }
+        self.patterns
+            .iter()
+            .any(|p| p.matches_with(&relative, MATCH_OPTIONS))
+            .then_some(relative)
+    }
+
+    /// Directories to watch: the literal prefix of each pattern, and whether
+    /// to watch it recursively (`prisma/schema.prisma` only needs `prisma/`)
+    pub fn watch_roots(&self) -> Vec<(PathBuf, bool)> {
+        let mut roots: Vec<(PathBuf, bool)> = Vec::new();
+        for pattern in &self.patterns {
+            let components: Vec<&str> = pattern.as_str().split('/').collect();
+            let literal = components
+                .iter()
+                .take_while(|c| !c.contains(['*', '?', '[']))
+                .count();
+            let (dir, recursive) = if literal == components.len() {
+                (&components[..literal - 1], false)
+            } else {
+                (
+                    &components[..literal],
+                    literal + 1 < components.len() || components[literal].contains("**"),
+                )
+            };
+            let dir = dir.iter().fold(self.root.clone(), |acc, c| acc.join(c));
+
+            // A recursive watch on a parent already covers this directory
+            let covered = roots
+                .iter()
+                .any(|(r, rec)| (*rec && dir.starts_with(r)) || (*r == dir && !recursive));
+            if covered {
+                continue;
+            }
+            roots.retain(|(r, _)| !(recursive && r.starts_with(&dir)));
+            roots.push((dir, recursive));
+        }
+        roots
+    }
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>, String> {
+    patterns
+        .iter()
+        .map(|p| {
+            Pattern::new(p.trim_start_matches("./"))
+                .map_err(|e| format!("Invalid glob pattern '{}': {}", p, e))
+        })
+        .collect()
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Validate a file trigger before it is saved
+pub fn validate_file_trigger(trigger: &WorkflowFileTrigger) -> Result<(), String> {
+    if trigger.patterns.iter().all(|p| p.trim().is_empty()) {
+        return Err("File trigger needs at least one glob pattern".to_string());
+    }
+    for pattern in trigger.patterns.iter().chain(&trigger.ignore) {
+        if Path::new(pattern).is_absolute() || pattern.split('/').any(|c| c == "..") {
+            return Err(format!(
+                "File trigger pattern '{}' must be relative to the project",
+                pattern
+            ));
+        }
+    }
+    compile_patterns(&trigger.patterns)?;
+    compile_patterns(&trigger.ignore)?;
+    Ok(())
+}
+
+/// Keeps file-triggered workflows from re-triggering themselves: changes are
+/// ignored while the workflow runs and for a cooldown after it finishes
+#[derive(Debug, Default)]
+pub struct RetriggerGuard {
+    running: HashSet<String>,
+    cooldown_until: HashMap<String, Instant>,
+}
+
+impl RetriggerGuard {
+    /// Whether a change may start the workflow now
+    pub fn can_fire(&self, workflow_id: &str, now: Instant) -> bool {
+        !self.running.contains(workflow_id)
+            && self
+                .cooldown_until
+                .get(workflow_id)
+                .is_none_or(|until| now >= *until)
+    }
+
+    pub fn started(&mut self, workflow_id: &str) {
+        self.running.insert(workflow_id.to_string());
+    }
+
+    pub fn finished(&mut self, workflow_id: &str, now: Instant, cooldown: Duration) {
+        if self.running.remove(workflow_id) {
+            self.cooldown_until
+                .insert(workflow_id.to_string(), now + cooldown);
+        }
+    }
+
+    /// Forget a workflow (trigger removed)
+    pub fn remove(&mut self, workflow_id: &str) {
+        self.running.remove(workflow_id);
+        self.cooldown_until.remove(workflow_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(patterns: &[&str], ignore: &[&str]) -> WorkflowFileTrigger {
+        WorkflowFileTrigger {
+            enabled: true,
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            ignore: ignore.iter().map(|p| p.to_string()).collect(),
+            debounce_ms: 500,
+            respect_gitignore: true,
+        }
+    }
+
+    #[test]
+    fn test_gitignore_rules() {
+        let rules = GitignoreRules::parse(
+            "# build output\ndist/\n*.log\n!keep.log\n/generated\ndocs/**/*.tmp\n",
+        );
+
+        assert!(rules.is_ignored("dist/index.js", false));
+        assert!(rules.is_ignored("packages/app/dist/index.js", false));
+        assert!(!rules.is_ignored("dist", false));
+        assert!(rules.is_ignored("logs/debug.log", false));
+        assert!(!rules.is_ignored("logs/keep.log", false));
+        assert!(rules.is_ignored("generated/schema.ts", false));
+        assert!(!rules.is_ignored("src/generated/schema.ts", false));
+        assert!(rules.is_ignored("docs/a/b/c.tmp", false));
+        assert!(!rules.is_ignored("src/schema.graphql", false));
+    }
+
+    #[test]
+    fn test_matcher_globs_and_ignores() {
+        let root =
+            std::env::temp_dir().join(format!("packageflow-trigger-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join(".gitignore"), "src/__generated__/\n").unwrap();
+
+        let matcher = FileTriggerMatcher::new(
+            &root,
+            &trigger(
+                &["src/**/*.graphql", "prisma/schema.prisma"],
+                &["src/**/*.test.graphql"],
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            matcher.matches(&root.join("src/api/user.graphql")),
+            Some("src/api/user.graphql".to_string())
+        );
+        assert!(matcher.matches(&root.join("src/schema.graphql")).is_some());
+        assert!(matcher
+            .matches(&root.join("prisma/schema.prisma"))
+            .is_some());
+        assert!(matcher
+            .matches(&root.join("prisma/migrations/001.sql"))
+            .is_none());
+        assert!(matcher
+            .matches(&root.join("src/api/user.test.graphql"))
+            .is_none());
+        assert!(matcher
+            .matches(&root.join("src/__generated__/types.graphql"))
+            .is_none());
+        assert!(matcher
+            .matches(&root.join("node_modules/x/src/a.graphql"))
+            .is_none());
+        assert!(matcher
+            .matches(Path::new("/elsewhere/src/a.graphql"))
+            .is_none());
+
+        assert_eq!(
+            matcher.watch_roots(),
+            vec![(root.join("src"), true), (root.join("prisma"), false)]
+        );
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_validate_file_trigger() {
+        assert!(validate_file_trigger(&trigger(&["src/**/*.ts"], &[])).is_ok());
+        assert!(validate_file_trigger(&trigger(&[], &[])).is_err());
+        assert!(validate_file_trigger(&trigger(&["../other/*.ts"], &[])).is_err());
+        assert!(validate_file_trigger(&trigger(&["/etc/*"], &[])).is_err());
+        assert!(validate_file_trigger(&trigger(&["src/[.ts"], &[])).is_err());
+    }
+
+    #[test]
+    fn test_retrigger_guard() {
+        let mut guard = RetriggerGuard::default();
+        let now = Instant::now();
+        let cooldown = Duration::from_millis(RETRIGGER_COOLDOWN_MS);
+
+        assert!(guard.can_fire("wf", now));
+        guard.started("wf");
+        assert!(!guard.can_fire("wf", now));
+        assert!(guard.can_fire("other", now));
+
+        guard.finished("wf", now, cooldown);
+        assert!(!guard.can_fire("wf", now + Duration::from_millis(500)));
+        assert!(guard.can_fire("wf", now + cooldown));
+    }
+}
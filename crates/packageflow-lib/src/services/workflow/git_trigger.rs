@@ -0,0 +1,363 @@
+// Workflow Git Triggers
+// Matching git events (commit, checkout, push) against workflow triggers, and the
+// thin git hooks that report terminal git usage back to PackageFlow
+
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+
+use crate::models::workflow::{GitTriggerEvent, WorkflowGitTrigger};
+
+/// First line after the shebang of every hook written by PackageFlow
+pub const HOOK_MARKER: &str = "# packageflow-hook";
+
+/// Set on git commands run by PackageFlow itself; the hooks then stay silent
+/// because the app already fires the trigger
+pub const HOOK_SKIP_ENV: &str = "PACKAGEFLOW_GIT";
+
+/// Extension of event files written by the hooks into the spool directory
+pub const HOOK_EVENT_EXTENSION: &str = "event";
+
+const PATH_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A git event in a project
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitEvent {
+    pub event: GitTriggerEvent,
+    /// Current branch (None when detached)
+    pub branch: Option<String>,
+    /// Files changed by the event, relative to the repository root (None when unknown)
+    pub changed_files: Option<Vec<String>>,
+}
+
+/// Whether a git event starts a workflow with this trigger
+pub fn git_trigger_matches(trigger: &WorkflowGitTrigger, event: &GitEvent) -> bool {
+    if !trigger.enabled || !trigger.events.contains(&event.event) {
+        return false;
+    }
+
+    if !trigger.branches.is_empty() {
+        let Some(branch) = event.branch.as_deref() else {
+            return false;
+        };
+        let matches_branch = trigger
+            .branches
+            .iter()
+            .filter_map(|b| Pattern::new(b).ok())
+            .any(|p| p.matches(branch));
+        if !matches_branch {
+            return false;
+        }
+    }
+
+    match (&event.changed_files, trigger.paths.is_empty()) {
+        (Some(files), false) => {
+            let patterns: Vec<Pattern> = trigger
+                .paths
+                .iter()
+                .filter_map(|p| Pattern::new(p).ok())
+                .collect();
+            files.iter().any(|f| {
+                patterns
+                    .iter()
+                    .any(|p| p.matches_with(f, PATH_MATCH_OPTIONS))
+            })
+        }
+        _ => true,
+    }
+}
+
+/// Validate a git trigger before it is saved
+pub fn validate_git_trigger(trigger: &WorkflowGitTrigger) -> Result<(), String> {
+    if trigger.events.is_empty() {
+        return Err("Git trigger needs at least one event".to_string());
+    }
+    for pattern in trigger.branches.iter().chain(&trigger.paths) {
+        Pattern::new(pattern).map_err(|e| format!("Invalid glob pattern '{}': {}", pattern, e))?;
+    }
+    Ok(())
+}
+
+/// Event reported by an installed hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHookEvent {
+    pub event: GitTriggerEvent,
+    /// Repository root (`git rev-parse --show-toplevel`)
+    pub project_path: String,
+    /// Arguments git passed to the hook
+    pub args: Vec<String>,
+}
+
+impl GitHookEvent {
+    /// Parse an event file written by a hook (`key=value` lines)
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut event = None;
+        let mut project_path = None;
+        let mut args = Vec::new();
+        for line in content.lines() {
+            match line.split_once('=') {
+                Some(("event", value)) => event = GitTriggerEvent::from_hook_name(value.trim()),
+                Some(("project", value)) => project_path = Some(value.trim().to_string()),
+                Some(("args", value)) => {
+                    args = value.split_whitespace().map(str::to_string).collect()
+                }
+                _ => {}
+            }
+        }
+        Some(Self {
+            event: event?,
+            project_path: project_path.filter(|p| !p.is_empty())?,
+            args,
+        })
+    }
+
+    /// Previous and new HEAD of a branch checkout (file checkouts are ignored)
+    pub fn checkout_heads(&self) -> Option<(&str, &str)> {
+        match (self.event, self.args.as_slice()) {
+            (GitTriggerEvent::PostCheckout, [previous, head, flag]) if flag == "1" => {
+                Some((previous.as_str(), head.as_str()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Hook script that writes an event file into `spool_dir` for the running app.
+/// It always exits 0: workflows start in the background, so a `pre-push` hook
+/// never blocks a push made from a terminal.
+pub fn hook_script(event: GitTriggerEvent, spool_dir: &Path) -> Result<String, String> {
+    let spool = spool_dir.to_string_lossy();
+    if spool.contains('\'') {
+        return Err(format!(
+            "Unsupported character in PackageFlow data path: {}",
+            spool
+        ));
+    }
+    let hook = event.hook_name();
+    Ok(format!(
+        r#"#!/bin/sh
+{marker}: starts PackageFlow workflows on {hook} (managed by PackageFlow, do not edit)
+[ "${skip}" = "1" ] && exit 0
+spool='{spool}'
+[ -d "$spool" ] || exit 0
+file="$spool/$(date +%s)-$$-{hook}.{ext}"
+printf 'event={hook}\nproject=%s\nargs=%s\n' "$(git rev-parse --show-toplevel)" "$*" > "$file.tmp" && mv "$file.tmp" "$file"
+exit 0
+"#,
+        marker = HOOK_MARKER,
+        hook = hook,
+        skip = HOOK_SKIP_ENV,
+        spool = spool,
+        ext = HOOK_EVENT_EXTENSION,
+    ))
+}
+
+fn is_packageflow_hook(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .nth(1)
+                .is_some_and(|l| l.starts_with(HOOK_MARKER))
+        })
+        .unwrap_or(false)
+}
+
+/// Install hooks for `events` into `hooks_dir`. Existing hooks not written by
+/// PackageFlow are never overwritten.
+pub fn install_hooks(
+    hooks_dir: &Path,
+    events: &[GitTriggerEvent],
+    spool_dir: &Path,
+) -> Result<Vec<GitTriggerEvent>, String> {
+    for event in events {
+        let path = hooks_dir.join(event.hook_name());
+        if path.exists() && !is_packageflow_hook(&path) {
+            return Err(format!(
+                "A {} hook already exists at {}; add PackageFlow to it manually or remove it first",
+                event.hook_name(),
+                path.display()
+            ));
+        }
+    }
+
+    std::fs::create_dir_all(hooks_dir)
+        .map_err(|e| format!("Failed to create hooks directory: {}", e))?;
+
+    for event in events {
+        let hook = event.hook_name();
+        let path = hooks_dir.join(hook);
+        std::fs::write(&path, hook_script(*event, spool_dir)?)
+            .map_err(|e| format!("Failed to write {} hook: {}", hook, e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(0o755);
+            std::fs::set_permissions(&path, permissions)
+                .map_err(|e| format!("Failed to make {} hook executable: {}", hook, e))?;
+        }
+    }
+
+    Ok(events.to_vec())
+}
+
+/// Remove hooks written by PackageFlow from `hooks_dir`
+pub fn uninstall_hooks(hooks_dir: &Path) -> Result<Vec<GitTriggerEvent>, String> {
+    let mut removed = Vec::new();
+    for event in installed_hooks(hooks_dir) {
+        std::fs::remove_file(hooks_dir.join(event.hook_name()))
+            .map_err(|e| format!("Failed to remove {} hook: {}", event.hook_name(), e))?;
+        removed.push(event);
+    }
+    Ok(removed)
+}
+
+/// Hooks in `hooks_dir` that were written by PackageFlow
+pub fn installed_hooks(hooks_dir: &Path) -> Vec<GitTriggerEvent> {
+    GitTriggerEvent::ALL
+        .into_iter()
+        .filter(|event| is_packageflow_hook(&hooks_dir.join(event.hook_name())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(
+        events: &[GitTriggerEvent],
+        branches: &[&str],
+        paths: &[&str],
+    ) -> WorkflowGitTrigger {
+        WorkflowGitTrigger {
+            enabled: true,
+            events: events.to_vec(),
+            branches: branches.iter().map(|b| b.to_string()).collect(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn checkout(branch: &str, files: Option<&[&str]>) -> GitEvent {
+        GitEvent {
+            event: GitTriggerEvent::PostCheckout,
+            branch: Some(branch.to_string()),
+            changed_files: files.map(|f| f.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn test_git_trigger_matches() {
+        // Reinstall deps when the lockfile differs after a branch switch
+        let reinstall = trigger(&[GitTriggerEvent::PostCheckout], &[], &["pnpm-lock.yaml"]);
+        assert!(git_trigger_matches(
+            &reinstall,
+            &checkout("feature/x", Some(&["src/a.ts", "pnpm-lock.yaml"]))
+        ));
+        assert!(!git_trigger_matches(
+            &reinstall,
+            &checkout("feature/x", Some(&["src/a.ts"]))
+        ));
+        assert!(git_trigger_matches(
+            &reinstall,
+            &checkout("feature/x", None)
+        ));
+
+        let release = trigger(
+            &[GitTriggerEvent::PostCommit, GitTriggerEvent::PrePush],
+            &["main", "release/*"],
+            &[],
+        );
+        let event = |event, branch: Option<&str>| GitEvent {
+            event,
+            branch: branch.map(str::to_string),
+            changed_files: None,
+        };
+        assert!(git_trigger_matches(
+            &release,
+            &event(GitTriggerEvent::PrePush, Some("release/1.2"))
+        ));
+        assert!(git_trigger_matches(
+            &release,
+            &event(GitTriggerEvent::PostCommit, Some("main"))
+        ));
+        assert!(!git_trigger_matches(
+            &release,
+            &event(GitTriggerEvent::PostCommit, Some("dev"))
+        ));
+        assert!(!git_trigger_matches(
+            &release,
+            &event(GitTriggerEvent::PostCommit, None)
+        ));
+        assert!(!git_trigger_matches(
+            &release,
+            &event(GitTriggerEvent::PostCheckout, Some("main"))
+        ));
+
+        let mut disabled = release.clone();
+        disabled.enabled = false;
+        assert!(!git_trigger_matches(
+            &disabled,
+            &event(GitTriggerEvent::PostCommit, Some("main"))
+        ));
+
+        assert!(validate_git_trigger(&release).is_ok());
+        assert!(validate_git_trigger(&trigger(&[], &[], &[])).is_err());
+        assert!(
+            validate_git_trigger(&trigger(&[GitTriggerEvent::PrePush], &["[main"], &[])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_hook_event() {
+        let parsed =
+            GitHookEvent::parse("event=post-checkout\nproject=/work/app\nargs=abc123 def456 1\n")
+                .unwrap();
+        assert_eq!(parsed.event, GitTriggerEvent::PostCheckout);
+        assert_eq!(parsed.project_path, "/work/app");
+        assert_eq!(parsed.checkout_heads(), Some(("abc123", "def456")));
+
+        let file_checkout =
+            GitHookEvent::parse("event=post-checkout\nproject=/work/app\nargs=abc def 0\n")
+                .unwrap();
+        assert_eq!(file_checkout.checkout_heads(), None);
+
+        assert!(GitHookEvent::parse("event=post-commit\nproject=/work/app\nargs=\n").is_some());
+        assert!(GitHookEvent::parse("event=pre-rebase\nproject=/work/app\n").is_none());
+        assert!(GitHookEvent::parse("event=post-commit\n").is_none());
+    }
+
+    #[test]
+    fn test_install_and_uninstall_hooks() {
+        let root = std::env::temp_dir().join(format!("packageflow-hooks-{}", uuid::Uuid::new_v4()));
+        let hooks_dir = root.join("hooks");
+        let spool = root.join("git-events");
+
+        std::fs::create_dir_all(&hooks_dir).unwrap();
+        std::fs::write(hooks_dir.join("pre-push"), "#!/bin/sh\nnpm test\n").unwrap();
+
+        // A user's own hook is never overwritten
+        assert!(install_hooks(&hooks_dir, &GitTriggerEvent::ALL, &spool).is_err());
+        assert!(installed_hooks(&hooks_dir).is_empty());
+
+        let events = [GitTriggerEvent::PostCommit, GitTriggerEvent::PostCheckout];
+        install_hooks(&hooks_dir, &events, &spool).unwrap();
+        assert_eq!(installed_hooks(&hooks_dir), events.to_vec());
+        let script = std::fs::read_to_string(hooks_dir.join("post-checkout")).unwrap();
+        assert!(script.contains(&spool.to_string_lossy().to_string()));
+        assert!(script.contains("PACKAGEFLOW_GIT"));
+
+        // Reinstalling over our own hooks is fine
+        install_hooks(&hooks_dir, &events, &spool).unwrap();
+
+        assert_eq!(uninstall_hooks(&hooks_dir).unwrap(), events.to_vec());
+        assert!(hooks_dir.join("pre-push").exists());
+        assert!(!hooks_dir.join("post-commit").exists());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,267 @@
+// Workflow HTTP Node
+// Validation of HTTP node configs, expected status checks and JSON-path extraction
+// of step outputs from a response body
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::models::workflow::HttpNodeConfig;
+
+use super::env::secret_references;
+
+/// Methods an HTTP node can use
+pub const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD"];
+
+/// Request timeout when the node does not set `timeoutMs`
+pub const DEFAULT_HTTP_TIMEOUT_MS: u64 = 30_000;
+
+/// Header names whose values must come from a secret
+const CREDENTIAL_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie"];
+
+/// Validate an HTTP node config before a workflow is saved
+pub fn validate_http_config(config: &HttpNodeConfig) -> Result<(), String> {
+    if !HTTP_METHODS.contains(&config.method.to_uppercase().as_str()) {
+        return Err(format!(
+            "Unsupported HTTP method '{}' (use one of {})",
+            config.method,
+            HTTP_METHODS.join(", ")
+        ));
+    }
+
+    let url = config.url.trim();
+    if url.is_empty() {
+        return Err("HTTP node needs a `url`".to_string());
+    }
+    // URLs built from placeholders are only known at run time
+    if !url.contains("{{") {
+        let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err("Only http and https URLs are supported".to_string());
+        }
+    }
+
+    for (name, value) in &config.headers {
+        let lower = name.to_lowercase();
+        let is_credential = CREDENTIAL_HEADERS.contains(&lower.as_str())
+            || lower.contains("token")
+            || lower.contains("api-key");
+        if is_credential && secret_references(std::iter::once(value)).is_empty() {
+            return Err(format!(
+                "Header '{}' looks like a credential; store it as a secret and reference it as {{{{secrets.<name>}}}}",
+                name
+            ));
+        }
+    }
+
+    if let Some(code) = config
+        .expected_status
+        .iter()
+        .find(|code| !(100..=599).contains(*code))
+    {
+        return Err(format!("Invalid expected status {}", code));
+    }
+
+    for (output, path) in &config.extract {
+        if output.trim().is_empty() {
+            return Err("Extracted output names must not be empty".to_string());
+        }
+        parse_json_path(path)?;
+    }
+
+    if let Some(secret) = &config.signing_secret {
+        if secret_references(std::iter::once(secret)).is_empty() {
+            return Err(
+                "`signingSecret` must reference a stored secret ({{secrets.<name>}})".to_string(),
+            );
+        }
+    }
+
+    if config.timeout_ms == Some(0) {
+        return Err("`timeoutMs` must be at least 1".to_string());
+    }
+
+    Ok(())
+}
+
+/// Whether a response status is accepted (no expected codes = any 2xx)
+pub fn status_matches(expected: &[u16], status: u16) -> bool {
+    if expected.is_empty() {
+        (200..300).contains(&status)
+    } else {
+        expected.contains(&status)
+    }
+}
+
+/// One step of a JSON path
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a JSON path such as `$.data.items[0].id` or `$["content-type"]`.
+/// The leading `$` is optional.
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let invalid = || format!("Invalid JSON path '{}'", path);
+    let trimmed = path.trim();
+    let rest = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let mut chars = rest.chars().peekable();
+    let mut segments = Vec::new();
+
+    // A path without `$` may start directly with a key
+    let mut expect_key = !rest.is_empty() && !rest.starts_with(['.', '[']);
+    loop {
+        if expect_key {
+            let mut key = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == '.' || c == '[' {
+                    break;
+                }
+                key.push(c);
+                chars.next();
+            }
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            segments.push(PathSegment::Key(key));
+            expect_key = false;
+        }
+
+        match chars.next() {
+            None => break,
+            Some('.') => expect_key = true,
+            Some('[') => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err(invalid()),
+                    }
+                }
+                let quoted = inner
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .or_else(|| inner.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')));
+                match quoted {
+                    Some(key) => segments.push(PathSegment::Key(key.to_string())),
+                    None => segments.push(PathSegment::Index(
+                        inner.trim().parse().map_err(|_| invalid())?,
+                    )),
+                }
+            }
+            Some(_) => return Err(invalid()),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Look up a JSON path in a value; `Ok(None)` when nothing is at the path
+pub fn extract_json_path<'a>(value: &'a Value, path: &str) -> Result<Option<&'a Value>, String> {
+    let mut current = value;
+    for segment in parse_json_path(path)? {
+        let next = match segment {
+            PathSegment::Key(key) => current.get(key.as_str()),
+            PathSegment::Index(index) => current.get(index),
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+/// Read the `extract` outputs of an HTTP node from a JSON response body.
+/// Strings are stored as-is, other values as JSON.
+pub fn extract_outputs(
+    body: &str,
+    extract: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    if extract.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let json: Value =
+        serde_json::from_str(body).map_err(|e| format!("Response is not valid JSON: {}", e))?;
+
+    extract
+        .iter()
+        .map(|(output, path)| {
+            let value = extract_json_path(&json, path)?
+                .ok_or_else(|| format!("JSON path '{}' matched nothing in the response", path))?;
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            Ok((output.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_outputs() {
+        let body = r#"{"data":{"id":42,"name":"acme","tags":["a","b"],"content-type":"json"}}"#;
+        let extract = HashMap::from([
+            ("id".to_string(), "$.data.id".to_string()),
+            ("name".to_string(), "data.name".to_string()),
+            ("second".to_string(), "$.data.tags[1]".to_string()),
+            ("type".to_string(), "$.data[\"content-type\"]".to_string()),
+            ("tags".to_string(), "$.data.tags".to_string()),
+        ]);
+        let outputs = extract_outputs(body, &extract).unwrap();
+        assert_eq!(outputs["id"], "42");
+        assert_eq!(outputs["name"], "acme");
+        assert_eq!(outputs["second"], "b");
+        assert_eq!(outputs["type"], "json");
+        assert_eq!(outputs["tags"], r#"["a","b"]"#);
+
+        let missing = HashMap::from([("x".to_string(), "$.data.missing".to_string())]);
+        assert!(extract_outputs(body, &missing)
+            .unwrap_err()
+            .contains("matched nothing"));
+        assert!(extract_outputs("not json", &missing).is_err());
+        assert!(parse_json_path("$.data[").is_err());
+        assert!(parse_json_path("$..data").is_err());
+    }
+
+    #[test]
+    fn test_validate_http_config() {
+        let mut config = HttpNodeConfig {
+            method: "post".to_string(),
+            url: "https://api.example.com/deploys".to_string(),
+            headers: HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer {{secrets.API_TOKEN}}".to_string(),
+            )]),
+            expected_status: vec![201],
+            signing_secret: Some("{{secrets.HOOK_SECRET}}".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_http_config(&config).is_ok());
+        assert!(status_matches(&config.expected_status, 201));
+        assert!(!status_matches(&config.expected_status, 200));
+        assert!(status_matches(&[], 204));
+
+        config.url = "ftp://example.com".to_string();
+        assert!(validate_http_config(&config).is_err());
+        config.url = "{{params.apiUrl}}/deploys".to_string();
+        assert!(validate_http_config(&config).is_ok());
+
+        config
+            .headers
+            .insert("Authorization".to_string(), "Bearer abc123".to_string());
+        assert!(validate_http_config(&config)
+            .unwrap_err()
+            .contains("credential"));
+        config.headers.clear();
+
+        config.signing_secret = Some("plaintext".to_string());
+        assert!(validate_http_config(&config).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+// Workflow Matrix
+// Expanding a node matrix (Node versions, workspace packages, value lists) into
+// the cells a matrix node runs its command for
+
+use std::collections::BTreeMap;
+
+use glob::Pattern;
+
+use crate::models::project::WorkspacePackage;
+use crate::models::workflow::NodeMatrix;
+
+/// Upper bound on the number of cells of one matrix node
+pub const MAX_MATRIX_CELLS: usize = 64;
+
+/// Dimension name of `nodeVersions`
+pub const MATRIX_NODE_DIMENSION: &str = "node";
+
+/// Dimension name of `packages`
+pub const MATRIX_PACKAGE_DIMENSION: &str = "package";
+
+/// One combination of matrix values
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixCell {
+    pub index: usize,
+    /// Dimension -> value
+    pub values: BTreeMap<String, String>,
+    /// Node.js version to pin with Volta
+    pub node_version: Option<String>,
+    /// Working directory of the cell (workspace package directory)
+    pub cwd: Option<String>,
+}
+
+impl MatrixCell {
+    /// Display label, e.g. `node=20, package=@acme/core`
+    pub fn label(&self) -> String {
+        self.values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn is_valid_dimension_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Validate a matrix before a workflow is saved
+pub fn validate_matrix(matrix: &NodeMatrix) -> Result<(), String> {
+    if matrix.node_versions.is_empty() && matrix.packages.is_empty() && matrix.values.is_empty() {
+        return Err("Matrix needs at least one dimension".to_string());
+    }
+
+    for version in &matrix.node_versions {
+        if version.trim().is_empty() || version.contains(char::is_whitespace) {
+            return Err(format!("Invalid Node version '{}' in matrix", version));
+        }
+    }
+    for pattern in &matrix.packages {
+        Pattern::new(pattern)
+            .map_err(|e| format!("Invalid package pattern '{}': {}", pattern, e))?;
+    }
+
+    let mut cells = matrix.node_versions.len().max(1);
+    for (name, values) in &matrix.values {
+        if !is_valid_dimension_name(name) {
+            return Err(format!(
+                "Invalid matrix dimension '{}': use letters, digits, '_' or '-'",
+                name
+            ));
+        }
+        if name == MATRIX_NODE_DIMENSION || name == MATRIX_PACKAGE_DIMENSION {
+            return Err(format!(
+                "Matrix dimension '{}' is reserved; use {} instead",
+                name,
+                if name == MATRIX_NODE_DIMENSION {
+                    "nodeVersions"
+                } else {
+                    "packages"
+                }
+            ));
+        }
+        if values.is_empty() {
+            return Err(format!("Matrix dimension '{}' has no values", name));
+        }
+        cells = cells.saturating_mul(values.len());
+    }
+    if cells > MAX_MATRIX_CELLS {
+        return Err(format!(
+            "Matrix has {} cells, the maximum is {}",
+            cells, MAX_MATRIX_CELLS
+        ));
+    }
+
+    if matrix.max_parallel == Some(0) {
+        return Err("Matrix maxParallel must be at least 1".to_string());
+    }
+    Ok(())
+}
+
+/// Expand a matrix into its cells. `packages` are the project's workspace packages,
+/// filtered by the matrix package patterns.
+pub fn expand_matrix(
+    matrix: &NodeMatrix,
+    packages: &[WorkspacePackage],
+) -> Result<Vec<MatrixCell>, String> {
+    validate_matrix(matrix)?;
+
+    let mut dimensions: Vec<(String, Vec<String>)> = Vec::new();
+    if !matrix.node_versions.is_empty() {
+        dimensions.push((
+            MATRIX_NODE_DIMENSION.to_string(),
+            matrix.node_versions.clone(),
+        ));
+    }
+
+    let mut package_dirs: BTreeMap<String, String> = BTreeMap::new();
+    if !matrix.packages.is_empty() {
+        let patterns: Vec<Pattern> = matrix
+            .packages
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect();
+        for package in packages {
+            if patterns.iter().any(|p| p.matches(&package.name)) {
+                package_dirs.insert(package.name.clone(), package.absolute_path.clone());
+            }
+        }
+        if package_dirs.is_empty() {
+            return Err(format!(
+                "No workspace package matches {}",
+                matrix.packages.join(", ")
+            ));
+        }
+        dimensions.push((
+            MATRIX_PACKAGE_DIMENSION.to_string(),
+            package_dirs.keys().cloned().collect(),
+        ));
+    }
+
+    for (name, values) in &matrix.values {
+        dimensions.push((name.clone(), values.clone()));
+    }
+
+    let total: usize = dimensions.iter().map(|(_, values)| values.len()).product();
+    if total > MAX_MATRIX_CELLS {
+        return Err(format!(
+            "Matrix has {} cells, the maximum is {}",
+            total, MAX_MATRIX_CELLS
+        ));
+    }
+
+    // Cartesian product; the first dimension varies slowest
+    let mut combinations: Vec<BTreeMap<String, String>> = vec![BTreeMap::new()];
+    for (name, values) in &dimensions {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                values.iter().map(move |value| {
+                    let mut next = combination.clone();
+                    next.insert(name.clone(), value.clone());
+                    next
+                })
+            })
+            .collect();
+    }
+
+    Ok(combinations
+        .into_iter()
+        .enumerate()
+        .map(|(index, values)| MatrixCell {
+            index,
+            node_version: values.get(MATRIX_NODE_DIMENSION).cloned(),
+            cwd: values
+                .get(MATRIX_PACKAGE_DIMENSION)
+                .and_then(|name| package_dirs.get(name))
+                .cloned(),
+            values,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, path: &str) -> WorkspacePackage {
+        WorkspacePackage::new(
+            name.to_string(),
+            path.to_string(),
+            format!("/repo/{}", path),
+        )
+    }
+
+    #[test]
+    fn test_expand_matrix() {
+        let matrix = NodeMatrix {
+            node_versions: vec!["18".to_string(), "20".to_string(), "22".to_string()],
+            values: BTreeMap::from([(
+                "db".to_string(),
+                vec!["postgres".to_string(), "sqlite".to_string()],
+            )]),
+            ..Default::default()
+        };
+        let cells = expand_matrix(&matrix, &[]).unwrap();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0].label(), "db=postgres, node=18");
+        assert_eq!(cells[1].label(), "db=sqlite, node=18");
+        assert_eq!(cells[5].node_version.as_deref(), Some("22"));
+        assert_eq!(cells[5].cwd, None);
+        assert_eq!(cells[5].index, 5);
+
+        // Packages are filtered by name and run in their own directory
+        let packages = [
+            package("@acme/core", "packages/core"),
+            package("@acme/ui", "packages/ui"),
+            package("docs", "apps/docs"),
+        ];
+        let matrix = NodeMatrix {
+            packages: vec!["@acme/*".to_string()],
+            ..Default::default()
+        };
+        let cells = expand_matrix(&matrix, &packages).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].values["package"], "@acme/core");
+        assert_eq!(cells[0].cwd.as_deref(), Some("/repo/packages/core"));
+        assert_eq!(cells[0].node_version, None);
+
+        let matrix = NodeMatrix {
+            packages: vec!["@other/*".to_string()],
+            ..Default::default()
+        };
+        assert!(expand_matrix(&matrix, &packages).is_err());
+    }
+
+    #[test]
+    fn test_validate_matrix() {
+        assert!(validate_matrix(&NodeMatrix::default()).is_err());
+
+        let reserved = NodeMatrix {
+            values: BTreeMap::from([("node".to_string(), vec!["20".to_string()])]),
+            ..Default::default()
+        };
+        assert!(validate_matrix(&reserved)
+            .unwrap_err()
+            .contains("nodeVersions"));
+
+        let empty_dimension = NodeMatrix {
+            values: BTreeMap::from([("os".to_string(), Vec::new())]),
+            ..Default::default()
+        };
+        assert!(validate_matrix(&empty_dimension).is_err());
+
+        let values: Vec<String> = (0..9).map(|i| i.to_string()).collect();
+        let too_large = NodeMatrix {
+            values: BTreeMap::from([("a".to_string(), values.clone()), ("b".to_string(), values)]),
+            ..Default::default()
+        };
+        assert!(validate_matrix(&too_large)
+            .unwrap_err()
+            .contains("81 cells"));
+
+        let zero_parallel = NodeMatrix {
+            node_versions: vec!["20".to_string()],
+            max_parallel: Some(0),
+            ..Default::default()
+        };
+        assert!(validate_matrix(&zero_parallel).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+// Workflow Engine Services
+// Execution-independent workflow logic shared by the Tauri executor and the MCP server
+
+pub mod artifacts;
+pub mod concurrency;
+pub mod condition;
+pub mod dag;
+pub mod env;
+pub mod file_format;
+pub mod file_trigger;
+pub mod git_trigger;
+pub mod http;
+pub mod matrix;
+pub mod plan;
+pub mod resume;
+pub mod retry;
+pub mod revision;
+pub mod schedule;
+pub mod stats;
+pub mod template;
+
+pub use artifacts::{
+    collect_artifacts, delete_execution_artifacts, execution_artifacts_dir, export_artifacts,
+    prune_execution_artifacts, select_expired_artifacts, validate_artifact_pattern,
+    ArtifactCollection, ArtifactOwner, MAX_NODE_ARTIFACT_BYTES,
+};
+pub use concurrency::{
+    admit_execution, is_process_alive, queue_positions, startable_executions,
+    QUEUE_POLL_INTERVAL_MS, WORKFLOW_CONCURRENCY_SETTINGS_KEY,
+};
+pub use condition::{evaluate_condition, ConditionContext, ConditionOutcome};
+pub use dag::{DagNodeSpec, DagScheduler, WorkflowDag, DEFAULT_MAX_PARALLELISM};
+pub use env::{mask_secret_values, merge_env, resolve_env, secret_references, validate_env};
+pub use file_format::{
+    apply_workflow_file, diff_workflow_file, export_workflow_file, find_workflow_file,
+    list_workflow_files, parse_workflow_file, read_workflow_file, serialize_workflow_file,
+    validate_workflow_file, workflow_file_statuses, workflow_to_file,
+};
+pub use file_trigger::{
+    validate_file_trigger, FileTriggerMatcher, GitignoreRules, RetriggerGuard,
+    RETRIGGER_COOLDOWN_MS,
+};
+pub use git_trigger::{
+    git_trigger_matches, validate_git_trigger, GitEvent, GitHookEvent, HOOK_SKIP_ENV,
+};
+pub use http::{
+    extract_json_path, extract_outputs, status_matches, validate_http_config,
+    DEFAULT_HTTP_TIMEOUT_MS, HTTP_METHODS,
+};
+pub use matrix::{expand_matrix, validate_matrix, MatrixCell, MAX_MATRIX_CELLS};
+pub use plan::{plan_workflow, trash_targets, PlanExecutor};
+pub use resume::{plan_resume, ResumePlan};
+pub use retry::{retry_delay, retry_output_patterns, should_retry};
+pub use revision::{diff_revisions, is_same_definition, restore_revision, workflow_definition};
+pub use schedule::{
+    evaluate_due_run, validate_schedule, CronSchedule, DueRun, MISSED_RUN_GRACE_SECS,
+};
+pub use stats::{compute_workflow_stats, percentile, StatsSample};
+pub use template::{
+    collect_set_outputs, interpolate, interpolate_command, interpolate_command_for_plan,
+    interpolate_for_plan, parse_set_output, render_placeholders, resolve_parameters,
+    validate_parameters, InterpolationContext, StepOutputs,
+};
@@ -0,0 +1,585 @@
+// Workflow Plans
+// Dry-run resolution of a workflow run: node order, working directories, env, the
+// final commands after shell selection and toolchain wrapping, and child workflows
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::execution::MAX_EXECUTION_DEPTH;
+use crate::models::project::WorkspacePackage;
+use crate::models::workflow::{
+    NodePlan, PlannedInvocation, ScriptNodeConfig, Workflow, WorkflowNode, WorkflowPlan,
+};
+use crate::utils::shell::{resolve_command, CommandInvocation};
+
+use super::dag::WorkflowDag;
+use super::env::merge_env;
+use super::matrix::{expand_matrix, MatrixCell};
+use super::template::{
+    interpolate_command_for_plan, interpolate_for_plan, resolve_parameters, InterpolationContext,
+};
+
+/// Shown instead of secret values
+const MASKED_SECRET: &str = "***";
+
+/// The executor-specific parts of a plan
+pub trait PlanExecutor {
+    /// Name recorded in the plan (`app` or `mcp`)
+    fn name(&self) -> &'static str;
+
+    /// Program and arguments after Volta/Corepack wrapping
+    fn wrap_command(
+        &self,
+        invocation: CommandInvocation,
+        cwd: Option<&str>,
+        node_version: Option<&str>,
+    ) -> Result<(String, Vec<String>), String>;
+
+    /// Whether `rm` run without a shell is moved to the Trash instead
+    fn intercepts_rm(&self) -> bool;
+
+    /// Names of the defined workflow secrets
+    fn secret_names(&self) -> Result<Vec<String>, String>;
+
+    /// Workspace packages of a project, for matrix `packages`
+    fn workspace_packages(&self, project_path: &str) -> Result<Vec<WorkspacePackage>, String>;
+
+    /// Workflow started by a trigger-workflow node, with its project path.
+    /// `Ok(None)` when the executor does not run child workflows.
+    fn child_workflow(
+        &self,
+        workflow_id: &str,
+    ) -> Result<Option<(Workflow, Option<String>)>, String>;
+
+    /// Why the executor cannot run the node
+    fn unsupported(&self, _node: &WorkflowNode) -> Option<String> {
+        None
+    }
+}
+
+/// Resolve what a run of `workflow` would do without running anything.
+/// Fails like the run would on invalid parameters or dependencies; problems of single
+/// nodes are reported in their `error`.
+pub fn plan_workflow<E: PlanExecutor>(
+    executor: &E,
+    workflow: &Workflow,
+    default_cwd: Option<&str>,
+    params: &HashMap<String, serde_json::Value>,
+) -> Result<WorkflowPlan, String> {
+    let secrets: HashMap<String, String> = executor
+        .secret_names()?
+        .into_iter()
+        .map(|name| (name, MASKED_SECRET.to_string()))
+        .collect();
+    plan_in_chain(
+        executor,
+        workflow,
+        default_cwd,
+        params,
+        &secrets,
+        std::slice::from_ref(&workflow.id),
+    )
+}
+
+/// Plan a workflow started by the workflows in `chain` (the workflow itself included)
+fn plan_in_chain<E: PlanExecutor>(
+    executor: &E,
+    workflow: &Workflow,
+    default_cwd: Option<&str>,
+    params: &HashMap<String, serde_json::Value>,
+    secrets: &HashMap<String, String>,
+    chain: &[String],
+) -> Result<WorkflowPlan, String> {
+    let parameters = resolve_parameters(&workflow.parameters, params)?;
+    let dag = WorkflowDag::new(&workflow.nodes)?;
+    let ctx = InterpolationContext {
+        params: parameters.clone(),
+        ..Default::default()
+    };
+
+    let mut nodes = Vec::new();
+    for node_id in dag.topological_order() {
+        let Some(node) = workflow.nodes.iter().find(|n| n.id == node_id) else {
+            continue;
+        };
+        let mut plan = NodePlan {
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            node_type: node.node_type.clone(),
+            depends_on: dag.dependencies_of(&node.id).to_vec(),
+            invocations: Vec::new(),
+            child_workflow: None,
+            notes: Vec::new(),
+            skipped: None,
+            error: None,
+        };
+        plan.error = match executor.unsupported(node) {
+            Some(reason) => Some(reason),
+            None => plan_node(
+                executor,
+                workflow,
+                node,
+                default_cwd,
+                &ctx,
+                secrets,
+                chain,
+                &mut plan,
+            )
+            .err(),
+        };
+        nodes.push(plan);
+    }
+
+    Ok(WorkflowPlan {
+        workflow_id: workflow.id.clone(),
+        workflow_name: workflow.name.clone(),
+        executor: executor.name().to_string(),
+        default_cwd: default_cwd.map(str::to_string),
+        parameters,
+        nodes,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn plan_node<E: PlanExecutor>(
+    executor: &E,
+    workflow: &Workflow,
+    node: &WorkflowNode,
+    default_cwd: Option<&str>,
+    ctx: &InterpolationContext,
+    secrets: &HashMap<String, String>,
+    chain: &[String],
+    plan: &mut NodePlan,
+) -> Result<(), String> {
+    if node.is_condition() {
+        let config = node
+            .get_condition_config()
+            .ok_or_else(|| "Invalid condition node config".to_string())?;
+        for (index, branch) in config.branches.iter().enumerate() {
+            plan.notes.push(format!(
+                "Branch '{}' runs: {}",
+                branch
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| format!("#{}", index + 1)),
+                describe_targets(&branch.targets)
+            ));
+        }
+        plan.notes.push(format!(
+            "Otherwise runs: {}",
+            describe_targets(&config.otherwise)
+        ));
+        return Ok(());
+    }
+
+    if node.is_approval() {
+        let config = node
+            .get_approval_config()
+            .ok_or_else(|| "Invalid approval node config".to_string())?;
+        plan.notes.push(match config.message {
+            Some(message) => format!("Waits for manual approval: {}", message),
+            None => "Waits for manual approval".to_string(),
+        });
+        if let Some(minutes) = config.timeout_minutes {
+            plan.notes
+                .push(format!("Rejected automatically after {} minutes", minutes));
+        }
+        return Ok(());
+    }
+
+    if node.is_http() {
+        let config = node
+            .get_http_config()
+            .ok_or_else(|| "Invalid HTTP node config".to_string())?;
+        let secret_ctx = InterpolationContext {
+            secrets: Some(secrets.clone()),
+            ..ctx.clone()
+        };
+        plan.notes.push(format!(
+            "{} {}",
+            config.method.to_uppercase(),
+            interpolate_for_plan(&config.url, &secret_ctx)?
+        ));
+        return Ok(());
+    }
+
+    if node.is_trigger_workflow() {
+        let config = node
+            .get_trigger_workflow_config()
+            .ok_or_else(|| "Invalid trigger-workflow node config".to_string())?;
+        if chain.contains(&config.target_workflow_id) {
+            return Err(format!(
+                "Workflow cycle: {} -> {}",
+                chain.join(" -> "),
+                config.target_workflow_id
+            ));
+        }
+        if chain.len() as u32 >= MAX_EXECUTION_DEPTH {
+            return Err(format!(
+                "Max recursion depth exceeded ({}/{})",
+                chain.len(),
+                MAX_EXECUTION_DEPTH
+            ));
+        }
+
+        let Some((child, child_cwd)) = executor.child_workflow(&config.target_workflow_id)? else {
+            plan.skipped = Some("Child workflows are not run by this executor".to_string());
+            return Ok(());
+        };
+        plan.notes.push(if config.wait_for_completion {
+            "Waits for the child workflow to finish".to_string()
+        } else {
+            "Starts the child workflow without waiting".to_string()
+        });
+
+        let mut child_chain = chain.to_vec();
+        child_chain.push(child.id.clone());
+        let child_plan = plan_in_chain(
+            executor,
+            &child,
+            child_cwd.as_deref(),
+            &HashMap::new(),
+            secrets,
+            &child_chain,
+        )?;
+        plan.child_workflow = Some(Box::new(child_plan));
+        return Ok(());
+    }
+
+    if !node.is_script() {
+        return Err(format!("Unknown node type '{}'", node.node_type));
+    }
+
+    let config = node
+        .get_script_config()
+        .ok_or_else(|| "Invalid script node config".to_string())?;
+    match &config.matrix {
+        Some(matrix) => {
+            let packages = if matrix.packages.is_empty() {
+                Vec::new()
+            } else {
+                let project_path = default_cwd
+                    .ok_or_else(|| "Matrix packages require a project directory".to_string())?;
+                executor.workspace_packages(project_path)?
+            };
+            let cells = expand_matrix(matrix, &packages)?;
+            plan.notes.push(format!(
+                "Runs {} matrix cells, {} at a time",
+                cells.len(),
+                matrix.max_parallel.unwrap_or(1).max(1)
+            ));
+            for cell in &cells {
+                plan.invocations.push(plan_invocation(
+                    executor,
+                    workflow,
+                    &config,
+                    default_cwd,
+                    ctx,
+                    secrets,
+                    Some(cell),
+                )?);
+            }
+        }
+        None => plan.invocations.push(plan_invocation(
+            executor,
+            workflow,
+            &config,
+            default_cwd,
+            ctx,
+            secrets,
+            None,
+        )?),
+    }
+
+    if config.retry.retries > 0 {
+        plan.notes
+            .push(format!("Retried up to {} times", config.retry.retries));
+    }
+    if !config.artifacts.is_empty() {
+        plan.notes.push(format!(
+            "Collects artifacts: {}",
+            config.artifacts.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Resolve the process of a script node (or one matrix cell) the way `execute_node` does
+fn plan_invocation<E: PlanExecutor>(
+    executor: &E,
+    workflow: &Workflow,
+    config: &ScriptNodeConfig,
+    default_cwd: Option<&str>,
+    ctx: &InterpolationContext,
+    secrets: &HashMap<String, String>,
+    cell: Option<&MatrixCell>,
+) -> Result<PlannedInvocation, String> {
+    let mut ctx = ctx.clone();
+    if let Some(cell) = cell {
+        ctx.matrix = cell.values.clone().into_iter().collect();
+    }
+    let command = interpolate_command_for_plan(&config.command, &ctx, config.shell)?;
+    let config_cwd = config
+        .cwd
+        .as_deref()
+        .map(|cwd| interpolate_for_plan(cwd, &ctx))
+        .transpose()?;
+
+    let secret_ctx = InterpolationContext {
+        secrets: Some(secrets.clone()),
+        ..ctx
+    };
+    let env = merge_env(&workflow.env, &config.env)
+        .into_iter()
+        .map(|(name, value)| {
+            interpolate_for_plan(&value, &secret_ctx)
+                .map(|value| (name.clone(), value))
+                .map_err(|e| format!("Environment variable '{}': {}", name, e))
+        })
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    // Matrix package > node config > default (project path)
+    let cwd = cell
+        .and_then(|cell| cell.cwd.clone())
+        .or(config_cwd)
+        .or_else(|| default_cwd.map(str::to_string));
+    let invocation = resolve_command(&command, config.shell)?;
+    let via_shell = invocation.via_shell;
+
+    if !via_shell && invocation.program == "rm" && executor.intercepts_rm() {
+        let args: Vec<&str> = invocation.args.iter().map(String::as_str).collect();
+        let trash_paths = trash_targets(&args, cwd.as_deref())
+            .into_iter()
+            .map(|(_, path)| path.display().to_string())
+            .collect();
+        return Ok(PlannedInvocation {
+            cell: cell.map(MatrixCell::label),
+            cwd,
+            env,
+            command,
+            program: invocation.program,
+            args: invocation.args,
+            via_shell,
+            trash_paths: Some(trash_paths),
+        });
+    }
+
+    let node_version = cell.and_then(|cell| cell.node_version.as_deref());
+    let (program, args) = executor.wrap_command(invocation, cwd.as_deref(), node_version)?;
+    Ok(PlannedInvocation {
+        cell: cell.map(MatrixCell::label),
+        cwd,
+        env,
+        command,
+        program,
+        args,
+        via_shell,
+        trash_paths: None,
+    })
+}
+
+fn describe_targets(targets: &[String]) -> String {
+    if targets.is_empty() {
+        "nothing".to_string()
+    } else {
+        targets.join(", ")
+    }
+}
+
+/// Operands of an intercepted `rm` (flags dropped) with the paths moved to the Trash:
+/// relative paths are resolved against `cwd` and `~` is expanded
+pub fn trash_targets(args: &[&str], cwd: Option<&str>) -> Vec<(String, PathBuf)> {
+    args.iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .map(|file| {
+            let path = if file.starts_with('~') {
+                PathBuf::from(shellexpand::tilde(file).to_string())
+            } else if file.starts_with('/') {
+                PathBuf::from(file)
+            } else {
+                match cwd {
+                    Some(base) => Path::new(base).join(file),
+                    None => PathBuf::from(file),
+                }
+            };
+            (file.to_string(), path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workflow::WorkflowParameter;
+
+    struct TestExecutor {
+        workflows: Vec<Workflow>,
+    }
+
+    impl PlanExecutor for TestExecutor {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn wrap_command(
+            &self,
+            invocation: CommandInvocation,
+            _cwd: Option<&str>,
+            node_version: Option<&str>,
+        ) -> Result<(String, Vec<String>), String> {
+            let mut args = vec!["run".to_string()];
+            if let Some(version) = node_version {
+                args.extend(["--node".to_string(), version.to_string()]);
+            }
+            args.push(invocation.program);
+            args.extend(invocation.args);
+            Ok(("volta".to_string(), args))
+        }
+
+        fn intercepts_rm(&self) -> bool {
+            true
+        }
+
+        fn secret_names(&self) -> Result<Vec<String>, String> {
+            Ok(vec!["NPM_TOKEN".to_string()])
+        }
+
+        fn workspace_packages(&self, _project_path: &str) -> Result<Vec<WorkspacePackage>, String> {
+            Ok(Vec::new())
+        }
+
+        fn child_workflow(
+            &self,
+            workflow_id: &str,
+        ) -> Result<Option<(Workflow, Option<String>)>, String> {
+            self.workflows
+                .iter()
+                .find(|w| w.id == workflow_id)
+                .map(|w| Some((w.clone(), Some("/child".to_string()))))
+                .ok_or_else(|| format!("Target workflow not found: {}", workflow_id))
+        }
+    }
+
+    fn script(id: &str, order: i32, config: serde_json::Value) -> WorkflowNode {
+        let mut node = WorkflowNode::new(id.to_string(), id.to_string(), String::new());
+        node.config = config;
+        node.order = order;
+        node
+    }
+
+    #[test]
+    fn test_plan_script_nodes() {
+        let mut workflow = Workflow::new("wf".to_string(), "Release".to_string());
+        workflow.parameters = vec![serde_json::from_value::<WorkflowParameter>(
+            serde_json::json!({ "name": "tag", "type": "string", "default": "latest" }),
+        )
+        .unwrap()];
+        workflow.env =
+            HashMap::from([("NPM_TOKEN".to_string(), "{{secrets.NPM_TOKEN}}".to_string())]);
+        workflow.nodes = vec![
+            script(
+                "clean",
+                0,
+                serde_json::json!({ "command": "rm -rf dist /tmp/cache" }),
+            ),
+            script(
+                "publish",
+                1,
+                serde_json::json!({
+                    "command": "npm publish --tag {{params.tag}} --otp {{steps.clean.output}}",
+                    "cwd": "/repo/packages/app"
+                }),
+            ),
+            script(
+                "missing",
+                2,
+                serde_json::json!({ "command": "echo {{params.other}}" }),
+            ),
+        ];
+
+        let executor = TestExecutor {
+            workflows: Vec::new(),
+        };
+        let plan = plan_workflow(&executor, &workflow, Some("/repo"), &HashMap::new()).unwrap();
+        assert_eq!(plan.executor, "test");
+        assert_eq!(plan.parameters["tag"], "latest");
+        let ids: Vec<&str> = plan.nodes.iter().map(|n| n.node_id.as_str()).collect();
+        assert_eq!(ids, vec!["clean", "publish", "missing"]);
+
+        // `rm` is moved to the Trash and never wrapped
+        let clean = &plan.nodes[0].invocations[0];
+        assert_eq!(clean.program, "rm");
+        let trash = clean.trash_paths.as_ref().unwrap();
+        assert_eq!(trash[0], "/repo/dist");
+        assert_eq!(trash[1], "/tmp/cache");
+        assert_eq!(clean.env["NPM_TOKEN"], MASKED_SECRET);
+
+        let publish = &plan.nodes[1];
+        assert_eq!(publish.depends_on, vec!["clean"]);
+        let invocation = &publish.invocations[0];
+        assert_eq!(invocation.cwd.as_deref(), Some("/repo/packages/app"));
+        assert_eq!(invocation.program, "volta");
+        assert_eq!(
+            invocation.args,
+            vec![
+                "run",
+                "npm",
+                "publish",
+                "--tag",
+                "latest",
+                "--otp",
+                "{{steps.clean.output}}"
+            ]
+        );
+
+        assert!(plan.nodes[2].error.as_ref().unwrap().contains("other"));
+        assert!(plan.has_errors());
+
+        let unknown = HashMap::from([("nope".to_string(), serde_json::json!(1))]);
+        assert!(plan_workflow(&executor, &workflow, None, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_plan_child_workflows() {
+        let mut child = Workflow::new("child".to_string(), "Build".to_string());
+        child.nodes = vec![script(
+            "build",
+            0,
+            serde_json::json!({ "command": "npm run build" }),
+        )];
+        let mut parent = Workflow::new("parent".to_string(), "Deploy".to_string());
+        parent.nodes = vec![WorkflowNode::new_trigger(
+            "trigger".to_string(),
+            "Build first".to_string(),
+            "child".to_string(),
+        )];
+
+        let mut executor = TestExecutor {
+            workflows: vec![child.clone(), parent.clone()],
+        };
+        let plan = plan_workflow(&executor, &parent, None, &HashMap::new()).unwrap();
+        let child_plan = plan.nodes[0].child_workflow.as_ref().unwrap();
+        assert_eq!(child_plan.default_cwd.as_deref(), Some("/child"));
+        assert_eq!(
+            child_plan.nodes[0].invocations[0].cwd.as_deref(),
+            Some("/child")
+        );
+        assert!(!plan.has_errors());
+
+        // A child that starts the parent again is a cycle
+        child.nodes.push(WorkflowNode::new_trigger(
+            "back".to_string(),
+            "Back".to_string(),
+            "parent".to_string(),
+        ));
+        executor.workflows = vec![child, parent.clone()];
+        let plan = plan_workflow(&executor, &parent, None, &HashMap::new()).unwrap();
+        let child_plan = plan.nodes[0].child_workflow.as_ref().unwrap();
+        assert!(child_plan.nodes[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("cycle"));
+        assert!(plan.has_errors());
+    }
+}
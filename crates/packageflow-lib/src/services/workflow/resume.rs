@@ -0,0 +1,253 @@
+// Workflow Resume
+// Planning which nodes of a past execution to run again ("retry from X", "run X..Y")
+// and which stored node results to reuse
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::models::execution::{NodeResult, NodeStatus, SkipReason};
+
+use super::dag::WorkflowDag;
+
+/// Nodes to run again and results carried over from the original execution
+#[derive(Debug, Clone)]
+pub struct ResumePlan {
+    /// Nodes that run again, in topological order
+    pub run_node_ids: Vec<String>,
+    /// Results of every other node: completed and condition-skipped results are reused
+    /// as-is, anything else is recorded as skipped so it does not run
+    pub reused_results: Vec<NodeResult>,
+}
+
+/// Plan re-running `from_node_id` and everything after it, or only the nodes between
+/// `from_node_id` and `to_node_id` (both included) when a range end is given.
+/// A node can only run again if each dependency outside the run either completed or was
+/// skipped by a condition in the original execution; a node skipped because of a failure
+/// or a cancellation has no output to reuse.
+pub fn plan_resume(
+    dag: &WorkflowDag,
+    previous: &[NodeResult],
+    from_node_id: &str,
+    to_node_id: Option<&str>,
+) -> Result<ResumePlan, String> {
+    for id in std::iter::once(from_node_id).chain(to_node_id) {
+        if !dag.node_ids().iter().any(|n| n == id) {
+            return Err(format!("Node '{}' is not part of the workflow", id));
+        }
+    }
+
+    let mut run: HashSet<String> = reachable(from_node_id, |id| dag.dependents_of(id));
+    if let Some(to) = to_node_id {
+        if !run.contains(to) {
+            return Err(format!(
+                "Node '{}' does not run after node '{}'",
+                to, from_node_id
+            ));
+        }
+        let upstream = reachable(to, |id| dag.dependencies_of(id));
+        run.retain(|id| upstream.contains(id));
+    }
+
+    let previous: HashMap<&str, &NodeResult> = previous
+        .iter()
+        .filter(|r| is_reusable(r))
+        .map(|r| (r.node_id.as_str(), r))
+        .collect();
+
+    let run_node_ids: Vec<String> = dag
+        .topological_order()
+        .into_iter()
+        .filter(|id| run.contains(id))
+        .collect();
+
+    for id in &run_node_ids {
+        let deps = dag.dependencies_of(id);
+        let mut any_ran = deps.is_empty();
+        for dep in deps.iter().filter(|dep| !run.contains(*dep)) {
+            match previous.get(dep.as_str()).map(|r| &r.status) {
+                Some(NodeStatus::Completed) => any_ran = true,
+                Some(_) => {}
+                None => {
+                    return Err(format!(
+                        "Node '{}' depends on node '{}', which did not complete in the original execution",
+                        id, dep
+                    ))
+                }
+            }
+        }
+        if deps.iter().any(|dep| run.contains(dep)) {
+            any_ran = true;
+        }
+        if !any_ran {
+            return Err(format!(
+                "Node '{}' cannot run: none of its dependencies ran in the original execution",
+                id
+            ));
+        }
+    }
+
+    let reused_results = dag
+        .node_ids()
+        .iter()
+        .filter(|id| !run.contains(*id))
+        .map(|id| match previous.get(id.as_str()) {
+            Some(result) => (*result).clone(),
+            None => {
+                let mut result = NodeResult::new(id.clone());
+                result.skip(SkipReason::NotInRun);
+                result.error_message = Some("Not part of this run".to_string());
+                result
+            }
+        })
+        .collect();
+
+    Ok(ResumePlan {
+        run_node_ids,
+        reused_results,
+    })
+}
+
+/// A result that stands in for its node in a resumed execution: the node completed,
+/// or a condition skipped it
+fn is_reusable(result: &NodeResult) -> bool {
+    match result.status {
+        NodeStatus::Completed => true,
+        NodeStatus::Skipped => result.skip_reason == Some(SkipReason::Condition),
+        _ => false,
+    }
+}
+
+/// `start` and every node reachable from it through `next`
+fn reachable<'a>(start: &str, next: impl Fn(&str) -> &'a [String]) -> HashSet<String> {
+    let mut seen: HashSet<String> = HashSet::from([start.to_string()]);
+    let mut queue: VecDeque<String> = VecDeque::from([start.to_string()]);
+    while let Some(current) = queue.pop_front() {
+        for id in next(&current) {
+            if seen.insert(id.clone()) {
+                queue.push_back(id.clone());
+            }
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::workflow::dag::DagNodeSpec;
+
+    fn spec(id: &str, order: i32, deps: &[&str]) -> DagNodeSpec {
+        DagNodeSpec {
+            id: id.to_string(),
+            name: id.to_string(),
+            order,
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            branch_targets: Vec::new(),
+        }
+    }
+
+    fn result(id: &str, status: NodeStatus) -> NodeResult {
+        let mut result = NodeResult::new(id.to_string());
+        result.status = status;
+        result
+    }
+
+    /// install -> (lint, test) -> build -> publish
+    fn ci_dag() -> WorkflowDag {
+        WorkflowDag::from_specs(vec![
+            spec("install", 0, &[]),
+            spec("lint", 1, &["install"]),
+            spec("test", 2, &["install"]),
+            spec("build", 3, &["lint", "test"]),
+            spec("publish", 4, &["build"]),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_from_failed_node() {
+        let previous = vec![
+            result("install", NodeStatus::Completed),
+            result("lint", NodeStatus::Completed),
+            result("test", NodeStatus::Failed),
+            result("build", NodeStatus::Skipped),
+            result("publish", NodeStatus::Skipped),
+        ];
+        let plan = plan_resume(&ci_dag(), &previous, "test", None).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["test", "build", "publish"]);
+        let reused: Vec<&str> = plan
+            .reused_results
+            .iter()
+            .map(|r| r.node_id.as_str())
+            .collect();
+        assert_eq!(reused, vec!["install", "lint"]);
+        assert!(plan
+            .reused_results
+            .iter()
+            .all(|r| r.status == NodeStatus::Completed));
+
+        // build also needs lint, which failed
+        let previous = vec![
+            result("install", NodeStatus::Completed),
+            result("lint", NodeStatus::Failed),
+            result("test", NodeStatus::Failed),
+        ];
+        let err = plan_resume(&ci_dag(), &previous, "test", None).unwrap_err();
+        assert!(err.contains("'build' depends on node 'lint'"));
+    }
+
+    #[test]
+    fn test_run_node_range() {
+        let previous: Vec<NodeResult> = ["install", "lint", "test", "build", "publish"]
+            .iter()
+            .map(|id| result(id, NodeStatus::Completed))
+            .collect();
+        let plan = plan_resume(&ci_dag(), &previous, "install", Some("build")).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["install", "lint", "test", "build"]);
+        assert_eq!(plan.reused_results.len(), 1);
+        assert_eq!(plan.reused_results[0].node_id, "publish");
+
+        let plan = plan_resume(&ci_dag(), &previous, "lint", Some("build")).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["lint", "build"]);
+
+        assert!(plan_resume(&ci_dag(), &previous, "lint", Some("test")).is_err());
+        assert!(plan_resume(&ci_dag(), &previous, "deploy", None).is_err());
+    }
+
+    #[test]
+    fn test_only_condition_skips_are_reused() {
+        let skipped = |id: &str, reason: SkipReason| {
+            let mut result = NodeResult::new(id.to_string());
+            result.skip(reason);
+            result
+        };
+        let mut previous = vec![
+            result("install", NodeStatus::Completed),
+            skipped("lint", SkipReason::Condition),
+            result("test", NodeStatus::Completed),
+            result("build", NodeStatus::Completed),
+            result("publish", NodeStatus::Failed),
+        ];
+        let plan = plan_resume(&ci_dag(), &previous, "publish", None).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["publish"]);
+        let plan = plan_resume(&ci_dag(), &previous, "build", None).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["build", "publish"]);
+        assert!(plan
+            .reused_results
+            .iter()
+            .any(|r| r.node_id == "lint" && r.skip_reason == Some(SkipReason::Condition)));
+
+        // lint was skipped because install failed: build has nothing to build on
+        previous[1] = skipped("lint", SkipReason::DependencyFailed);
+        let err = plan_resume(&ci_dag(), &previous, "build", None).unwrap_err();
+        assert!(err.contains("'build' depends on node 'lint'"));
+
+        let plan = plan_resume(&ci_dag(), &previous, "lint", None).unwrap();
+        assert_eq!(plan.run_node_ids, vec!["lint", "build", "publish"]);
+        let test = plan
+            .reused_results
+            .iter()
+            .find(|r| r.node_id == "test")
+            .unwrap();
+        assert_eq!(test.status, NodeStatus::Completed);
+    }
+}
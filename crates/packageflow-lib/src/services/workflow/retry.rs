@@ -0,0 +1,169 @@
+// Workflow Retry
+// Decides whether a failed node attempt is retried and how long to wait before it
+
+use std::time::Duration;
+
+use regex::Regex;
+
+use crate::models::workflow::{RetryBackoff, RetryOn, RetryPolicy};
+
+/// Delay before a retry when the policy does not set `retryDelayMs`
+pub const DEFAULT_RETRY_DELAY_MS: u64 = 1_000;
+
+/// Upper bound for a single retry delay (exponential backoff is capped here)
+pub const MAX_RETRY_DELAY_MS: u64 = 300_000;
+
+/// Validate patterns of a retry policy before it is saved or run
+pub fn validate_retry_policy(policy: &RetryPolicy) -> Result<(), String> {
+    for retry_on in &policy.retry_on {
+        if let RetryOn::OutputMatches { pattern } = retry_on {
+            Regex::new(pattern)
+                .map_err(|e| format!("Invalid retry output pattern '{}': {}", pattern, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a failed attempt should be retried.
+/// `attempt` is the 1-based number of the attempt that just failed; `exit_code` is None
+/// when the process could not be run at all.
+pub fn should_retry(
+    policy: &RetryPolicy,
+    attempt: u32,
+    exit_code: Option<i32>,
+    output: &str,
+) -> bool {
+    if attempt > policy.retries {
+        return false;
+    }
+
+    if policy.retry_on.is_empty() {
+        return true;
+    }
+
+    policy.retry_on.iter().any(|retry_on| match retry_on {
+        RetryOn::ExitCode { codes } => exit_code.map(|c| codes.contains(&c)).unwrap_or(false),
+        RetryOn::OutputMatches { pattern } => Regex::new(pattern)
+            .map(|re| re.is_match(output))
+            .unwrap_or(false),
+    })
+}
+
+/// `retryOn` output patterns of a policy (invalid ones are ignored, as in `should_retry`).
+/// Matching each line as it streams means a long output need not be kept: the lines
+/// that matched are enough for `should_retry`.
+pub fn retry_output_patterns(policy: &RetryPolicy) -> Vec<Regex> {
+    policy
+        .retry_on
+        .iter()
+        .filter_map(|retry_on| match retry_on {
+            RetryOn::OutputMatches { pattern } => Regex::new(pattern).ok(),
+            RetryOn::ExitCode { .. } => None,
+        })
+        .collect()
+}
+
+/// Delay before retry number `retry` (1 = first retry)
+pub fn retry_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let base = policy.retry_delay_ms.unwrap_or(DEFAULT_RETRY_DELAY_MS);
+    let delay = match policy.retry_backoff {
+        Some(RetryBackoff::Exponential) => {
+            let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+            base.saturating_mul(factor)
+        }
+        Some(RetryBackoff::Fixed) | None => base,
+    };
+    Duration::from_millis(delay.min(MAX_RETRY_DELAY_MS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workflow::ScriptNodeConfig;
+
+    #[test]
+    fn test_retry_policy_from_script_config() {
+        let config: ScriptNodeConfig = serde_json::from_value(serde_json::json!({
+            "command": "pnpm install",
+            "retries": 2,
+            "retryDelayMs": 500,
+            "retryBackoff": "exponential",
+            "retryOn": [
+                { "kind": "exitCode", "codes": [1] },
+                { "kind": "outputMatches", "pattern": "ECONNRESET|ETIMEDOUT" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(config.command, "pnpm install");
+        assert_eq!(config.retry.retries, 2);
+        assert_eq!(config.retry.retry_backoff, Some(RetryBackoff::Exponential));
+        assert!(validate_retry_policy(&config.retry).is_ok());
+
+        // Existing configs without retry fields never retry
+        let plain: ScriptNodeConfig =
+            serde_json::from_value(serde_json::json!({ "command": "pnpm build" })).unwrap();
+        assert_eq!(plain.retry, RetryPolicy::default());
+        assert!(!should_retry(&plain.retry, 1, Some(1), ""));
+    }
+
+    #[test]
+    fn test_should_retry_respects_limit_and_filters() {
+        let policy = RetryPolicy {
+            retries: 2,
+            retry_on: vec![
+                RetryOn::ExitCode { codes: vec![137] },
+                RetryOn::OutputMatches {
+                    pattern: "ECONNRESET".to_string(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert!(should_retry(&policy, 1, Some(137), ""));
+        assert!(should_retry(
+            &policy,
+            2,
+            Some(1),
+            "npm ERR! network ECONNRESET"
+        ));
+        assert!(!should_retry(&policy, 1, Some(1), "Type error"));
+        assert!(!should_retry(&policy, 3, Some(137), ""));
+
+        let patterns = retry_output_patterns(&policy);
+        assert_eq!(patterns.len(), 1);
+        assert!(patterns[0].is_match("npm ERR! network ECONNRESET"));
+
+        let any_failure = RetryPolicy {
+            retries: 1,
+            ..Default::default()
+        };
+        assert!(should_retry(&any_failure, 1, None, ""));
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        let fixed = RetryPolicy {
+            retries: 3,
+            retry_delay_ms: Some(200),
+            ..Default::default()
+        };
+        assert_eq!(retry_delay(&fixed, 3), Duration::from_millis(200));
+
+        let exponential = RetryPolicy {
+            retry_backoff: Some(RetryBackoff::Exponential),
+            ..fixed.clone()
+        };
+        assert_eq!(retry_delay(&exponential, 1), Duration::from_millis(200));
+        assert_eq!(retry_delay(&exponential, 3), Duration::from_millis(800));
+        assert_eq!(
+            retry_delay(&exponential, 40),
+            Duration::from_millis(MAX_RETRY_DELAY_MS)
+        );
+
+        assert_eq!(
+            retry_delay(&RetryPolicy::default(), 1),
+            Duration::from_millis(DEFAULT_RETRY_DELAY_MS)
+        );
+    }
+}
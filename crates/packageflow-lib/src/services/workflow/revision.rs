@@ -0,0 +1,224 @@
+// Workflow Revisions
+// Compares saved revisions of a workflow and restores old ones
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::{Map, Value};
+
+use crate::models::{
+    Workflow, WorkflowFieldChange, WorkflowNode, WorkflowNodeChange, WorkflowRevision,
+    WorkflowRevisionDiff,
+};
+
+/// Workflow fields that change without the workflow being edited
+const VOLATILE_FIELDS: &[&str] = &["createdAt", "updatedAt", "lastExecutedAt"];
+
+/// The parts of a workflow a revision tracks (everything except timestamps)
+pub fn workflow_definition(workflow: &Workflow) -> Value {
+    let mut value = serde_json::to_value(workflow).unwrap_or_default();
+    if let Some(fields) = value.as_object_mut() {
+        for field in VOLATILE_FIELDS {
+            fields.remove(*field);
+        }
+    }
+    value
+}
+
+/// Whether two workflows only differ in their timestamps
+pub fn is_same_definition(a: &Workflow, b: &Workflow) -> bool {
+    workflow_definition(a) == workflow_definition(b)
+}
+
+/// Changed fields between two JSON objects, sorted by name
+fn field_changes(
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+    skip: &[&str],
+    prefix: &str,
+) -> Vec<WorkflowFieldChange> {
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| !skip.contains(&key.as_str()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| WorkflowFieldChange {
+            field: format!("{}{}", prefix, key),
+            before: before.get(key).cloned().unwrap_or(Value::Null),
+            after: after.get(key).cloned().unwrap_or(Value::Null),
+        })
+        .collect()
+}
+
+/// Changed fields of a node; config entries are compared one by one (`config.<key>`)
+fn node_changes(before: &WorkflowNode, after: &WorkflowNode) -> Vec<WorkflowFieldChange> {
+    let to_object = |node: &WorkflowNode| match serde_json::to_value(node) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    let mut changes = field_changes(&to_object(before), &to_object(after), &["id", "config"], "");
+
+    match (before.config.as_object(), after.config.as_object()) {
+        (Some(before_config), Some(after_config)) => {
+            changes.extend(field_changes(before_config, after_config, &[], "config."));
+        }
+        _ if before.config != after.config => changes.push(WorkflowFieldChange {
+            field: "config".to_string(),
+            before: before.config.clone(),
+            after: after.config.clone(),
+        }),
+        _ => {}
+    }
+    changes
+}
+
+/// Compare two revisions of a workflow
+pub fn diff_revisions(from: &WorkflowRevision, to: &WorkflowRevision) -> WorkflowRevisionDiff {
+    let to_object = |workflow: &Workflow| match workflow_definition(workflow) {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    };
+    let changes = field_changes(
+        &to_object(&from.workflow),
+        &to_object(&to.workflow),
+        &["id", "nodes"],
+        "",
+    );
+
+    let old_nodes: HashMap<&str, &WorkflowNode> = from
+        .workflow
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+    let mut diff = WorkflowRevisionDiff {
+        workflow_id: to.workflow_id.clone(),
+        from_revision: from.revision,
+        to_revision: to.revision,
+        changes,
+        added_nodes: Vec::new(),
+        removed_nodes: Vec::new(),
+        changed_nodes: Vec::new(),
+    };
+    for node in &to.workflow.nodes {
+        match old_nodes.get(node.id.as_str()) {
+            None => diff.added_nodes.push(node.clone()),
+            Some(old) => {
+                let changes = node_changes(old, node);
+                if !changes.is_empty() {
+                    diff.changed_nodes.push(WorkflowNodeChange {
+                        node_id: node.id.clone(),
+                        node_name: node.name.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed_nodes = from
+        .workflow
+        .nodes
+        .iter()
+        .filter(|old| !to.workflow.nodes.iter().any(|node| node.id == old.id))
+        .cloned()
+        .collect();
+    diff
+}
+
+/// Workflow to save when rolling back to `revision`: its definition with the identity
+/// and run timestamps of the current workflow
+pub fn restore_revision(current: &Workflow, revision: &WorkflowRevision) -> Workflow {
+    Workflow {
+        id: current.id.clone(),
+        created_at: current.created_at.clone(),
+        updated_at: chrono::Utc::now().to_rfc3339(),
+        last_executed_at: current.last_executed_at.clone(),
+        ..revision.workflow.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RevisionSource;
+
+    fn revision(number: u32, workflow: Workflow) -> WorkflowRevision {
+        WorkflowRevision {
+            workflow_id: workflow.id.clone(),
+            revision: number,
+            source: RevisionSource::Ui,
+            summary: None,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            workflow,
+        }
+    }
+
+    #[test]
+    fn test_same_definition_ignores_timestamps() {
+        let workflow = Workflow::new("wf-1".to_string(), "Build".to_string());
+        let mut touched = workflow.clone();
+        touched.updated_at = "2030-01-01T00:00:00Z".to_string();
+        touched.last_executed_at = Some("2030-01-01T00:00:00Z".to_string());
+        assert!(is_same_definition(&workflow, &touched));
+
+        touched.description = Some("CI build".to_string());
+        assert!(!is_same_definition(&workflow, &touched));
+    }
+
+    #[test]
+    fn test_diff_revisions() {
+        let mut old = Workflow::new("wf-1".to_string(), "Build".to_string());
+        old.nodes = vec![
+            WorkflowNode::new("a".to_string(), "Install".to_string(), "npm ci".to_string()),
+            WorkflowNode::new(
+                "b".to_string(),
+                "Lint".to_string(),
+                "npm run lint".to_string(),
+            ),
+        ];
+        let mut new = old.clone();
+        new.name = "Build and test".to_string();
+        new.nodes[0].config["command"] = Value::String("pnpm install".to_string());
+        new.nodes.remove(1);
+        new.nodes.push(WorkflowNode::new(
+            "c".to_string(),
+            "Test".to_string(),
+            "npm test".to_string(),
+        ));
+
+        let diff = diff_revisions(&revision(1, old), &revision(2, new));
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].field, "name");
+        assert_eq!(diff.changes[0].before, Value::String("Build".to_string()));
+        assert_eq!(diff.added_nodes[0].id, "c");
+        assert_eq!(diff.removed_nodes[0].id, "b");
+        assert_eq!(
+            diff.changed_nodes,
+            vec![WorkflowNodeChange {
+                node_id: "a".to_string(),
+                node_name: "Install".to_string(),
+                changes: vec![WorkflowFieldChange {
+                    field: "config.command".to_string(),
+                    before: Value::String("npm ci".to_string()),
+                    after: Value::String("pnpm install".to_string()),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_restore_revision_keeps_identity() {
+        let mut old = Workflow::new("wf-1".to_string(), "Build".to_string());
+        old.created_at = "2020-01-01T00:00:00Z".to_string();
+        let mut current = old.clone();
+        current.name = "Renamed".to_string();
+        current.created_at = "2024-01-01T00:00:00Z".to_string();
+        current.last_executed_at = Some("2024-06-01T00:00:00Z".to_string());
+
+        let restored = restore_revision(&current, &revision(1, old));
+        assert_eq!(restored.name, "Build");
+        assert_eq!(restored.created_at, current.created_at);
+        assert_eq!(restored.last_executed_at, current.last_executed_at);
+    }
+}
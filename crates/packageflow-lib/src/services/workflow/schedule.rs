@@ -0,0 +1,351 @@
+// Workflow Schedules
+// Cron expression parsing, next-run calculation and the missed-run policy
+// used by the background workflow scheduler
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+use crate::models::workflow::{MissedRunPolicy, WorkflowSchedule};
+
+/// Runs that are due for longer than this were missed (app closed or machine asleep)
+pub const MISSED_RUN_GRACE_SECS: i64 = 120;
+
+/// Upper bound when searching for the next run (e.g. `0 0 30 2 *` never matches)
+const MAX_SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parsed 5-field cron expression: minute hour day-of-month month day-of-week
+/// Supports `*`, lists, ranges, steps, month/day names and @hourly/@daily/@weekly/@monthly/@yearly
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    /// `*` day-of-month: only day-of-week restricts the day
+    any_day_of_month: bool,
+    /// `*` day-of-week: only day-of-month restricts the day
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            ));
+        }
+
+        let invalid = |e: String| format!("Invalid cron expression '{}': {}", expression, e);
+        let mut days_of_week = parse_field(fields[4], 0, 7, DAY_NAMES).map_err(invalid)?;
+        // 7 is an alias for Sunday
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, &[]).map_err(invalid)?,
+            hours: parse_field(fields[1], 0, 23, &[]).map_err(invalid)?,
+            days_of_month: parse_field(fields[2], 1, 31, &[]).map_err(invalid)?,
+            months: parse_field(fields[3], 1, 12, MONTH_NAMES).map_err(invalid)?,
+            days_of_week,
+            any_day_of_month: fields[2] == "*",
+            any_day_of_week: fields[4] == "*",
+        })
+    }
+
+    /// First run strictly after `after`, in the time zone of `after`.
+    /// Local times skipped by a DST change are not run; repeated ones run once.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit_year = start.year() + MAX_SEARCH_YEARS;
+        let mut candidate = start;
+
+        while candidate.year() <= limit_year {
+            if !self.months[candidate.month() as usize - 1] {
+                candidate = first_of_next_month(candidate.date())?;
+                continue;
+            }
+            if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.hours[candidate.hour() as usize] {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !self.minutes[candidate.minute() as usize] {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+
+            if let Some(next) = tz.from_local_datetime(&candidate).earliest() {
+                if next > *after {
+                    return Some(next);
+                }
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month[date.day() as usize - 1];
+        let dow = self.days_of_week[date.weekday().num_days_from_sunday() as usize];
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            // Standard cron: when both are restricted, either one matching is enough
+            (false, false) => dom || dow,
+        }
+    }
+}
+
+/// Parse one cron field into a lookup table indexed from 0 (`min` maps to index `min - offset`)
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Vec<bool>, String> {
+    let mut allowed = vec![false; (max - min + 1) as usize];
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("step must be greater than 0".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (
+                parse_value(a, min, max, names)?,
+                parse_value(b, min, max, names)?,
+            )
+        } else {
+            let value = parse_value(range, min, max, names)?;
+            // `5/15` means "from 5 to the end, every 15"
+            (value, if part.contains('/') { max } else { value })
+        };
+
+        if start > end {
+            return Err(format!("invalid range '{}'", range));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            allowed[(value - min) as usize] = true;
+        }
+    }
+
+    Ok(allowed)
+}
+
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let upper = value.to_ascii_uppercase();
+    if let Some(index) = names.iter().position(|n| *n == upper) {
+        // Month names start at 1, day names at 0
+        return Ok(index as u32 + min);
+    }
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("invalid value '{}'", value))?;
+    if parsed < min || parsed > max {
+        return Err(format!("value {} out of range {}-{}", parsed, min, max));
+    }
+    Ok(parsed)
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Validate a schedule before it is saved
+pub fn validate_schedule(schedule: &WorkflowSchedule) -> Result<(), String> {
+    let cron = CronSchedule::parse(&schedule.cron)?;
+    if cron.next_after(&chrono::Utc::now()).is_none() {
+        return Err(format!(
+            "Cron expression '{}' never matches a date",
+            schedule.cron
+        ));
+    }
+    Ok(())
+}
+
+/// What the scheduler should do with a schedule at a given time
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueRun<Tz: TimeZone> {
+    /// Start the workflow now
+    pub run: bool,
+    /// The due time passed while the app was not running (or asleep)
+    pub missed: bool,
+    /// Next time the schedule is due
+    pub next_run_at: Option<DateTime<Tz>>,
+}
+
+/// Decide whether a schedule whose stored next run is `next_run_at` runs at `now`.
+/// Missed runs are coalesced: at most one catch-up run, and only with `RunOnce`.
+pub fn evaluate_due_run<Tz: TimeZone>(
+    cron: &CronSchedule,
+    policy: MissedRunPolicy,
+    next_run_at: &DateTime<Tz>,
+    now: &DateTime<Tz>,
+) -> DueRun<Tz> {
+    if next_run_at > now {
+        return DueRun {
+            run: false,
+            missed: false,
+            next_run_at: Some(next_run_at.clone()),
+        };
+    }
+
+    let missed = now.clone() - next_run_at.clone() > Duration::seconds(MISSED_RUN_GRACE_SECS);
+    DueRun {
+        run: !missed || policy == MissedRunPolicy::RunOnce,
+        missed,
+        next_run_at: cron.next_after(now),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_fields_and_macros() {
+        assert!(CronSchedule::parse("*/15 9-17 * * MON-FRI").is_ok());
+        assert!(CronSchedule::parse("0 3 * * sun").is_ok());
+        assert!(CronSchedule::parse("30 2 1,15 JAN,jul *").is_ok());
+        assert_eq!(
+            CronSchedule::parse("@daily").unwrap(),
+            CronSchedule::parse("0 0 * * *").unwrap()
+        );
+        assert_eq!(
+            CronSchedule::parse("0 0 * * 7").unwrap(),
+            CronSchedule::parse("0 0 * * 0").unwrap()
+        );
+
+        assert!(CronSchedule::parse("0 0 * *").is_err());
+        assert!(CronSchedule::parse("60 0 * * *").is_err());
+        assert!(CronSchedule::parse("0 0 * * MON-FOO").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 5-1 * * *").is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        // Nightly audit at 03:00
+        let nightly = CronSchedule::parse("0 3 * * *").unwrap();
+        assert_eq!(
+            nightly.next_after(&at("2025-03-10T02:59:30Z")),
+            Some(at("2025-03-10T03:00:00Z"))
+        );
+        assert_eq!(
+            nightly.next_after(&at("2025-03-10T03:00:00Z")),
+            Some(at("2025-03-11T03:00:00Z"))
+        );
+
+        // Weekly dependency update, Monday 09:30 (2025-03-10 is a Monday)
+        let weekly = CronSchedule::parse("30 9 * * MON").unwrap();
+        assert_eq!(
+            weekly.next_after(&at("2025-03-10T10:00:00Z")),
+            Some(at("2025-03-17T09:30:00Z"))
+        );
+
+        // Steps and month rollover
+        let quarter = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            quarter.next_after(&at("2025-12-31T23:50:00Z")),
+            Some(at("2026-01-01T00:00:00Z"))
+        );
+
+        // Day-of-month OR day-of-week when both are restricted
+        let either = CronSchedule::parse("0 0 13 * FRI").unwrap();
+        assert_eq!(
+            either.next_after(&at("2025-03-10T00:00:00Z")),
+            Some(at("2025-03-13T00:00:00Z"))
+        );
+
+        // Leap day only
+        let leap = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(&at("2025-03-01T00:00:00Z")),
+            Some(at("2028-02-29T00:00:00Z"))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("0 0 31 2 *")
+                .unwrap()
+                .next_after(&at("2025-01-01T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_missed_run_policy() {
+        let nightly = CronSchedule::parse("0 3 * * *").unwrap();
+        let due = at("2025-03-10T03:00:00Z");
+
+        // Not due yet
+        let early = evaluate_due_run(
+            &nightly,
+            MissedRunPolicy::Skip,
+            &due,
+            &at("2025-03-10T02:00:00Z"),
+        );
+        assert!(!early.run);
+        assert_eq!(early.next_run_at, Some(due));
+
+        // Due now (within the grace period)
+        let on_time = evaluate_due_run(
+            &nightly,
+            MissedRunPolicy::Skip,
+            &due,
+            &at("2025-03-10T03:00:30Z"),
+        );
+        assert!(on_time.run && !on_time.missed);
+        assert_eq!(on_time.next_run_at, Some(at("2025-03-11T03:00:00Z")));
+
+        // Machine was asleep for two days: skip, or run once and continue from now
+        let woke = at("2025-03-12T08:00:00Z");
+        let skipped = evaluate_due_run(&nightly, MissedRunPolicy::Skip, &due, &woke);
+        assert!(!skipped.run && skipped.missed);
+        assert_eq!(skipped.next_run_at, Some(at("2025-03-13T03:00:00Z")));
+
+        let caught_up = evaluate_due_run(&nightly, MissedRunPolicy::RunOnce, &due, &woke);
+        assert!(caught_up.run && caught_up.missed);
+        assert_eq!(caught_up.next_run_at, Some(at("2025-03-13T03:00:00Z")));
+    }
+}
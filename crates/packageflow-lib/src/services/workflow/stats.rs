@@ -0,0 +1,375 @@
+// Workflow Statistics
+// Aggregating execution history into success rates, node timing percentiles,
+// daily trends and flaky node detection
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::DateTime;
+
+use crate::models::execution::{NodeResult, NodeStatus};
+use crate::models::workflow::WorkflowNode;
+use crate::models::workflow_stats::{
+    FlakyNode, NodeTimingStats, StatsTimelinePoint, WorkflowStats,
+};
+
+/// Number of latest node runs compared against the runs before them
+pub const RECENT_RUNS: usize = 5;
+
+/// Recent median / previous median ratio from which a node counts as slowed down
+pub const SLOWDOWN_RATIO: f64 = 1.5;
+
+/// One past execution of a workflow
+#[derive(Debug, Clone)]
+pub struct StatsSample {
+    pub execution_id: String,
+    /// "completed" | "failed" | "cancelled"
+    pub status: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub git_commit: Option<String>,
+    /// Final node results (empty when they were not recorded)
+    pub node_results: Vec<NodeResult>,
+}
+
+/// A timed run of one node
+struct NodeRun<'a> {
+    started_at: &'a str,
+    git_commit: Option<&'a str>,
+    passed: bool,
+    duration_ms: Option<u64>,
+}
+
+/// Compute the statistics of a workflow from its past executions.
+/// Approval nodes are left out (their duration is human wait time), as are node results a
+/// resumed execution carried over from the execution it resumed and nodes stopped by a cancel.
+pub fn compute_workflow_stats(
+    workflow_id: &str,
+    nodes: &[WorkflowNode],
+    samples: &[StatsSample],
+) -> WorkflowStats {
+    let mut samples: Vec<&StatsSample> = samples.iter().collect();
+    samples.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+
+    let count = |status: &str| samples.iter().filter(|s| s.status == status).count();
+    let completed_count = count("completed");
+    let failed_count = count("failed");
+    let completed_durations: Vec<u64> = samples
+        .iter()
+        .filter(|s| s.status == "completed")
+        .map(|s| s.duration_ms)
+        .collect();
+
+    let node_names: HashMap<&str, &str> = nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.name.as_str()))
+        .collect();
+    let skipped: Vec<&str> = nodes
+        .iter()
+        .filter(|n| n.is_approval())
+        .map(|n| n.id.as_str())
+        .collect();
+    let node_name = |id: &str| node_names.get(id).copied().unwrap_or(id).to_string();
+
+    // Node runs in execution order, grouped by node
+    let mut runs: BTreeMap<&str, Vec<NodeRun>> = BTreeMap::new();
+    for sample in &samples {
+        for result in &sample.node_results {
+            if skipped.contains(&result.node_id.as_str()) {
+                continue;
+            }
+            // Cancelling a run fails its running nodes; that is not a node failure
+            let passed = match result.status {
+                NodeStatus::Completed => true,
+                NodeStatus::Failed if sample.status != "cancelled" => false,
+                _ => continue,
+            };
+            if is_carried_over(result, sample) {
+                continue;
+            }
+            runs.entry(result.node_id.as_str())
+                .or_default()
+                .push(NodeRun {
+                    started_at: &sample.started_at,
+                    git_commit: sample.git_commit.as_deref(),
+                    passed,
+                    duration_ms: node_duration_ms(result),
+                });
+        }
+    }
+
+    let mut node_stats: Vec<NodeTimingStats> = runs
+        .iter()
+        .map(|(node_id, runs)| node_timing(node_id, node_name(node_id), runs))
+        .collect();
+    node_stats.sort_by(|a, b| b.p50_ms.cmp(&a.p50_ms).then(a.node_id.cmp(&b.node_id)));
+
+    let flaky_nodes = runs
+        .iter()
+        .flat_map(|(node_id, runs)| flaky_commits(node_id, &node_name(node_id), runs))
+        .collect();
+
+    WorkflowStats {
+        workflow_id: workflow_id.to_string(),
+        run_count: samples.len(),
+        completed_count,
+        failed_count,
+        cancelled_count: count("cancelled"),
+        success_rate: rate(completed_count, completed_count + failed_count),
+        p50_duration_ms: percentile(&completed_durations, 50.0),
+        p95_duration_ms: percentile(&completed_durations, 95.0),
+        nodes: node_stats,
+        timeline: timeline(&samples, &skipped),
+        flaky_nodes,
+    }
+}
+
+fn node_timing(node_id: &str, node_name: String, runs: &[NodeRun]) -> NodeTimingStats {
+    let failed_count = runs.iter().filter(|r| !r.passed).count();
+    let durations: Vec<u64> = runs
+        .iter()
+        .filter(|r| r.passed)
+        .filter_map(|r| r.duration_ms)
+        .collect();
+
+    let (recent_p50_ms, previous_p50_ms) = if durations.len() >= RECENT_RUNS * 2 {
+        let (previous, recent) = durations.split_at(durations.len() - RECENT_RUNS);
+        (percentile(recent, 50.0), percentile(previous, 50.0))
+    } else {
+        (None, None)
+    };
+    let slowed_down = match (recent_p50_ms, previous_p50_ms) {
+        (Some(recent), Some(previous)) if previous > 0 => {
+            recent as f64 / previous as f64 >= SLOWDOWN_RATIO
+        }
+        _ => false,
+    };
+
+    NodeTimingStats {
+        node_id: node_id.to_string(),
+        node_name,
+        run_count: runs.len(),
+        failed_count,
+        success_rate: rate(runs.len() - failed_count, runs.len()),
+        p50_ms: percentile(&durations, 50.0),
+        p95_ms: percentile(&durations, 95.0),
+        max_ms: durations.iter().max().copied(),
+        recent_p50_ms,
+        previous_p50_ms,
+        slowed_down,
+    }
+}
+
+/// Commits on which a node both passed and failed
+fn flaky_commits(node_id: &str, node_name: &str, runs: &[NodeRun]) -> Vec<FlakyNode> {
+    let mut by_commit: BTreeMap<&str, Vec<&NodeRun>> = BTreeMap::new();
+    for run in runs {
+        if let Some(commit) = run.git_commit {
+            by_commit.entry(commit).or_default().push(run);
+        }
+    }
+
+    by_commit
+        .into_iter()
+        .filter_map(|(commit, runs)| {
+            let flip_count = runs
+                .windows(2)
+                .filter(|w| w[0].passed != w[1].passed)
+                .count();
+            if flip_count == 0 {
+                return None;
+            }
+            let passed_count = runs.iter().filter(|r| r.passed).count();
+            Some(FlakyNode {
+                node_id: node_id.to_string(),
+                node_name: node_name.to_string(),
+                git_commit: commit.to_string(),
+                passed_count,
+                failed_count: runs.len() - passed_count,
+                flip_count,
+                last_run_at: runs.last().map(|r| r.started_at.to_string())?,
+            })
+        })
+        .collect()
+}
+
+/// Group runs by UTC day (samples must be sorted by start time)
+fn timeline(samples: &[&StatsSample], skipped: &[&str]) -> Vec<StatsTimelinePoint> {
+    let mut days: BTreeMap<String, Vec<&StatsSample>> = BTreeMap::new();
+    for sample in samples {
+        let date = DateTime::parse_from_rfc3339(&sample.started_at)
+            .map(|t| t.naive_utc().date().to_string())
+            .unwrap_or_else(|_| sample.started_at.chars().take(10).collect());
+        days.entry(date).or_default().push(sample);
+    }
+
+    days.into_iter()
+        .map(|(date, samples)| {
+            let completed = samples.iter().filter(|s| s.status == "completed").count();
+            let failed = samples.iter().filter(|s| s.status == "failed").count();
+            let durations: Vec<u64> = samples
+                .iter()
+                .filter(|s| s.status == "completed")
+                .map(|s| s.duration_ms)
+                .collect();
+
+            let mut node_durations: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+            for sample in &samples {
+                for result in &sample.node_results {
+                    if result.status != NodeStatus::Completed
+                        || is_carried_over(result, sample)
+                        || skipped.contains(&result.node_id.as_str())
+                    {
+                        continue;
+                    }
+                    if let Some(ms) = node_duration_ms(result) {
+                        node_durations
+                            .entry(result.node_id.as_str())
+                            .or_default()
+                            .push(ms);
+                    }
+                }
+            }
+            let slowest = node_durations
+                .iter()
+                .filter_map(|(id, durations)| percentile(durations, 50.0).map(|p50| (*id, p50)))
+                .max_by_key(|(_, p50)| *p50);
+
+            StatsTimelinePoint {
+                date,
+                run_count: samples.len(),
+                success_rate: rate(completed, completed + failed),
+                p50_duration_ms: percentile(&durations, 50.0),
+                slowest_node_id: slowest.map(|(id, _)| id.to_string()),
+                slowest_node_p50_ms: slowest.map(|(_, p50)| p50),
+            }
+        })
+        .collect()
+}
+
+/// Result a resumed execution reused from an earlier execution
+fn is_carried_over(result: &NodeResult, sample: &StatsSample) -> bool {
+    match (
+        DateTime::parse_from_rfc3339(&result.started_at),
+        DateTime::parse_from_rfc3339(&sample.started_at),
+    ) {
+        (Ok(node_started), Ok(run_started)) => node_started < run_started,
+        _ => false,
+    }
+}
+
+/// Duration of a finished node run
+fn node_duration_ms(result: &NodeResult) -> Option<u64> {
+    let started = DateTime::parse_from_rfc3339(&result.started_at).ok()?;
+    let finished = DateTime::parse_from_rfc3339(result.finished_at.as_deref()?).ok()?;
+    u64::try_from((finished - started).num_milliseconds()).ok()
+}
+
+/// Nearest-rank percentile (None for no values)
+pub fn percentile(values: &[u64], p: f64) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn rate(part: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| part as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_result(id: &str, status: NodeStatus, started_at: &str, secs: i64) -> NodeResult {
+        let started = DateTime::parse_from_rfc3339(started_at).unwrap();
+        let mut result = NodeResult::new(id.to_string());
+        result.status = status;
+        result.started_at = started.to_rfc3339();
+        result.finished_at = Some((started + chrono::Duration::seconds(secs)).to_rfc3339());
+        result
+    }
+
+    fn sample(day: u32, status: &str, commit: &str, build: (NodeStatus, i64)) -> StatsSample {
+        let start = format!("2025-03-{:02}T10:00:00Z", day);
+        StatsSample {
+            execution_id: format!("exec-{}", day),
+            status: status.to_string(),
+            started_at: start.clone(),
+            duration_ms: (build.1 as u64 + 10) * 1000,
+            git_commit: Some(commit.to_string()),
+            node_results: vec![
+                node_result("install", NodeStatus::Completed, &start, 10),
+                node_result("build", build.0, &start, build.1),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(percentile(&[7], 95.0), Some(7));
+        let values: Vec<u64> = (1..=20).rev().collect();
+        assert_eq!(percentile(&values, 50.0), Some(10));
+        assert_eq!(percentile(&values, 95.0), Some(19));
+        assert_eq!(percentile(&values, 100.0), Some(20));
+    }
+
+    #[test]
+    fn test_workflow_stats() {
+        let nodes = vec![
+            WorkflowNode::new("install".into(), "Install".into(), "pnpm i".into()),
+            WorkflowNode::new("build".into(), "Build".into(), "pnpm build".into()),
+        ];
+        // pnpm build doubles from 60s to 120s on day 6; on day 11 it flips on commit c3
+        let mut samples: Vec<StatsSample> = (1..=10)
+            .map(|day| {
+                let secs = if day <= 5 { 60 } else { 120 };
+                let commit = if day <= 5 { "c1" } else { "c2" };
+                sample(day, "completed", commit, (NodeStatus::Completed, secs))
+            })
+            .collect();
+        samples.push(sample(11, "failed", "c3", (NodeStatus::Failed, 5)));
+        samples.push(sample(12, "completed", "c3", (NodeStatus::Completed, 120)));
+        samples.push(sample(13, "cancelled", "c3", (NodeStatus::Failed, 1)));
+
+        let stats = compute_workflow_stats("wf-1", &nodes, &samples);
+        assert_eq!(stats.run_count, 13);
+        assert_eq!(
+            (
+                stats.completed_count,
+                stats.failed_count,
+                stats.cancelled_count
+            ),
+            (11, 1, 1)
+        );
+        assert_eq!(stats.success_rate, Some(11.0 / 12.0));
+
+        let build = &stats.nodes[0];
+        assert_eq!(build.node_name, "Build");
+        assert_eq!(build.run_count, 12);
+        assert_eq!(build.failed_count, 1);
+        assert_eq!(build.p50_ms, Some(120_000));
+        assert_eq!(build.previous_p50_ms, Some(60_000));
+        assert_eq!(build.recent_p50_ms, Some(120_000));
+        assert!(build.slowed_down);
+        assert!(!stats.nodes[1].slowed_down);
+
+        assert_eq!(stats.timeline.len(), 13);
+        assert_eq!(stats.timeline[0].date, "2025-03-01");
+        assert_eq!(stats.timeline[0].slowest_node_id.as_deref(), Some("build"));
+
+        assert_eq!(stats.flaky_nodes.len(), 1);
+        let flaky = &stats.flaky_nodes[0];
+        assert_eq!(
+            (flaky.node_id.as_str(), flaky.git_commit.as_str()),
+            ("build", "c3")
+        );
+        assert_eq!(
+            (flaky.passed_count, flaky.failed_count, flaky.flip_count),
+            (1, 1, 1)
+        );
+    }
+}
@@ -0,0 +1,555 @@
+// Workflow Templates
+// `{{...}}` placeholder substitution shared by webhook payloads and node commands,
+// workflow parameter resolution and `::set-output` parsing
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::models::execution::NodeResult;
+use crate::models::workflow::{ParameterType, ScriptShell, WorkflowParameter};
+use crate::utils::shell::quote_for_command;
+
+/// Matches `{{name}}`, `{{params.x}}`, `{{steps.<id>.output}}`, `{{matrix.node}}` (surrounding spaces allowed)
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([\w.\-]+)\s*\}\}").unwrap());
+
+/// Prefix of step output lines, e.g. `::set-output name=version::1.2.3`
+const SET_OUTPUT_PREFIX: &str = "::set-output name=";
+
+/// Maximum stdout kept as a step's `{{steps.<id>.output}}`
+pub const STEP_OUTPUT_MAX_BYTES: usize = 64 * 1024;
+
+/// Replace `{{name}}` placeholders using `resolve`; unresolved placeholders are kept as-is
+pub fn render_placeholders<F>(template: &str, mut resolve: F) -> String
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    PLACEHOLDER_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            resolve(&caps[1]).unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string()
+}
+
+/// Outputs of a finished step available to later steps
+#[derive(Debug, Clone, Default)]
+pub struct StepOutputs {
+    /// Captured output (trimmed)
+    pub output: String,
+    /// Values exported with `::set-output`
+    pub outputs: HashMap<String, String>,
+    /// `output` stopped at `STEP_OUTPUT_MAX_BYTES`
+    pub truncated: bool,
+}
+
+impl StepOutputs {
+    /// Append an output line as it is streamed; lines past `STEP_OUTPUT_MAX_BYTES`
+    /// are dropped and mark the output as truncated
+    pub fn push_output_line(&mut self, line: &str) {
+        if self.truncated {
+            return;
+        }
+        let separator = usize::from(!self.output.is_empty());
+        if self.output.len() + separator + line.len() > STEP_OUTPUT_MAX_BYTES {
+            self.truncated = true;
+            return;
+        }
+        if separator > 0 {
+            self.output.push('\n');
+        }
+        self.output.push_str(line);
+    }
+}
+
+impl From<&NodeResult> for StepOutputs {
+    fn from(result: &NodeResult) -> Self {
+        Self {
+            output: result.output.trim().to_string(),
+            outputs: result.outputs.clone(),
+            truncated: result.output_truncated,
+        }
+    }
+}
+
+/// Values available to `{{params.*}}`, `{{steps.*}}`, `{{matrix.*}}` and `{{secrets.*}}` placeholders
+#[derive(Debug, Clone, Default)]
+pub struct InterpolationContext {
+    pub params: HashMap<String, String>,
+    pub steps: HashMap<String, StepOutputs>,
+    /// Values of the matrix cell being run (empty outside matrix nodes)
+    pub matrix: HashMap<String, String>,
+    /// Decrypted secrets; only set when rendering env values (None rejects `{{secrets.*}}`)
+    pub secrets: Option<HashMap<String, String>>,
+}
+
+impl InterpolationContext {
+    /// Resolve a placeholder path. `Ok(None)` means the placeholder is not a
+    /// params/steps reference and is left untouched.
+    fn resolve(&self, path: &str) -> Result<Option<String>, String> {
+        if let Some(name) = path.strip_prefix("params.") {
+            return self
+                .params
+                .get(name)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| format!("Unknown parameter '{}'", name));
+        }
+
+        if let Some(name) = path.strip_prefix("matrix.") {
+            return self
+                .matrix
+                .get(name)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| format!("Unknown matrix value '{}' (not a matrix node?)", name));
+        }
+
+        if let Some(name) = path.strip_prefix("secrets.") {
+            return match &self.secrets {
+                Some(secrets) => secrets
+                    .get(name)
+                    .cloned()
+                    .map(Some)
+                    .ok_or_else(|| format!("Secret '{}' is not defined", name)),
+                None => Err(format!(
+                    "Secret '{}' can only be referenced from env values",
+                    name
+                )),
+            };
+        }
+
+        if let Some(rest) = path.strip_prefix("steps.") {
+            let (step_id, field) = rest
+                .split_once('.')
+                .ok_or_else(|| format!("Invalid step reference '{{{{{}}}}}'", path))?;
+            let step = self
+                .steps
+                .get(step_id)
+                .ok_or_else(|| format!("Step '{}' has no output (not run yet?)", step_id))?;
+
+            if field == "output" {
+                if step.truncated {
+                    return Err(format!(
+                        "Output of step '{}' is larger than {} bytes; export the value with ::set-output instead",
+                        step_id, STEP_OUTPUT_MAX_BYTES
+                    ));
+                }
+                return Ok(Some(step.output.clone()));
+            }
+            if let Some(key) = field.strip_prefix("outputs.") {
+                return step
+                    .outputs
+                    .get(key)
+                    .cloned()
+                    .map(Some)
+                    .ok_or_else(|| format!("Step '{}' did not set output '{}'", step_id, key));
+            }
+            return Err(format!("Invalid step reference '{{{{{}}}}}'", path));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Interpolate placeholders, passing each resolved value through `quote` along with the
+/// template text before it. With `keep_pending_steps`, references to steps that have not
+/// run are kept as-is.
+fn interpolate_with<Q>(
+    template: &str,
+    ctx: &InterpolationContext,
+    keep_pending_steps: bool,
+    mut quote: Q,
+) -> Result<String, String>
+where
+    Q: FnMut(&str, &str) -> Result<String, String>,
+{
+    let mut error: Option<String> = None;
+    let rendered = PLACEHOLDER_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            let path = &caps[1];
+            let pending_step = keep_pending_steps
+                && path
+                    .strip_prefix("steps.")
+                    .and_then(|rest| rest.split_once('.'))
+                    .is_some_and(|(step_id, _)| !ctx.steps.contains_key(step_id));
+            if pending_step {
+                return caps[0].to_string();
+            }
+
+            let preceding = &template[..caps.get(0).map_or(0, |m| m.start())];
+            let value = ctx.resolve(path).and_then(|value| {
+                value
+                    .map(|value| {
+                        quote(&value, preceding)
+                            .map_err(|e| format!("Cannot substitute '{}': {}", &caps[0], e))
+                    })
+                    .transpose()
+            });
+            match value {
+                Ok(Some(value)) => value,
+                Ok(None) => caps[0].to_string(),
+                Err(e) => {
+                    error.get_or_insert(e);
+                    caps[0].to_string()
+                }
+            }
+        })
+        .to_string();
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(rendered),
+    }
+}
+
+/// Interpolate `{{params.*}}`, `{{steps.*}}`, `{{matrix.*}}` and `{{secrets.*}}` placeholders.
+/// Fails on references that cannot be resolved so that a command never runs with
+/// a literal placeholder; other `{{...}}` text is left untouched.
+pub fn interpolate(template: &str, ctx: &InterpolationContext) -> Result<String, String> {
+    interpolate_with(template, ctx, false, |value, _| Ok(value.to_string()))
+}
+
+/// Like `interpolate`, but `{{steps.*}}` references to steps that have not run are kept
+/// as-is. Used by dry-run plans, where no step output exists yet.
+pub fn interpolate_for_plan(template: &str, ctx: &InterpolationContext) -> Result<String, String> {
+    interpolate_with(template, ctx, true, |value, _| Ok(value.to_string()))
+}
+
+/// Like `interpolate`, for a command line run by `shell`: each value is quoted so it
+/// reaches the command as one literal argument (webhook parameters are untrusted).
+pub fn interpolate_command(
+    template: &str,
+    ctx: &InterpolationContext,
+    shell: Option<ScriptShell>,
+) -> Result<String, String> {
+    interpolate_with(template, ctx, false, |value, preceding| {
+        quote_for_command(value, preceding, shell)
+    })
+}
+
+/// `interpolate_command` for dry-run plans (see `interpolate_for_plan`)
+pub fn interpolate_command_for_plan(
+    template: &str,
+    ctx: &InterpolationContext,
+    shell: Option<ScriptShell>,
+) -> Result<String, String> {
+    interpolate_with(template, ctx, true, |value, preceding| {
+        quote_for_command(value, preceding, shell)
+    })
+}
+
+/// Parse a `::set-output name=<key>::<value>` line
+pub fn parse_set_output(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix(SET_OUTPUT_PREFIX)?;
+    let (key, value) = rest.split_once("::")?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key.to_string(), value.to_string()))
+}
+
+/// Collect exported values from output lines (later lines win)
+pub fn collect_set_outputs<'a, I>(lines: I) -> HashMap<String, String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    lines.into_iter().filter_map(parse_set_output).collect()
+}
+
+/// Validate parameter declarations before a workflow is saved
+pub fn validate_parameters(declared: &[WorkflowParameter]) -> Result<(), String> {
+    for (index, param) in declared.iter().enumerate() {
+        if param.name.is_empty()
+            || !param
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "Invalid parameter name '{}': use letters, digits, '_' or '-'",
+                param.name
+            ));
+        }
+        if declared[..index].iter().any(|p| p.name == param.name) {
+            return Err(format!("Duplicate parameter '{}'", param.name));
+        }
+        if param.param_type == ParameterType::Choice && param.options.is_empty() {
+            return Err(format!(
+                "Choice parameter '{}' must list at least one option",
+                param.name
+            ));
+        }
+        if let Some(default) = &param.default {
+            coerce_parameter(param, default)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate provided values against the declared parameters and apply defaults.
+/// Returns the values as strings, ready for interpolation.
+pub fn resolve_parameters(
+    declared: &[WorkflowParameter],
+    provided: &HashMap<String, serde_json::Value>,
+) -> Result<HashMap<String, String>, String> {
+    if let Some(unknown) = provided
+        .keys()
+        .find(|name| !declared.iter().any(|p| &p.name == *name))
+    {
+        return Err(format!("Unknown parameter '{}'", unknown));
+    }
+
+    let mut resolved = HashMap::new();
+    for param in declared {
+        let value = match provided.get(&param.name).or(param.default.as_ref()) {
+            Some(value) => value,
+            None if param.required => {
+                return Err(format!("Missing required parameter '{}'", param.name));
+            }
+            None => continue,
+        };
+        resolved.insert(param.name.clone(), coerce_parameter(param, value)?);
+    }
+
+    Ok(resolved)
+}
+
+/// Convert a parameter value to its string form, checking it against the declared type
+fn coerce_parameter(
+    param: &WorkflowParameter,
+    value: &serde_json::Value,
+) -> Result<String, String> {
+    let invalid = || {
+        format!(
+            "Parameter '{}' expects a {} value, got {}",
+            param.name,
+            format!("{:?}", param.param_type).to_lowercase(),
+            value
+        )
+    };
+
+    match param.param_type {
+        ParameterType::String => match value {
+            serde_json::Value::String(s) => Ok(s.clone()),
+            serde_json::Value::Number(n) => Ok(n.to_string()),
+            serde_json::Value::Bool(b) => Ok(b.to_string()),
+            _ => Err(invalid()),
+        },
+        ParameterType::Number => match value {
+            serde_json::Value::Number(n) => Ok(n.to_string()),
+            serde_json::Value::String(s) if s.trim().parse::<f64>().is_ok() => {
+                Ok(s.trim().to_string())
+            }
+            _ => Err(invalid()),
+        },
+        ParameterType::Boolean => match value {
+            serde_json::Value::Bool(b) => Ok(b.to_string()),
+            serde_json::Value::String(s) if s == "true" || s == "false" => Ok(s.clone()),
+            _ => Err(invalid()),
+        },
+        ParameterType::Choice => {
+            let s = value.as_str().ok_or_else(invalid)?;
+            if param.options.iter().any(|o| o == s) {
+                Ok(s.to_string())
+            } else {
+                Err(format!(
+                    "Parameter '{}' must be one of: {}",
+                    param.name,
+                    param.options.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn param(name: &str, param_type: ParameterType) -> WorkflowParameter {
+        WorkflowParameter {
+            name: name.to_string(),
+            param_type,
+            description: None,
+            default: None,
+            required: false,
+            options: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders_keeps_unknown() {
+        let rendered =
+            render_placeholders(
+                "{{ status }} in {{duration}}ms {{other}}",
+                |name| match name {
+                    "status" => Some("completed".to_string()),
+                    "duration" => Some("42".to_string()),
+                    _ => None,
+                },
+            );
+        assert_eq!(rendered, "completed in 42ms {{other}}");
+    }
+
+    #[test]
+    fn test_interpolate_params_and_steps() {
+        let mut ctx = InterpolationContext::default();
+        ctx.params.insert("env".to_string(), "staging".to_string());
+        ctx.steps.insert(
+            "node-build".to_string(),
+            StepOutputs {
+                output: "built".to_string(),
+                outputs: HashMap::from([("version".to_string(), "1.2.3".to_string())]),
+                truncated: false,
+            },
+        );
+
+        assert_eq!(
+            interpolate(
+                "deploy --env {{params.env}} --tag {{steps.node-build.outputs.version}} # {{steps.node-build.output}}",
+                &ctx
+            )
+            .unwrap(),
+            "deploy --env staging --tag 1.2.3 # built"
+        );
+
+        // Shell-style braces are not placeholders
+        assert_eq!(interpolate("echo ${HOME}", &ctx).unwrap(), "echo ${HOME}");
+
+        assert!(interpolate("{{params.missing}}", &ctx).is_err());
+        assert!(interpolate("npm publish --token {{secrets.NPM_TOKEN}}", &ctx).is_err());
+        assert!(interpolate("{{steps.other.output}}", &ctx).is_err());
+        assert!(interpolate("{{steps.node-build.outputs.sha}}", &ctx).is_err());
+
+        assert!(interpolate("volta run --node {{matrix.node}} node -v", &ctx).is_err());
+        ctx.matrix.insert("node".to_string(), "20".to_string());
+        assert_eq!(
+            interpolate("volta run --node {{matrix.node}} node -v", &ctx).unwrap(),
+            "volta run --node 20 node -v"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_command_quotes_values() {
+        let mut ctx = InterpolationContext::default();
+        ctx.params
+            .insert("msg".to_string(), "x; rm -rf ~ $(id)".to_string());
+        ctx.params.insert("env".to_string(), "staging".to_string());
+        let bash = Some(ScriptShell::Bash);
+
+        assert_eq!(
+            interpolate_command("echo {{params.msg}} --env {{params.env}}", &ctx, bash).unwrap(),
+            "echo 'x; rm -rf ~ $(id)' --env staging"
+        );
+        assert_eq!(
+            interpolate_command("echo \"msg: {{params.msg}}\"", &ctx, bash).unwrap(),
+            "echo \"msg: x; rm -rf ~ \\$(id)\""
+        );
+        assert_eq!(
+            interpolate_command_for_plan("echo {{params.msg}} {{steps.build.output}}", &ctx, bash)
+                .unwrap(),
+            "echo 'x; rm -rf ~ $(id)' {{steps.build.output}}"
+        );
+        assert!(interpolate_command("echo \\{{params.msg}}", &ctx, bash).is_err());
+    }
+
+    #[test]
+    fn test_parse_set_output() {
+        assert_eq!(
+            parse_set_output("::set-output name=version::1.2.3"),
+            Some(("version".to_string(), "1.2.3".to_string()))
+        );
+        assert_eq!(
+            parse_set_output("  ::set-output name=url::https://a.b/c::d"),
+            Some(("url".to_string(), "https://a.b/c::d".to_string()))
+        );
+        assert_eq!(parse_set_output("echo ::set-output name=x::1"), None);
+        assert_eq!(parse_set_output("::set-output name=::1"), None);
+
+        let outputs = collect_set_outputs([
+            "building...",
+            "::set-output name=a::1",
+            "::set-output name=a::2",
+        ]);
+        assert_eq!(outputs.get("a"), Some(&"2".to_string()));
+    }
+
+    #[test]
+    fn test_step_output_is_capped() {
+        let mut step = StepOutputs::default();
+        step.push_output_line("first");
+        step.push_output_line("second");
+        assert_eq!(step.output, "first\nsecond");
+        assert!(!step.truncated);
+
+        step.push_output_line(&"x".repeat(STEP_OUTPUT_MAX_BYTES));
+        step.push_output_line("last");
+        assert_eq!(step.output, "first\nsecond");
+        assert!(step.truncated);
+
+        let mut ctx = InterpolationContext::default();
+        ctx.steps.insert("build".to_string(), step);
+        assert!(interpolate("{{steps.build.output}}", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_resolve_parameters() {
+        let mut env = param("env", ParameterType::Choice);
+        env.options = vec!["staging".to_string(), "production".to_string()];
+        env.default = Some(json!("staging"));
+        let mut retries = param("retries", ParameterType::Number);
+        retries.required = true;
+        let dry_run = param("dryRun", ParameterType::Boolean);
+        let declared = vec![env, retries, dry_run];
+
+        let resolved = resolve_parameters(
+            &declared,
+            &HashMap::from([("retries".to_string(), json!(3))]),
+        )
+        .unwrap();
+        assert_eq!(resolved.get("env"), Some(&"staging".to_string()));
+        assert_eq!(resolved.get("retries"), Some(&"3".to_string()));
+        assert!(!resolved.contains_key("dryRun"));
+
+        // Missing required, unknown, wrong type, invalid choice
+        assert!(resolve_parameters(&declared, &HashMap::new()).is_err());
+        assert!(resolve_parameters(
+            &declared,
+            &HashMap::from([
+                ("retries".to_string(), json!(1)),
+                ("other".to_string(), json!("x"))
+            ])
+        )
+        .is_err());
+        assert!(resolve_parameters(
+            &declared,
+            &HashMap::from([("retries".to_string(), json!("many"))])
+        )
+        .is_err());
+        assert!(resolve_parameters(
+            &declared,
+            &HashMap::from([
+                ("retries".to_string(), json!(1)),
+                ("env".to_string(), json!("dev"))
+            ])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_parameters() {
+        let mut env = param("env", ParameterType::Choice);
+        env.options = vec!["staging".to_string()];
+        assert!(validate_parameters(&[env.clone(), param("tag", ParameterType::String)]).is_ok());
+
+        assert!(validate_parameters(&[env.clone(), env.clone()]).is_err());
+        assert!(validate_parameters(&[param("a.b", ParameterType::String)]).is_err());
+        assert!(validate_parameters(&[param("mode", ParameterType::Choice)]).is_err());
+
+        env.default = Some(json!("production"));
+        assert!(validate_parameters(&[env]).is_err());
+    }
+}
@@ -8,4 +8,5 @@ pub mod path_resolver;
 pub mod plist_parser;
 pub mod schema;
 pub mod shared_store;
+pub mod shell;
 pub mod store;
@@ -4,7 +4,7 @@
 use rusqlite::{Connection, params};
 
 /// Current schema version
-pub const CURRENT_VERSION: i32 = 8;
+pub const CURRENT_VERSION: i32 = 23;
 
 /// Migration struct containing version and SQL statements
 struct Migration {
@@ -937,6 +937,274 @@ const MIGRATIONS: &[Migration] = &[
             END;
         "#,
     },
+    Migration {
+        version: 9,
+        description: "Workflow DAG execution - per-workflow max parallelism",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN max_parallelism INTEGER;
+        "#,
+    },
+    Migration {
+        version: 10,
+        description: "Workflow parameters",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN parameters TEXT;
+        "#,
+    },
+    Migration {
+        version: 11,
+        description: "Workflow environment variables and encrypted secrets",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN env TEXT;
+
+            CREATE TABLE IF NOT EXISTS workflow_secrets (
+                name TEXT PRIMARY KEY,
+                ciphertext TEXT NOT NULL,
+                nonce TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+        "#,
+    },
+    Migration {
+        version: 12,
+        description: "Workflow cron schedules and scheduler state",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN schedule TEXT;
+
+            CREATE TABLE IF NOT EXISTS workflow_schedule_state (
+                workflow_id TEXT PRIMARY KEY REFERENCES workflows(id) ON DELETE CASCADE,
+                cron TEXT NOT NULL,
+                next_run_at TEXT,
+                last_run_at TEXT,
+                last_execution_id TEXT,
+                last_error TEXT,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_workflow_schedule_state_next ON workflow_schedule_state(next_run_at);
+        "#,
+    },
+    Migration {
+        version: 13,
+        description: "Workflow file-change triggers",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN file_trigger TEXT;
+        "#,
+    },
+    Migration {
+        version: 14,
+        description: "Workflow git-event triggers",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN git_trigger TEXT;
+        "#,
+    },
+    Migration {
+        version: 15,
+        description: "Persisted execution logs with full-text index",
+        up: r#"
+            CREATE TABLE IF NOT EXISTS execution_logs (
+                execution_id TEXT PRIMARY KEY,
+                kind TEXT NOT NULL,
+                workflow_id TEXT,
+                name TEXT NOT NULL,
+                project_path TEXT,
+                status TEXT NOT NULL DEFAULT 'running',
+                started_at TEXT NOT NULL,
+                finished_at TEXT,
+                line_count INTEGER NOT NULL DEFAULT 0,
+                size_bytes INTEGER NOT NULL DEFAULT 0,
+                compressed_bytes INTEGER NOT NULL DEFAULT 0,
+                file_path TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_execution_logs_workflow ON execution_logs(workflow_id, started_at);
+            CREATE INDEX IF NOT EXISTS idx_execution_logs_started ON execution_logs(started_at);
+
+            -- Maps full-text index rows to log lines
+            CREATE TABLE IF NOT EXISTS execution_log_lines (
+                id INTEGER PRIMARY KEY,
+                execution_id TEXT NOT NULL REFERENCES execution_logs(execution_id) ON DELETE CASCADE,
+                line_no INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_execution_log_lines_execution ON execution_log_lines(execution_id);
+
+            -- Contentless: line text lives only in the compressed log files
+            CREATE VIRTUAL TABLE IF NOT EXISTS execution_log_fts USING fts5(
+                content,
+                content='',
+                contentless_delete=1,
+                tokenize='trigram'
+            );
+        "#,
+    },
+    Migration {
+        version: 16,
+        description: "Finished execution results for resumable workflows",
+        up: r#"
+            -- Final state (node results, parameters) of finished workflow executions
+            CREATE TABLE IF NOT EXISTS finished_executions (
+                id TEXT PRIMARY KEY,
+                workflow_id TEXT NOT NULL,
+                execution_data TEXT NOT NULL,
+                finished_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_finished_executions_workflow ON finished_executions(workflow_id, finished_at);
+
+            ALTER TABLE execution_history ADD COLUMN resumed_from_execution_id TEXT;
+        "#,
+    },
+    Migration {
+        version: 17,
+        description: "Workflow concurrency policies and the shared run queue",
+        up: r#"
+            ALTER TABLE workflows ADD COLUMN concurrency TEXT;
+
+            -- Running and queued top-level executions of the app and MCP server
+            CREATE TABLE IF NOT EXISTS active_executions (
+                execution_id TEXT PRIMARY KEY,
+                workflow_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                policy TEXT NOT NULL,
+                queued INTEGER NOT NULL DEFAULT 0,
+                registered_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_active_executions_registered ON active_executions(registered_at);
+        "#,
+    },
+    Migration {
+        version: 18,
+        description: "Execution artifacts",
+        up: r#"
+            -- Files collected from script node artifact globs (copies live in the artifact store)
+            CREATE TABLE IF NOT EXISTS execution_artifacts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                execution_id TEXT NOT NULL,
+                workflow_id TEXT NOT NULL,
+                node_id TEXT NOT NULL,
+                node_name TEXT NOT NULL,
+                cell_index INTEGER,
+                path TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                sha256 TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                collected_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_execution_artifacts_execution ON execution_artifacts(execution_id);
+        "#,
+    },
+    Migration {
+        version: 19,
+        description: "Workflow revisions",
+        up: r#"
+            -- Immutable copy of every saved change to a workflow
+            CREATE TABLE IF NOT EXISTS workflow_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                workflow_id TEXT NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+                revision INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                summary TEXT,
+                snapshot TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE(workflow_id, revision)
+            );
+
+            ALTER TABLE execution_history ADD COLUMN revision INTEGER;
+        "#,
+    },
+    Migration {
+        version: 20,
+        description: "Snapshot dependency graph",
+        up: r#"
+            -- Parent -> child edges of each snapshot's lockfile; a NULL parent is package.json
+            CREATE TABLE IF NOT EXISTS snapshot_dependency_edges (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                snapshot_id TEXT NOT NULL,
+                parent_name TEXT,
+                parent_version TEXT,
+                child_name TEXT NOT NULL,
+                child_version TEXT NOT NULL,
+                kind TEXT NOT NULL CHECK(kind IN ('prod', 'dev', 'optional', 'peer')),
+                FOREIGN KEY (snapshot_id) REFERENCES execution_snapshots(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_snapshot_edges_snapshot ON snapshot_dependency_edges(snapshot_id);
+
+            -- Cached diffs predate transitive change attribution
+            DELETE FROM snapshot_diff_cache;
+        "#,
+    },
+    Migration {
+        version: 21,
+        description: "Snapshot retention",
+        up: r#"
+            ALTER TABLE execution_snapshots ADD COLUMN is_pinned INTEGER DEFAULT 0;
+
+            -- Snapshot a deployment was built from; retention keeps it while the deploy succeeded
+            ALTER TABLE deployments ADD COLUMN snapshot_id TEXT;
+            CREATE INDEX IF NOT EXISTS idx_deployments_snapshot ON deployments(snapshot_id);
+
+            ALTER TABLE time_machine_settings ADD COLUMN retention_enabled INTEGER DEFAULT 0;
+            ALTER TABLE time_machine_settings ADD COLUMN retention_keep_recent INTEGER DEFAULT 10;
+            ALTER TABLE time_machine_settings ADD COLUMN retention_keep_daily INTEGER DEFAULT 7;
+            ALTER TABLE time_machine_settings ADD COLUMN retention_keep_weekly INTEGER DEFAULT 4;
+            ALTER TABLE time_machine_settings ADD COLUMN retention_keep_monthly INTEGER DEFAULT 6;
+            ALTER TABLE time_machine_settings ADD COLUMN retention_max_project_mb INTEGER;
+        "#,
+    },
+    Migration {
+        version: 22,
+        description: "Imported snapshot bundles",
+        up: r#"
+            -- Rebuild execution_snapshots to allow trigger_source 'import'.
+            -- Foreign keys are off so dropping the old table keeps dependent rows.
+            PRAGMA foreign_keys=OFF;
+
+            CREATE TABLE execution_snapshots_new (
+                id TEXT PRIMARY KEY,
+                project_path TEXT NOT NULL,
+                status TEXT NOT NULL CHECK(status IN ('capturing', 'completed', 'failed')),
+                trigger_source TEXT NOT NULL DEFAULT 'lockfile_change' CHECK(trigger_source IN ('lockfile_change', 'manual', 'import')),
+                lockfile_type TEXT CHECK(lockfile_type IN ('npm', 'pnpm', 'yarn', 'bun')),
+                lockfile_hash TEXT,
+                dependency_tree_hash TEXT,
+                package_json_hash TEXT,
+                total_dependencies INTEGER DEFAULT 0,
+                direct_dependencies INTEGER DEFAULT 0,
+                dev_dependencies INTEGER DEFAULT 0,
+                security_score INTEGER,
+                postinstall_count INTEGER DEFAULT 0,
+                storage_path TEXT,
+                compressed_size INTEGER,
+                error_message TEXT,
+                created_at TEXT NOT NULL,
+                is_pinned INTEGER DEFAULT 0
+            );
+
+            INSERT INTO execution_snapshots_new
+            SELECT
+                id, project_path, status, trigger_source, lockfile_type, lockfile_hash,
+                dependency_tree_hash, package_json_hash, total_dependencies, direct_dependencies,
+                dev_dependencies, security_score, postinstall_count, storage_path, compressed_size,
+                error_message, created_at, is_pinned
+            FROM execution_snapshots;
+
+            DROP TABLE execution_snapshots;
+            ALTER TABLE execution_snapshots_new RENAME TO execution_snapshots;
+
+            CREATE INDEX idx_snapshots_project ON execution_snapshots(project_path);
+            CREATE INDEX idx_snapshots_created ON execution_snapshots(created_at DESC);
+            CREATE INDEX idx_snapshots_hash ON execution_snapshots(project_path, lockfile_hash);
+            CREATE INDEX idx_snapshots_trigger ON execution_snapshots(trigger_source);
+
+            PRAGMA foreign_keys=ON;
+        "#,
+    },
+    Migration {
+        version: 23,
+        description: "Owner process of active executions",
+        up: r#"
+            -- Lets a crashed app or MCP server's runs be reaped from the run queue
+            ALTER TABLE active_executions ADD COLUMN owner_pid INTEGER;
+        "#,
+    },
 ];
 
 /// Run all pending migrations using Database wrapper
@@ -1053,6 +1321,18 @@ mod tests {
         assert!(table_exists(&conn, "snapshot_diff_cache").unwrap());
         // Lockfile validation config table (v7)
         assert!(table_exists(&conn, "lockfile_validation_config").unwrap());
+        // Execution logs (v15)
+        assert!(table_exists(&conn, "execution_logs").unwrap());
+        assert!(table_exists(&conn, "execution_log_lines").unwrap());
+        assert!(table_exists(&conn, "finished_executions").unwrap());
+        // Run queue (v17)
+        assert!(table_exists(&conn, "active_executions").unwrap());
+        // Artifacts (v18)
+        assert!(table_exists(&conn, "execution_artifacts").unwrap());
+        // Workflow revisions (v19)
+        assert!(table_exists(&conn, "workflow_revisions").unwrap());
+        // Snapshot dependency graph (v20)
+        assert!(table_exists(&conn, "snapshot_dependency_edges").unwrap());
     }
 
     #[test]
@@ -0,0 +1,412 @@
+// Shell command parsing
+// Shell-words splitting and shell selection shared by workflow script nodes,
+// execute_command and MCP script actions
+
+use crate::models::ScriptShell;
+
+/// Shell used when the command needs one and none is configured
+#[cfg(not(windows))]
+const DEFAULT_SHELL: &str = "/bin/sh";
+#[cfg(windows)]
+const DEFAULT_SHELL: &str = "cmd";
+
+/// Characters that only a shell can interpret when they appear unquoted
+const SHELL_METACHARACTERS: &[char] = &[
+    '|', '&', ';', '<', '>', '(', ')', '$', '`', '*', '?', '[', '\n',
+];
+
+/// How a command is spawned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandInvocation {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Whether `program` is a shell running the whole command line
+    pub via_shell: bool,
+}
+
+/// Split a command line into words following POSIX shell quoting rules, or on
+/// Windows the rules of direct execution where only double quotes group.
+/// Nothing is expanded: `$HOME` or `*` stay literal.
+pub fn split_shell_words(input: &str) -> Result<Vec<String>, String> {
+    let posix = uses_posix_quoting(None);
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\n' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' if posix => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated single quote in command".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if posix => match chars.next() {
+                            Some(c @ ('$' | '`' | '"' | '\\')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("Unterminated double quote in command".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("Unterminated double quote in command".to_string()),
+                    }
+                }
+            }
+            '\\' if posix => match chars.next() {
+                // Line continuation
+                Some('\n') => {}
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => {
+                    in_word = true;
+                    word.push('\\');
+                }
+            },
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Whether a word needs no quoting in any shell
+fn is_plain_word(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+}
+
+/// Quote a word so `split_shell_words` (and `sh`) read it back unchanged
+pub fn quote_shell_word(word: &str) -> String {
+    if is_plain_word(word) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Quoting in effect at a position of a command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteContext {
+    Unquoted,
+    Single,
+    Double,
+    /// Right after a backslash that escapes the next character
+    Escaped,
+}
+
+/// Quoting in effect after `prefix`. Without POSIX rules only double quotes group
+/// and backslashes are literal.
+fn quote_context(prefix: &str, posix: bool) -> QuoteContext {
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut escaped = false;
+
+    for c in prefix.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '"' if !in_single => in_double = !in_double,
+            '\'' if posix && !in_double => in_single = !in_single,
+            '\\' if posix && !in_single => escaped = true,
+            _ => {}
+        }
+    }
+
+    if escaped {
+        QuoteContext::Escaped
+    } else if in_single {
+        QuoteContext::Single
+    } else if in_double {
+        QuoteContext::Double
+    } else {
+        QuoteContext::Unquoted
+    }
+}
+
+/// Whether the command line is read with POSIX quoting rules. On Windows the default
+/// `cmd` and direct execution only group words with double quotes.
+fn uses_posix_quoting(shell: Option<ScriptShell>) -> bool {
+    cfg!(not(windows)) || shell.is_some_and(|shell| shell.program().is_some())
+}
+
+/// Quote a value substituted into a command line so that `shell` (see `resolve_command`)
+/// reads it as literal text, never as shell syntax. `preceding` is the command line
+/// before the value and tells whether the value sits inside quotes.
+pub fn quote_for_command(
+    value: &str,
+    preceding: &str,
+    shell: Option<ScriptShell>,
+) -> Result<String, String> {
+    let posix = uses_posix_quoting(shell);
+    match quote_context(preceding, posix) {
+        QuoteContext::Escaped => Err("Values cannot follow a backslash".to_string()),
+        QuoteContext::Unquoted if posix => Ok(quote_shell_word(value)),
+        QuoteContext::Single => Ok(value.replace('\'', r"'\''")),
+        QuoteContext::Double if posix => Ok(value.chars().fold(String::new(), |mut quoted, c| {
+            if matches!(c, '\\' | '"' | '$' | '`') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+            quoted
+        })),
+        context => {
+            // cmd cannot escape a double quote inside a quoted argument
+            if value.contains(['"', '\n', '\r']) {
+                return Err(
+                    "Values with double quotes or line breaks cannot be passed to cmd".to_string(),
+                );
+            }
+            if context == QuoteContext::Double || is_plain_word(value) {
+                Ok(value.to_string())
+            } else {
+                Ok(format!("\"{}\"", value))
+            }
+        }
+    }
+}
+
+/// Join words into a command line that `resolve_command` with `shell` splits back
+/// into the same words, quoting where needed
+pub fn join_shell_words<S: AsRef<str>>(
+    words: &[S],
+    shell: Option<ScriptShell>,
+) -> Result<String, String> {
+    Ok(words
+        .iter()
+        .map(|word| quote_for_command(word.as_ref(), "", shell))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(" "))
+}
+
+/// Whether the command uses syntax only a shell understands: unquoted pipes,
+/// redirects, globs, variables, command substitution, comments or `VAR=value` prefixes
+pub fn needs_shell(command: &str) -> bool {
+    let starts_with_assignment = command
+        .split_whitespace()
+        .next()
+        .and_then(|word| word.split_once('='))
+        .map(|(name, _)| {
+            name.chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .unwrap_or(false);
+    if starts_with_assignment {
+        return true;
+    }
+
+    // Without an explicit shell, Windows runs the command through cmd, where only
+    // double quotes group and backslashes are literal
+    let posix = uses_posix_quoting(None);
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut escaped = false;
+    let mut word_start = true;
+
+    for c in command.chars() {
+        if escaped {
+            escaped = false;
+            word_start = false;
+            continue;
+        }
+        if in_single {
+            in_single = c != '\'';
+            continue;
+        }
+        if in_double {
+            match c {
+                '"' => in_double = false,
+                '\\' if posix => escaped = true,
+                // Still expanded inside double quotes
+                '$' | '`' => return true,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '\\' if posix => escaped = true,
+            '\'' if posix => in_single = true,
+            '"' => in_double = true,
+            '~' | '#' if word_start => return true,
+            c if SHELL_METACHARACTERS.contains(&c) => return true,
+            _ => {}
+        }
+        word_start = c == ' ' || c == '\t';
+    }
+
+    false
+}
+
+/// Decide how to spawn a command line.
+/// An explicit shell always runs the command through it and `ScriptShell::None`
+/// always executes it directly; without one, the default shell (`sh`, or `cmd` on
+/// Windows) is used only when `needs_shell` says so.
+pub fn resolve_command(
+    command: &str,
+    shell: Option<ScriptShell>,
+) -> Result<CommandInvocation, String> {
+    if command.trim().is_empty() {
+        return Err("Empty command".to_string());
+    }
+
+    let shell_program = match shell {
+        Some(shell) => shell.program(),
+        None if needs_shell(command) => Some(DEFAULT_SHELL),
+        None => None,
+    };
+
+    match shell_program {
+        Some(program) => Ok(CommandInvocation {
+            program: program.to_string(),
+            args: vec![
+                if program == "cmd" { "/C" } else { "-c" }.to_string(),
+                command.to_string(),
+            ],
+            via_shell: true,
+        }),
+        None => {
+            let mut words = split_shell_words(command)?;
+            if words.is_empty() {
+                return Err("Empty command".to_string());
+            }
+            let program = words.remove(0);
+            Ok(CommandInvocation {
+                program,
+                args: words,
+                via_shell: false,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_split_shell_words() {
+        assert_eq!(
+            split_shell_words(r#"git commit -m "a b""#).unwrap(),
+            vec!["git", "commit", "-m", "a b"]
+        );
+        assert_eq!(
+            split_shell_words(r#"echo 'it''s' "say \"hi\"" a\ b "" $HOME"#).unwrap(),
+            vec!["echo", "its", r#"say "hi""#, "a b", "", "$HOME"]
+        );
+        assert!(split_shell_words("echo 'oops").is_err());
+        assert!(split_shell_words("echo \"oops").is_err());
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_split_shell_words_windows() {
+        assert_eq!(
+            split_shell_words(r#"copy C:\src\a.txt "C:\My Files\" 'x y'"#).unwrap(),
+            vec!["copy", r"C:\src\a.txt", r"C:\My Files\", "'x", "y'"]
+        );
+        assert!(!needs_shell(r#"node C:\tools\build.js "it's""#));
+        assert!(split_shell_words("echo \"oops").is_err());
+    }
+
+    #[test]
+    fn test_join_shell_words() {
+        let words = vec!["say", "it's", "a b", "", "--flag=x"];
+        let line = join_shell_words(&words, None).unwrap();
+        assert_eq!(resolve_command(&line, None).unwrap().args, &words[1..]);
+    }
+
+    #[test]
+    fn test_quote_for_command() {
+        let bash = Some(ScriptShell::Bash);
+        let value = "x'; rm -rf ~ $(id) `id` \\";
+        assert_eq!(
+            quote_for_command(value, "echo ", bash).unwrap(),
+            r"'x'\''; rm -rf ~ $(id) `id` \'"
+        );
+        assert_eq!(
+            quote_for_command(value, "echo 'a ", bash).unwrap(),
+            r"x'\''; rm -rf ~ $(id) `id` \"
+        );
+        assert_eq!(
+            quote_for_command(value, r#"echo "it's "#, bash).unwrap(),
+            r"x'; rm -rf ~ \$(id) \`id\` \\"
+        );
+        assert!(quote_for_command(value, r"echo \", bash).is_err());
+
+        // Read back as one literal word
+        #[cfg(not(windows))]
+        for preceding in ["echo ", "echo 'a ", "echo \"a "] {
+            let quoted = quote_for_command(value, preceding, bash).unwrap();
+            let closing = &preceding[5..6.min(preceding.len())];
+            let command = format!("{}{}{}", preceding, quoted, closing);
+            let words = split_shell_words(&command).unwrap();
+            assert!(words[1].ends_with(value), "{}", command);
+        }
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_needs_shell() {
+        assert!(!needs_shell(r#"git commit -m "a | b; c*""#));
+        assert!(!needs_shell("npm run build -- --mode=production"));
+        assert!(!needs_shell(r"echo \$HOME"));
+        assert!(needs_shell("npm test | tee out.log"));
+        assert!(needs_shell("ls *.json"));
+        assert!(needs_shell(r#"echo "$HOME""#));
+        assert!(needs_shell("NODE_ENV=production npm run build"));
+        assert!(needs_shell("cd ~/project"));
+    }
+
+    #[test]
+    fn test_resolve_command() {
+        let direct = resolve_command(r#"git commit -m "a b""#, None).unwrap();
+        assert_eq!(direct.program, "git");
+        assert_eq!(direct.args, vec!["commit", "-m", "a b"]);
+        assert!(!direct.via_shell);
+
+        let bash = resolve_command("echo hi", Some(ScriptShell::Bash)).unwrap();
+        assert_eq!(bash.program, "bash");
+        assert_eq!(bash.args, vec!["-c", "echo hi"]);
+
+        // Shell syntax is passed through literally without a shell
+        let literal = resolve_command("echo *", Some(ScriptShell::None)).unwrap();
+        assert_eq!(literal.args, vec!["*"]);
+        assert!(!literal.via_shell);
+
+        assert!(resolve_command("ls | wc -l", None).unwrap().via_shell);
+        assert!(resolve_command("  ", None).is_err());
+    }
+}
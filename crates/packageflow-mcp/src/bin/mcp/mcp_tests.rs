@@ -29,7 +29,7 @@ use packageflow_lib::models::mcp::{DevServerMode, MCPEncryptedSecrets, MCPPermis
 // Tool Name Constants
 // ============================================================================
 
-/// All ReadOnly tools (26 tools)
+/// All ReadOnly tools (27 tools)
 const READONLY_TOOLS: &[&str] = &[
     "list_projects",
     "get_project",
@@ -38,6 +38,7 @@ const READONLY_TOOLS: &[&str] = &[
     "get_git_diff",
     "list_workflows",
     "get_workflow",
+    "plan_workflow",
     "list_step_templates",
     "list_actions",
     "get_action",
@@ -169,7 +170,7 @@ mod tool_category_tests {
     #[test]
     fn test_tool_count_matches_expected() {
         // Verify we have the expected number of tools
-        assert_eq!(READONLY_TOOLS.len(), 26, "Expected 26 ReadOnly tools");
+        assert_eq!(READONLY_TOOLS.len(), 27, "Expected 27 ReadOnly tools");
         assert_eq!(WRITE_TOOLS.len(), 6, "Expected 6 Write tools");
         assert_eq!(EXECUTE_TOOLS.len(), 7, "Expected 7 Execute tools");
     }
@@ -13,6 +13,8 @@
 //! - `background/`: Background process management (~595 lines)
 //! - `tools_registry`: Centralized tool definitions (~300 lines)
 //! - `instance_manager`: Smart multi-instance management with heartbeat (~400 lines)
+//! - `run_queue`: Workflow run queue shared with the app (~110 lines)
+//! - `plan`: Dry-run plans of workflow runs (~100 lines)
 //!
 //! The main tool implementations remain in `mcp_server.rs` due to
 //! `rmcp` crate's requirement that all `#[tool]` methods be in a
@@ -27,6 +29,8 @@ pub mod store;
 pub mod background;
 pub mod tools_registry;
 pub mod instance_manager;
+pub mod run_queue;
+pub mod plan;
 
 // Re-export commonly used items
 pub use security::{ToolCategory, get_tool_category, is_tool_allowed};
@@ -35,12 +39,15 @@ pub use state::{RATE_LIMITER, TOOL_RATE_LIMITERS, ACTION_SEMAPHORE};
 pub use templates::get_builtin_templates;
 pub use store::{
     read_store_data, write_store_data, log_request, open_database, get_database_path,
-    Project, Workflow, WorkflowNode, CustomStepTemplate,
+    read_workflow_secrets, record_workflow_revision, Project, Workflow, WorkflowNode,
+    CustomStepTemplate,
 };
 pub use background::{
     BackgroundProcessStatus, BACKGROUND_PROCESS_MANAGER, CLEANUP_INTERVAL_SECS,
 };
 pub use instance_manager::InstanceManager;
+pub use run_queue::acquire_run_slot;
+pub use plan::plan_workflow_run;
 
 // Test module (only compiled in test builds)
 #[cfg(test)]
@@ -0,0 +1,106 @@
+//! Dry-run plans of workflow runs
+//!
+//! Plans are resolved the way `run_workflow` runs workflows: commands are spawned as
+//! resolved (no Volta wrapping, no Trash interception of `rm`), trigger-workflow steps
+//! are skipped and approval, HTTP and matrix steps only run in the app.
+
+use std::collections::HashMap;
+
+use packageflow_lib::models::project::WorkspacePackage;
+use packageflow_lib::models::workflow::{Workflow, WorkflowNode, WorkflowPlan};
+use packageflow_lib::repositories::{ProjectRepository, WorkflowRepository};
+use packageflow_lib::services::workflow::{plan_workflow, PlanExecutor};
+use packageflow_lib::utils::database::Database;
+use packageflow_lib::utils::shell::CommandInvocation;
+
+use super::store::open_database;
+
+/// Resolves plans for `run_workflow`
+struct McpPlanExecutor {
+    db: Database,
+}
+
+impl PlanExecutor for McpPlanExecutor {
+    fn name(&self) -> &'static str {
+        "mcp"
+    }
+
+    fn wrap_command(
+        &self,
+        invocation: CommandInvocation,
+        _cwd: Option<&str>,
+        _node_version: Option<&str>,
+    ) -> Result<(String, Vec<String>), String> {
+        Ok((invocation.program, invocation.args))
+    }
+
+    fn intercepts_rm(&self) -> bool {
+        false
+    }
+
+    fn secret_names(&self) -> Result<Vec<String>, String> {
+        Ok(WorkflowRepository::new(self.db.clone())
+            .list_secrets()?
+            .into_iter()
+            .map(|secret| secret.name)
+            .collect())
+    }
+
+    fn workspace_packages(&self, _project_path: &str) -> Result<Vec<WorkspacePackage>, String> {
+        Err("Matrix nodes can only be run from the PackageFlow app".to_string())
+    }
+
+    fn child_workflow(
+        &self,
+        _workflow_id: &str,
+    ) -> Result<Option<(Workflow, Option<String>)>, String> {
+        Ok(None)
+    }
+
+    fn unsupported(&self, node: &WorkflowNode) -> Option<String> {
+        let kind = if node.is_approval() {
+            "Approval"
+        } else if node.is_http() {
+            "HTTP"
+        } else if node
+            .get_script_config()
+            .is_some_and(|config| config.matrix.is_some())
+        {
+            "Matrix"
+        } else {
+            return None;
+        };
+        Some(format!(
+            "{} nodes can only be run from the PackageFlow app",
+            kind
+        ))
+    }
+}
+
+/// Plan a `run_workflow` call. `Ok(None)` when the workflow does not exist.
+pub fn plan_workflow_run(
+    workflow_id: &str,
+    project_path: Option<&str>,
+    params: &HashMap<String, serde_json::Value>,
+) -> Result<Option<WorkflowPlan>, String> {
+    let db = open_database()?;
+    let Some(workflow) = WorkflowRepository::new(db.clone()).get(workflow_id)? else {
+        return Ok(None);
+    };
+
+    // Same working directory as run_workflow: override > project path > current directory
+    let cwd = match (project_path, &workflow.project_id) {
+        (Some(path), _) => Some(path.to_string()),
+        (None, Some(project_id)) => ProjectRepository::new(db.clone())
+            .get(project_id)?
+            .map(|project| project.path),
+        (None, None) => None,
+    }
+    .or_else(|| {
+        std::env::current_dir()
+            .ok()
+            .map(|dir| dir.to_string_lossy().to_string())
+    });
+
+    plan_workflow(&McpPlanExecutor { db }, &workflow, cwd.as_deref(), params).map(Some)
+}
@@ -0,0 +1,123 @@
+//! Workflow run queue shared with the PackageFlow app
+//!
+//! `run_workflow` registers its execution in the `active_executions` table, so workflow
+//! concurrency policies and the global execution limit cover app and MCP runs alike.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use packageflow_lib::models::execution::ACTIVE_EXECUTION_SOURCE_MCP;
+use packageflow_lib::models::{ActiveExecution, ConcurrencyPolicy, WorkflowConcurrencySettings};
+use packageflow_lib::repositories::{
+    ActiveExecutionRepository, SettingsRepository, WorkflowRepository,
+};
+use packageflow_lib::services::workflow::{
+    admit_execution, queue_positions, startable_executions, QUEUE_POLL_INTERVAL_MS,
+    WORKFLOW_CONCURRENCY_SETTINGS_KEY,
+};
+use packageflow_lib::utils::database::Database;
+
+use super::store::open_database;
+use super::types::RunQueueState;
+
+/// Longest time a run waits in the queue before giving up
+pub const MAX_QUEUE_WAIT: Duration = Duration::from_secs(600);
+
+/// Registration of a run in the queue, removed when dropped. Rows left by a killed
+/// server carry its PID and are reaped by the next admission.
+pub struct ActiveRunGuard {
+    db: Database,
+    execution_id: String,
+}
+
+impl Drop for ActiveRunGuard {
+    fn drop(&mut self) {
+        if let Err(e) = ActiveExecutionRepository::new(self.db.clone()).remove(&self.execution_id) {
+            eprintln!("[MCP Server] Failed to leave the run queue: {}", e);
+        }
+    }
+}
+
+/// Wait until the workflow may run under its concurrency policy and the global limit.
+///
+/// `cancel-previous` waits like `queue`: runs owned by the app or another MCP server
+/// cannot be cancelled from here.
+pub async fn acquire_run_slot(
+    workflow_id: &str,
+    execution_id: &str,
+) -> Result<(ActiveRunGuard, RunQueueState), String> {
+    let db = open_database()?;
+    let repo = ActiveExecutionRepository::new(db.clone());
+
+    let policy = WorkflowRepository::new(db.clone())
+        .get(workflow_id)?
+        .and_then(|w| w.concurrency)
+        .unwrap_or_default();
+    let effective_policy = match policy {
+        ConcurrencyPolicy::CancelPrevious => ConcurrencyPolicy::Queue,
+        other => other,
+    };
+
+    let execution = ActiveExecution {
+        execution_id: execution_id.to_string(),
+        workflow_id: workflow_id.to_string(),
+        source: ACTIVE_EXECUTION_SOURCE_MCP.to_string(),
+        policy: effective_policy,
+        queued: true,
+        registered_at: Utc::now().to_rfc3339(),
+        owner_pid: Some(std::process::id()),
+    };
+    repo.admit_and_register(&execution, |active| {
+        admit_execution(
+            effective_policy,
+            workflow_id,
+            ACTIVE_EXECUTION_SOURCE_MCP,
+            active,
+        )
+    })?;
+    let guard = ActiveRunGuard {
+        db: db.clone(),
+        execution_id: execution_id.to_string(),
+    };
+
+    let waited = Instant::now();
+    let mut initial_position = None;
+    loop {
+        let active = repo.list()?;
+        let settings: WorkflowConcurrencySettings = SettingsRepository::new(db.clone())
+            .get(WORKFLOW_CONCURRENCY_SETTINGS_KEY)?
+            .unwrap_or_default();
+
+        if startable_executions(&active, settings.max_concurrent_executions)
+            .iter()
+            .any(|id| id == execution_id)
+        {
+            repo.set_started(execution_id)?;
+            let waited_ms = waited.elapsed().as_millis() as u64;
+            return Ok((
+                guard,
+                RunQueueState {
+                    policy: policy.to_string(),
+                    queued: initial_position.is_some(),
+                    initial_position,
+                    waited_ms,
+                },
+            ));
+        }
+
+        if initial_position.is_none() {
+            initial_position = queue_positions(&active)
+                .into_iter()
+                .find(|(id, _)| id == execution_id)
+                .map(|(_, position)| position);
+        }
+        if waited.elapsed() >= MAX_QUEUE_WAIT {
+            return Err(format!(
+                "Timed out after {}s waiting in the run queue",
+                MAX_QUEUE_WAIT.as_secs()
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(QUEUE_POLL_INTERVAL_MS)).await;
+    }
+}
@@ -17,6 +17,7 @@ use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 
 use packageflow_lib::models::mcp::MCPServerConfig;
+use packageflow_lib::models::{RevisionSource, WorkflowParameter};
 use packageflow_lib::repositories::{
     MCPRepository, McpLogEntry, ProjectRepository, SettingsRepository,
     TemplateRepository, WorkflowRepository,
@@ -120,6 +121,7 @@ pub fn read_store_data() -> Result<StoreData, String> {
                     config: n.config,
                     order: n.order,
                     position,
+                    depends_on: n.depends_on,
                 }
             }).collect(),
             created_at: w.created_at,
@@ -127,6 +129,8 @@ pub fn read_store_data() -> Result<StoreData, String> {
             last_executed_at: w.last_executed_at,
             webhook: w.webhook.map(|wh| serde_json::to_value(wh).unwrap_or_default()),
             incoming_webhook: w.incoming_webhook.map(|iwh| serde_json::to_value(iwh).unwrap_or_default()),
+            parameters: w.parameters,
+            env: w.env,
         })
         .collect();
 
@@ -175,6 +179,8 @@ pub fn write_store_data(data: &StoreData) -> Result<(), String> {
 
     // Save workflows
     for workflow in &data.workflows {
+        // Fields not modeled by the local Workflow type are preserved from the stored row
+        let stored = workflow_repo.get(&workflow.id)?;
         let w = packageflow_lib::models::Workflow {
             id: workflow.id.clone(),
             name: workflow.name.clone(),
@@ -192,10 +198,18 @@ pub fn write_store_data(data: &StoreData) -> Result<(), String> {
                     config: n.config.clone(),
                     order: n.order,
                     position,
+                    depends_on: n.depends_on.clone(),
                 }
             }).collect(),
-            webhook: None,
-            incoming_webhook: None,
+            webhook: stored.as_ref().and_then(|s| s.webhook.clone()),
+            incoming_webhook: stored.as_ref().and_then(|s| s.incoming_webhook.clone()),
+            max_parallelism: stored.as_ref().and_then(|s| s.max_parallelism),
+            schedule: stored.as_ref().and_then(|s| s.schedule.clone()),
+            file_trigger: stored.as_ref().and_then(|s| s.file_trigger.clone()),
+            git_trigger: stored.as_ref().and_then(|s| s.git_trigger.clone()),
+            concurrency: stored.as_ref().and_then(|s| s.concurrency),
+            parameters: workflow.parameters.clone(),
+            env: workflow.env.clone(),
             created_at: workflow.created_at.clone(),
             updated_at: workflow.updated_at.clone(),
             last_executed_at: workflow.last_executed_at.clone(),
@@ -224,6 +238,28 @@ pub fn write_store_data(data: &StoreData) -> Result<(), String> {
     Ok(())
 }
 
+/// Decrypt the named workflow secrets (referenced from env as `{{secrets.<name>}}`)
+pub fn read_workflow_secrets(names: &[String]) -> Result<HashMap<String, String>, String> {
+    if names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    WorkflowRepository::new(open_database()?).load_secrets(names)
+}
+
+/// Record the stored workflow as a revision (a no-op when it matches the latest one)
+/// Returns the workflow's current revision number
+pub fn record_workflow_revision(
+    workflow_id: &str,
+    source: RevisionSource,
+    summary: Option<&str>,
+) -> Result<u32, String> {
+    let workflow_repo = WorkflowRepository::new(open_database()?);
+    let workflow = workflow_repo
+        .get(workflow_id)?
+        .ok_or_else(|| format!("Workflow not found: {}", workflow_id))?;
+    workflow_repo.record_revision(&workflow, source, summary)
+}
+
 // ============================================================================
 // Logging Functions
 // ============================================================================
@@ -387,6 +423,10 @@ pub struct Workflow {
     pub webhook: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub incoming_webhook: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<WorkflowParameter>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -400,6 +440,8 @@ pub struct WorkflowNode {
     pub order: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<NodePosition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -479,6 +521,8 @@ mod tests {
             last_executed_at: None,
             webhook: None,
             incoming_webhook: None,
+            parameters: vec![],
+            env: HashMap::new(),
         };
 
         let json = serde_json::to_string(&workflow).unwrap();
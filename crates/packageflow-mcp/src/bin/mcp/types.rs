@@ -264,6 +264,22 @@ pub struct RunWorkflowParams {
     /// Optional project path override (for working directory)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_path: Option<String>,
+    /// Values for the workflow's declared parameters (omitted ones use their defaults)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanWorkflowParams {
+    /// Workflow ID to plan
+    pub workflow_id: String,
+    /// Optional project path override (for working directory), as for run_workflow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Values for the workflow's declared parameters (omitted ones use their defaults)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -770,6 +786,27 @@ pub struct RunWorkflowResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub failed_step: Option<FailedStepInfo>,
     pub output_summary: String,
+    /// How the run was admitted by the run queue
+    pub queue: RunQueueState,
+    /// Files collected from artifact globs (`<node id>/<path>`), kept with the execution
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+    /// Workflow revision that was run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunQueueState {
+    /// Concurrency policy of the workflow
+    pub policy: String,
+    /// Whether the run had to wait for a free slot
+    pub queued: bool,
+    /// Position in the run queue when the run was submitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_position: Option<usize>,
+    pub waited_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -841,6 +878,23 @@ pub struct CompareSnapshotsParams {
     pub snapshot_b_id: String,
 }
 
+/// Parameters for why_installed tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WhyInstalledParams {
+    /// Snapshot ID to query
+    pub snapshot_id: String,
+    /// Package to explain (required unless postinstallOnly is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
+    /// Only explain this version of the package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Explain every package with a postinstall script instead (default: false)
+    #[serde(default)]
+    pub postinstall_only: bool,
+}
+
 /// Parameters for search_snapshots tool
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -19,11 +19,16 @@ use mcp::{
     get_builtin_templates,
     // Store (database access and local types)
     read_store_data, write_store_data, log_request, open_database, get_database_path,
-    Project, Workflow, WorkflowNode, CustomStepTemplate,
+    read_workflow_secrets, record_workflow_revision, Project, Workflow, WorkflowNode,
+    CustomStepTemplate,
     // Background process management
     BackgroundProcessStatus, BACKGROUND_PROCESS_MANAGER, CLEANUP_INTERVAL_SECS,
     // Instance management (smart multi-instance support)
     InstanceManager,
+    // Workflow run queue shared with the app
+    acquire_run_slot,
+    // Dry-run plans
+    plan_workflow_run,
 };
 
 use std::collections::HashMap;
@@ -53,7 +58,7 @@ use packageflow_lib::repositories::{
     MCPActionRepository,
     // New repositories for enhanced MCP tools
     AIRepository, AIConversationRepository, NotificationRepository,
-    SecurityRepository, DeployRepository, SnapshotRepository,
+    SecurityRepository, DeployRepository, SnapshotRepository, ArtifactRepository,
 };
 
 // Import MCP action models and services
@@ -76,10 +81,12 @@ use packageflow_lib::utils::shared_store::{
 
 // Import MCP types from models
 use packageflow_lib::models::mcp::{MCPServerConfig, DevServerMode};
+use packageflow_lib::models::RevisionSource;
+use packageflow_lib::models::execution::{Execution, NodeAttempt, NodeResult, NodeStatus};
 
 // Import snapshot services for Time Machine
 use packageflow_lib::services::snapshot::{
-    SnapshotStorage, SnapshotDiffService, SnapshotReplayService, SnapshotSearchService,
+    SnapshotStorage, SnapshotDiffService, SnapshotGraphService, SnapshotReplayService, SnapshotSearchService,
     SnapshotCaptureService,
 };
 
@@ -97,8 +104,19 @@ use packageflow_lib::services::security_guardian::{
 // Import snapshot models
 use packageflow_lib::models::snapshot::SnapshotFilter;
 
+// Import workflow engine helpers (dependency scheduling, conditions and templates)
+use packageflow_lib::models::workflow::{ConditionNodeConfig, RetryPolicy, ScriptShell};
+use packageflow_lib::services::workflow::condition::{current_git_branch, uses_git_branch};
+use packageflow_lib::services::workflow::{
+    collect_artifacts, execution_artifacts_dir, ArtifactCollection, ArtifactOwner, MAX_NODE_ARTIFACT_BYTES,
+    collect_set_outputs, evaluate_condition, interpolate, interpolate_command, mask_secret_values,
+    merge_env, resolve_env, resolve_parameters, retry_delay, secret_references, should_retry,
+    ConditionContext, DagNodeSpec, DagScheduler, InterpolationContext, StepOutputs, WorkflowDag,
+};
+
 // Import path_resolver for proper command execution on macOS GUI apps
 use packageflow_lib::utils::path_resolver;
+use packageflow_lib::utils::shell::{resolve_command, CommandInvocation};
 
 // Rate limiters, semaphore, and security are now imported from mcp::{state, security}
 // Background process management is now imported from mcp::background
@@ -171,15 +189,30 @@ impl PackageFlowMcp {
     /// - Maximum timeout: 1 hour (from validation)
     /// - Returns error if command exceeds timeout
     async fn shell_command_async(cwd: &str, command: &str, timeout_ms: Option<u64>) -> Result<(i32, String, String), String> {
+        Self::shell_command_with_env_async(cwd, command, timeout_ms, &HashMap::new()).await
+    }
+
+    /// Execute a shell command with additional environment variables
+    async fn shell_command_with_env_async(cwd: &str, command: &str, timeout_ms: Option<u64>, env: &HashMap<String, String>) -> Result<(i32, String, String), String> {
+        let invocation = CommandInvocation {
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), command.to_string()],
+            via_shell: true,
+        };
+        Self::invoke_command_with_env_async(cwd, &invocation, timeout_ms, env).await
+    }
+
+    /// Execute a resolved command (shell or direct) with additional environment variables
+    async fn invoke_command_with_env_async(cwd: &str, invocation: &CommandInvocation, timeout_ms: Option<u64>, env: &HashMap<String, String>) -> Result<(i32, String, String), String> {
         // Default timeout: 5 minutes, max is enforced by validate_timeout (1 hour)
         let timeout_duration = Duration::from_millis(timeout_ms.unwrap_or(300_000));
 
         // Use path_resolver::create_async_command for proper environment setup
         // This ensures the command has access to Volta, Homebrew, and other tools
-        let mut cmd = path_resolver::create_async_command("sh");
-        cmd.arg("-c")
-            .arg(command)
+        let mut cmd = path_resolver::create_async_command(&invocation.program);
+        cmd.args(&invocation.args)
             .current_dir(cwd)
+            .envs(env)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped());
 
@@ -852,12 +885,15 @@ impl PackageFlowMcp {
             last_executed_at: None,
             webhook: None,
             incoming_webhook: None,
+            parameters: Vec::new(),
+            env: HashMap::new(),
         };
 
         store_data.workflows.push(workflow);
 
         write_store_data(&store_data)
             .map_err(|e| McpError::internal_error(e, None))?;
+        Self::record_revision(&workflow_id, "create_workflow");
 
         let response = CreateWorkflowResponse {
             workflow_id,
@@ -977,6 +1013,7 @@ RETURNS: Created workflow ID and step details. Use workflow_id with run_workflow
                 config,
                 order,
                 position: None,
+                depends_on: Vec::new(),
             });
 
             created_steps.push(CreatedStepInfo {
@@ -998,6 +1035,8 @@ RETURNS: Created workflow ID and step details. Use workflow_id with run_workflow
             last_executed_at: None,
             webhook: None,
             incoming_webhook: None,
+            parameters: Vec::new(),
+            env: HashMap::new(),
         };
 
         store_data.workflows.push(workflow);
@@ -1012,6 +1051,7 @@ RETURNS: Created workflow ID and step details. Use workflow_id with run_workflow
             })?;
 
         eprintln!("[MCP Debug] create_workflow_with_steps - Write SUCCESS");
+        Self::record_revision(&workflow_id, "create_workflow_with_steps");
 
         let response = CreateWorkflowWithStepsResponse {
             success: true,
@@ -1071,6 +1111,7 @@ RETURNS: Created workflow ID and step details. Use workflow_id with run_workflow
                     config,
                     order,
                     position: None,
+                    depends_on: Vec::new(),
                 };
 
                 w.nodes.push(node);
@@ -1086,6 +1127,7 @@ RETURNS: Created workflow ID and step details. Use workflow_id with run_workflow
                     })?;
 
                 eprintln!("[MCP Debug] add_workflow_step - Write SUCCESS");
+                Self::record_revision(&params.workflow_id, "add_workflow_step");
 
                 let response = AddStepResponse {
                     node_id,
@@ -1243,6 +1285,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
                 config,
                 order,
                 position: None,
+                depends_on: Vec::new(),
             };
 
             store_data.workflows[workflow_idx].nodes.push(node);
@@ -1271,6 +1314,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
             })?;
 
         eprintln!("[MCP Debug] add_workflow_steps - Write SUCCESS");
+        Self::record_revision(&params.workflow_id, "add_workflow_steps");
 
         let response = AddWorkflowStepsResponse {
             success: true,
@@ -1384,8 +1428,38 @@ RETURNS: List of created step IDs and their assigned order positions.")]
     // Workflow Execution Tool
     // ========================================================================
 
+    /// Show what run_workflow would do without running anything
+    #[tool(description = "Show the execution plan of a workflow without running it: steps in the order they would start with their dependencies, each step's final command (after parameter substitution and shell selection), working directory and environment (secret values masked), and steps that would be skipped or fail. Use it before run_workflow to review what a workflow will do. `{{steps.*}}` placeholders stay unresolved until the steps run.")]
+    async fn plan_workflow(
+        &self,
+        Parameters(params): Parameters<PlanWorkflowParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let plan = match plan_workflow_run(
+            &params.workflow_id,
+            params.project_path.as_deref(),
+            &params.params.unwrap_or_default(),
+        ) {
+            Ok(Some(plan)) => plan,
+            Ok(None) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    format!("Workflow not found: {}", params.workflow_id)
+                )]));
+            }
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    format!("Cannot plan workflow: {}", e)
+                )]));
+            }
+        };
+
+        let json = serde_json::to_string_pretty(&plan)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
     /// Execute a workflow synchronously
-    #[tool(description = "Execute a workflow synchronously and return the execution result. Runs all steps in order and stops on first failure.")]
+    #[tool(description = "Execute a workflow synchronously and return the execution result. Runs all steps in dependency order and stops on first failure. Waits for a free slot when the workflow's concurrency policy or the global execution limit requires it (see `queue` in the result). Files matching a step's artifact globs are kept with the execution (see `artifacts`).")]
     async fn run_workflow(
         &self,
         Parameters(params): Parameters<RunWorkflowParams>,
@@ -1406,10 +1480,29 @@ RETURNS: List of created step IDs and their assigned order positions.")]
             }
         };
 
-        // Record execution start time
+        // Wait for a slot under the workflow's concurrency policy; the guard leaves the queue on return
         let execution_id = format!("exec-{}", Uuid::new_v4());
+        let (_queue_guard, queue) = match acquire_run_slot(&workflow.id, &execution_id).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    format!("Cannot run workflow '{}': {}", workflow.name, e)
+                )]));
+            }
+        };
+
+        // Record execution start time
         let started_at = Utc::now();
 
+        // Revision being run (workflows last saved before revisions were recorded get their first one)
+        let revision = match record_workflow_revision(&workflow.id, RevisionSource::Unknown, None) {
+            Ok(revision) => Some(revision),
+            Err(e) => {
+                eprintln!("[MCP Server] Failed to read workflow revision: {}", e);
+                None
+            }
+        };
+
         // Determine working directory
         let cwd = if let Some(ref path) = params.project_path {
             path.clone()
@@ -1423,19 +1516,126 @@ RETURNS: List of created step IDs and their assigned order positions.")]
             std::env::current_dir().unwrap().to_string_lossy().to_string()
         };
 
-        // Sort nodes by order
-        let mut nodes = workflow.nodes.clone();
-        nodes.sort_by_key(|n| n.order);
+        // Validate parameters and apply defaults; `{{params.*}}`/`{{steps.*}}` are substituted per node
+        let parameters = match resolve_parameters(&workflow.parameters, &params.params.clone().unwrap_or_default()) {
+            Ok(parameters) => parameters,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    format!("Invalid workflow parameters: {}", e)
+                )]));
+            }
+        };
+        let mut interpolation_ctx = InterpolationContext {
+            params: parameters,
+            ..Default::default()
+        };
+
+        // Schedule nodes by their dependencies (linear workflows fall back to `order`)
+        let mut dag_specs: Vec<DagNodeSpec> = Vec::new();
+        for n in &workflow.nodes {
+            let condition = if n.node_type == "condition" {
+                match serde_json::from_value::<ConditionNodeConfig>(n.config.clone()) {
+                    Ok(config) => Some(config),
+                    Err(e) => {
+                        return Ok(CallToolResult::error(vec![Content::text(
+                            format!("Invalid condition config for node '{}': {}", n.name, e)
+                        )]));
+                    }
+                }
+            } else {
+                None
+            };
+            dag_specs.push(DagNodeSpec {
+                id: n.id.clone(),
+                name: n.name.clone(),
+                order: n.order,
+                depends_on: n.depends_on.clone(),
+                branch_targets: condition.map(|c| c.all_targets()).unwrap_or_default(),
+            });
+        }
+        let dag = match WorkflowDag::from_specs(dag_specs) {
+            Ok(dag) => dag,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    format!("Invalid workflow dependencies: {}", e)
+                )]));
+            }
+        };
+        let mut scheduler = DagScheduler::new(dag);
 
-        let total_steps = nodes.len();
+        let total_steps = workflow.nodes.len();
         let mut steps_executed = 0;
         let mut failed_step: Option<FailedStepInfo> = None;
         let mut output_lines: Vec<String> = Vec::new();
+        let mut artifacts: Vec<String> = Vec::new();
+        // Final result of every script node, kept with the execution like app runs
+        let mut node_results: Vec<NodeResult> = Vec::new();
+
+        // Step results available to condition nodes
+        let mut condition_ctx = ConditionContext {
+            env: std::env::vars().collect(),
+            ..Default::default()
+        };
+
+        // Run one ready node at a time
+        while let Some(node_id) = scheduler.ready_nodes().into_iter().next() {
+            let node = match workflow.nodes.iter().find(|n| n.id == node_id) {
+                Some(node) => node,
+                None => break,
+            };
+            scheduler.mark_running(&node.id);
+
+            // Condition nodes route to one branch and skip the others
+            if node.node_type == "condition" {
+                let config: ConditionNodeConfig = serde_json::from_value(node.config.clone())
+                    .unwrap_or_default();
+                if uses_git_branch(&config) && condition_ctx.git_branch.is_none() {
+                    condition_ctx.git_branch = current_git_branch(&cwd);
+                }
+                match evaluate_condition(&config, &condition_ctx) {
+                    Ok(outcome) => {
+                        output_lines.push(format!("[COND] {}: matched {}", node.name, outcome.branch_label));
+                        for skipped_id in scheduler.mark_branch_result(&node.id, &outcome.not_taken) {
+                            let skipped_name = workflow.nodes.iter()
+                                .find(|n| n.id == skipped_id)
+                                .map(|n| n.name.clone())
+                                .unwrap_or(skipped_id);
+                            output_lines.push(format!("[SKIP] {}: Branch not selected", skipped_name));
+                        }
+                    }
+                    Err(e) => {
+                        output_lines.push(format!("[ERROR] {}: {}", node.name, e));
+                        failed_step = Some(FailedStepInfo {
+                            node_id: node.id.clone(),
+                            node_name: node.name.clone(),
+                            exit_code: -1,
+                            error_message: e,
+                        });
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            // Never run past a manual gate without a human decision; HTTP nodes need the
+            // app's request signing and would otherwise be skipped as non-script nodes
+            if node.node_type == "approval" || node.node_type == "http" {
+                let kind = if node.node_type == "http" { "HTTP" } else { "Approval" };
+                let message = format!("{} nodes can only be run from the PackageFlow app", kind);
+                output_lines.push(format!("[ERROR] {}: {}", node.name, message));
+                failed_step = Some(FailedStepInfo {
+                    node_id: node.id.clone(),
+                    node_name: node.name.clone(),
+                    exit_code: -1,
+                    error_message: message,
+                });
+                break;
+            }
 
-        for node in &nodes {
             // Only execute script nodes
             if node.node_type != "script" {
                 output_lines.push(format!("[SKIP] {}: Not a script node", node.name));
+                scheduler.mark_completed(&node.id);
                 continue;
             }
 
@@ -1446,30 +1646,204 @@ RETURNS: List of created step IDs and their assigned order positions.")]
 
             if command.is_empty() {
                 output_lines.push(format!("[SKIP] {}: Empty command", node.name));
+                scheduler.mark_completed(&node.id);
                 continue;
             }
 
+            // Matrix nodes need Volta pinning and workspace detection from the app
+            if node.config.get("matrix").is_some_and(|m| !m.is_null()) {
+                let message = "Matrix nodes can only be run from the PackageFlow app".to_string();
+                output_lines.push(format!("[ERROR] {}: {}", node.name, message));
+                failed_step = Some(FailedStepInfo {
+                    node_id: node.id.clone(),
+                    node_name: node.name.clone(),
+                    exit_code: -1,
+                    error_message: message,
+                });
+                break;
+            }
+
             // Get node-specific cwd or use workflow cwd
             let node_cwd = node.config.get("cwd")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| cwd.clone());
 
+            // Same shell selection as the app: the node's `shell`, else `sh` only for shell syntax
+            let shell: Option<ScriptShell> = node.config.get("shell")
+                .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+            // Substitute parameters and outputs of previous steps, quoted for the shell
+            let resolved = interpolate_command(command, &interpolation_ctx, shell)
+                .and_then(|command| {
+                    let invocation = resolve_command(&command, shell)?;
+                    Ok((command, invocation, interpolate(&node_cwd, &interpolation_ctx)?))
+                });
+            let (command, invocation, node_cwd) = match resolved {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    output_lines.push(format!("[ERROR] {}: {}", node.name, e));
+                    failed_step = Some(FailedStepInfo {
+                        node_id: node.id.clone(),
+                        node_name: node.name.clone(),
+                        exit_code: -1,
+                        error_message: e,
+                    });
+                    break;
+                }
+            };
+            let command = command.as_str();
+
+            // Workflow + node env; referenced secrets are decrypted and masked in the output
+            let node_env: HashMap<String, String> = node.config.get("env")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            let env = merge_env(&workflow.env, &node_env);
+            let resolved_env = read_workflow_secrets(&secret_references(env.values()))
+                .and_then(|secrets| {
+                    let env = resolve_env(&env, &interpolation_ctx, &secrets)?;
+                    Ok((env, secrets.into_values().collect::<Vec<String>>()))
+                });
+            let (env, secret_values) = match resolved_env {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    output_lines.push(format!("[ERROR] {}: {}", node.name, e));
+                    failed_step = Some(FailedStepInfo {
+                        node_id: node.id.clone(),
+                        node_name: node.name.clone(),
+                        exit_code: -1,
+                        error_message: e,
+                    });
+                    break;
+                }
+            };
+
             let timeout = node.config.get("timeout")
                 .and_then(|v| v.as_u64());
 
             output_lines.push(format!("[RUN] {}: {} (timeout: {}s)", node.name, command, timeout.unwrap_or(300_000) / 1000));
 
-            // Use async shell command with timeout enforcement
-            match Self::shell_command_async(&node_cwd, command, timeout).await {
+            // Retry failed attempts according to the node's retry policy; as in the app,
+            // every attempt of a node with a retry policy is recorded in its node result
+            let retry_policy: RetryPolicy =
+                serde_json::from_value(node.config.clone()).unwrap_or_default();
+            let mut node_result = NodeResult::new(node.id.clone());
+            node_result.start();
+            let mut attempt: u32 = 1;
+            let run_result = loop {
+                let attempt_started_at = Utc::now().to_rfc3339();
+                // Use async shell command with timeout enforcement
+                let result =
+                    Self::invoke_command_with_env_async(&node_cwd, &invocation, timeout, &env)
+                        .await;
+                let (exit_code, attempt_output, error_message) = match &result {
+                    Ok((0, stdout, stderr)) => (Some(0), format!("{}{}", stdout, stderr), None),
+                    Ok((code, stdout, stderr)) => (
+                        Some(*code),
+                        format!("{}{}", stdout, stderr),
+                        Some(format!("Exit code: {}", code)),
+                    ),
+                    Err(e) => (None, e.clone(), Some(sanitize_error(e))),
+                };
+                if retry_policy.retries > 0 {
+                    node_result.attempts.push(NodeAttempt {
+                        attempt,
+                        status: if exit_code == Some(0) {
+                            NodeStatus::Completed
+                        } else {
+                            NodeStatus::Failed
+                        },
+                        exit_code,
+                        error_message,
+                        started_at: attempt_started_at,
+                        finished_at: Some(Utc::now().to_rfc3339()),
+                    });
+                }
+                if exit_code == Some(0)
+                    || !should_retry(&retry_policy, attempt, exit_code, &attempt_output)
+                {
+                    break result;
+                }
+
+                let delay = retry_delay(&retry_policy, attempt);
+                output_lines.push(format!(
+                    "[RETRY] {}: attempt {}/{} failed, retrying in {}ms",
+                    node.name,
+                    attempt,
+                    retry_policy.retries + 1,
+                    delay.as_millis()
+                ));
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            };
+
+            match &run_result {
+                Ok((exit_code, stdout, _)) => {
+                    node_result.complete(*exit_code);
+                    if *exit_code != 0 {
+                        node_result.error_message = Some(format!("Exit code: {}", exit_code));
+                    }
+                    let stdout = mask_secret_values(stdout, &secret_values);
+                    let mut step = StepOutputs::default();
+                    for line in stdout.lines() {
+                        step.push_output_line(line);
+                    }
+                    node_result.output = step.output;
+                    node_result.output_truncated = step.truncated;
+                    node_result.outputs = collect_set_outputs(stdout.lines());
+                }
+                Err(e) => {
+                    node_result.status = NodeStatus::Failed;
+                    node_result.finished_at = Some(Utc::now().to_rfc3339());
+                    node_result.error_message = Some(sanitize_error(e));
+                }
+            }
+            let step_outputs = StepOutputs::from(&node_result);
+            node_results.push(node_result);
+
+            match run_result {
                 Ok((exit_code, stdout, stderr)) => {
                     steps_executed += 1;
 
-                    // Sanitize output to redact sensitive content (API keys, tokens, etc.)
+                    // Sanitize output to redact sensitive content (API keys, tokens, secret values)
+                    let stdout = mask_secret_values(&stdout, &secret_values);
+                    let stderr = mask_secret_values(&stderr, &secret_values);
                     let sanitized_stdout = sanitize_output(&stdout);
                     let sanitized_stderr = sanitize_output(&stderr);
 
+                    condition_ctx.exit_codes.insert(node.id.clone(), exit_code);
+                    condition_ctx.outputs.insert(node.id.clone(), format!("{}{}", stdout, stderr));
+
+                    // Keep the files matching the node's artifact globs once it succeeded
+                    let artifact_patterns: Vec<String> = node.config.get("artifacts")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok())
+                        .unwrap_or_default();
+                    if exit_code == 0 && !artifact_patterns.is_empty() {
+                        match Self::collect_node_artifacts(&execution_id, &workflow.id, node, &node_cwd, &artifact_patterns).await {
+                            Ok(collection) => {
+                                output_lines.push(format!("[ARTIFACTS] {}: collected {} file(s)", node.name, collection.artifacts.len()));
+                                if !collection.unmatched.is_empty() {
+                                    output_lines.push(format!("  No files matched {}", collection.unmatched.join(", ")));
+                                }
+                                artifacts.extend(collection.artifacts.into_iter().map(|a| format!("{}/{}", a.node_id, a.path)));
+                            }
+                            Err(e) => {
+                                let error_message = format!("Failed to collect artifacts: {}", e);
+                                output_lines.push(format!("[ERROR] {}: {}", node.name, error_message));
+                                failed_step = Some(FailedStepInfo {
+                                    node_id: node.id.clone(),
+                                    node_name: node.name.clone(),
+                                    exit_code: -1,
+                                    error_message,
+                                });
+                                break;
+                            }
+                        }
+                    }
+
                     if exit_code == 0 {
+                        scheduler.mark_completed(&node.id);
+                        interpolation_ctx.steps.insert(node.id.clone(), step_outputs);
                         output_lines.push(format!("[OK] {} completed successfully", node.name));
                         if !sanitized_stdout.trim().is_empty() {
                             // Add last 10 lines of stdout
@@ -1539,10 +1913,27 @@ RETURNS: List of created step IDs and their assigned order positions.")]
             steps_executed,
             failed_step.as_ref().map(|f| f.error_message.clone()),
             &output_lines,
+            revision,
         ) {
             eprintln!("[MCP Server] Failed to save execution history: {}", e);
         }
 
+        // Final node results (with retry attempts) feed workflow stats and resume
+        let mut execution = Execution::new(execution_id.clone(), workflow.id.clone());
+        execution.started_at = started_at.to_rfc3339();
+        execution.node_results = node_results;
+        execution.parameters = interpolation_ctx.params.clone();
+        execution.revision = revision;
+        if failed_step.is_some() {
+            execution.fail();
+        } else {
+            execution.complete();
+        }
+        execution.finished_at = Some(finished_at.to_rfc3339());
+        if let Err(e) = Self::save_finished_execution(&execution) {
+            eprintln!("[MCP Server] Failed to save execution result: {}", e);
+        }
+
         let response = RunWorkflowResponse {
             success: failed_step.is_none(),
             workflow_id: workflow.id,
@@ -1552,6 +1943,9 @@ RETURNS: List of created step IDs and their assigned order positions.")]
             status: status.to_string(),
             failed_step,
             output_summary,
+            queue,
+            artifacts,
+            revision,
         };
 
         let json = serde_json::to_string_pretty(&response)
@@ -1994,6 +2388,41 @@ RETURNS: List of created step IDs and their assigned order positions.")]
         }
     }
 
+    /// Copy a node's artifacts into the artifact store shared with the app and record them.
+    /// Runs on a blocking thread: copying and hashing large files would stall the server.
+    async fn collect_node_artifacts(
+        execution_id: &str,
+        workflow_id: &str,
+        node: &WorkflowNode,
+        cwd: &str,
+        patterns: &[String],
+    ) -> Result<ArtifactCollection, String> {
+        let owner = ArtifactOwner {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            cell_index: None,
+        };
+        let cwd = std::path::PathBuf::from(cwd);
+        let patterns = patterns.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let collection = collect_artifacts(&execution_artifacts_dir()?, &cwd, &patterns, &owner, MAX_NODE_ARTIFACT_BYTES)?;
+            ArtifactRepository::new(open_database()?).save(&collection.artifacts)?;
+            Ok(collection)
+        })
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+    }
+
+    /// Record a workflow changed by a tool as a new revision. The change is already saved,
+    /// so a failure is only logged.
+    fn record_revision(workflow_id: &str, tool_name: &str) {
+        if let Err(e) = record_workflow_revision(workflow_id, RevisionSource::Mcp, Some(tool_name)) {
+            eprintln!("[MCP Server] Failed to record workflow revision: {}", e);
+        }
+    }
+
     /// Save execution history to database
     fn save_execution_history(
         execution_id: &str,
@@ -2007,6 +2436,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
         completed_node_count: usize,
         error_message: Option<String>,
         output_lines: &[String],
+        revision: Option<u32>,
     ) -> Result<(), String> {
         let db_path = get_database_path()?;
         let db = Database::new(db_path)?;
@@ -2035,8 +2465,8 @@ RETURNS: List of created step IDs and their assigned order positions.")]
                 INSERT OR REPLACE INTO execution_history
                 (id, workflow_id, workflow_name, status, started_at, finished_at,
                  duration_ms, node_count, completed_node_count, error_message,
-                 output, triggered_by)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 output, triggered_by, revision)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
                 "#,
                 params![
                     execution_id,
@@ -2051,6 +2481,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
                     error_message,
                     output_str,
                     "mcp", // triggered_by
+                    revision,
                 ],
             )
             .map_err(|e| format!("Failed to save execution history: {}", e))?;
@@ -2059,6 +2490,34 @@ RETURNS: List of created step IDs and their assigned order positions.")]
         })
     }
 
+    /// Save the final state of a finished execution (node results and retry attempts)
+    fn save_finished_execution(execution: &Execution) -> Result<(), String> {
+        let db_path = get_database_path()?;
+        let db = Database::new(db_path)?;
+
+        let execution_data = serde_json::to_string(execution)
+            .map_err(|e| format!("Failed to serialize execution: {}", e))?;
+
+        db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO finished_executions
+                (id, workflow_id, execution_data, finished_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    execution.id,
+                    execution.workflow_id,
+                    execution_data,
+                    execution.finished_at,
+                ],
+            )
+            .map_err(|e| format!("Failed to save execution result: {}", e))?;
+
+            Ok(())
+        })
+    }
+
     // ========================================================================
     // MCP Action Tools
     // ========================================================================
@@ -3151,6 +3610,32 @@ RETURNS: List of created step IDs and their assigned order positions.")]
         Ok(CallToolResult::success(vec![Content::text(json)]))
     }
 
+    /// Explain why a package is in a snapshot
+    #[tool(description = "Explain why a package is installed in a snapshot: every dependency path from the project's direct dependencies down to it, with the kind of each edge (prod, dev, optional, peer). Omit version to explain every installed version. With postinstallOnly, explains every package in the snapshot that has a postinstall script instead.")]
+    async fn why_installed(
+        &self,
+        Parameters(params): Parameters<WhyInstalledParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let db = open_database()
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        let service = SnapshotGraphService::new(db);
+        let result = match (params.postinstall_only, &params.package_name) {
+            (true, _) => service.postinstall_origins(&params.snapshot_id),
+            (false, Some(name)) => service.why_installed(&params.snapshot_id, name, params.version.as_deref()),
+            (false, None) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    "packageName is required unless postinstallOnly is set".to_string()
+                )]));
+            }
+        };
+        let reasons = result.map_err(|e| McpError::internal_error(e, None))?;
+
+        let json = serde_json::to_string_pretty(&reasons)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
     /// Search snapshots
     #[tool(description = "Search snapshots by package name, version, or date range. Find when specific packages were added, removed, or updated across executions.")]
     async fn search_snapshots(
@@ -3439,6 +3924,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
 
                 write_store_data(&store_data)
                     .map_err(|e| McpError::internal_error(e, None))?;
+                Self::record_revision(&params.workflow_id, "update_workflow");
 
                 let response = serde_json::json!({
                     "success": true,
@@ -3491,6 +3977,7 @@ RETURNS: List of created step IDs and their assigned order positions.")]
 
                 write_store_data(&store_data)
                     .map_err(|e| McpError::internal_error(e, None))?;
+                Self::record_revision(&params.workflow_id, "delete_workflow_step");
 
                 let response = serde_json::json!({
                     "success": true,
@@ -3981,6 +4468,7 @@ MCP TOOLS:
     add_workflow_step   Add a script step to a workflow
     update_workflow     Update workflow name/description
     delete_workflow_step Remove a step from a workflow
+    plan_workflow       Show what a workflow run would do without running it
     run_workflow        Execute a workflow synchronously
     get_workflow_execution_details Get execution logs
 
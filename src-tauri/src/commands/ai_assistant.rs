@@ -1155,6 +1155,12 @@ async fn execute_workflow_tool(
         .map(|w| w.name.clone())
         .unwrap_or_else(|| workflow_id.clone());
 
+    // Optional workflow parameters ({"name": value})
+    let params = tool_call
+        .arguments
+        .get("params")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+
     // Execute the workflow using the real execution function
     match execute_workflow_internal(
         app.clone(),
@@ -1162,6 +1168,7 @@ async fn execute_workflow_tool(
         workflow_id.clone(),
         None, // parent_execution_id
         None, // parent_node_id
+        params,
     ).await {
         Ok(execution_id) => {
             let output_json = serde_json::json!({
@@ -9,7 +9,7 @@ use crate::models::deploy::{
     DeploymentConfig, DeploymentStatus, DeploymentStatusEvent, GitHubWorkflowResult,
     OAuthFlowResult, PlatformType, RemoveAccountResult,
 };
-use crate::repositories::DeployRepository;
+use crate::repositories::{DeployRepository, SnapshotRepository};
 use crate::services::crypto;
 use crate::services::crypto::EncryptedData;
 use crate::services::deploy as deploy_service;
@@ -753,7 +753,14 @@ pub async fn start_deployment(
     let access_token = get_deployment_access_token(&app, &config)?;
 
     // Create deployment record
-    let deployment = Deployment::new(project_id.clone(), config.platform.clone());
+    let mut deployment = Deployment::new(project_id.clone(), config.platform.clone());
+
+    // Link the latest Time Machine snapshot so retention keeps what was deployed
+    deployment.snapshot_id = SnapshotRepository::new(get_db(&app))
+        .get_latest_snapshot(&project_path)
+        .ok()
+        .flatten()
+        .map(|snapshot| snapshot.id);
 
     // Save initial deployment to history
     save_deployment_to_history(&app, &deployment)?;
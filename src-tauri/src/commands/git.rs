@@ -9,7 +9,11 @@ use crate::models::git::{
     Branch, Commit, DiffHunk, DiffLine, DiffLineType, FileDiff, FileDiffStatus, GitFile,
     GitFileStatus, GitStatus, Stash,
 };
+use crate::models::GitTriggerEvent;
+use crate::services::git_trigger::{git_events_dir, run_pre_push_workflows, spawn_git_event};
 use crate::services::notification::{send_notification, NotificationType};
+use crate::services::workflow::git_trigger::{install_hooks, installed_hooks, uninstall_hooks};
+use crate::services::workflow::HOOK_SKIP_ENV;
 use crate::utils::path_resolver;
 
 // ============================================================================
@@ -271,6 +275,8 @@ pub fn exec_git(cwd: &Path, args: &[&str]) -> Result<String, String> {
         .env("GIT_TERMINAL_PROMPT", "0")
         // Use SSH agent if available, don't prompt for password
         .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
+        // Installed PackageFlow hooks stay silent; the app fires git triggers itself
+        .env(HOOK_SKIP_ENV, "1")
         .output()
         .map_err(|e| format!("Failed to execute git: {}", e))?;
 
@@ -579,6 +585,7 @@ pub async fn unstage_files(
 /// Create a new commit
 #[tauri::command]
 pub async fn create_commit(
+    app: AppHandle,
     project_path: String,
     message: String,
     amend_last: Option<bool>,
@@ -628,6 +635,7 @@ pub async fn create_commit(
         Ok(_) => {
             // Get the commit hash
             let hash = exec_git(path, &["rev-parse", "HEAD"]).unwrap_or_default();
+            spawn_git_event(&app, path, GitTriggerEvent::PostCommit, None);
             Ok(CreateCommitResponse {
                 success: true,
                 commit_hash: Some(hash),
@@ -799,6 +807,7 @@ pub async fn create_branch(
 /// Switch to a branch
 #[tauri::command]
 pub async fn switch_branch(
+    app: AppHandle,
     project_path: String,
     branch_name: String,
     force: Option<bool>,
@@ -817,6 +826,7 @@ pub async fn switch_branch(
     // Get current branch before switching
     let previous_branch = exec_git(path, &["rev-parse", "--abbrev-ref", "HEAD"])
         .unwrap_or_else(|_| "HEAD".to_string());
+    let previous_head = exec_git(path, &["rev-parse", "HEAD"]).ok();
 
     // Check if branch exists
     if exec_git(path, &["rev-parse", "--verify", &branch_name]).is_err() {
@@ -836,11 +846,23 @@ pub async fn switch_branch(
     };
 
     match result {
-        Ok(_) => Ok(SwitchBranchResponse {
-            success: true,
-            previous_branch: Some(previous_branch),
-            error: None,
-        }),
+        Ok(_) => {
+            if let (Some(previous_head), Ok(head)) =
+                (previous_head, exec_git(path, &["rev-parse", "HEAD"]))
+            {
+                spawn_git_event(
+                    &app,
+                    path,
+                    GitTriggerEvent::PostCheckout,
+                    Some((previous_head, head)),
+                );
+            }
+            Ok(SwitchBranchResponse {
+                success: true,
+                previous_branch: Some(previous_branch),
+                error: None,
+            })
+        }
         Err(e) => {
             if e.contains("local changes") || e.contains("uncommitted changes") {
                 return Ok(SwitchBranchResponse {
@@ -1060,6 +1082,22 @@ pub async fn git_push(
         });
     }
 
+    // pre-push workflows must complete before anything is pushed
+    if let Err(e) = run_pre_push_workflows(&app, path).await {
+        let error = format!("PRE_PUSH_FAILED: {}", e);
+        let _ = send_notification(
+            &app,
+            NotificationType::GitPushFailed {
+                project_name,
+                error: error.clone(),
+            },
+        );
+        return Ok(GitPushResponse {
+            success: false,
+            error: Some(error),
+        });
+    }
+
     match exec_git(path, &args) {
         Ok(_) => {
             // Send success notification
@@ -2645,3 +2683,91 @@ fn get_deleted_file_diff(repo_path: &Path, file_path: &str) -> Result<GetFileDif
         }),
     }
 }
+
+// ============================================================================
+// Git Hook Commands (workflow git triggers)
+// ============================================================================
+
+/// Response for git hook install/uninstall/status
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHooksResponse {
+    pub success: bool,
+    pub hooks_dir: Option<String>,
+    /// Hooks written by PackageFlow that are currently installed
+    pub installed: Vec<GitTriggerEvent>,
+    pub error: Option<String>,
+}
+
+impl GitHooksResponse {
+    fn error(error: String) -> Self {
+        Self {
+            success: false,
+            hooks_dir: None,
+            installed: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    fn status(hooks_dir: &Path) -> Self {
+        Self {
+            success: true,
+            hooks_dir: Some(hooks_dir.to_string_lossy().to_string()),
+            installed: installed_hooks(hooks_dir),
+            error: None,
+        }
+    }
+}
+
+/// Resolve the hooks directory of a repository (respects core.hooksPath and worktrees)
+fn git_hooks_dir(path: &Path) -> Result<std::path::PathBuf, String> {
+    let hooks = exec_git(path, &["rev-parse", "--git-path", "hooks"])?;
+    Ok(path.join(hooks))
+}
+
+/// Install hooks so commits, checkouts and pushes made outside the app start workflows
+#[tauri::command]
+pub async fn install_git_hooks(
+    project_path: String,
+    events: Option<Vec<GitTriggerEvent>>,
+) -> Result<GitHooksResponse, String> {
+    let path = Path::new(&project_path);
+    if !is_git_repo(path) {
+        return Ok(GitHooksResponse::error("NOT_GIT_REPO".to_string()));
+    }
+
+    let hooks_dir = git_hooks_dir(path)?;
+    let events = events.unwrap_or_else(|| GitTriggerEvent::ALL.to_vec());
+    if let Err(e) = install_hooks(&hooks_dir, &events, &git_events_dir()?) {
+        return Ok(GitHooksResponse::error(e));
+    }
+
+    Ok(GitHooksResponse::status(&hooks_dir))
+}
+
+/// Remove the hooks installed by PackageFlow; other hooks are left untouched
+#[tauri::command]
+pub async fn uninstall_git_hooks(project_path: String) -> Result<GitHooksResponse, String> {
+    let path = Path::new(&project_path);
+    if !is_git_repo(path) {
+        return Ok(GitHooksResponse::error("NOT_GIT_REPO".to_string()));
+    }
+
+    let hooks_dir = git_hooks_dir(path)?;
+    if let Err(e) = uninstall_hooks(&hooks_dir) {
+        return Ok(GitHooksResponse::error(e));
+    }
+
+    Ok(GitHooksResponse::status(&hooks_dir))
+}
+
+/// List the PackageFlow hooks installed in a repository
+#[tauri::command]
+pub async fn get_git_hooks_status(project_path: String) -> Result<GitHooksResponse, String> {
+    let path = Path::new(&project_path);
+    if !is_git_repo(path) {
+        return Ok(GitHooksResponse::error("NOT_GIT_REPO".to_string()));
+    }
+
+    Ok(GitHooksResponse::status(&git_hooks_dir(path)?))
+}
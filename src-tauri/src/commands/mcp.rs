@@ -506,7 +506,7 @@ pub fn clear_mcp_logs(db: tauri::State<'_, DatabaseState>) -> Result<(), String>
 
 use crate::models::mcp_action::{
     ActionFilter, ExecutionFilter, ExecutionStatus, MCPAction, MCPActionExecution,
-    MCPActionPermission, MCPActionType, PermissionLevel,
+    MCPActionPermission, MCPActionType, PermissionLevel, WORKFLOW_APPROVAL_SOURCE,
 };
 use crate::repositories::MCPActionRepository;
 
@@ -535,11 +535,21 @@ pub fn get_pending_action_requests(
     Ok(pending
         .into_iter()
         .map(|exec| {
-            let description = format!(
-                "Execute {} action: {}",
-                exec.action_type.to_string().to_lowercase(),
-                exec.action_name
-            );
+            // Workflow approval nodes carry their own message
+            let description = if exec.source_client.as_deref() == Some(WORKFLOW_APPROVAL_SOURCE) {
+                exec.parameters
+                    .as_ref()
+                    .and_then(|p| p.get("message"))
+                    .and_then(|m| m.as_str())
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| format!("Approve workflow step: {}", exec.action_name))
+            } else {
+                format!(
+                    "Execute {} action: {}",
+                    exec.action_type.to_string().to_lowercase(),
+                    exec.action_name
+                )
+            };
             PendingActionRequest {
                 execution_id: exec.id,
                 action_id: exec.action_id,
@@ -593,9 +603,18 @@ pub fn respond_to_action_request(
         ExecutionStatus::Denied
     };
 
-    let error_message = if approved { None } else { reason };
+    // For workflow approval nodes the reason doubles as the approval comment
+    let is_workflow_approval = execution.source_client.as_deref() == Some(WORKFLOW_APPROVAL_SOURCE);
+    let (result, error_message) = match (approved, is_workflow_approval) {
+        (true, true) => (
+            reason.map(|comment| serde_json::json!({ "comment": comment })),
+            None,
+        ),
+        (true, false) => (None, None),
+        (false, _) => (None, reason),
+    };
 
-    repo.update_execution_status(&execution_id, new_status.clone(), None, error_message)?;
+    repo.update_execution_status(&execution_id, new_status.clone(), result, error_message)?;
 
     // Emit event to notify MCP server and other listeners
     let _ = app.emit("mcp:action-response", serde_json::json!({
@@ -75,6 +75,44 @@ pub fn get_volta_wrapped_command(
     (path_resolver::get_tool_path(base_command), base_args)
 }
 
+/// Returns (command, args) running the command with a specific Node.js version via
+/// `volta run --node <version>`, overriding any project pin. Fails when Volta is not installed.
+pub fn get_volta_pinned_command(
+    project_path: &Path,
+    node_version: &str,
+    base_command: &str,
+    base_args: Vec<String>,
+) -> Result<(String, Vec<String>), String> {
+    let pin = ["--node".to_string(), node_version.to_string()];
+
+    let (command, mut args) = get_volta_wrapped_command(project_path, base_command, base_args);
+    let volta_wrapped = args.len() >= 2 && args[0] == "run" && args[1] == base_command;
+    if volta_wrapped {
+        args.splice(1..1, pin);
+        return Ok((command, args));
+    }
+
+    let volta_status = detect_volta();
+    if !volta_status.available {
+        return Err(format!(
+            "Volta is required to run with Node {} but was not found",
+            node_version
+        ));
+    }
+    let volta_command = volta_status
+        .path
+        .unwrap_or_else(|| path_resolver::get_tool_path("volta"));
+    let mut volta_args = vec!["run".to_string()];
+    volta_args.extend(pin);
+    volta_args.push(base_command.to_string());
+    volta_args.extend(args);
+    println!(
+        "[version-manager] Using Volta: {} run --node {} {} ...",
+        volta_command, node_version, base_command
+    );
+    Ok((volta_command, volta_args))
+}
+
 // ============================================================================
 // Tool Detection Commands
 // ============================================================================
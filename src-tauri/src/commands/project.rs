@@ -518,8 +518,11 @@ pub async fn get_workspace_packages(
     _app: tauri::AppHandle,
     project_path: String,
 ) -> Result<Vec<WorkspacePackage>, String> {
-    let path = Path::new(&project_path);
+    list_workspace_packages(Path::new(&project_path))
+}
 
+/// Workspace packages of a project (empty when it is not a monorepo)
+pub fn list_workspace_packages(path: &Path) -> Result<Vec<WorkspacePackage>, String> {
     // Validate path exists
     if !path.exists() || !path.is_dir() {
         return Err("INVALID_PATH".to_string());
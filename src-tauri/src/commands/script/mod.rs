@@ -23,7 +23,7 @@ pub use types::{
 
 // Internal imports
 use chrono::Utc;
-use output::{handle_process_completion, stream_output, StreamType};
+use output::{create_script_log, handle_process_completion, stream_output, StreamType};
 use process::{cleanup_expired_executions, kill_process_tree};
 use std::path::Path;
 use std::process::Stdio;
@@ -35,6 +35,7 @@ use uuid::Uuid;
 use crate::commands::project::parse_package_json;
 use crate::commands::version::detect_volta;
 use crate::utils::path_resolver;
+use crate::utils::shell::resolve_command;
 
 // ============================================================================
 // Commands
@@ -171,6 +172,7 @@ pub async fn execute_script(
     let started_at_iso = Utc::now().to_rfc3339();
     let stored_path = stored_project_path.clone();
     let stored_name = project_name.clone();
+    let log = create_script_log(&app, &execution_id, &script_name, &stored_path);
     {
         let state = app.state::<ScriptExecutionState>();
         let mut executions = state.executions.write().await;
@@ -191,6 +193,7 @@ pub async fn execute_script(
                 status: ExecutionStatus::Running,
                 exit_code: None,
                 completed_at: None,
+                log,
             },
         );
     }
@@ -238,6 +241,32 @@ pub async fn execute_command(
     project_path: Option<String>, // Feature 007: Optional project root
     project_name: Option<String>, // Feature 007: Optional project name
 ) -> Result<ExecuteScriptResponse, String> {
+    // Without args, `command` is a full command line split like a script node's command.
+    // No shell is started here, so shell syntax is refused rather than passed literally
+    let (command, args) = if args.is_empty() {
+        match resolve_command(&command, None) {
+            Ok(invocation) if !invocation.via_shell => (invocation.program, invocation.args),
+            Ok(_) => {
+                return Ok(ExecuteScriptResponse {
+                    success: false,
+                    execution_id: None,
+                    error: Some(
+                        "Shell syntax (pipes, redirects, globs, variables) is not supported; use a workflow script node instead".to_string(),
+                    ),
+                });
+            }
+            Err(e) => {
+                return Ok(ExecuteScriptResponse {
+                    success: false,
+                    execution_id: None,
+                    error: Some(e),
+                });
+            }
+        }
+    } else {
+        (command, args)
+    };
+
     // Validate allowed commands
     let allowed_commands = [
         // Package managers
@@ -394,6 +423,8 @@ pub async fn execute_command(
     // Feature 007: Store execution state with extended fields
     let started_at_iso = Utc::now().to_rfc3339();
     let stored_path = project_path.unwrap_or_else(|| cwd.clone());
+    let script_name = format!("{} {}", command, args.join(" "));
+    let log = create_script_log(&app, &execution_id, &script_name, &stored_path);
     {
         let state = app.state::<ScriptExecutionState>();
         let mut executions = state.executions.write().await;
@@ -401,7 +432,7 @@ pub async fn execute_command(
             execution_id.clone(),
             RunningExecution {
                 execution_id: execution_id.clone(),
-                script_name: format!("{} {}", command, args.join(" ")),
+                script_name,
                 started_at: start_time,
                 child: Some(child),
                 stdin,
@@ -414,6 +445,7 @@ pub async fn execute_command(
                 status: ExecutionStatus::Running,
                 exit_code: None,
                 completed_at: None,
+                log,
             },
         );
     }
@@ -10,6 +10,37 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 
 use super::state::ScriptExecutionState;
 use super::types::{OutputLine, ScriptCompletedPayload, ScriptOutputPayload};
+use crate::models::{ExecutionLogInfo, ExecutionLogKind};
+use crate::services::execution_log::{execution_logs_dir, ExecutionLogSender, ExecutionLogWriter};
+use crate::DatabaseState;
+
+// ============================================================================
+// Persisted Log
+// ============================================================================
+
+/// Start the persisted log of a script execution
+pub fn create_script_log(
+    app: &AppHandle,
+    execution_id: &str,
+    name: &str,
+    project_path: &str,
+) -> Option<ExecutionLogSender> {
+    use tauri::Manager;
+    let info = ExecutionLogInfo {
+        project_path: Some(project_path.to_string()),
+        ..ExecutionLogInfo::new(
+            execution_id.to_string(),
+            ExecutionLogKind::Script,
+            name.to_string(),
+        )
+    };
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    execution_logs_dir()
+        .and_then(|dir| ExecutionLogWriter::create(db, &dir, info))
+        .map(ExecutionLogWriter::spawn)
+        .map_err(|e| log::warn!("[script] Failed to create execution log: {}", e))
+        .ok()
+}
 
 // ============================================================================
 // Output Batcher
@@ -79,6 +110,9 @@ impl OutputBatcher {
         {
             let mut executions = state.executions.write().await;
             if let Some(exec) = executions.get_mut(&self.execution_id) {
+                if let Some(log) = &exec.log {
+                    log.append(None, &self.stream_type, &timestamp, &self.buffer);
+                }
                 exec.output_buffer.push(OutputLine {
                     content: self.buffer.clone(),
                     stream: self.stream_type.clone(),
@@ -173,6 +207,17 @@ pub async fn handle_process_completion(
     let state = app.state::<ScriptExecutionState>();
     let mut executions = state.executions.write().await;
     if let Some(exec) = executions.get_mut(&execution_id) {
+        let log_status = if exec.status == ExecutionStatus::Cancelled {
+            "cancelled"
+        } else if exit_code == 0 {
+            "completed"
+        } else {
+            "failed"
+        };
+        if let Some(log) = exec.log.take() {
+            log.finish(log_status);
+        }
+
         exec.status = if exit_code == 0 {
             ExecutionStatus::Completed
         } else {
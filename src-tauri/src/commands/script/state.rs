@@ -8,6 +8,7 @@ use tokio::process::{Child, ChildStdin};
 use tokio::sync::RwLock;
 
 use super::types::{ExecutionStatus, OutputBuffer};
+use crate::services::execution_log::ExecutionLogSender;
 
 /// Execution state stored in app state
 pub struct ScriptExecutionState {
@@ -41,4 +42,6 @@ pub struct RunningExecution {
     pub status: ExecutionStatus,
     pub exit_code: Option<i32>,
     pub completed_at: Option<String>,
+    /// Persisted log of the full output (None if it could not be created)
+    pub log: Option<ExecutionLogSender>,
 }
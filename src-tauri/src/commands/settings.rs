@@ -2,7 +2,7 @@
 // Implements US7: Data Persistence Across Sessions
 // Updated to use SQLite database for storage
 
-use crate::models::{Project, Workflow};
+use crate::models::{Project, RevisionSource, Workflow};
 use crate::repositories::{ProjectRepository, SettingsRepository, WorkflowRepository};
 use crate::services::crypto;
 use crate::utils::database::get_database_path;
@@ -262,8 +262,8 @@ pub async fn save_workflows(
             None
         };
 
-        // Step 1: Save workflow first
-        repo.save(&workflow_to_save)?;
+        // Step 1: Save workflow first (unchanged workflows keep their revision)
+        repo.save_with_revision(&workflow_to_save, RevisionSource::Ui, None)?;
 
         // Step 2: Now save the token (after workflow exists in DB)
         if let Some(token) = token_to_save {
@@ -1,7 +1,7 @@
 // Snapshot Commands
 // Tauri commands for Time Machine functionality
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, State};
@@ -9,12 +9,16 @@ use tauri::{AppHandle, Manager, State};
 use crate::models::ai::{ChatMessage, ChatOptions, FinishReason};
 use crate::models::security_insight::{InsightSummary, SecurityInsight};
 use crate::models::snapshot::{
-    CreateSnapshotRequest, ExecutionSnapshot, SnapshotDiff, SnapshotFilter, SnapshotListItem,
+    CreateSnapshotRequest, ExecutionSnapshot, InstallReason, SnapshotBundleExport,
+    SnapshotBundleImport, SnapshotDiff, SnapshotFilter, SnapshotListItem, SnapshotRetentionResult,
     SnapshotWithDependencies, TimeMachineSettings,
 };
 use crate::repositories::{AIRepository, SnapshotRepository};
 use crate::services::ai::{create_provider, AIKeychain};
-use crate::services::snapshot::{SnapshotCaptureService, SnapshotDiffService, SnapshotStorage};
+use crate::services::snapshot::{
+    SnapshotBundleService, SnapshotCaptureService, SnapshotDiffService, SnapshotGraphService,
+    SnapshotRetentionService, SnapshotStorage,
+};
 use crate::utils::database::Database;
 use crate::DatabaseState;
 
@@ -115,6 +119,7 @@ pub async fn delete_snapshot(
 
         // Delete file storage first
         storage.delete_snapshot(&snapshot_id)?;
+        storage.prune_blobs()?;
 
         // Delete database record
         repo.delete_snapshot(&snapshot_id)
@@ -123,6 +128,23 @@ pub async fn delete_snapshot(
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// Pin or unpin a snapshot so retention never removes it
+#[tauri::command]
+pub async fn set_snapshot_pinned(
+    db: State<'_, DatabaseState>,
+    snapshot_id: String,
+    pinned: bool,
+) -> Result<bool, String> {
+    let db = (*db.0).clone();
+
+    tokio::task::spawn_blocking(move || {
+        let repo = SnapshotRepository::new(db);
+        repo.set_snapshot_pinned(&snapshot_id, pinned)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
 /// Prune old snapshots (keep last N per workflow)
 #[tauri::command]
 pub async fn prune_snapshots(
@@ -273,6 +295,44 @@ pub async fn analyze_diff_patterns(
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+// =========================================================================
+// Dependency Graph
+// =========================================================================
+
+/// Explain why a package is in a snapshot (every path from a direct dependency)
+#[tauri::command]
+pub async fn why_installed(
+    db: State<'_, DatabaseState>,
+    snapshot_id: String,
+    package_name: String,
+    version: Option<String>,
+) -> Result<Vec<InstallReason>, String> {
+    let db = (*db.0).clone();
+
+    tokio::task::spawn_blocking(move || {
+        let service = SnapshotGraphService::new(db);
+        service.why_installed(&snapshot_id, &package_name, version.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Explain what pulled in each package with a postinstall script
+#[tauri::command]
+pub async fn get_postinstall_origins(
+    db: State<'_, DatabaseState>,
+    snapshot_id: String,
+) -> Result<Vec<InstallReason>, String> {
+    let db = (*db.0).clone();
+
+    tokio::task::spawn_blocking(move || {
+        let service = SnapshotGraphService::new(db);
+        service.postinstall_origins(&snapshot_id)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
 // =========================================================================
 // Security Insights
 // =========================================================================
@@ -347,12 +407,19 @@ pub async fn get_snapshot_storage_stats(
 
         let mut total_size = 0u64;
         let snapshot_count = snapshots.len();
+        let mut blobs = std::collections::HashSet::new();
 
         for snapshot in &snapshots {
             if let Ok(size) = storage.get_snapshot_size(&snapshot.id) {
                 total_size += size;
             }
+            blobs.extend(storage.lockfile_blob_hash(&snapshot.id));
         }
+        // Shared lockfiles count once
+        total_size += blobs
+            .iter()
+            .map(|hash| storage.blob_size(hash))
+            .sum::<u64>();
 
         Ok(SnapshotStorageStats {
             total_snapshots: snapshot_count,
@@ -381,7 +448,27 @@ pub async fn cleanup_orphaned_storage(
         let snapshots = repo.list_snapshots(&filter)?;
         let valid_ids: Vec<String> = snapshots.iter().map(|s| s.id.clone()).collect();
 
-        storage.cleanup_orphaned(&valid_ids)
+        let removed = storage.cleanup_orphaned(&valid_ids)?;
+        storage.prune_blobs()?;
+        Ok(removed)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Apply the saved retention policy now, to one project or all projects
+#[tauri::command]
+pub async fn apply_snapshot_retention(
+    db: State<'_, DatabaseState>,
+    project_path: Option<String>,
+) -> Result<SnapshotRetentionResult, String> {
+    let db = (*db.0).clone();
+    let base_path = get_storage_base_path()?;
+
+    tokio::task::spawn_blocking(move || {
+        let settings = SnapshotRepository::new(db.clone()).get_time_machine_settings()?;
+        let service = SnapshotRetentionService::new(SnapshotStorage::new(base_path), db);
+        service.apply_retention(&settings.retention, project_path.as_deref())
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
@@ -693,6 +780,50 @@ pub async fn restore_lockfile(
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
+// =========================================================================
+// Snapshot Bundles
+// =========================================================================
+
+/// Export a snapshot as a signed bundle file to share with teammates
+#[tauri::command]
+pub async fn export_snapshot_bundle(
+    db: State<'_, DatabaseState>,
+    snapshot_id: String,
+    output_path: String,
+    signing_secret: String,
+) -> Result<SnapshotBundleExport, String> {
+    let db = (*db.0).clone();
+    let base_path = get_storage_base_path()?;
+
+    tokio::task::spawn_blocking(move || {
+        let storage = SnapshotStorage::new(base_path);
+        let service = SnapshotBundleService::new(storage, db);
+        service.export_bundle(&snapshot_id, Path::new(&output_path), &signing_secret)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Import a snapshot bundle as a snapshot of a local project, ready to replay
+#[tauri::command]
+pub async fn import_snapshot_bundle(
+    db: State<'_, DatabaseState>,
+    bundle_path: String,
+    project_path: String,
+    signing_secret: String,
+) -> Result<SnapshotBundleImport, String> {
+    let db = (*db.0).clone();
+    let base_path = get_storage_base_path()?;
+
+    tokio::task::spawn_blocking(move || {
+        let storage = SnapshotStorage::new(base_path);
+        let service = SnapshotBundleService::new(storage, db);
+        service.import_bundle(Path::new(&bundle_path), &project_path, &signing_secret)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
 // =========================================================================
 // Security Insights Dashboard (US5)
 // =========================================================================
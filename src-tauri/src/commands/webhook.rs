@@ -6,6 +6,10 @@ use chrono::Utc;
 use std::collections::HashMap;
 
 use crate::models::webhook::{WebhookTestResult, DEFAULT_PAYLOAD_TEMPLATE, SUPPORTED_VARIABLES};
+use crate::services::outgoing_http;
+
+/// Timeout of a test delivery
+const TEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 /// Test a webhook by sending a test request
 /// Returns the result including status code, response time, and any errors
@@ -31,24 +35,14 @@ pub async fn test_webhook(
         .unwrap_or(DEFAULT_PAYLOAD_TEMPLATE);
     let payload = render_test_template(template);
 
-    // Build HTTP client with 10 second timeout
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    // Build request
-    let mut request = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .body(payload);
-
-    // Add custom headers
-    if let Some(h) = &headers {
-        for (key, value) in h {
-            request = request.header(key, value);
-        }
-    }
+    let request = outgoing_http::build_request(
+        reqwest::Method::POST,
+        &url,
+        headers.as_ref(),
+        Some(payload),
+        None,
+        TEST_TIMEOUT,
+    );
 
     // Send request
     match request.send().await {
@@ -90,13 +84,7 @@ pub async fn test_webhook(
         }
         Err(e) => {
             let response_time = start.elapsed().as_millis() as u64;
-            let error_msg = if e.is_timeout() {
-                "Request timed out (10s)".to_string()
-            } else if e.is_connect() {
-                "Connection failed".to_string()
-            } else {
-                e.to_string()
-            };
+            let error_msg = outgoing_http::describe_send_error(&e, TEST_TIMEOUT);
 
             println!("[webhook] Test error: {} ({}ms)", error_msg, response_time);
 
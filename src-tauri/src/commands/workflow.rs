@@ -3,28 +3,72 @@
 // Updated to use SQLite database for storage
 
 use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use uuid::Uuid;
 
-use crate::commands::monorepo::get_volta_wrapped_command;
+use crate::commands::monorepo::{get_volta_pinned_command, get_volta_wrapped_command};
+use crate::commands::project::list_workspace_packages;
+use crate::models::execution::{
+    ApprovalStatus, MatrixCellResult, NodeApproval, NodeAttempt, NodeResult, NodeStatus,
+    SkipReason, ACTIVE_EXECUTION_SOURCE_APP,
+};
+use crate::models::mcp_action::{
+    ExecutionStatus as ActionStatus, MCPActionType, WORKFLOW_APPROVAL_SOURCE,
+};
 use crate::models::webhook::{
     WebhookConfig, WebhookDeliveryPayload, WebhookTrigger, DEFAULT_PAYLOAD_TEMPLATE,
 };
-use crate::models::{Execution, ExecutionStatus, Project, Workflow, WorkflowNode};
-use crate::repositories::{ExecutionRepository, ProjectRepository, WorkflowRepository};
+use crate::models::{
+    ActiveExecution, Execution, ExecutionArtifact, ExecutionLogInfo, ExecutionLogKind,
+    ExecutionLogPage, ExecutionLogSearchHit, ExecutionStatus, NodeMatrix, Project, ResumeOrigin,
+    RevisionSource, Workflow, WorkflowConcurrencySettings, WorkflowFile, WorkflowFileError,
+    WorkflowFileFormat, WorkflowFileState, WorkflowFileStatus, WorkflowNode, WorkflowPlan,
+    WorkflowRevision, WorkflowRevisionDiff, WorkflowScheduleState, WorkflowSecretInfo,
+    WorkflowStats, WorkspacePackage,
+};
+use crate::repositories::{
+    ActiveExecutionRepository, ArtifactRepository, ExecutionLogRepository, ExecutionRepository,
+    MCPActionRepository, ProjectRepository, ScheduleRepository, WorkflowRepository,
+};
 use crate::services::crypto;
+use crate::services::execution_log::{
+    self, delete_execution_log, execution_logs_dir, ExecutionLogSender, ExecutionLogWriter,
+};
+use crate::services::file_watcher::WorkflowFileTriggerManager;
 use crate::services::notification::{
     send_notification, send_webhook_notification, NotificationType, WebhookNotificationType,
 };
+use crate::services::outgoing_http;
+use crate::services::workflow::condition::{
+    current_git_branch, current_git_commit, uses_git_branch,
+};
+use crate::services::workflow::dag::find_path;
+use crate::services::workflow::{
+    admit_execution, apply_workflow_file, collect_artifacts, compute_workflow_stats,
+    delete_execution_artifacts, diff_revisions, evaluate_condition, execution_artifacts_dir,
+    expand_matrix, export_artifacts, export_workflow_file, extract_outputs, interpolate,
+    interpolate_command, is_same_definition, mask_secret_values, merge_env, parse_set_output,
+    plan_resume, plan_workflow, queue_positions, read_workflow_file, render_placeholders,
+    resolve_env, resolve_parameters, restore_revision, retry_delay, retry_output_patterns,
+    secret_references, should_retry, startable_executions, status_matches, trash_targets,
+    validate_artifact_pattern, validate_env, validate_file_trigger, validate_git_trigger,
+    validate_http_config, validate_parameters, validate_schedule, workflow_file_statuses,
+    ArtifactOwner, ConditionContext, ConditionOutcome, CronSchedule, DagScheduler,
+    InterpolationContext, MatrixCell, PlanExecutor, StatsSample, StepOutputs, WorkflowDag,
+    DEFAULT_HTTP_TIMEOUT_MS, DEFAULT_MAX_PARALLELISM, MAX_NODE_ARTIFACT_BYTES,
+    QUEUE_POLL_INTERVAL_MS, WORKFLOW_CONCURRENCY_SETTINGS_KEY,
+};
 use crate::utils::database::Database;
 use crate::utils::path_resolver;
+use crate::utils::shell::{resolve_command, CommandInvocation};
 use crate::DatabaseState;
 
 // Unix-specific imports for process signal handling
@@ -117,27 +161,83 @@ impl Default for WorkflowOutputBuffer {
 pub struct WorkflowExecutionState {
     /// Map of execution_id -> running execution
     pub executions: Mutex<HashMap<String, RunningWorkflowExecution>>,
+    /// Map of execution_id -> execution waiting for a concurrency slot
+    queued: Mutex<HashMap<String, QueuedWorkflowExecution>>,
 }
 
 impl Default for WorkflowExecutionState {
     fn default() -> Self {
         Self {
             executions: Mutex::new(HashMap::new()),
+            queued: Mutex::new(HashMap::new()),
         }
     }
 }
 
+/// Execution waiting in the run queue, started by `start_queued_executions`
+struct QueuedWorkflowExecution {
+    ctx: WorkflowExecutionContext,
+    workflow: Workflow,
+    execution: Execution,
+    project_path: Option<String>,
+}
+
 /// Running workflow execution info
 pub struct RunningWorkflowExecution {
     pub execution: Execution,
     pub workflow: Workflow,
-    pub current_node_index: usize,
+    /// IDs of nodes currently running (several when DAG branches run in parallel)
+    pub running_node_ids: Vec<String>,
     pub is_paused: bool,
     pub should_cancel: bool,
-    /// PID of currently running child process (for killing on cancel)
-    pub current_process_id: Option<u32>,
+    /// PIDs of running child processes by node ID (for killing on cancel)
+    pub process_ids: HashMap<String, u32>,
     /// Output buffer for storing execution output history
     pub output_buffer: WorkflowOutputBuffer,
+    /// Persisted log of the full output (None if it could not be created)
+    pub log: Option<ExecutionLogSender>,
+}
+
+impl RunningWorkflowExecution {
+    /// Buffer an output line and queue it for the persisted log
+    pub fn push_output(&mut self, line: WorkflowOutputLine) {
+        if let Some(log) = &self.log {
+            let node = Some((line.node_id.as_str(), line.node_name.as_str()));
+            log.append(node, &line.stream, &line.timestamp, &line.content);
+        }
+        self.output_buffer.push(line);
+    }
+
+    /// Complete the persisted log with the final status (written by the log thread)
+    pub fn finish_log(&mut self, status: &str) {
+        if let Some(log) = self.log.take() {
+            log.finish(status);
+        }
+    }
+}
+
+/// Start the persisted log of a workflow execution
+fn create_execution_log(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow: &Workflow,
+    project_path: Option<&str>,
+) -> Option<ExecutionLogSender> {
+    let info = ExecutionLogInfo {
+        workflow_id: Some(workflow.id.clone()),
+        project_path: project_path.map(str::to_string),
+        ..ExecutionLogInfo::new(
+            execution_id.to_string(),
+            ExecutionLogKind::Workflow,
+            workflow.name.clone(),
+        )
+    };
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    execution_logs_dir()
+        .and_then(|dir| ExecutionLogWriter::create(db, &dir, info))
+        .map(ExecutionLogWriter::spawn)
+        .map_err(|e| log::warn!("[workflow] Failed to create execution log: {}", e))
+        .ok()
 }
 
 // ============================================================================
@@ -278,6 +378,40 @@ pub async fn save_workflow(
         }
     }
 
+    // Reject dependency cycles, unknown dependencies, invalid parameters, invalid
+    // cron schedules, file or git triggers, plaintext credentials in env and artifact
+    // patterns outside the working directory before persisting
+    WorkflowDag::new(&workflow.nodes)?;
+    validate_parameters(&workflow.parameters)?;
+    if let Some(ref schedule) = workflow.schedule {
+        validate_schedule(schedule)?;
+    }
+    if let Some(ref trigger) = workflow.file_trigger {
+        validate_file_trigger(trigger)?;
+        if trigger.enabled && workflow.project_id.is_none() {
+            return Err("File triggers require the workflow to belong to a project".to_string());
+        }
+    }
+    if let Some(ref trigger) = workflow.git_trigger {
+        validate_git_trigger(trigger)?;
+        if trigger.enabled && workflow.project_id.is_none() {
+            return Err("Git triggers require the workflow to belong to a project".to_string());
+        }
+    }
+    validate_env(&workflow.env)?;
+    for node in &workflow.nodes {
+        if let Some(config) = node.get_script_config() {
+            validate_env(&config.env).map_err(|e| format!("Node '{}': {}", node.name, e))?;
+            for pattern in &config.artifacts {
+                validate_artifact_pattern(pattern)
+                    .map_err(|e| format!("Node '{}': {}", node.name, e))?;
+            }
+        }
+        if let Some(config) = node.get_http_config() {
+            validate_http_config(&config).map_err(|e| format!("Node '{}': {}", node.name, e))?;
+        }
+    }
+
     let repo = WorkflowRepository::new(db.0.as_ref().clone());
 
     // IMPORTANT: Save workflow FIRST, then token
@@ -300,7 +434,7 @@ pub async fn save_workflow(
     };
 
     // Step 1: Save workflow first (this may trigger cascade delete on old token)
-    repo.save(&workflow_to_save)?;
+    repo.save_with_revision(&workflow_to_save, RevisionSource::Ui, None)?;
     println!("[workflow] Saved workflow to database: {}", workflow.name);
 
     // Step 2: Now save the token (after workflow exists in DB)
@@ -323,12 +457,19 @@ pub async fn save_workflow(
         );
     }
 
-    // Sync incoming webhook server state
-    if let Err(e) = crate::commands::incoming_webhook::sync_incoming_webhook_server(&app).await {
+    sync_workflow_triggers(&app, db.0.as_ref()).await;
+    Ok(())
+}
+
+/// Refresh trigger state (incoming webhook server, file triggers) after workflows changed
+async fn sync_workflow_triggers(app: &AppHandle, db: &Database) {
+    if let Err(e) = crate::commands::incoming_webhook::sync_incoming_webhook_server(app).await {
         log::warn!("[workflow] Failed to sync incoming webhook server: {}", e);
     }
 
-    Ok(())
+    if let Err(e) = app.state::<WorkflowFileTriggerManager>().sync(app, db) {
+        log::warn!("[workflow] Failed to sync workflow file triggers: {}", e);
+    }
 }
 
 /// Delete a workflow from SQLite database
@@ -341,22 +482,243 @@ pub async fn delete_workflow(
     let repo = WorkflowRepository::new(db.0.as_ref().clone());
     repo.delete(&workflow_id)?;
 
-    // Sync incoming webhook server state
-    if let Err(e) = crate::commands::incoming_webhook::sync_incoming_webhook_server(&app).await {
-        log::warn!("[workflow] Failed to sync incoming webhook server: {}", e);
+    sync_workflow_triggers(&app, db.0.as_ref()).await;
+    Ok(())
+}
+
+/// List stored workflow secrets (names only, values are never returned)
+#[tauri::command]
+pub async fn list_workflow_secrets(
+    db: tauri::State<'_, DatabaseState>,
+) -> Result<Vec<WorkflowSecretInfo>, String> {
+    let repo = WorkflowRepository::new(db.0.as_ref().clone());
+    repo.list_secrets()
+}
+
+/// Encrypt and store a secret referenced from workflow env as `{{secrets.<name>}}`
+#[tauri::command]
+pub async fn save_workflow_secret(
+    db: tauri::State<'_, DatabaseState>,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid secret name '{}': use letters, digits, '_' or '-'",
+            name
+        ));
     }
 
-    Ok(())
+    let encrypted =
+        crypto::encrypt(&value).map_err(|e| format!("Failed to encrypt secret: {}", e))?;
+    let repo = WorkflowRepository::new(db.0.as_ref().clone());
+    repo.store_secret(&name, &encrypted.ciphertext, &encrypted.nonce)
+}
+
+/// Delete a workflow secret
+#[tauri::command]
+pub async fn delete_workflow_secret(
+    db: tauri::State<'_, DatabaseState>,
+    name: String,
+) -> Result<bool, String> {
+    let repo = WorkflowRepository::new(db.0.as_ref().clone());
+    repo.delete_secret(&name)
+}
+
+/// Get the background scheduler state (next run, last run) of scheduled workflows
+#[tauri::command]
+pub async fn get_workflow_schedule_states(
+    db: tauri::State<'_, DatabaseState>,
+) -> Result<Vec<WorkflowScheduleState>, String> {
+    let repo = ScheduleRepository::new(db.0.as_ref().clone());
+    repo.list_states()
+}
+
+/// Preview the next run times (local time, RFC 3339) of a cron expression
+#[tauri::command]
+pub async fn preview_workflow_schedule(
+    cron: String,
+    count: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let schedule = CronSchedule::parse(&cron)?;
+    let mut runs = Vec::new();
+    let mut after = chrono::Local::now();
+    while runs.len() < count.unwrap_or(5).min(50) {
+        match schedule.next_after(&after) {
+            Some(next) => {
+                runs.push(next.to_rfc3339());
+                after = next;
+            }
+            None => break,
+        }
+    }
+    Ok(runs)
+}
+
+// ============================================================================
+// Workflow Files (.packageflow/workflows)
+// ============================================================================
+
+/// Result of importing a project's workflow files
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileSyncResult {
+    /// IDs of workflows created or updated from files
+    pub imported: Vec<String>,
+    /// File statuses before the sync (failed imports are marked invalid)
+    pub statuses: Vec<WorkflowFileStatus>,
+}
+
+fn get_project(db: &Database, project_id: &str) -> Result<Project, String> {
+    ProjectRepository::new(db.clone())
+        .get(project_id)?
+        .ok_or_else(|| "Project not found".to_string())
+}
+
+fn join_file_errors(errors: &[WorkflowFileError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Store a workflow read from a file of `project_id`
+fn import_workflow_file(
+    db: &Database,
+    file: WorkflowFile,
+    project_id: &str,
+) -> Result<Workflow, String> {
+    let repo = WorkflowRepository::new(db.clone());
+    let existing = repo.get(&file.id)?;
+    if let Some(other) = existing.as_ref().and_then(|w| w.project_id.as_deref()) {
+        if other != project_id {
+            return Err(format!(
+                "Workflow '{}' already belongs to another project",
+                file.id
+            ));
+        }
+    }
+
+    let workflow = apply_workflow_file(file, existing.as_ref(), Some(project_id.to_string()));
+    repo.save_with_revision(&workflow, RevisionSource::File, None)?;
+    Ok(workflow)
+}
+
+/// Compare a project's workflow files with its stored workflows
+#[tauri::command]
+pub async fn get_workflow_files_status(
+    db: tauri::State<'_, DatabaseState>,
+    project_id: String,
+) -> Result<Vec<WorkflowFileStatus>, String> {
+    let db = db.0.as_ref().clone();
+    let project = get_project(&db, &project_id)?;
+    let workflows = WorkflowRepository::new(db).list_by_project(&project_id)?;
+    Ok(workflow_file_statuses(Path::new(&project.path), &workflows))
+}
+
+/// Write a project workflow to `.packageflow/workflows` and return the file path
+#[tauri::command]
+pub async fn export_workflow_to_file(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+    format: Option<WorkflowFileFormat>,
+) -> Result<String, String> {
+    let db = db.0.as_ref().clone();
+    let workflow = WorkflowRepository::new(db.clone())
+        .get(&workflow_id)?
+        .ok_or_else(|| "Workflow not found".to_string())?;
+    let project_id = workflow
+        .project_id
+        .as_deref()
+        .ok_or_else(|| "Only project workflows can be exported to a workflow file".to_string())?;
+    let project = get_project(&db, project_id)?;
+
+    let path = export_workflow_file(Path::new(&project.path), &workflow, format)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Import one workflow file (path absolute or relative to the project root)
+#[tauri::command]
+pub async fn import_workflow_from_file(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+    project_id: String,
+    file_path: String,
+) -> Result<Workflow, String> {
+    let db = db.0.as_ref().clone();
+    let project = get_project(&db, &project_id)?;
+
+    let path = Path::new(&project.path).join(&file_path);
+    let file = read_workflow_file(&path).map_err(|errors| join_file_errors(&errors))?;
+    let workflow = import_workflow_file(&db, file, &project_id)?;
+
+    sync_workflow_triggers(&app, &db).await;
+    Ok(workflow)
+}
+
+/// Import every new or changed workflow file of a project
+#[tauri::command]
+pub async fn sync_workflow_files(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+    project_id: String,
+) -> Result<WorkflowFileSyncResult, String> {
+    let db = db.0.as_ref().clone();
+    let project = get_project(&db, &project_id)?;
+    let project_path = Path::new(&project.path);
+    let workflows = WorkflowRepository::new(db.clone()).list_by_project(&project_id)?;
+
+    let mut statuses = workflow_file_statuses(project_path, &workflows);
+    let mut imported = Vec::new();
+    for status in &mut statuses {
+        let Some(path) = status.path.as_deref() else {
+            continue;
+        };
+        if !matches!(
+            status.state,
+            WorkflowFileState::New | WorkflowFileState::Modified
+        ) {
+            continue;
+        }
+
+        let result = read_workflow_file(&project_path.join(path))
+            .map_err(|errors| join_file_errors(&errors))
+            .and_then(|file| import_workflow_file(&db, file, &project_id));
+        match result {
+            Ok(workflow) => imported.push(workflow.id),
+            Err(e) => {
+                log::warn!("[workflow] Failed to import {}: {}", path, e);
+                status.state = WorkflowFileState::Invalid;
+                status.errors.push(WorkflowFileError {
+                    node_index: None,
+                    node_id: None,
+                    message: e,
+                });
+            }
+        }
+    }
+
+    if !imported.is_empty() {
+        sync_workflow_triggers(&app, &db).await;
+    }
+    Ok(WorkflowFileSyncResult { imported, statuses })
 }
 
 /// Execute a workflow (internal implementation)
 /// Takes Database directly for use from non-command contexts (e.g., incoming webhooks)
+/// `params` are validated against the workflow's declared parameters (defaults applied)
 pub async fn execute_workflow_internal(
     app: AppHandle,
     db: Database,
     workflow_id: String,
     parent_execution_id: Option<String>,
     parent_node_id: Option<String>,
+    params: Option<HashMap<String, serde_json::Value>>,
 ) -> Result<String, String> {
     println!(
         "[workflow] execute_workflow called with id: {}, parent: {:?}",
@@ -380,6 +742,8 @@ pub async fn execute_workflow_internal(
         workflow.nodes.len()
     );
 
+    let parameters = resolve_parameters(&workflow.parameters, &params.unwrap_or_default())?;
+
     // Calculate depth from parent execution (Feature 013: T009)
     let depth = if let Some(ref parent_exec_id) = parent_execution_id {
         let state = app.state::<WorkflowExecutionState>();
@@ -419,7 +783,7 @@ pub async fn execute_workflow_internal(
 
     // Create execution with parent tracking (Feature 013)
     let execution_id = Uuid::new_v4().to_string();
-    let execution = if let (Some(ref parent_exec_id), Some(ref parent_node)) =
+    let mut execution = if let (Some(ref parent_exec_id), Some(ref parent_node)) =
         (&parent_execution_id, &parent_node_id)
     {
         Execution::new_child(
@@ -432,12 +796,52 @@ pub async fn execute_workflow_internal(
     } else {
         Execution::new(execution_id.clone(), workflow_id.clone())
     };
+    execution.parameters = parameters;
+    // Workflows last saved before revisions were recorded get their first revision here
+    execution.revision =
+        Some(workflow_repo.record_revision(&workflow, RevisionSource::Unknown, None)?);
+
+    // Feature 013: Create execution context with pre-loaded data
+    // T043: Initialize execution_chain with the starting workflow ID
+    let ctx = WorkflowExecutionContext {
+        workflows: workflow_repo.list()?,
+        projects: project_repo.list()?,
+        execution_chain: vec![workflow_id.clone()],
+    };
+
+    // Child executions run as part of their parent and bypass the run queue
+    if execution.is_child() {
+        start_workflow_execution(app, ctx, workflow, execution, project_path);
+    } else {
+        admit_workflow_execution(app, &db, ctx, workflow, execution, project_path).await?;
+    }
+
+    Ok(execution_id)
+}
+
+/// Store a new execution as running and spawn the task executing its nodes
+/// Nodes that already have a completed or skipped result in `execution` are not run
+fn start_workflow_execution(
+    app: AppHandle,
+    ctx: WorkflowExecutionContext,
+    workflow: Workflow,
+    execution: Execution,
+    project_path: Option<String>,
+) {
+    let execution_id = execution.id.clone();
+    let parent_execution_id = execution.parent_execution_id.clone();
+    let parent_node_id = execution.parent_node_id.clone();
+
+    // Commit under test, used to tell flaky nodes from real breakages
+    let mut execution = execution;
+    execution.git_commit = project_path.as_deref().and_then(current_git_commit);
 
     // Sort nodes by order
     let mut sorted_nodes = workflow.nodes.clone();
     sorted_nodes.sort_by_key(|n| n.order);
 
     // Store execution state
+    let log = create_execution_log(&app, &execution_id, &workflow, project_path.as_deref());
     {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
@@ -445,45 +849,219 @@ pub async fn execute_workflow_internal(
             execution_id.clone(),
             RunningWorkflowExecution {
                 execution,
-                workflow: workflow.clone(),
-                current_node_index: 0,
+                workflow,
+                running_node_ids: Vec::new(),
                 is_paused: false,
                 should_cancel: false,
-                current_process_id: None,
+                process_ids: HashMap::new(),
                 output_buffer: WorkflowOutputBuffer::new(),
+                log,
             },
         );
     }
 
-    // Feature 013: Create execution context with pre-loaded data
-    // T043: Initialize execution_chain with the starting workflow ID
-    let ctx = WorkflowExecutionContext {
-        workflows: workflow_repo.list()?,
-        projects: project_repo.list()?,
-        execution_chain: vec![workflow_id.clone()],
-    };
-
-    // Clone for async task
-    let app_clone = app.clone();
-    let exec_id = execution_id.clone();
-    let parent_exec_id_clone = parent_execution_id.clone();
-    let parent_node_id_clone = parent_node_id.clone();
-
     // Spawn execution task
     tauri::async_runtime::spawn(async move {
         execute_workflow_nodes_with_context(
-            app_clone,
+            app,
             ctx,
-            exec_id,
+            execution_id,
             sorted_nodes,
             project_path,
-            parent_exec_id_clone,
-            parent_node_id_clone,
+            parent_execution_id,
+            parent_node_id,
         )
         .await;
     });
+}
 
-    Ok(execution_id)
+/// Start a top-level execution under the workflow's concurrency policy and the global
+/// limit. Executions that cannot start yet wait in the run queue shared with the MCP server.
+async fn admit_workflow_execution(
+    app: AppHandle,
+    db: &Database,
+    ctx: WorkflowExecutionContext,
+    workflow: Workflow,
+    execution: Execution,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let policy = workflow.concurrency.unwrap_or_default();
+    let repo = ActiveExecutionRepository::new(db.clone());
+
+    // Admitted and registered together; previous runs are cancelled afterwards and the
+    // new run waits in the queue until they are gone
+    let execution_id = execution.id.clone();
+    let to_cancel = repo
+        .admit_and_register(
+            &ActiveExecution {
+                execution_id: execution_id.clone(),
+                workflow_id: workflow.id.clone(),
+                source: ACTIVE_EXECUTION_SOURCE_APP.to_string(),
+                policy,
+                queued: true,
+                registered_at: Utc::now().to_rfc3339(),
+                owner_pid: Some(std::process::id()),
+            },
+            |active| admit_execution(policy, &workflow.id, ACTIVE_EXECUTION_SOURCE_APP, active),
+        )
+        .map_err(|e| format!("Cannot start '{}': {}", workflow.name, e))?;
+    for execution_id in to_cancel {
+        println!(
+            "[workflow] Cancelling previous execution {} of {}",
+            execution_id, workflow.name
+        );
+        if let Err(e) = cancel_execution(app.clone(), execution_id.clone(), Some(true)).await {
+            println!(
+                "[workflow] Warning: Failed to cancel execution {}: {}",
+                execution_id, e
+            );
+        }
+    }
+
+    {
+        let state = app.state::<WorkflowExecutionState>();
+        let mut queued = state.queued.lock().unwrap();
+        queued.insert(
+            execution_id.clone(),
+            QueuedWorkflowExecution {
+                ctx,
+                workflow,
+                execution: Execution {
+                    status: ExecutionStatus::Queued,
+                    ..execution
+                },
+                project_path,
+            },
+        );
+    }
+
+    start_queued_executions(&app);
+    if !is_execution_queued(&app, &execution_id) {
+        return Ok(());
+    }
+
+    println!("[workflow] Execution {} queued", execution_id);
+    // Slots are also freed by MCP runs, so keep checking until the execution has left the queue
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_millis(QUEUE_POLL_INTERVAL_MS)).await;
+            if !is_execution_queued(&app, &execution_id) {
+                break;
+            }
+            start_queued_executions(&app);
+        }
+    });
+
+    Ok(())
+}
+
+fn is_execution_queued(app: &AppHandle, execution_id: &str) -> bool {
+    let state = app.state::<WorkflowExecutionState>();
+    let queued = state.queued.lock().unwrap();
+    queued.contains_key(execution_id)
+}
+
+/// Wait for an execution of the app to finish and return its final status
+/// (None when it is still queued, running or paused after `timeout`)
+pub async fn wait_for_execution(
+    app: &AppHandle,
+    execution_id: &str,
+    timeout: std::time::Duration,
+) -> Option<ExecutionStatus> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    loop {
+        let status = {
+            let state = app.state::<WorkflowExecutionState>();
+            let executions = state.executions.lock().unwrap();
+            executions
+                .get(execution_id)
+                .map(|exec| exec.execution.status.clone())
+        };
+        match status {
+            Some(
+                ExecutionStatus::Completed | ExecutionStatus::Failed | ExecutionStatus::Cancelled,
+            ) => {
+                return status;
+            }
+            Some(_) => {}
+            None => {
+                // Between the queue and the running executions the run keeps its slot
+                let has_slot = ActiveExecutionRepository::new(db.clone())
+                    .list()
+                    .map(|active| active.iter().any(|a| a.execution_id == execution_id))
+                    .unwrap_or(false);
+                if !has_slot && !is_execution_queued(app, execution_id) {
+                    // Finished runs are saved before they are dropped; killed runs are not
+                    let finished = ExecutionRepository::new(db).get_finished(execution_id);
+                    return Some(
+                        finished
+                            .ok()
+                            .flatten()
+                            .map_or(ExecutionStatus::Cancelled, |execution| execution.status),
+                    );
+                }
+            }
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    }
+}
+
+/// Global concurrency settings (defaults when never saved)
+fn concurrency_settings(db: &Database) -> WorkflowConcurrencySettings {
+    use crate::repositories::SettingsRepository;
+
+    SettingsRepository::new(db.clone())
+        .get(WORKFLOW_CONCURRENCY_SETTINGS_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Start the app's queued executions that have a free slot
+fn start_queued_executions(app: &AppHandle) {
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    let repo = ActiveExecutionRepository::new(db.clone());
+    let active = match repo.list() {
+        Ok(active) => active,
+        Err(e) => {
+            println!("[workflow] Failed to read the run queue: {}", e);
+            return;
+        }
+    };
+    let max_concurrent = concurrency_settings(&db).max_concurrent_executions;
+
+    for execution_id in startable_executions(&active, max_concurrent) {
+        // Queued executions of the MCP server are started by the MCP server
+        let queued = {
+            let state = app.state::<WorkflowExecutionState>();
+            let mut queued = state.queued.lock().unwrap();
+            queued.remove(&execution_id)
+        };
+        let Some(queued) = queued else {
+            continue;
+        };
+
+        if let Err(e) = repo.set_started(&execution_id) {
+            println!("[workflow] Failed to update the run queue: {}", e);
+        }
+        let execution = Execution {
+            status: ExecutionStatus::Running,
+            started_at: Utc::now().to_rfc3339(),
+            ..queued.execution
+        };
+        start_workflow_execution(
+            app.clone(),
+            queued.ctx,
+            queued.workflow,
+            execution,
+            queued.project_path,
+        );
+    }
 }
 
 /// Execute a workflow (Tauri command wrapper)
@@ -495,6 +1073,7 @@ pub async fn execute_workflow(
     workflow_id: String,
     parent_execution_id: Option<String>,
     parent_node_id: Option<String>,
+    params: Option<HashMap<String, serde_json::Value>>,
 ) -> Result<String, String> {
     execute_workflow_internal(
         app,
@@ -502,11 +1081,14 @@ pub async fn execute_workflow(
         workflow_id,
         parent_execution_id,
         parent_node_id,
+        params,
     )
     .await
 }
 
-/// Execute workflow nodes sequentially with pre-loaded context
+/// Execute workflow nodes as a dependency DAG with pre-loaded context
+/// Nodes whose dependencies have completed run concurrently (bounded by the workflow's
+/// max_parallelism). A failed node skips its dependents; independent branches keep running.
 /// Feature 013: Extended to support trigger-workflow nodes and child execution tracking
 async fn execute_workflow_nodes_with_context(
     app: AppHandle,
@@ -521,429 +1103,1319 @@ async fn execute_workflow_nodes_with_context(
     let total_nodes = nodes.len();
 
     // Get workflow_id early for event payloads (fixes output mixing between workflows)
-    let workflow_id = {
+    // Nodes already completed or skipped by a condition (e.g. before a pause) are not run again
+    let (workflow_id, max_parallelism, completed_node_ids, skipped_node_ids) = {
         let state = app.state::<WorkflowExecutionState>();
         let executions = state.executions.lock().unwrap();
-        executions
-            .get(&execution_id)
-            .map(|e| e.workflow.id.clone())
-            .unwrap_or_default()
+        match executions.get(&execution_id) {
+            Some(exec) => (
+                exec.workflow.id.clone(),
+                exec.workflow
+                    .max_parallelism
+                    .map(|n| n.max(1) as usize)
+                    .unwrap_or(DEFAULT_MAX_PARALLELISM),
+                exec.execution
+                    .node_results
+                    .iter()
+                    .filter(|r| r.status == NodeStatus::Completed)
+                    .map(|r| r.node_id.clone())
+                    .collect::<Vec<String>>(),
+                exec.execution
+                    .node_results
+                    .iter()
+                    .filter(|r| r.status == NodeStatus::Skipped)
+                    .map(|r| r.node_id.clone())
+                    .collect::<Vec<String>>(),
+            ),
+            None => return,
+        }
     };
 
-    for (index, node) in nodes.iter().enumerate() {
-        // Check if paused or cancelled
-        {
-            let state = app.state::<WorkflowExecutionState>();
-            let executions = state.executions.lock().unwrap();
-            if let Some(exec) = executions.get(&execution_id) {
-                if exec.is_paused {
-                    // Emit paused event
-                    let _ = app.emit(
-                        "execution_paused",
-                        ExecutionPausedPayload {
-                            execution_id: execution_id.clone(),
-                            workflow_id: exec.workflow.id.clone(),
-                            paused_at_node_id: node.id.clone(),
-                            reason: "user_requested".to_string(),
-                        },
-                    );
-                    return;
-                }
-                if exec.should_cancel {
-                    break;
-                }
-            } else {
-                return;
-            }
+    let dag = match WorkflowDag::new(&nodes) {
+        Ok(dag) => dag,
+        Err(e) => {
+            println!("[workflow] Invalid workflow graph: {}", e);
+            finalize_execution(
+                &app,
+                &execution_id,
+                &workflow_id,
+                ExecutionStatus::Failed,
+                start_time.elapsed().as_millis() as u64,
+                Some(e),
+                parent_execution_id.as_deref(),
+                parent_node_id.as_deref(),
+            );
+            return;
         }
+    };
 
-        // Update current node index
-        {
-            let state = app.state::<WorkflowExecutionState>();
-            let mut executions = state.executions.lock().unwrap();
-            if let Some(exec) = executions.get_mut(&execution_id) {
-                exec.current_node_index = index;
+    let nodes_by_id: HashMap<&str, &WorkflowNode> =
+        nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+    let mut scheduler = DagScheduler::new(dag);
+    for node_id in &completed_node_ids {
+        scheduler.mark_completed(node_id);
+    }
+    scheduler.skip_branch(&skipped_node_ids);
+
+    let mut in_flight = FuturesUnordered::new();
+    let mut started_count = scheduler.completed_count();
+    let mut first_error: Option<String> = None;
+
+    loop {
+        // Stop scheduling new nodes once paused or cancelled; in-flight nodes are drained
+        let (is_paused, should_cancel) = execution_control_flags(&app, &execution_id);
+
+        if !is_paused && !should_cancel {
+            let capacity = max_parallelism.saturating_sub(in_flight.len());
+            for node_id in scheduler.ready_nodes().into_iter().take(capacity) {
+                let node = match nodes_by_id.get(node_id.as_str()) {
+                    Some(node) => *node,
+                    None => continue,
+                };
+                scheduler.mark_running(&node_id);
+                in_flight.push(run_workflow_node(
+                    &app,
+                    &ctx,
+                    &execution_id,
+                    &workflow_id,
+                    node,
+                    default_cwd.as_deref(),
+                    (started_count, total_nodes),
+                    (parent_execution_id.as_deref(), parent_node_id.as_deref()),
+                ));
+                started_count += 1;
             }
         }
 
-        // Feature 013: Get target workflow name for trigger-workflow nodes
-        let target_workflow_name = if node.is_trigger_workflow() {
-            node.get_trigger_workflow_config().and_then(|config| {
-                ctx.workflows
-                    .iter()
-                    .find(|w| w.id == config.target_workflow_id)
-                    .map(|w| w.name.clone())
-            })
-        } else {
-            None
+        let Some((node_id, result)) = in_flight.next().await else {
+            break;
         };
 
-        // Emit node started
+        let node_name = nodes_by_id
+            .get(node_id.as_str())
+            .map(|n| n.name.clone())
+            .unwrap_or_else(|| node_id.clone());
+
+        match result {
+            Ok(None) => scheduler.mark_completed(&node_id),
+            Ok(Some(outcome)) => {
+                // Condition node: skip the branches it did not select
+                for skipped_id in scheduler.mark_branch_result(&node_id, &outcome.not_taken) {
+                    skip_workflow_node(
+                        &app,
+                        &execution_id,
+                        &workflow_id,
+                        &skipped_id,
+                        SkipReason::Condition,
+                        format!("Condition '{}' did not select this branch", node_name),
+                    );
+                }
+            }
+            Err(error) => {
+                if first_error.is_none() {
+                    first_error = Some(error);
+                }
+                for skipped_id in scheduler.mark_failed(&node_id) {
+                    skip_workflow_node(
+                        &app,
+                        &execution_id,
+                        &workflow_id,
+                        &skipped_id,
+                        SkipReason::DependencyFailed,
+                        format!("Skipped because dependency '{}' failed", node_name),
+                    );
+                }
+            }
+        }
+    }
+
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+    let (is_paused, should_cancel) = {
+        let state = app.state::<WorkflowExecutionState>();
+        let executions = state.executions.lock().unwrap();
+        match executions.get(&execution_id) {
+            Some(exec) => (exec.is_paused, exec.should_cancel),
+            // Execution was removed (e.g. killed) - nothing left to report
+            None => return,
+        }
+    };
+
+    if should_cancel {
+        for skipped_id in scheduler.skip_pending() {
+            skip_workflow_node(
+                &app,
+                &execution_id,
+                &workflow_id,
+                &skipped_id,
+                SkipReason::Cancelled,
+                "Execution cancelled by user".to_string(),
+            );
+        }
+        finalize_execution(
+            &app,
+            &execution_id,
+            &workflow_id,
+            ExecutionStatus::Cancelled,
+            duration_ms,
+            None,
+            parent_execution_id.as_deref(),
+            parent_node_id.as_deref(),
+        );
+    } else if scheduler.has_failures() {
+        finalize_execution(
+            &app,
+            &execution_id,
+            &workflow_id,
+            ExecutionStatus::Failed,
+            duration_ms,
+            first_error,
+            parent_execution_id.as_deref(),
+            parent_node_id.as_deref(),
+        );
+    } else if is_paused && !scheduler.is_finished() {
+        // Emit paused event; continue_execution resumes from the remaining nodes
+        let paused_at_node_id = scheduler.ready_nodes().into_iter().next().unwrap_or_default();
+        {
+            let state = app.state::<WorkflowExecutionState>();
+            let mut executions = state.executions.lock().unwrap();
+            if let Some(exec) = executions.get_mut(&execution_id) {
+                exec.execution.pause();
+            }
+        }
         let _ = app.emit(
-            "execution_node_started",
-            NodeStartedPayload {
+            "execution_paused",
+            ExecutionPausedPayload {
                 execution_id: execution_id.clone(),
                 workflow_id: workflow_id.clone(),
-                node_id: node.id.clone(),
-                node_name: node.name.clone(),
-                node_type: node.node_type.clone(),
-                target_workflow_name,
-                started_at: Utc::now().to_rfc3339(),
+                paused_at_node_id,
+                reason: "user_requested".to_string(),
             },
         );
+    } else {
+        finalize_execution(
+            &app,
+            &execution_id,
+            &workflow_id,
+            ExecutionStatus::Completed,
+            duration_ms,
+            None,
+            parent_execution_id.as_deref(),
+            parent_node_id.as_deref(),
+        );
+    }
+}
 
-        // Feature 013: Emit child execution progress if this is a child execution
-        if let (Some(ref parent_exec_id), Some(ref parent_node)) =
-            (&parent_execution_id, &parent_node_id)
-        {
-            let _ = app.emit(
-                "child_execution_progress",
-                ChildExecutionProgressPayload {
-                    parent_execution_id: parent_exec_id.clone(),
-                    parent_node_id: parent_node.clone(),
-                    child_execution_id: execution_id.clone(),
-                    current_step: index,
-                    total_steps: total_nodes,
-                    current_node_id: node.id.clone(),
-                    current_node_name: node.name.clone(),
-                    timestamp: Utc::now().to_rfc3339(),
-                },
+/// Read the pause/cancel flags of a running execution
+/// A missing execution (e.g. killed) is treated as cancelled
+fn execution_control_flags(app: &AppHandle, execution_id: &str) -> (bool, bool) {
+    let state = app.state::<WorkflowExecutionState>();
+    let executions = state.executions.lock().unwrap();
+    executions
+        .get(execution_id)
+        .map(|e| (e.is_paused, e.should_cancel))
+        .unwrap_or((false, true))
+}
+
+/// Store a node result on the running execution, replacing any previous result for the node
+fn record_node_result(app: &AppHandle, execution_id: &str, result: NodeResult) {
+    let state = app.state::<WorkflowExecutionState>();
+    let mut executions = state.executions.lock().unwrap();
+    if let Some(exec) = executions.get_mut(execution_id) {
+        let results = &mut exec.execution.node_results;
+        match results.iter_mut().find(|r| r.node_id == result.node_id) {
+            Some(existing) => *existing = result,
+            None => results.push(result),
+        }
+    }
+}
+
+/// Mark a node as skipped and notify the frontend
+fn skip_workflow_node(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node_id: &str,
+    reason: SkipReason,
+    message: String,
+) {
+    let mut node_result = NodeResult::new(node_id.to_string());
+    node_result.skip(reason);
+    node_result.error_message = Some(message.clone());
+    record_node_result(app, execution_id, node_result);
+
+    let _ = app.emit(
+        "execution_node_completed",
+        NodeCompletedPayload {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node_id.to_string(),
+            status: "skipped".to_string(),
+            exit_code: None,
+            error_message: Some(message),
+            finished_at: Utc::now().to_rfc3339(),
+        },
+    );
+}
+
+/// Run a single node of a DAG execution, emitting its lifecycle events
+/// Returns the node ID together with the result: Ok (with the selected branch for
+/// condition nodes) on success or the error message on failure
+#[allow(clippy::too_many_arguments)]
+async fn run_workflow_node(
+    app: &AppHandle,
+    ctx: &WorkflowExecutionContext,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    default_cwd: Option<&str>,
+    (step_index, total_steps): (usize, usize),
+    (parent_execution_id, parent_node_id): (Option<&str>, Option<&str>),
+) -> (String, Result<Option<ConditionOutcome>, String>) {
+    let mut node_result = NodeResult::new(node.id.clone());
+    node_result.start();
+    record_node_result(app, execution_id, node_result.clone());
+
+    {
+        let state = app.state::<WorkflowExecutionState>();
+        let mut executions = state.executions.lock().unwrap();
+        if let Some(exec) = executions.get_mut(execution_id) {
+            exec.running_node_ids.push(node.id.clone());
+        }
+    }
+
+    // Feature 013: Get target workflow name for trigger-workflow nodes
+    let target_workflow_name = if node.is_trigger_workflow() {
+        node.get_trigger_workflow_config().and_then(|config| {
+            ctx.workflows
+                .iter()
+                .find(|w| w.id == config.target_workflow_id)
+                .map(|w| w.name.clone())
+        })
+    } else {
+        None
+    };
+
+    // Emit node started
+    let _ = app.emit(
+        "execution_node_started",
+        NodeStartedPayload {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            node_type: node.node_type.clone(),
+            target_workflow_name,
+            started_at: node_result.started_at.clone(),
+        },
+    );
+
+    // Feature 013: Emit child execution progress if this is a child execution
+    if let (Some(parent_exec_id), Some(parent_node)) = (parent_execution_id, parent_node_id) {
+        let _ = app.emit(
+            "child_execution_progress",
+            ChildExecutionProgressPayload {
+                parent_execution_id: parent_exec_id.to_string(),
+                parent_node_id: parent_node.to_string(),
+                child_execution_id: execution_id.to_string(),
+                current_step: step_index,
+                total_steps,
+                current_node_id: node.id.clone(),
+                current_node_name: node.name.clone(),
+                timestamp: Utc::now().to_rfc3339(),
+            },
+        );
+    }
+
+    // Execute node based on type (Feature 013: T011)
+    let mut condition_outcome: Option<ConditionOutcome> = None;
+    let result = if node.is_condition() {
+        execute_condition_node(app, execution_id, workflow_id, node, default_cwd)
+            .await
+            .map(|outcome| {
+                condition_outcome = Some(outcome);
+                0
+            })
+    } else if node.is_trigger_workflow() {
+        execute_trigger_workflow_node(app, ctx, execution_id, node).await
+    } else if node.is_approval() {
+        execute_approval_node(app, execution_id, workflow_id, node, &mut node_result).await
+    } else if node.is_http() {
+        execute_http_node(app, execution_id, workflow_id, node, &mut node_result).await
+    } else if let Some(matrix) = node.get_script_config().and_then(|config| config.matrix) {
+        execute_matrix_node(
+            app,
+            execution_id,
+            workflow_id,
+            node,
+            &matrix,
+            default_cwd,
+            &mut node_result,
+        )
+        .await
+    } else {
+        execute_node_with_retry(
+            app,
+            execution_id,
+            workflow_id,
+            node,
+            default_cwd,
+            None,
+            |attempt| {
+                node_result.attempts.push(attempt);
+                record_node_result(app, execution_id, node_result.clone());
+            },
+        )
+        .await
+        .map(|(code, run_output)| {
+            record_step_output(&mut node_result, run_output.step);
+            code
+        })
+    };
+
+    let was_cancelled = {
+        let state = app.state::<WorkflowExecutionState>();
+        let mut executions = state.executions.lock().unwrap();
+        match executions.get_mut(execution_id) {
+            Some(exec) => {
+                exec.running_node_ids.retain(|id| id != &node.id);
+                exec.should_cancel
+            }
+            None => true,
+        }
+    };
+
+    // Emit node completed
+    let (status_str, exit_code, error_msg) = match &result {
+        Ok(0) => ("completed".to_string(), Some(0), None),
+        Ok(code) => (
+            "failed".to_string(),
+            Some(*code),
+            Some(format!("Exit code: {}", code)),
+        ),
+        Err(_) if was_cancelled => (
+            "cancelled".to_string(),
+            None,
+            Some("Execution cancelled by user".to_string()),
+        ),
+        Err(e) => ("failed".to_string(), None, Some(e.clone())),
+    };
+
+    match exit_code {
+        Some(code) => node_result.complete(code),
+        None => {
+            node_result.status = NodeStatus::Failed;
+            node_result.finished_at = Some(Utc::now().to_rfc3339());
+        }
+    }
+    node_result.error_message = error_msg.clone();
+    record_node_result(app, execution_id, node_result);
+
+    let _ = app.emit(
+        "execution_node_completed",
+        NodeCompletedPayload {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node.id.clone(),
+            status: status_str.clone(),
+            exit_code,
+            error_message: error_msg.clone(),
+            finished_at: Utc::now().to_rfc3339(),
+        },
+    );
+
+    let outcome = if status_str == "completed" {
+        Ok(condition_outcome)
+    } else {
+        Err(error_msg.unwrap_or_else(|| "Unknown error".to_string()))
+    };
+    (node.id.clone(), outcome)
+}
+
+/// Evaluate a condition node against the results of previously finished nodes
+/// The selected branch is written to the node output so it shows up in the execution log
+async fn execute_condition_node(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    default_cwd: Option<&str>,
+) -> Result<ConditionOutcome, String> {
+    let config = node
+        .get_condition_config()
+        .ok_or_else(|| "Invalid condition node config".to_string())?;
+
+    let mut condition_ctx = {
+        let state = app.state::<WorkflowExecutionState>();
+        let executions = state.executions.lock().unwrap();
+        let exec = executions
+            .get(execution_id)
+            .ok_or_else(|| "Execution not found".to_string())?;
+
+        let mut outputs: HashMap<String, String> = HashMap::new();
+        for line in exec.output_buffer.get_lines() {
+            let output = outputs.entry(line.node_id).or_default();
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(&line.content);
+        }
+
+        ConditionContext {
+            exit_codes: exec
+                .execution
+                .node_results
+                .iter()
+                .filter_map(|r| r.exit_code.map(|code| (r.node_id.clone(), code)))
+                .collect(),
+            outputs,
+            env: std::env::vars().collect(),
+            git_branch: None,
+        }
+    };
+
+    if uses_git_branch(&config) {
+        if let Some(cwd) = default_cwd {
+            let cwd = cwd.to_string();
+            condition_ctx.git_branch =
+                tokio::task::spawn_blocking(move || current_git_branch(&cwd))
+                    .await
+                    .ok()
+                    .flatten();
+        }
+    }
+
+    let outcome = evaluate_condition(&config, &condition_ctx)?;
+
+    let taken = if outcome.taken.is_empty() {
+        "no nodes".to_string()
+    } else {
+        outcome.taken.join(", ")
+    };
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        format!(
+            "Condition matched {} → running {}",
+            outcome.branch_label, taken
+        ),
+    );
+
+    Ok(outcome)
+}
+
+/// How often a waiting approval node checks for a decision
+const APPROVAL_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Wait at an approval node until someone approves or rejects it
+/// The node registers a pending action request, so it is listed by `get_pending_action_requests`
+/// and answered with `respond_to_action_request` (the reason is kept as the comment).
+/// Rejection, timeout and cancellation fail the node.
+async fn execute_approval_node(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    node_result: &mut NodeResult,
+) -> Result<i32, String> {
+    let config = node
+        .get_approval_config()
+        .ok_or_else(|| "Invalid approval node config".to_string())?;
+    let message = match config.message {
+        Some(ref message) => Some(interpolate(
+            message,
+            &node_interpolation_context(app, execution_id),
+        )?),
+        None => None,
+    };
+    let workflow_name = {
+        let state = app.state::<WorkflowExecutionState>();
+        let executions = state.executions.lock().unwrap();
+        executions
+            .get(execution_id)
+            .map(|exec| exec.workflow.name.clone())
+            .unwrap_or_default()
+    };
+
+    let repo = MCPActionRepository::new(app.state::<DatabaseState>().0.as_ref().clone());
+    let request = repo.create_execution(
+        None,
+        MCPActionType::Workflow,
+        format!("{} › {}", workflow_name, node.name),
+        Some(WORKFLOW_APPROVAL_SOURCE.to_string()),
+        Some(serde_json::json!({
+            "workflowId": workflow_id,
+            "executionId": execution_id,
+            "nodeId": node.id,
+            "message": message,
+        })),
+        ActionStatus::PendingConfirm,
+    )?;
+
+    node_result.approval = Some(NodeApproval {
+        request_id: request.id.clone(),
+        status: ApprovalStatus::Pending,
+        message: message.clone(),
+        comment: None,
+        requested_at: request.started_at.clone(),
+        decided_at: None,
+    });
+    record_node_result(app, execution_id, node_result.clone());
+
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        match message {
+            Some(ref message) => format!("Waiting for approval: {}", message),
+            None => "Waiting for approval".to_string(),
+        },
+    );
+    let _ = send_notification(
+        app,
+        NotificationType::WorkflowApprovalRequested {
+            workflow_name,
+            node_name: node.name.clone(),
+            message,
+        },
+    );
+
+    let timeout = config
+        .timeout_minutes
+        .map(|minutes| std::time::Duration::from_secs(minutes as u64 * 60));
+    let waiting_since = std::time::Instant::now();
+
+    let (status, comment) = loop {
+        tokio::time::sleep(std::time::Duration::from_millis(APPROVAL_POLL_INTERVAL_MS)).await;
+
+        let (_, should_cancel) = execution_control_flags(app, execution_id);
+        if should_cancel {
+            repo.update_execution_status(
+                &request.id,
+                ActionStatus::Cancelled,
+                None,
+                Some("Workflow execution cancelled".to_string()),
+            )?;
+            break (ApprovalStatus::Cancelled, None);
+        }
+
+        let current = repo
+            .get_execution(&request.id)?
+            .ok_or_else(|| "Approval request was removed".to_string())?;
+        match current.status {
+            ActionStatus::PendingConfirm => {}
+            ActionStatus::Denied => break (ApprovalStatus::Rejected, current.error_message),
+            ActionStatus::Running => {
+                let comment = current
+                    .result
+                    .as_ref()
+                    .and_then(|r| r.get("comment"))
+                    .and_then(|c| c.as_str())
+                    .map(|c| c.to_string());
+                repo.update_execution_status(
+                    &request.id,
+                    ActionStatus::Completed,
+                    current.result,
+                    None,
+                )?;
+                break (ApprovalStatus::Approved, comment);
+            }
+            other => return Err(format!("Approval request ended as {}", other)),
+        }
+
+        if timeout.is_some_and(|timeout| waiting_since.elapsed() >= timeout) {
+            repo.update_execution_status(
+                &request.id,
+                ActionStatus::TimedOut,
+                None,
+                Some("No decision before the approval timeout".to_string()),
+            )?;
+            break (ApprovalStatus::TimedOut, None);
+        }
+    };
+
+    if let Some(approval) = node_result.approval.as_mut() {
+        approval.status = status.clone();
+        approval.comment = comment.clone();
+        approval.decided_at = Some(Utc::now().to_rfc3339());
+    }
+    record_node_result(app, execution_id, node_result.clone());
+
+    let comment_suffix = comment
+        .as_deref()
+        .map(|c| format!(": {}", c))
+        .unwrap_or_default();
+    match status {
+        ApprovalStatus::Approved => {
+            emit_node_output(
+                app,
+                execution_id,
+                workflow_id,
+                node,
+                format!("Approved{}", comment_suffix),
             );
+            Ok(0)
         }
+        ApprovalStatus::Rejected => Err(format!("Rejected{}", comment_suffix)),
+        ApprovalStatus::TimedOut => Err("Approval timed out".to_string()),
+        _ => Err("Execution cancelled".to_string()),
+    }
+}
 
-        // Execute node based on type (Feature 013: T011)
-        let result = if node.is_trigger_workflow() {
-            execute_trigger_workflow_node(&app, &ctx, &execution_id, node).await
+/// Response body lines written to the node output; the rest is summarized
+const HTTP_OUTPUT_MAX_LINES: usize = 200;
+
+/// Send the request of an HTTP node
+/// The response body becomes the node output, `status` and the `extract` JSON paths become
+/// step outputs. A status outside `expectedStatus` (default: any 2xx) fails the node.
+async fn execute_http_node(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    node_result: &mut NodeResult,
+) -> Result<i32, String> {
+    let config = node
+        .get_http_config()
+        .ok_or_else(|| "Invalid HTTP node config".to_string())?;
+    let method = reqwest::Method::from_bytes(config.method.to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method '{}'", config.method))?;
+
+    let templates = std::iter::once(&config.url)
+        .chain(config.headers.values())
+        .chain(config.body.iter())
+        .chain(config.signing_secret.iter());
+    let secrets = load_secrets(app, secret_references(templates))?;
+    let secret_values: Vec<String> = secrets.values().cloned().collect();
+    let interpolation_ctx = InterpolationContext {
+        secrets: Some(secrets),
+        ..node_interpolation_context(app, execution_id)
+    };
+    let render = |template: &str| interpolate(template, &interpolation_ctx);
+
+    let url = render(&config.url)?;
+    let headers = config
+        .headers
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), render(value)?)))
+        .collect::<Result<HashMap<String, String>, String>>()?;
+    let body = config.body.as_deref().map(render).transpose()?;
+    let signing_secret = config.signing_secret.as_deref().map(render).transpose()?;
+    let timeout =
+        std::time::Duration::from_millis(config.timeout_ms.unwrap_or(DEFAULT_HTTP_TIMEOUT_MS));
+
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        mask_secret_values(&format!("{} {}", method, url), &secret_values),
+    );
+
+    let start = std::time::Instant::now();
+    let response = outgoing_http::build_request(
+        method,
+        &url,
+        Some(&headers),
+        body,
+        signing_secret.as_deref(),
+        timeout,
+    )
+    .send()
+    .await
+    .map_err(|e| outgoing_http::describe_send_error(&e, timeout))?;
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response body: {}", e))?;
+
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        format!("HTTP {} ({}ms)", status, start.elapsed().as_millis()),
+    );
+    let text = mask_secret_values(&text, &secret_values);
+    let mut step = StepOutputs::default();
+    for line in text.lines() {
+        step.push_output_line(line);
+    }
+    node_result.output = step.output;
+    node_result.output_truncated = step.truncated;
+    let line_count = text.lines().count();
+    for line in text.lines().take(HTTP_OUTPUT_MAX_LINES) {
+        emit_node_output(app, execution_id, workflow_id, node, line.to_string());
+    }
+    if line_count > HTTP_OUTPUT_MAX_LINES {
+        emit_node_output(
+            app,
+            execution_id,
+            workflow_id,
+            node,
+            format!("… {} more lines", line_count - HTTP_OUTPUT_MAX_LINES),
+        );
+    }
+
+    node_result
+        .outputs
+        .insert("status".to_string(), status.to_string());
+    if !status_matches(&config.expected_status, status) {
+        let expected = if config.expected_status.is_empty() {
+            "2xx".to_string()
         } else {
-            execute_node(
-                &app,
-                &execution_id,
-                &workflow_id,
-                node,
-                default_cwd.as_deref(),
-            )
+            config
+                .expected_status
+                .iter()
+                .map(|code| code.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        return Err(format!(
+            "Unexpected HTTP status {} (expected {})",
+            status, expected
+        ));
+    }
+
+    node_result
+        .outputs
+        .extend(extract_outputs(&text, &config.extract)?);
+    Ok(0)
+}
+
+/// Run a script node (or one cell of a matrix node), retrying failed attempts according
+/// to its retry policy. Every attempt is passed to `on_attempt` so the log shows which one succeeded
+async fn execute_node_with_retry(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    default_cwd: Option<&str>,
+    cell: Option<&MatrixCell>,
+    mut on_attempt: impl FnMut(NodeAttempt),
+) -> Result<(i32, ScriptRunOutput), String> {
+    let policy = node
+        .get_script_config()
+        .map(|config| config.retry)
+        .unwrap_or_default();
+    if policy.retries == 0 {
+        return execute_node(app, execution_id, workflow_id, node, default_cwd, cell).await;
+    }
+
+    let prefix = cell.map(matrix_cell_prefix).unwrap_or_default();
+    let mut attempt: u32 = 1;
+    loop {
+        let started_at = Utc::now().to_rfc3339();
+
+        let result = execute_node(app, execution_id, workflow_id, node, default_cwd, cell).await;
+
+        let (status, exit_code, error_message) = match &result {
+            Ok((0, _)) => (NodeStatus::Completed, Some(0), None),
+            Ok((code, _)) => (
+                NodeStatus::Failed,
+                Some(*code),
+                Some(format!("Exit code: {}", code)),
+            ),
+            Err(e) => (NodeStatus::Failed, None, Some(e.clone())),
+        };
+        on_attempt(NodeAttempt {
+            attempt,
+            status: status.clone(),
+            exit_code,
+            error_message,
+            started_at,
+            finished_at: Some(Utc::now().to_rfc3339()),
+        });
+
+        let (_, should_cancel) = execution_control_flags(app, execution_id);
+        if status == NodeStatus::Completed || should_cancel {
+            return result;
+        }
+
+        // Output of this attempt, captured by execute_node itself
+        let attempt_output = match &result {
+            Ok((_, output)) => output.retry_match.clone().unwrap_or_default(),
+            Err(_) => String::new(),
+        };
+        if !should_retry(&policy, attempt, exit_code, &attempt_output) {
+            return result;
+        }
+
+        let delay = retry_delay(&policy, attempt);
+        emit_node_output(
+            app,
+            execution_id,
+            workflow_id,
+            node,
+            format!(
+                "{}Attempt {}/{} failed, retrying in {}ms",
+                prefix,
+                attempt,
+                policy.retries + 1,
+                delay.as_millis()
+            ),
+        );
+
+        // Wait before the next attempt, giving up early if the execution is cancelled
+        let deadline = tokio::time::Instant::now() + delay;
+        while tokio::time::Instant::now() < deadline {
+            if execution_control_flags(app, execution_id).1 {
+                return result;
+            }
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            tokio::time::sleep(remaining.min(tokio::time::Duration::from_millis(100))).await;
+        }
+
+        attempt += 1;
+    }
+}
+
+/// Output line prefix of a matrix cell, e.g. `[node=20] `
+fn matrix_cell_prefix(cell: &MatrixCell) -> String {
+    format!("[{}] ", cell.label())
+}
+
+/// Update one cell result of a running matrix node and store the node result
+fn update_matrix_cell(
+    app: &AppHandle,
+    execution_id: &str,
+    node_result: &Mutex<NodeResult>,
+    index: usize,
+    update: impl FnOnce(&mut MatrixCellResult),
+) {
+    let mut node_result = node_result.lock().unwrap();
+    if let Some(cell) = node_result.matrix_cells.get_mut(index) {
+        update(cell);
+    }
+    record_node_result(app, execution_id, node_result.clone());
+}
+
+/// Run a script node once per matrix cell, at most `maxParallel` cells at a time.
+/// The node succeeds when every cell succeeds; after a failed cell the remaining cells
+/// are skipped unless `continueOnFailure` is set. Cell results go to `node_result.matrix_cells`;
+/// the cells' stdout (prefixed with the cell label) and `::set-output` values (later cells
+/// win) become the node's step output.
+async fn execute_matrix_node(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    matrix: &NodeMatrix,
+    default_cwd: Option<&str>,
+    node_result: &mut NodeResult,
+) -> Result<i32, String> {
+    let packages = if matrix.packages.is_empty() {
+        Vec::new()
+    } else {
+        let project_path =
+            default_cwd.ok_or_else(|| "Matrix packages require a project directory".to_string())?;
+        crate::commands::project::get_workspace_packages(app.clone(), project_path.to_string())
             .await
-        };
+            .map_err(|e| format!("Failed to list workspace packages: {}", e))?
+    };
+    let cells = expand_matrix(matrix, &packages)?;
+    let total = cells.len();
 
-        // Emit node completed
-        let (status_str, exit_code, error_msg) = match &result {
-            Ok(code) => {
-                if *code == 0 {
-                    ("completed".to_string(), Some(*code), None)
-                } else {
-                    (
-                        "failed".to_string(),
-                        Some(*code),
-                        Some(format!("Exit code: {}", code)),
-                    )
-                }
-            }
-            Err(e) => ("failed".to_string(), None, Some(e.clone())),
-        };
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        format!(
+            "Matrix: {} cells ({})",
+            total,
+            cells
+                .iter()
+                .map(MatrixCell::label)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+    );
 
-        let _ = app.emit(
-            "execution_node_completed",
-            NodeCompletedPayload {
-                execution_id: execution_id.clone(),
-                workflow_id: workflow_id.clone(),
-                node_id: node.id.clone(),
-                status: status_str.clone(),
-                exit_code,
-                error_message: error_msg.clone(),
-                finished_at: Utc::now().to_rfc3339(),
-            },
-        );
+    node_result.matrix_cells = cells
+        .iter()
+        .map(|cell| MatrixCellResult {
+            index: cell.index,
+            label: cell.label(),
+            values: cell.values.clone(),
+            status: NodeStatus::Pending,
+            exit_code: None,
+            error_message: None,
+            started_at: None,
+            finished_at: None,
+            attempts: Vec::new(),
+        })
+        .collect();
+    record_node_result(app, execution_id, node_result.clone());
+    let shared_result = Mutex::new(node_result.clone());
+
+    let run_cell = |cell: MatrixCell| {
+        let shared_result = &shared_result;
+        async move {
+            update_matrix_cell(app, execution_id, shared_result, cell.index, |result| {
+                result.status = NodeStatus::Running;
+                result.started_at = Some(Utc::now().to_rfc3339());
+            });
+
+            let result = execute_node_with_retry(
+                app,
+                execution_id,
+                workflow_id,
+                node,
+                default_cwd,
+                Some(&cell),
+                |attempt| {
+                    update_matrix_cell(app, execution_id, shared_result, cell.index, |result| {
+                        result.attempts.push(attempt)
+                    })
+                },
+            )
+            .await;
+
+            let (status, exit_code, error_message) = match &result {
+                Ok((0, _)) => (NodeStatus::Completed, Some(0), None),
+                Ok((code, _)) => (
+                    NodeStatus::Failed,
+                    Some(*code),
+                    Some(format!("Exit code: {}", code)),
+                ),
+                Err(e) => (NodeStatus::Failed, None, Some(e.clone())),
+            };
+            let passed = status == NodeStatus::Completed;
+            update_matrix_cell(app, execution_id, shared_result, cell.index, |result| {
+                result.status = status;
+                result.exit_code = exit_code;
+                result.error_message = error_message;
+                result.finished_at = Some(Utc::now().to_rfc3339());
+            });
+            let step = result.ok().map(|(_, run_output)| run_output.step);
+            (cell, passed, step)
+        }
+    };
 
-        // If node failed, stop execution
-        if status_str == "failed" {
-            // Update execution status
-            {
-                let state = app.state::<WorkflowExecutionState>();
-                let mut executions = state.executions.lock().unwrap();
-                if let Some(exec) = executions.get_mut(&execution_id) {
-                    exec.execution.fail();
-                }
-            }
+    let max_parallel = matrix.max_parallel.unwrap_or(1).max(1) as usize;
+    let mut pending: VecDeque<MatrixCell> = cells.into();
+    let mut running = FuturesUnordered::new();
+    let mut failed: Vec<String> = Vec::new();
+    let mut cell_steps: Vec<(MatrixCell, StepOutputs)> = Vec::new();
+    let mut stop_launching = false;
 
-            // Get workflow info for webhook
-            let (workflow_id, workflow_name, webhook_config) = {
-                let state = app.state::<WorkflowExecutionState>();
-                let executions = state.executions.lock().unwrap();
-                executions
-                    .get(&execution_id)
-                    .map(|e| {
-                        (
-                            e.workflow.id.clone(),
-                            e.workflow.name.clone(),
-                            e.workflow.webhook.clone(),
-                        )
-                    })
-                    .unwrap_or_default()
+    loop {
+        while !stop_launching && running.len() < max_parallel {
+            let Some(cell) = pending.pop_front() else {
+                break;
             };
+            running.push(run_cell(cell));
+        }
 
-            let duration_ms = start_time.elapsed().as_millis() as u64;
+        let Some((cell, passed, step)) = running.next().await else {
+            break;
+        };
+        if !passed {
+            failed.push(cell.label());
+            if !matrix.continue_on_failure {
+                stop_launching = true;
+            }
+        }
+        if let Some(step) = step {
+            cell_steps.push((cell, step));
+        }
+        if execution_control_flags(app, execution_id).1 {
+            stop_launching = true;
+        }
+    }
+    drop(running);
 
-            // Emit execution completed
-            if !workflow_id.is_empty() {
-                let _ = app.emit(
-                    "execution_completed",
-                    ExecutionCompletedPayload {
-                        execution_id: execution_id.clone(),
-                        workflow_id: workflow_id.clone(),
-                        status: "failed".to_string(),
-                        finished_at: Utc::now().to_rfc3339(),
-                        total_duration_ms: duration_ms,
-                    },
-                );
+    let skipped = pending.len();
+    for cell in pending {
+        update_matrix_cell(app, execution_id, &shared_result, cell.index, |result| {
+            result.status = NodeStatus::Skipped;
+        });
+    }
+    *node_result = shared_result.into_inner().unwrap();
+
+    cell_steps.sort_by_key(|(cell, _)| cell.index);
+    let mut node_step = StepOutputs::default();
+    for (cell, step) in cell_steps {
+        let prefix = matrix_cell_prefix(&cell);
+        for line in step.output.lines() {
+            node_step.push_output_line(&format!("{}{}", prefix, line));
+        }
+        node_step.truncated |= step.truncated;
+        node_step.outputs.extend(step.outputs);
+    }
+    record_step_output(node_result, node_step);
 
-                // Send desktop notification for workflow failure
-                let _ = send_notification(
-                    &app,
-                    NotificationType::WorkflowFailed {
-                        workflow_name: workflow_name.clone(),
-                        error: error_msg.clone().unwrap_or_else(|| "Unknown error".to_string()),
-                    },
-                );
+    emit_node_output(
+        app,
+        execution_id,
+        workflow_id,
+        node,
+        format!(
+            "Matrix finished: {} passed, {} failed, {} skipped",
+            total - failed.len() - skipped,
+            failed.len(),
+            skipped
+        ),
+    );
 
-                // Feature 013: Emit child execution completed if this is a child execution
-                if let (Some(ref parent_exec_id), Some(ref parent_node)) =
-                    (&parent_execution_id, &parent_node_id)
-                {
-                    let _ = app.emit(
-                        "child_execution_completed",
-                        ChildExecutionCompletedPayload {
-                            parent_execution_id: parent_exec_id.clone(),
-                            parent_node_id: parent_node.clone(),
-                            child_execution_id: execution_id.clone(),
-                            child_workflow_id: workflow_id.clone(),
-                            status: "failed".to_string(),
-                            duration_ms,
-                            error_message: error_msg.clone(),
-                            finished_at: Utc::now().to_rfc3339(),
-                        },
-                    );
-                }
+    if execution_control_flags(app, execution_id).1 {
+        return Err("Execution cancelled".to_string());
+    }
+    if !failed.is_empty() {
+        return Err(format!(
+            "{} of {} matrix cells failed: {}",
+            failed.len(),
+            total,
+            failed.join("; ")
+        ));
+    }
+    Ok(0)
+}
 
-                // Send webhook if configured and trigger condition matches
-                if let Some(ref webhook) = webhook_config {
-                    if webhook.enabled && check_trigger_condition(&webhook.trigger, "failed") {
-                        let app_clone = app.clone();
-                        let webhook_clone = webhook.clone();
-                        let wf_id = workflow_id.clone();
-                        let wf_name = workflow_name.clone();
-                        let exec_id = execution_id.clone();
-                        let error_msg = error_msg.clone();
-
-                        // Fire-and-forget webhook
-                        tauri::async_runtime::spawn(async move {
-                            send_webhook(
-                                app_clone,
-                                &webhook_clone,
-                                &wf_id,
-                                &wf_name,
-                                &exec_id,
-                                "failed",
-                                duration_ms,
-                                error_msg.as_deref(),
-                            )
-                            .await;
-                        });
-                    }
-                }
-            }
+/// Parameters and finished step outputs of a running execution, for placeholder substitution
+fn node_interpolation_context(app: &AppHandle, execution_id: &str) -> InterpolationContext {
+    let state = app.state::<WorkflowExecutionState>();
+    let executions = state.executions.lock().unwrap();
+    let Some(exec) = executions.get(execution_id) else {
+        return InterpolationContext::default();
+    };
 
-            // Remove from running executions
-            {
-                let state = app.state::<WorkflowExecutionState>();
-                let mut executions = state.executions.lock().unwrap();
-                executions.remove(&execution_id);
-            }
+    let steps = exec
+        .execution
+        .node_results
+        .iter()
+        .filter(|r| r.status == NodeStatus::Completed)
+        .map(|r| (r.node_id.clone(), StepOutputs::from(r)))
+        .collect();
 
-            return;
-        }
+    InterpolationContext {
+        params: exec.execution.parameters.clone(),
+        steps,
+        ..Default::default()
     }
+}
 
-    // Check if execution was cancelled
-    let was_cancelled = {
+/// Environment of a script node: workflow `env` merged with the node `env`, placeholders
+/// rendered and referenced secrets decrypted. Also returns the secret values for masking.
+fn node_environment(
+    app: &AppHandle,
+    execution_id: &str,
+    node_env: &HashMap<String, String>,
+    interpolation_ctx: &InterpolationContext,
+) -> Result<(HashMap<String, String>, Vec<String>), String> {
+    let workflow_env = {
         let state = app.state::<WorkflowExecutionState>();
         let executions = state.executions.lock().unwrap();
         executions
-            .get(&execution_id)
-            .map(|e| e.should_cancel)
-            .unwrap_or(false)
+            .get(execution_id)
+            .map(|exec| exec.workflow.env.clone())
+            .unwrap_or_default()
     };
+    let env = merge_env(&workflow_env, node_env);
+    if env.is_empty() {
+        return Ok((env, Vec::new()));
+    }
 
-    let duration_ms = start_time.elapsed().as_millis() as u64;
-
-    if was_cancelled {
-        // Execution was cancelled - get current node index
-        let current_node_index = {
-            let state = app.state::<WorkflowExecutionState>();
-            let mut executions = state.executions.lock().unwrap();
-            if let Some(exec) = executions.get_mut(&execution_id) {
-                exec.execution.cancel();
-                exec.current_node_index
-            } else {
-                0
-            }
-        };
-
-        // Emit node_completed for the current running node (mark as cancelled)
-        if current_node_index < nodes.len() {
-            let current_node = &nodes[current_node_index];
-            let _ = app.emit(
-                "execution_node_completed",
-                NodeCompletedPayload {
-                    execution_id: execution_id.clone(),
-                    workflow_id: workflow_id.clone(),
-                    node_id: current_node.id.clone(),
-                    status: "cancelled".to_string(),
-                    exit_code: None,
-                    error_message: Some("Execution cancelled by user".to_string()),
-                    finished_at: Utc::now().to_rfc3339(),
-                },
-            );
-        }
+    let secrets = load_secrets(app, secret_references(env.values()))?;
+    let resolved = resolve_env(&env, interpolation_ctx, &secrets)?;
+    Ok((resolved, secrets.into_values().collect()))
+}
 
-        if !workflow_id.is_empty() {
-            let wf_id = workflow_id.clone();
-            println!(
-                "[workflow] Emitting execution_completed (cancelled) for {}",
-                execution_id
-            );
-            let _ = app.emit(
-                "execution_completed",
-                ExecutionCompletedPayload {
-                    execution_id: execution_id.clone(),
-                    workflow_id: wf_id.clone(),
-                    status: "cancelled".to_string(),
-                    finished_at: Utc::now().to_rfc3339(),
-                    total_duration_ms: duration_ms,
-                },
-            );
+/// Decrypt the named workflow secrets
+fn load_secrets(app: &AppHandle, names: Vec<String>) -> Result<HashMap<String, String>, String> {
+    if names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let db = app.state::<DatabaseState>();
+    WorkflowRepository::new(db.0.as_ref().clone()).load_secrets(&names)
+}
 
-            // Feature 013: Emit child execution completed if this is a child execution
-            if let (Some(ref parent_exec_id), Some(ref parent_node)) =
-                (&parent_execution_id, &parent_node_id)
-            {
-                let _ = app.emit(
-                    "child_execution_completed",
-                    ChildExecutionCompletedPayload {
-                        parent_execution_id: parent_exec_id.clone(),
-                        parent_node_id: parent_node.clone(),
-                        child_execution_id: execution_id.clone(),
-                        child_workflow_id: wf_id.clone(),
-                        status: "cancelled".to_string(),
-                        duration_ms,
-                        error_message: None,
-                        finished_at: Utc::now().to_rfc3339(),
-                    },
-                );
-            }
-        }
+/// Emit an engine-generated output line for a node and store it in the output buffer
+fn emit_node_output(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    node: &WorkflowNode,
+    line: String,
+) {
+    let timestamp = Utc::now().to_rfc3339();
+    let _ = app.emit(
+        "execution_output",
+        ExecutionOutputPayload {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node.id.clone(),
+            output: line.clone(),
+            stream: "stdout".to_string(),
+            timestamp: timestamp.clone(),
+        },
+    );
 
-        // Remove from running executions
-        {
-            let state = app.state::<WorkflowExecutionState>();
-            let mut executions = state.executions.lock().unwrap();
-            executions.remove(&execution_id);
-        }
-        return;
+    let state = app.state::<WorkflowExecutionState>();
+    let mut executions = state.executions.lock().unwrap();
+    if let Some(exec) = executions.get_mut(execution_id) {
+        exec.push_output(WorkflowOutputLine {
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            content: line,
+            stream: "stdout".to_string(),
+            timestamp,
+        });
     }
+}
+
+/// Finish an execution: update its status, emit completion events, send notifications
+/// and webhooks, then remove it from the running executions
+fn finalize_execution(
+    app: &AppHandle,
+    execution_id: &str,
+    workflow_id: &str,
+    status: ExecutionStatus,
+    duration_ms: u64,
+    error_message: Option<String>,
+    parent_execution_id: Option<&str>,
+    parent_node_id: Option<&str>,
+) {
+    let status_str = match status {
+        ExecutionStatus::Completed => "completed",
+        ExecutionStatus::Failed => "failed",
+        _ => "cancelled",
+    };
 
-    // All nodes completed successfully
-    let (workflow_id, workflow_name, webhook_config) = {
+    // Update execution status and get workflow info for webhook
+    let workflow_info = {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
-        if let Some(exec) = executions.get_mut(&execution_id) {
-            exec.execution.complete();
+        executions.get_mut(execution_id).map(|exec| {
+            match status {
+                ExecutionStatus::Completed => exec.execution.complete(),
+                ExecutionStatus::Failed => exec.execution.fail(),
+                _ => exec.execution.cancel(),
+            }
+            exec.finish_log(status_str);
             (
-                Some(exec.workflow.id.clone()),
+                exec.execution.clone(),
                 exec.workflow.name.clone(),
                 exec.workflow.webhook.clone(),
             )
-        } else {
-            (None, String::new(), None)
-        }
+        })
     };
 
-    if let Some(wf_id) = workflow_id {
+    if let Some((execution, workflow_name, webhook_config)) = workflow_info {
+        // Keep the final node results so the execution can be resumed later
+        let db = app.state::<DatabaseState>().0.as_ref().clone();
+        if let Err(e) = ExecutionRepository::new(db).save_finished(&execution) {
+            println!("[workflow] Failed to save execution result: {}", e);
+        }
+
         println!(
-            "[workflow] Emitting execution_completed for {}",
-            execution_id
+            "[workflow] Emitting execution_completed ({}) for {}",
+            status_str, execution_id
         );
         let _ = app.emit(
             "execution_completed",
             ExecutionCompletedPayload {
-                execution_id: execution_id.clone(),
-                workflow_id: wf_id.clone(),
-                status: "completed".to_string(),
+                execution_id: execution_id.to_string(),
+                workflow_id: workflow_id.to_string(),
+                status: status_str.to_string(),
                 finished_at: Utc::now().to_rfc3339(),
                 total_duration_ms: duration_ms,
             },
         );
 
-        // Send desktop notification for workflow completion
-        let _ = send_notification(
-            &app,
-            NotificationType::WorkflowCompleted {
-                workflow_name: workflow_name.clone(),
-                duration_ms,
-            },
-        );
+        // Send desktop notification for workflow completion/failure
+        match status {
+            ExecutionStatus::Completed => {
+                let _ = send_notification(
+                    app,
+                    NotificationType::WorkflowCompleted {
+                        workflow_name: workflow_name.clone(),
+                        duration_ms,
+                    },
+                );
+            }
+            ExecutionStatus::Failed => {
+                let _ = send_notification(
+                    app,
+                    NotificationType::WorkflowFailed {
+                        workflow_name: workflow_name.clone(),
+                        error: error_message
+                            .clone()
+                            .unwrap_or_else(|| "Unknown error".to_string()),
+                    },
+                );
+            }
+            _ => {}
+        }
 
         // Feature 013: Emit child execution completed if this is a child execution
-        if let (Some(ref parent_exec_id), Some(ref parent_node)) =
-            (&parent_execution_id, &parent_node_id)
-        {
+        if let (Some(parent_exec_id), Some(parent_node)) = (parent_execution_id, parent_node_id) {
             let _ = app.emit(
                 "child_execution_completed",
                 ChildExecutionCompletedPayload {
-                    parent_execution_id: parent_exec_id.clone(),
-                    parent_node_id: parent_node.clone(),
-                    child_execution_id: execution_id.clone(),
-                    child_workflow_id: wf_id.clone(),
-                    status: "completed".to_string(),
+                    parent_execution_id: parent_exec_id.to_string(),
+                    parent_node_id: parent_node.to_string(),
+                    child_execution_id: execution_id.to_string(),
+                    child_workflow_id: workflow_id.to_string(),
+                    status: status_str.to_string(),
                     duration_ms,
-                    error_message: None,
+                    error_message: error_message.clone(),
                     finished_at: Utc::now().to_rfc3339(),
                 },
             );
         }
 
         // Send webhook if configured and trigger condition matches
-        if let Some(ref webhook) = webhook_config {
-            if webhook.enabled && check_trigger_condition(&webhook.trigger, "completed") {
+        if let Some(webhook) = webhook_config {
+            if status_str != "cancelled"
+                && webhook.enabled
+                && check_trigger_condition(&webhook.trigger, status_str)
+            {
                 let app_clone = app.clone();
-                let webhook_clone = webhook.clone();
-                let wf_id_clone = wf_id.clone();
-                let wf_name = workflow_name.clone();
-                let exec_id = execution_id.clone();
+                let exec_id = execution_id.to_string();
+                let workflow_id = workflow_id.to_string();
 
                 // Fire-and-forget webhook
                 tauri::async_runtime::spawn(async move {
                     send_webhook(
                         app_clone,
-                        &webhook_clone,
-                        &wf_id_clone,
-                        &wf_name,
+                        &webhook,
+                        &workflow_id,
+                        &workflow_name,
                         &exec_id,
-                        "completed",
+                        status_str,
                         duration_ms,
-                        None,
+                        error_message.as_deref(),
                     )
                     .await;
                 });
             }
         }
-
     }
 
     // Remove from running executions
     {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
-        executions.remove(&execution_id);
+        executions.remove(execution_id);
+    }
+
+    // Let file-triggered workflows fire again once their own writes settle (also when
+    // the execution was killed and is no longer tracked)
+    app.state::<WorkflowFileTriggerManager>()
+        .execution_finished(workflow_id);
+
+    // Free the concurrency slot for queued executions
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    match ActiveExecutionRepository::new(db).remove(execution_id) {
+        Ok(true) => start_queued_executions(app),
+        Ok(false) => {}
+        Err(e) => println!("[workflow] Failed to update the run queue: {}", e),
     }
 }
 
@@ -1057,7 +2529,8 @@ async fn execute_trigger_workflow_node(
                     target_workflow_name
                 ));
             }
-            Some(crate::models::execution::ExecutionStatus::Paused) => {
+            Some(crate::models::execution::ExecutionStatus::Paused)
+            | Some(crate::models::execution::ExecutionStatus::Queued) => {
                 // Still considered running for our purposes
                 tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
             }
@@ -1158,21 +2631,26 @@ fn execute_child_workflow_sync(
         None
     };
 
+    // Child workflows run with their parameter defaults
+    let parameters = resolve_parameters(&workflow.parameters, &HashMap::new())?;
+
     // Create child execution
     let execution_id = Uuid::new_v4().to_string();
-    let execution = Execution::new_child(
+    let mut execution = Execution::new_child(
         execution_id.clone(),
         workflow_id.to_string(),
         parent_execution_id.to_string(),
         parent_node_id.to_string(),
         depth,
     );
+    execution.parameters = parameters;
 
     // Sort nodes by order
     let mut sorted_nodes = workflow.nodes.clone();
     sorted_nodes.sort_by_key(|n| n.order);
 
     // Store execution state
+    let log = create_execution_log(app, &execution_id, &workflow, project_path.as_deref());
     {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
@@ -1181,11 +2659,12 @@ fn execute_child_workflow_sync(
             RunningWorkflowExecution {
                 execution,
                 workflow: workflow.clone(),
-                current_node_index: 0,
+                running_node_ids: Vec::new(),
                 is_paused: false,
                 should_cancel: false,
-                current_process_id: None,
+                process_ids: HashMap::new(),
                 output_buffer: WorkflowOutputBuffer::new(),
+                log,
             },
         );
     }
@@ -1216,7 +2695,82 @@ fn execute_child_workflow_sync(
     Ok(execution_id)
 }
 
-/// Execute a single node
+/// Final program and arguments of a script node command (shared with dry-run plans)
+/// Shell commands are only wrapped to pin a matrix Node version; direct commands
+/// use Volta/Corepack according to the project's package.json
+fn wrap_node_command(
+    invocation: CommandInvocation,
+    cwd: Option<&str>,
+    node_version: Option<&str>,
+) -> Result<(String, Vec<String>), String> {
+    if invocation.via_shell {
+        // A matrix Node version pins the whole shell through Volta
+        return match node_version {
+            Some(version) => get_volta_pinned_command(
+                Path::new(cwd.unwrap_or(".")),
+                version,
+                &invocation.program,
+                invocation.args,
+            ),
+            None => Ok((invocation.program, invocation.args)),
+        };
+    }
+
+    let cmd_name = invocation.program.as_str();
+    let args = invocation.args;
+
+    // Check if we should use Volta for version management
+    if let Some(version) = node_version {
+        get_volta_pinned_command(Path::new(cwd.unwrap_or(".")), version, cmd_name, args)
+    } else if let Some(cwd_path) = cwd {
+        let project_path = std::path::Path::new(cwd_path);
+        Ok(get_volta_wrapped_command(project_path, cmd_name, args))
+    } else {
+        Ok((path_resolver::get_tool_path(cmd_name), args))
+    }
+}
+
+/// Output a script run keeps for itself while streaming. The display buffer drops old
+/// lines once it is full, so nothing that decides how the run continues reads from it.
+#[derive(Debug, Default)]
+struct ScriptRunOutput {
+    /// First line (stdout or stderr) matching one of the node's `retryOn` output patterns
+    retry_match: Option<String>,
+    /// Stdout and `::set-output` values (from either stream) for later nodes
+    step: StepOutputs,
+}
+
+impl ScriptRunOutput {
+    /// Record a streamed line (secrets masked, without the matrix cell prefix)
+    fn capture(&mut self, line: &str, stream: &str, retry_patterns: &[Regex]) {
+        if self.retry_match.is_none() && retry_patterns.iter().any(|re| re.is_match(line)) {
+            self.retry_match = Some(line.to_string());
+        }
+        if let Some((key, value)) = parse_set_output(line) {
+            self.step.outputs.insert(key, value);
+        }
+        if stream == "stdout" {
+            self.step.push_output_line(line);
+        }
+    }
+
+    /// Add what the stderr stream of the same run captured
+    fn merge(&mut self, stderr: ScriptRunOutput) {
+        if self.retry_match.is_none() {
+            self.retry_match = stderr.retry_match;
+        }
+        self.step.outputs.extend(stderr.step.outputs);
+    }
+}
+
+/// Keep the step output of a script node on its result
+fn record_step_output(node_result: &mut NodeResult, step: StepOutputs) {
+    node_result.output = step.output;
+    node_result.output_truncated = step.truncated;
+    node_result.outputs = step.outputs;
+}
+
+/// Execute a single node (or one cell of a matrix node)
 /// Uses path_resolver to handle macOS GUI app PATH issues
 /// Supports cancellation by checking should_cancel flag and killing the process
 async fn execute_node(
@@ -1225,7 +2779,8 @@ async fn execute_node(
     workflow_id: &str,
     node: &WorkflowNode,
     default_cwd: Option<&str>,
-) -> Result<i32, String> {
+    cell: Option<&MatrixCell>,
+) -> Result<(i32, ScriptRunOutput), String> {
     // Only script nodes can be executed with this function
     if !node.is_script() {
         return Err(format!(
@@ -1234,68 +2789,45 @@ async fn execute_node(
         ));
     }
 
-    let config = node
+    let mut config = node
         .get_script_config()
         .ok_or_else(|| "Invalid script node config".to_string())?;
 
-    // Determine working directory: node config > default (project path)
-    let cwd = config.cwd.as_deref().or(default_cwd);
-
-    // Check if command contains shell special characters (pipes, redirects, etc.)
-    let needs_shell = config.command.contains('|')
-        || config.command.contains("&&")
-        || config.command.contains("||")
-        || config.command.contains(';')
-        || config.command.contains('>')
-        || config.command.contains('<')
-        || config.command.contains('$')
-        || config.command.contains('`')
-        || config.command.contains('*')
-        || config.command.contains('?');
-
-    let (final_cmd, final_args) = if needs_shell {
-        // Execute through shell for complex commands
-        #[cfg(unix)]
-        {
-            (
-                "/bin/sh".to_string(),
-                vec!["-c".to_string(), config.command.clone()],
-            )
-        }
-        #[cfg(windows)]
-        {
-            (
-                "cmd".to_string(),
-                vec!["/C".to_string(), config.command.clone()],
-            )
-        }
-    } else {
-        // Parse simple command
-        let parts: Vec<&str> = config.command.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err("Empty command".to_string());
-        }
-
-        let cmd_name = parts[0];
-        let args: Vec<&str> = parts[1..].to_vec();
-
-        // 🗑️ Intercept rm command - use trash instead of permanent delete
-        if cmd_name == "rm" {
-            return execute_trash_command(app, execution_id, workflow_id, node, &args, cwd).await;
-        }
+    // Substitute {{params.*}}, {{steps.*}} and {{matrix.*}} placeholders; values in the
+    // command are quoted for the node's shell so they cannot inject shell syntax
+    let mut interpolation_ctx = node_interpolation_context(app, execution_id);
+    if let Some(cell) = cell {
+        interpolation_ctx.matrix = cell.values.clone().into_iter().collect();
+    }
+    config.command = interpolate_command(&config.command, &interpolation_ctx, config.shell)?;
+    config.cwd = config
+        .cwd
+        .map(|cwd| interpolate(&cwd, &interpolation_ctx))
+        .transpose()?;
+
+    // Workflow + node env with secrets decrypted; secret values are masked in the output
+    let (node_env, secret_values) =
+        node_environment(app, execution_id, &config.env, &interpolation_ctx)?;
+
+    // Determine working directory: matrix package > node config > default (project path)
+    let cwd = cell
+        .and_then(|cell| cell.cwd.as_deref())
+        .or(config.cwd.as_deref())
+        .or(default_cwd);
+    let node_version = cell.and_then(|cell| cell.node_version.as_deref());
+
+    // The node's shell, or `sh` (`cmd` on Windows) only when the command uses shell syntax
+    let invocation = resolve_command(&config.command, config.shell)?;
+
+    // 🗑️ Intercept rm command - use trash instead of permanent delete
+    if !invocation.via_shell && invocation.program == "rm" {
+        let args: Vec<&str> = invocation.args.iter().map(String::as_str).collect();
+        return execute_trash_command(app, execution_id, workflow_id, node, &args, cwd)
+            .await
+            .map(|code| (code, ScriptRunOutput::default()));
+    }
 
-        // Check if we should use Volta for version management
-        if let Some(cwd_path) = cwd {
-            let project_path = std::path::Path::new(cwd_path);
-            let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
-            get_volta_wrapped_command(project_path, cmd_name, args_vec)
-        } else {
-            (
-                path_resolver::get_tool_path(cmd_name),
-                args.iter().map(|s| s.to_string()).collect(),
-            )
-        }
-    };
+    let (final_cmd, final_args) = wrap_node_command(invocation, cwd, node_version)?;
 
     // Spawn and handle output
     println!(
@@ -1308,8 +2840,8 @@ async fn execute_node(
     command.args(&final_args);
 
     // Expand ~ in cwd path
-    if let Some(cwd_path) = cwd {
-        let expanded_cwd = if cwd_path.starts_with("~/") {
+    let expanded_cwd = cwd.map(|cwd_path| {
+        if cwd_path.starts_with("~/") {
             if let Some(home) = path_resolver::get_home_dir() {
                 cwd_path.replacen("~", &home, 1)
             } else {
@@ -1319,13 +2851,16 @@ async fn execute_node(
             path_resolver::get_home_dir().unwrap_or_else(|| cwd_path.to_string())
         } else {
             cwd_path.to_string()
-        };
-        command.current_dir(&expanded_cwd);
+        }
+    });
+    if let Some(ref expanded_cwd) = expanded_cwd {
+        command.current_dir(expanded_cwd);
     }
 
     // Set CI=true to prevent interactive prompts from pnpm/npm
     // This fixes ERR_PNPM_ABORTED_REMOVE_MODULES_DIR_NO_TTY error
     command.env("CI", "true");
+    command.envs(&node_env);
 
     // Set up stdio for streaming output
     // Kill child processes when the main process is killed
@@ -1347,12 +2882,17 @@ async fn execute_node(
         })?;
 
     // Store the process ID for potential cancellation
+    // Matrix cells run concurrently, so each cell has its own key
+    let process_key = match cell {
+        Some(cell) => format!("{}#{}", node.id, cell.index),
+        None => node.id.clone(),
+    };
     let child_pid = child.id();
     {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
-        if let Some(exec) = executions.get_mut(execution_id) {
-            exec.current_process_id = child_pid;
+        if let (Some(exec), Some(pid)) = (executions.get_mut(execution_id), child_pid) {
+            exec.process_ids.insert(process_key.clone(), pid);
         }
     }
 
@@ -1373,10 +2913,21 @@ async fn execute_node(
     let node_id_stderr = node.id.clone();
     let node_name_stdout = node.name.clone();
     let node_name_stderr = node.name.clone();
-
-    // Spawn tasks to read stdout and stderr concurrently
+    let secrets_stdout = secret_values.clone();
+    let secrets_stderr = secret_values;
+    // Lines of a matrix cell are prefixed with the cell label
+    let prefix_stdout = cell.map(matrix_cell_prefix).unwrap_or_default();
+    let prefix_stderr = prefix_stdout.clone();
+    let retry_patterns_stdout = retry_output_patterns(&config.retry);
+    let retry_patterns_stderr = retry_patterns_stdout.clone();
+
+    // Spawn tasks to read stdout and stderr concurrently; each returns what it captured
     let stdout_task = tokio::spawn(async move {
+        let mut run_output = ScriptRunOutput::default();
         while let Ok(Some(line)) = stdout_reader.next_line().await {
+            let line = mask_secret_values(&line, &secrets_stdout);
+            run_output.capture(&line, "stdout", &retry_patterns_stdout);
+            let line = prefix_stdout.clone() + &line;
             println!("[workflow] stdout: {}", line);
             let timestamp = Utc::now().to_rfc3339();
             let _ = app_stdout.emit(
@@ -1395,7 +2946,7 @@ async fn execute_node(
                 let state = app_stdout.state::<WorkflowExecutionState>();
                 let mut executions = state.executions.lock().unwrap();
                 if let Some(exec) = executions.get_mut(&exec_id_stdout) {
-                    exec.output_buffer.push(WorkflowOutputLine {
+                    exec.push_output(WorkflowOutputLine {
                         node_id: node_id_stdout.clone(),
                         node_name: node_name_stdout.clone(),
                         content: line,
@@ -1405,10 +2956,15 @@ async fn execute_node(
                 }
             }
         }
+        run_output
     });
 
     let stderr_task = tokio::spawn(async move {
+        let mut run_output = ScriptRunOutput::default();
         while let Ok(Some(line)) = stderr_reader.next_line().await {
+            let line = mask_secret_values(&line, &secrets_stderr);
+            run_output.capture(&line, "stderr", &retry_patterns_stderr);
+            let line = prefix_stderr.clone() + &line;
             let timestamp = Utc::now().to_rfc3339();
             let _ = app_stderr.emit(
                 "execution_output",
@@ -1426,7 +2982,7 @@ async fn execute_node(
                 let state = app_stderr.state::<WorkflowExecutionState>();
                 let mut executions = state.executions.lock().unwrap();
                 if let Some(exec) = executions.get_mut(&exec_id_stderr) {
-                    exec.output_buffer.push(WorkflowOutputLine {
+                    exec.push_output(WorkflowOutputLine {
                         node_id: node_id_stderr.clone(),
                         node_name: node_name_stderr.clone(),
                         content: line,
@@ -1436,6 +2992,7 @@ async fn execute_node(
                 }
             }
         }
+        run_output
     });
 
     // Wait for the process to complete, checking for cancellation periodically
@@ -1497,15 +3054,70 @@ async fn execute_node(
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
         if let Some(exec) = executions.get_mut(execution_id) {
-            exec.current_process_id = None;
+            exec.process_ids.remove(&process_key);
         }
     }
 
     // Wait for output tasks to complete
-    let _ = stdout_task.await;
-    let _ = stderr_task.await;
+    let mut run_output = stdout_task.await.unwrap_or_default();
+    run_output.merge(stderr_task.await.unwrap_or_default());
+
+    // Keep the files matching the node's artifact globs once it succeeded
+    if matches!(result, Ok(0)) && !config.artifacts.is_empty() {
+        let owner = ArtifactOwner {
+            execution_id: execution_id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            node_id: node.id.clone(),
+            node_name: node.name.clone(),
+            cell_index: cell.map(|cell| cell.index),
+        };
+        let prefix = cell.map(matrix_cell_prefix).unwrap_or_default();
+        let artifact_cwd = expanded_cwd.unwrap_or_else(|| ".".to_string());
+        let line =
+            collect_node_artifacts(app, owner, artifact_cwd.into(), config.artifacts.clone())
+                .await
+                .map_err(|e| format!("Failed to collect artifacts: {}", e))?;
+        emit_node_output(app, execution_id, workflow_id, node, prefix + &line);
+    }
 
-    result
+    result.map(|code| (code, run_output))
+}
+
+/// Copy a node's artifacts into the artifact store and record them, on a blocking thread
+/// since copying and hashing large files would stall the runtime.
+/// Returns a summary line for the node output.
+async fn collect_node_artifacts(
+    app: &AppHandle,
+    owner: ArtifactOwner,
+    cwd: PathBuf,
+    patterns: Vec<String>,
+) -> Result<String, String> {
+    let store_dir = execution_artifacts_dir()?;
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    let collection = tokio::task::spawn_blocking(move || {
+        let collection =
+            collect_artifacts(&store_dir, &cwd, &patterns, &owner, MAX_NODE_ARTIFACT_BYTES)?;
+        ArtifactRepository::new(db).save(&collection.artifacts)?;
+        Ok::<_, String>(collection)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))??;
+
+    let count = collection.artifacts.len();
+    let total_bytes: u64 = collection.artifacts.iter().map(|a| a.size_bytes).sum();
+    let mut line = format!(
+        "Collected {} artifact file{} ({} bytes)",
+        count,
+        if count == 1 { "" } else { "s" },
+        total_bytes
+    );
+    if !collection.unmatched.is_empty() {
+        line.push_str(&format!(
+            "; no files matched {}",
+            collection.unmatched.join(", ")
+        ));
+    }
+    Ok(line)
 }
 
 /// Execute rm command by moving files to trash instead of permanent deletion
@@ -1520,14 +3132,10 @@ async fn execute_trash_command(
 ) -> Result<i32, String> {
     println!("[workflow] Intercepting rm command, using trash instead");
 
-    // Filter out flags (like -r, -f, -rf, etc.) and get file paths
-    let files: Vec<&str> = args
-        .iter()
-        .filter(|arg| !arg.starts_with('-'))
-        .copied()
-        .collect();
-
-    if files.is_empty() {
+    // Flags (like -r, -f, -rf, etc.) are dropped; relative paths are resolved against cwd
+    let targets = trash_targets(args, cwd);
+
+    if targets.is_empty() {
         let msg = "rm: missing operand (no files specified)".to_string();
         let _ = app.emit(
             "execution_output",
@@ -1543,28 +3151,10 @@ async fn execute_trash_command(
         return Err(msg);
     }
 
-    let base_path = cwd.map(PathBuf::from);
     let mut success_count = 0;
     let mut error_count = 0;
 
-    for file in &files {
-        // Resolve path: if relative, join with cwd
-        let path = if file.starts_with('/') || file.starts_with('~') {
-            // Absolute path or home-relative
-            let expanded = if file.starts_with('~') {
-                shellexpand::tilde(file).to_string()
-            } else {
-                file.to_string()
-            };
-            PathBuf::from(expanded)
-        } else {
-            // Relative path
-            match &base_path {
-                Some(base) => base.join(file),
-                None => PathBuf::from(file),
-            }
-        };
-
+    for (file, path) in &targets {
         // Check if path exists
         if !path.exists() {
             let msg = format!("rm: {}: No such file or directory", file);
@@ -1654,6 +3244,32 @@ pub async fn cancel_execution(
 ) -> Result<(), String> {
     let cascade = cascade.unwrap_or(true); // Default to cascading cancel
 
+    // A queued execution has not started yet and is simply dropped from the queue
+    let queued = {
+        let state = app.state::<WorkflowExecutionState>();
+        let mut queued = state.queued.lock().unwrap();
+        queued.remove(&execution_id)
+    };
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    let active_repo = ActiveExecutionRepository::new(db);
+    if let Some(queued) = queued {
+        active_repo.remove(&execution_id)?;
+        app.state::<WorkflowFileTriggerManager>()
+            .execution_finished(&queued.workflow.id);
+        println!("[workflow] Queued execution {} cancelled", execution_id);
+        let _ = app.emit(
+            "execution_completed",
+            ExecutionCompletedPayload {
+                execution_id: execution_id.clone(),
+                workflow_id: queued.workflow.id,
+                status: "cancelled".to_string(),
+                finished_at: Utc::now().to_rfc3339(),
+                total_duration_ms: 0,
+            },
+        );
+        return Ok(());
+    }
+
     // Collect child execution IDs first (to avoid holding lock during cancel)
     let child_execution_ids: Vec<String> = if cascade {
         let state = app.state::<WorkflowExecutionState>();
@@ -1690,6 +3306,14 @@ pub async fn cancel_execution(
             "[workflow] Execution {} cancelled (cascade={})",
             execution_id, cascade
         );
+        let workflow_id = exec.workflow.id.clone();
+        drop(executions);
+        app.state::<WorkflowFileTriggerManager>()
+            .execution_finished(&workflow_id);
+        // The execution no longer holds a concurrency slot while it winds down
+        if active_repo.remove(&execution_id)? {
+            start_queued_executions(&app);
+        }
         Ok(())
     } else {
         Err("Execution not found".to_string())
@@ -1703,7 +3327,7 @@ pub async fn continue_execution(
     db: tauri::State<'_, DatabaseState>,
     execution_id: String,
 ) -> Result<(), String> {
-    let (workflow, start_index, project_id, workflow_id, parent_execution_id, parent_node_id) = {
+    let (sorted_nodes, project_id, workflow_id, parent_execution_id, parent_node_id) = {
         let state = app.state::<WorkflowExecutionState>();
         let mut executions = state.executions.lock().unwrap();
 
@@ -1719,7 +3343,6 @@ pub async fn continue_execution(
 
             (
                 sorted_nodes,
-                exec.current_node_index,
                 exec.workflow.project_id.clone(),
                 exec.workflow.id.clone(),
                 exec.execution.parent_execution_id.clone(),
@@ -1751,9 +3374,7 @@ pub async fn continue_execution(
         None
     };
 
-    // Continue from current node
-    let remaining_nodes: Vec<WorkflowNode> = workflow.into_iter().skip(start_index).collect();
-
+    // Continue with the full graph; nodes already completed are not run again
     let app_clone = app.clone();
     let exec_id = execution_id.clone();
 
@@ -1762,7 +3383,7 @@ pub async fn continue_execution(
             app_clone,
             ctx,
             exec_id,
-            remaining_nodes,
+            sorted_nodes,
             project_path,
             parent_execution_id,
             parent_node_id,
@@ -1773,16 +3394,115 @@ pub async fn continue_execution(
     Ok(())
 }
 
-/// Get all running executions
+/// Re-run part of a finished execution as a new execution: `from_node_id` and everything
+/// after it, or only the nodes from `from_node_id` to `to_node_id`.
+/// Stored results of the other nodes are reused; the new execution links to the original.
+/// Refused once the workflow definition differs from the revision the original ran.
+#[tauri::command]
+pub async fn resume_execution(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+    execution_id: String,
+    from_node_id: String,
+    to_node_id: Option<String>,
+) -> Result<String, String> {
+    let db = db.0.as_ref().clone();
+    let original = ExecutionRepository::new(db.clone())
+        .get_finished(&execution_id)?
+        .ok_or_else(|| format!("No stored results for execution {}", execution_id))?;
+
+    let workflow_repo = WorkflowRepository::new(db.clone());
+    let project_repo = ProjectRepository::new(db.clone());
+    let workflow = workflow_repo
+        .get(&original.workflow_id)?
+        .ok_or_else(|| "Workflow not found".to_string())?;
+
+    // Reused results only fit the definition that produced them
+    let revision = original.revision.ok_or_else(|| {
+        format!(
+            "Execution {} did not record its workflow revision",
+            execution_id
+        )
+    })?;
+    let unchanged = workflow_repo
+        .get_revision(&workflow.id, revision)?
+        .is_some_and(|ran| is_same_definition(&ran.workflow, &workflow));
+    if !unchanged {
+        return Err(format!(
+            "Workflow '{}' changed since execution {} ran (revision {}); start a new run instead",
+            workflow.name, execution_id, revision
+        ));
+    }
+
+    let dag = WorkflowDag::new(&workflow.nodes)?;
+    let plan = plan_resume(
+        &dag,
+        &original.node_results,
+        &from_node_id,
+        to_node_id.as_deref(),
+    )?;
+    println!(
+        "[workflow] Resuming execution {} of {}: running {:?}",
+        execution_id, workflow.name, plan.run_node_ids
+    );
+
+    let project_path: Option<String> = match workflow.project_id {
+        Some(ref project_id) => project_repo.get(project_id)?.map(|p| p.path),
+        None => None,
+    };
+
+    let mut execution = Execution::new(Uuid::new_v4().to_string(), workflow.id.clone());
+    execution.parameters = original.parameters;
+    execution.revision =
+        Some(workflow_repo.record_revision(&workflow, RevisionSource::Unknown, None)?);
+    execution.node_results = plan.reused_results;
+    execution.resumed_from = Some(ResumeOrigin {
+        execution_id,
+        from_node_id,
+        to_node_id,
+    });
+    let new_execution_id = execution.id.clone();
+
+    let ctx = WorkflowExecutionContext {
+        workflows: workflow_repo.list()?,
+        projects: project_repo.list()?,
+        execution_chain: vec![workflow.id.clone()],
+    };
+
+    admit_workflow_execution(app, &db, ctx, workflow, execution, project_path).await?;
+
+    Ok(new_execution_id)
+}
+
+/// Get all running executions, including queued ones (status `queued` with their
+/// position in the run queue shared with the MCP server)
 #[tauri::command]
-pub async fn get_running_executions(app: AppHandle) -> Result<HashMap<String, Execution>, String> {
+pub async fn get_running_executions(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+) -> Result<HashMap<String, Execution>, String> {
+    let positions: HashMap<String, usize> =
+        queue_positions(&ActiveExecutionRepository::new(db.0.as_ref().clone()).list()?)
+            .into_iter()
+            .collect();
+
     let state = app.state::<WorkflowExecutionState>();
-    let executions = state.executions.lock().unwrap();
+    let mut result: HashMap<String, Execution> = {
+        let executions = state.executions.lock().unwrap();
+        executions
+            .iter()
+            .map(|(id, exec)| (id.clone(), exec.execution.clone()))
+            .collect()
+    };
 
-    let result: HashMap<String, Execution> = executions
-        .iter()
-        .map(|(id, exec)| (id.clone(), exec.execution.clone()))
-        .collect();
+    let queued = state.queued.lock().unwrap();
+    result.extend(queued.iter().map(|(id, queued)| {
+        let execution = Execution {
+            queue_position: positions.get(id).copied(),
+            ..queued.execution.clone()
+        };
+        (id.clone(), execution)
+    }));
 
     Ok(result)
 }
@@ -1876,7 +3596,12 @@ pub async fn kill_process(app: AppHandle, execution_id: String) -> Result<(), St
             },
         );
 
+        exec.finish_log("cancelled");
+        let workflow_id = exec.workflow.id.clone();
         executions.remove(&execution_id);
+        drop(executions);
+        app.state::<WorkflowFileTriggerManager>()
+            .execution_finished(&workflow_id);
         Ok(())
     } else {
         Err("Execution not found".to_string())
@@ -1910,24 +3635,21 @@ fn render_template(
     let timestamp = Utc::now().to_rfc3339();
     let error = error_message.unwrap_or("");
 
-    // Use regex for variable substitution
-    let re = Regex::new(r"\{\{(\w+)\}\}").unwrap();
-
-    re.replace_all(template, |caps: &regex::Captures| {
-        match &caps[1] {
-            "workflow_id" => workflow_id.to_string(),
-            "workflow_name" => workflow_name.to_string(),
-            "execution_id" => execution_id.to_string(),
-            "status" => status.to_string(),
-            "duration" => duration_ms.to_string(),
-            "timestamp" => timestamp.clone(),
-            "error_message" => error.to_string(),
-            _ => caps[0].to_string(), // Keep unknown variables as-is
-        }
+    render_placeholders(template, |name| match name {
+        "workflow_id" => Some(workflow_id.to_string()),
+        "workflow_name" => Some(workflow_name.to_string()),
+        "execution_id" => Some(execution_id.to_string()),
+        "status" => Some(status.to_string()),
+        "duration" => Some(duration_ms.to_string()),
+        "timestamp" => Some(timestamp.clone()),
+        "error_message" => Some(error.to_string()),
+        _ => None, // Keep unknown variables as-is
     })
-    .to_string()
 }
 
+/// Timeout of an outgoing webhook delivery
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// Send webhook notification (fire-and-forget)
 async fn send_webhook(
     app: AppHandle,
@@ -1960,38 +3682,14 @@ async fn send_webhook(
 
     println!("[webhook] Sending to {}: {}", webhook.url, payload);
 
-    // Build HTTP client with 10 second timeout
-    let client = match reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            let _ = emit_webhook_delivery(
-                &app,
-                execution_id,
-                workflow_id,
-                false,
-                None,
-                Some(format!("Failed to create HTTP client: {}", e)),
-                Some(start.elapsed().as_millis() as u64),
-            );
-            return;
-        }
-    };
-
-    // Build request
-    let mut request = client
-        .post(&webhook.url)
-        .header("Content-Type", "application/json")
-        .body(payload);
-
-    // Add custom headers
-    if let Some(headers) = &webhook.headers {
-        for (key, value) in headers {
-            request = request.header(key, value);
-        }
-    }
+    let request = outgoing_http::build_request(
+        reqwest::Method::POST,
+        &webhook.url,
+        webhook.headers.as_ref(),
+        Some(payload),
+        None,
+        WEBHOOK_TIMEOUT,
+    );
 
     // Send request
     match request.send().await {
@@ -2035,13 +3733,7 @@ async fn send_webhook(
             println!("[webhook] Response: {} (success={})", status_code, success);
         }
         Err(e) => {
-            let error_msg = if e.is_timeout() {
-                "Request timed out (10s)".to_string()
-            } else if e.is_connect() {
-                "Connection failed".to_string()
-            } else {
-                e.to_string()
-            };
+            let error_msg = outgoing_http::describe_send_error(&e, WEBHOOK_TIMEOUT);
 
             let _ = emit_webhook_delivery(
                 &app,
@@ -2203,54 +3895,9 @@ pub async fn detect_workflow_cycle(
 
     // DFS to detect cycle starting from target_workflow_id
     // (if target can reach source, adding source->target creates a cycle)
-    let mut visited: HashSet<String> = HashSet::new();
-    let mut rec_stack: HashSet<String> = HashSet::new();
-    let mut path: Vec<String> = Vec::new();
-
-    fn dfs(
-        node: &str,
-        target: &str,
-        adjacency: &HashMap<String, Vec<String>>,
-        visited: &mut HashSet<String>,
-        rec_stack: &mut HashSet<String>,
-        path: &mut Vec<String>,
-    ) -> bool {
-        visited.insert(node.to_string());
-        rec_stack.insert(node.to_string());
-        path.push(node.to_string());
-
-        if let Some(neighbors) = adjacency.get(node) {
-            for neighbor in neighbors {
-                // Found the target - cycle detected
-                if neighbor == target {
-                    path.push(neighbor.to_string());
-                    return true;
-                }
-
-                if !visited.contains(neighbor) {
-                    if dfs(neighbor, target, adjacency, visited, rec_stack, path) {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        rec_stack.remove(node);
-        path.pop();
-        false
-    }
+    let cycle_path = find_path(&adjacency, &target_workflow_id, &source_workflow_id);
 
-    // Check if target can reach source (which would create a cycle)
-    let has_cycle = dfs(
-        &target_workflow_id,
-        &source_workflow_id,
-        &adjacency,
-        &mut visited,
-        &mut rec_stack,
-        &mut path,
-    );
-
-    if has_cycle {
+    if let Some(path) = cycle_path {
         // Build workflow name map for human-readable description
         let name_map: HashMap<String, String> = workflows
             .iter()
@@ -2321,6 +3968,170 @@ pub async fn get_child_executions(
     Ok(children)
 }
 
+// ============================================================================
+// Workflow Revisions
+// ============================================================================
+
+/// List the saved revisions of a workflow, newest first
+#[tauri::command]
+pub async fn list_workflow_revisions(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+) -> Result<Vec<WorkflowRevision>, String> {
+    WorkflowRepository::new(db.0.as_ref().clone()).list_revisions(&workflow_id)
+}
+
+/// Compare two revisions of a workflow (`to_revision` defaults to the latest)
+#[tauri::command]
+pub async fn diff_workflow_revisions(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+    from_revision: u32,
+    to_revision: Option<u32>,
+) -> Result<WorkflowRevisionDiff, String> {
+    let repo = WorkflowRepository::new(db.0.as_ref().clone());
+    let get = |revision: u32| {
+        repo.get_revision(&workflow_id, revision)?
+            .ok_or_else(|| format!("Revision {} not found", revision))
+    };
+
+    let from = get(from_revision)?;
+    let to = match to_revision {
+        Some(revision) => get(revision)?,
+        None => repo
+            .get_latest_revision(&workflow_id)?
+            .ok_or_else(|| "Workflow has no revisions".to_string())?,
+    };
+    Ok(diff_revisions(&from, &to))
+}
+
+/// Restore a workflow to an earlier revision. The rollback is saved as a new revision;
+/// returns its number.
+#[tauri::command]
+pub async fn rollback_workflow(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+    revision: u32,
+) -> Result<u32, String> {
+    let repo = WorkflowRepository::new(db.0.as_ref().clone());
+    let current = repo
+        .get(&workflow_id)?
+        .ok_or_else(|| "Workflow not found".to_string())?;
+    let target = repo
+        .get_revision(&workflow_id, revision)?
+        .ok_or_else(|| format!("Revision {} not found", revision))?;
+
+    let restored = restore_revision(&current, &target);
+    WorkflowDag::new(&restored.nodes)?;
+    let new_revision = repo.save_with_revision(
+        &restored,
+        RevisionSource::Ui,
+        Some(&format!("Rolled back to revision {}", revision)),
+    )?;
+    println!(
+        "[workflow] Rolled back {} to revision {} (now revision {})",
+        current.name, revision, new_revision
+    );
+
+    sync_workflow_triggers(&app, db.0.as_ref()).await;
+    Ok(new_revision)
+}
+
+// ============================================================================
+// Execution Plans (dry run)
+// ============================================================================
+
+/// Resolves plans the way this app runs workflows
+struct AppPlanExecutor {
+    workflows: Vec<Workflow>,
+    projects: Vec<Project>,
+    secret_names: Vec<String>,
+}
+
+impl AppPlanExecutor {
+    fn project_path(&self, workflow: &Workflow) -> Option<String> {
+        let project_id = workflow.project_id.as_ref()?;
+        self.projects
+            .iter()
+            .find(|p| &p.id == project_id)
+            .map(|p| p.path.clone())
+    }
+}
+
+impl PlanExecutor for AppPlanExecutor {
+    fn name(&self) -> &'static str {
+        "app"
+    }
+
+    fn wrap_command(
+        &self,
+        invocation: CommandInvocation,
+        cwd: Option<&str>,
+        node_version: Option<&str>,
+    ) -> Result<(String, Vec<String>), String> {
+        wrap_node_command(invocation, cwd, node_version)
+    }
+
+    fn intercepts_rm(&self) -> bool {
+        true
+    }
+
+    fn secret_names(&self) -> Result<Vec<String>, String> {
+        Ok(self.secret_names.clone())
+    }
+
+    fn workspace_packages(&self, project_path: &str) -> Result<Vec<WorkspacePackage>, String> {
+        list_workspace_packages(Path::new(project_path))
+            .map_err(|e| format!("Failed to list workspace packages: {}", e))
+    }
+
+    fn child_workflow(
+        &self,
+        workflow_id: &str,
+    ) -> Result<Option<(Workflow, Option<String>)>, String> {
+        let workflow = self
+            .workflows
+            .iter()
+            .find(|w| w.id == workflow_id)
+            .ok_or_else(|| format!("Target workflow not found: {}", workflow_id))?;
+        Ok(Some((workflow.clone(), self.project_path(workflow))))
+    }
+}
+
+/// Resolve what running a workflow would do without running it: node order, final
+/// commands after Volta/Corepack wrapping, cwd, env, `rm` moved to the Trash and
+/// the child workflows that would be triggered
+#[tauri::command]
+pub async fn get_workflow_plan(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+    params: Option<HashMap<String, serde_json::Value>>,
+) -> Result<WorkflowPlan, String> {
+    let db = db.0.as_ref().clone();
+    let workflow_repo = WorkflowRepository::new(db.clone());
+    let workflow = workflow_repo
+        .get(&workflow_id)?
+        .ok_or_else(|| "Workflow not found".to_string())?;
+
+    let executor = AppPlanExecutor {
+        workflows: workflow_repo.list()?,
+        projects: ProjectRepository::new(db).list()?,
+        secret_names: workflow_repo
+            .list_secrets()?
+            .into_iter()
+            .map(|secret| secret.name)
+            .collect(),
+    };
+    let default_cwd = executor.project_path(&workflow);
+    plan_workflow(
+        &executor,
+        &workflow,
+        default_cwd.as_deref(),
+        &params.unwrap_or_default(),
+    )
+}
+
 // ============================================================================
 // Execution History Commands
 // ============================================================================
@@ -2341,6 +4152,15 @@ pub struct ExecutionHistoryItem {
     pub error_message: Option<String>,
     pub output: Vec<WorkflowOutputLine>,
     pub triggered_by: String,
+    /// Execution this run resumed (retry from a node / run a node range)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from_execution_id: Option<String>,
+    /// Revision of the workflow that was run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+    /// Files collected from artifact globs (read from the artifact store, not saved here)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ExecutionArtifact>,
 }
 
 /// Execution history settings
@@ -2350,6 +4170,20 @@ pub struct ExecutionHistorySettings {
     pub max_history_per_workflow: usize,
     pub retention_days: u32,
     pub max_output_lines: usize,
+    /// Size limit of all persisted execution logs (0 = unlimited)
+    #[serde(default = "default_max_log_storage_mb")]
+    pub max_log_storage_mb: u64,
+    /// Size limit of all collected artifacts (0 = unlimited)
+    #[serde(default = "default_max_artifact_storage_mb")]
+    pub max_artifact_storage_mb: u64,
+}
+
+fn default_max_log_storage_mb() -> u64 {
+    500
+}
+
+fn default_max_artifact_storage_mb() -> u64 {
+    2048
 }
 
 impl Default for ExecutionHistorySettings {
@@ -2358,6 +4192,8 @@ impl Default for ExecutionHistorySettings {
             max_history_per_workflow: 50,
             retention_days: 30,
             max_output_lines: 500,
+            max_log_storage_mb: default_max_log_storage_mb(),
+            max_artifact_storage_mb: default_max_artifact_storage_mb(),
         }
     }
 }
@@ -2421,8 +4257,20 @@ pub async fn save_execution_history(
         .get(EXECUTION_HISTORY_SETTINGS_KEY)?
         .unwrap_or_default();
 
-    // Trim output if needed
+    // Link runs to the workflow revision they ran and, when started with
+    // resume_execution, to the execution they resumed
     let mut trimmed_item = item;
+    if let Some(execution) = execution_repo.get_finished(&trimmed_item.id)? {
+        if trimmed_item.resumed_from_execution_id.is_none() {
+            trimmed_item.resumed_from_execution_id =
+                execution.resumed_from.map(|origin| origin.execution_id);
+        }
+        if trimmed_item.revision.is_none() {
+            trimmed_item.revision = execution.revision;
+        }
+    }
+
+    // Trim output if needed
     if trimmed_item.output.len() > settings.max_output_lines {
         trimmed_item.output = trimmed_item
             .output
@@ -2438,8 +4286,8 @@ pub async fn save_execution_history(
     // Save history item
     execution_repo.save_history(&trimmed_item)?;
 
-    // Prune old history (enforce max limit per workflow)
-    execution_repo.prune_history(settings.max_history_per_workflow)?;
+    // Prune old history and logs (enforce retention settings)
+    execution_repo.prune_history(&settings)?;
 
     Ok(())
 }
@@ -2453,6 +4301,8 @@ pub async fn delete_execution_history(
 ) -> Result<(), String> {
     let repo = ExecutionRepository::new(db.0.as_ref().clone());
     repo.delete_history(&history_id)?;
+    delete_execution_log(db.0.as_ref(), &history_id)?;
+    delete_execution_artifacts(db.0.as_ref(), &execution_artifacts_dir()?, &history_id)?;
     Ok(())
 }
 
@@ -2464,6 +4314,15 @@ pub async fn clear_workflow_execution_history(
 ) -> Result<(), String> {
     let repo = ExecutionRepository::new(db.0.as_ref().clone());
     repo.clear_workflow_history(&workflow_id)?;
+    let logs = ExecutionLogRepository::new(db.0.as_ref().clone()).list(Some(&workflow_id), None)?;
+    for log in logs {
+        delete_execution_log(db.0.as_ref(), &log.execution_id)?;
+    }
+    let artifacts_dir = execution_artifacts_dir()?;
+    let usage = ArtifactRepository::new(db.0.as_ref().clone()).list_usage()?;
+    for execution in usage.iter().filter(|u| u.workflow_id == workflow_id) {
+        delete_execution_artifacts(db.0.as_ref(), &artifacts_dir, &execution.execution_id)?;
+    }
     Ok(())
 }
 
@@ -2477,6 +4336,173 @@ pub async fn update_execution_history_settings(
 
     let settings_repo = SettingsRepository::new(db.0.as_ref().clone());
     settings_repo.set(EXECUTION_HISTORY_SETTINGS_KEY, &settings)?;
+    ExecutionRepository::new(db.0.as_ref().clone()).prune_history(&settings)?;
+    Ok(())
+}
+
+/// Get the global workflow concurrency settings
+#[tauri::command]
+pub async fn get_workflow_concurrency_settings(
+    db: tauri::State<'_, DatabaseState>,
+) -> Result<WorkflowConcurrencySettings, String> {
+    Ok(concurrency_settings(db.0.as_ref()))
+}
+
+/// Update the global workflow concurrency settings
+#[tauri::command]
+pub async fn update_workflow_concurrency_settings(
+    app: AppHandle,
+    db: tauri::State<'_, DatabaseState>,
+    settings: WorkflowConcurrencySettings,
+) -> Result<(), String> {
+    use crate::repositories::SettingsRepository;
+
+    SettingsRepository::new(db.0.as_ref().clone())
+        .set(WORKFLOW_CONCURRENCY_SETTINGS_KEY, &settings)?;
+    // A higher limit may let queued executions start
+    start_queued_executions(&app);
     Ok(())
 }
 
+// ============================================================================
+// Execution Log Commands
+// ============================================================================
+
+/// List persisted execution logs (most recent first), optionally of one workflow
+#[tauri::command]
+pub async fn list_execution_logs(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ExecutionLogInfo>, String> {
+    ExecutionLogRepository::new(db.0.as_ref().clone()).list(workflow_id.as_deref(), limit)
+}
+
+/// Read a window of lines from the persisted log of an execution
+#[tauri::command]
+pub async fn read_execution_log(
+    db: tauri::State<'_, DatabaseState>,
+    execution_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<ExecutionLogPage, String> {
+    execution_log::read_execution_log_page(db.0.as_ref(), &execution_id, offset.unwrap_or(0), limit)
+}
+
+/// Full-text search across persisted execution logs
+#[tauri::command]
+pub async fn search_execution_logs(
+    db: tauri::State<'_, DatabaseState>,
+    query: String,
+    workflow_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ExecutionLogSearchHit>, String> {
+    execution_log::search_execution_logs(
+        db.0.as_ref(),
+        &query,
+        workflow_id.as_deref(),
+        limit.unwrap_or(200),
+    )
+}
+
+// ============================================================================
+// Execution Artifact Commands
+// ============================================================================
+
+/// List the artifacts collected by an execution
+#[tauri::command]
+pub async fn list_execution_artifacts(
+    db: tauri::State<'_, DatabaseState>,
+    execution_id: String,
+) -> Result<Vec<ExecutionArtifact>, String> {
+    ArtifactRepository::new(db.0.as_ref().clone()).list(&execution_id)
+}
+
+/// Copy the artifacts of an execution (optionally of one node) into a directory,
+/// keeping their relative paths. Returns the number of files copied.
+#[tauri::command]
+pub async fn export_execution_artifacts(
+    db: tauri::State<'_, DatabaseState>,
+    execution_id: String,
+    destination: String,
+    node_id: Option<String>,
+) -> Result<usize, String> {
+    let artifacts: Vec<ExecutionArtifact> = ArtifactRepository::new(db.0.as_ref().clone())
+        .list(&execution_id)?
+        .into_iter()
+        .filter(|a| node_id.as_ref().map_or(true, |id| &a.node_id == id))
+        .collect();
+    if artifacts.is_empty() {
+        return Err("No artifacts to export".to_string());
+    }
+    export_artifacts(&artifacts, Path::new(&destination))
+}
+
+/// Open a stored artifact with the default application, or reveal it in the file manager
+#[tauri::command]
+pub async fn open_execution_artifact(
+    db: tauri::State<'_, DatabaseState>,
+    execution_id: String,
+    file_path: String,
+    reveal: Option<bool>,
+) -> Result<(), String> {
+    // Only files of the execution's artifact store can be opened
+    let artifact = ArtifactRepository::new(db.0.as_ref().clone())
+        .list(&execution_id)?
+        .into_iter()
+        .find(|a| a.file_path == file_path)
+        .ok_or_else(|| "Artifact not found".to_string())?;
+
+    if reveal.unwrap_or(false) {
+        tauri_plugin_opener::reveal_item_in_dir(&artifact.file_path)
+            .map_err(|e| format!("Failed to reveal artifact: {}", e))
+    } else {
+        tauri_plugin_opener::open_path(artifact.file_path, None::<&str>)
+            .map_err(|e| format!("Failed to open artifact: {}", e))
+    }
+}
+
+// ============================================================================
+// Workflow Statistics
+// ============================================================================
+
+/// Success rate, per-node timing percentiles, daily timeline and flaky nodes of a workflow,
+/// computed from its most recent executions (default: 200)
+#[tauri::command]
+pub async fn get_workflow_stats(
+    db: tauri::State<'_, DatabaseState>,
+    workflow_id: String,
+    limit: Option<usize>,
+) -> Result<WorkflowStats, String> {
+    let db = db.0.as_ref().clone();
+    let nodes = WorkflowRepository::new(db.clone())
+        .get(&workflow_id)?
+        .map(|workflow| workflow.nodes)
+        .unwrap_or_default();
+
+    let repo = ExecutionRepository::new(db);
+    let history = repo.list_history_by_workflow(&workflow_id, Some(limit.unwrap_or(200)))?;
+    // Node results are kept with the final execution state, not in the history row
+    let mut finished: HashMap<String, Execution> = repo
+        .list_finished_by_workflow(&workflow_id)?
+        .into_iter()
+        .map(|execution| (execution.id.clone(), execution))
+        .collect();
+
+    let samples: Vec<StatsSample> = history
+        .into_iter()
+        .map(|item| {
+            let execution = finished.remove(&item.id);
+            StatsSample {
+                execution_id: item.id,
+                status: item.status,
+                started_at: item.started_at,
+                duration_ms: item.duration_ms,
+                git_commit: execution.as_ref().and_then(|e| e.git_commit.clone()),
+                node_results: execution.map(|e| e.node_results).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(compute_workflow_stats(&workflow_id, &nodes, &samples))
+}
@@ -29,9 +29,13 @@ use commands::{
     ai, ai_assistant, ai_cli, apk, audit, deploy, file_watcher, git, incoming_webhook, ipa, mcp, monorepo, notification, project, script, security,
     settings, shortcuts, snapshot, step_template, toolchain, version, webhook, workflow, worktree,
 };
-use services::{DatabaseWatcher, FileWatcherManager, IncomingWebhookManager, LockfileWatcherManager};
+use services::{
+    DatabaseWatcher, FileWatcherManager, IncomingWebhookManager, LockfileWatcherManager,
+    WorkflowFileTriggerManager,
+};
 use commands::snapshot::LockfileWatcherState;
 use services::ai_assistant::StreamManager;
+use services::git_trigger::GitHookWatcher;
 use tauri::Manager;
 use utils::database::{Database, get_database_path};
 
@@ -84,6 +88,8 @@ pub fn run() {
         .manage(WorkflowExecutionState::default())
         .manage(IncomingWebhookManager::new())
         .manage(FileWatcherManager::new())
+        .manage(WorkflowFileTriggerManager::new())
+        .manage(GitHookWatcher::new())
         .manage(DatabaseWatcher::new())
         .manage(LockfileWatcherState(Arc::new(LockfileWatcherManager::new())))
         .manage(CLIExecutorState::new())
@@ -144,9 +150,23 @@ pub fn run() {
             // Note: load_workflows is provided by settings module
             workflow::save_workflow,
             workflow::delete_workflow,
+            // Workflow revisions
+            workflow::list_workflow_revisions,
+            workflow::diff_workflow_revisions,
+            workflow::rollback_workflow,
+            workflow::list_workflow_secrets,
+            workflow::save_workflow_secret,
+            workflow::delete_workflow_secret,
+            workflow::get_workflow_schedule_states,
+            workflow::preview_workflow_schedule,
+            workflow::get_workflow_files_status,
+            workflow::export_workflow_to_file,
+            workflow::import_workflow_from_file,
+            workflow::sync_workflow_files,
             workflow::execute_workflow,
             workflow::cancel_execution,
             workflow::continue_execution,
+            workflow::resume_execution,
             workflow::get_running_executions,
             workflow::get_workflow_output,
             workflow::restore_running_executions,
@@ -156,6 +176,8 @@ pub fn run() {
             workflow::detect_workflow_cycle,
             // Feature 013: Child execution query
             workflow::get_child_executions,
+            // Dry-run plans
+            workflow::get_workflow_plan,
             // Execution history commands
             workflow::load_execution_history,
             workflow::load_all_execution_history,
@@ -163,6 +185,18 @@ pub fn run() {
             workflow::delete_execution_history,
             workflow::clear_workflow_execution_history,
             workflow::update_execution_history_settings,
+            // Concurrency settings
+            workflow::get_workflow_concurrency_settings,
+            workflow::update_workflow_concurrency_settings,
+            // Execution log commands
+            workflow::list_execution_logs,
+            workflow::read_execution_log,
+            workflow::search_execution_logs,
+            // Execution artifact commands
+            workflow::list_execution_artifacts,
+            workflow::export_execution_artifacts,
+            workflow::open_execution_artifact,
+            workflow::get_workflow_stats,
             // Worktree commands (US5)
             worktree::is_git_repo,
             worktree::list_branches,
@@ -263,6 +297,10 @@ pub fn run() {
             git::test_remote_connection,
             // Git diff viewer (010-git-diff-viewer)
             git::get_file_diff,
+            // Git hooks for workflow git triggers
+            git::install_git_hooks,
+            git::uninstall_git_hooks,
+            git::get_git_hooks_status,
             // Step template commands (011-workflow-step-templates)
             step_template::load_custom_step_templates,
             step_template::save_custom_step_template,
@@ -446,16 +484,20 @@ pub fn run() {
             snapshot::get_latest_snapshot,
             snapshot::delete_snapshot,
             snapshot::prune_snapshots,
+            snapshot::set_snapshot_pinned,
             snapshot::capture_snapshot,
             snapshot::compare_snapshots,
             snapshot::get_diff_ai_prompt,
             snapshot::get_comparison_candidates,
             snapshot::analyze_diff_patterns,
+            snapshot::why_installed,
+            snapshot::get_postinstall_origins,
             snapshot::get_security_insights,
             snapshot::get_insight_summary,
             snapshot::dismiss_insight,
             snapshot::get_snapshot_storage_stats,
             snapshot::cleanup_orphaned_storage,
+            snapshot::apply_snapshot_retention,
             snapshot::request_ai_analysis,
             // Security Guardian - Dependency Integrity (025-ai-workflow-generator US3)
             snapshot::check_dependency_integrity,
@@ -464,6 +506,8 @@ pub fn run() {
             snapshot::prepare_replay,
             snapshot::execute_replay,
             snapshot::restore_lockfile,
+            snapshot::export_snapshot_bundle,
+            snapshot::import_snapshot_bundle,
             // Security Insights Dashboard (025-ai-workflow-generator US5)
             snapshot::get_project_security_overview,
             // Searchable Execution History (025-ai-workflow-generator US6)
@@ -560,6 +604,44 @@ pub fn run() {
             // Cleanup old notifications on startup
             services::notification::cleanup_old_notifications(app.handle());
 
+            // Executions of a previous app session, or of MCP servers that were killed,
+            // no longer hold concurrency slots
+            {
+                let db_state = app.handle().state::<DatabaseState>();
+                let active_repo = repositories::ActiveExecutionRepository::new(db_state.0.as_ref().clone());
+                if let Err(e) = active_repo.clear_source(models::execution::ACTIVE_EXECUTION_SOURCE_APP) {
+                    log::warn!("[setup] Failed to clear the run queue: {}", e);
+                }
+                if let Err(e) = active_repo.remove_dead_owners() {
+                    log::warn!("[setup] Failed to reap stale runs from the run queue: {}", e);
+                }
+            }
+
+            // Logs of executions cut short by the previous session stay readable as interrupted
+            {
+                let db_state = app.handle().state::<DatabaseState>();
+                if let Err(e) = services::execution_log::mark_interrupted_logs(db_state.0.as_ref()) {
+                    log::warn!("[setup] Failed to mark interrupted execution logs: {}", e);
+                }
+            }
+
+            // Start cron scheduler for scheduled workflows
+            services::workflow_scheduler::start_workflow_scheduler(handle.clone());
+
+            // Start file-change triggers for workflows
+            {
+                let db_state = app.handle().state::<DatabaseState>();
+                let trigger_manager = app.handle().state::<WorkflowFileTriggerManager>();
+                if let Err(e) = trigger_manager.sync(&handle, db_state.0.as_ref()) {
+                    log::warn!("[setup] Failed to start workflow file triggers: {}", e);
+                }
+            }
+
+            // Receive git events from installed hooks
+            if let Err(e) = app.handle().state::<GitHookWatcher>().start_watching(&handle) {
+                log::warn!("[setup] Failed to start git hook watcher: {}", e);
+            }
+
             // Sync incoming webhook server
             tauri::async_runtime::spawn(async move {
                 // Small delay to ensure store is ready
@@ -3,8 +3,13 @@
 
 use rusqlite::params;
 
-use crate::commands::workflow::{ExecutionHistoryItem, WorkflowOutputLine};
-use crate::models::Execution;
+use crate::commands::workflow::{
+    ExecutionHistoryItem, ExecutionHistorySettings, WorkflowOutputLine,
+};
+use crate::models::{Execution, LogRetentionPolicy};
+use crate::repositories::ArtifactRepository;
+use crate::services::execution_log::prune_execution_logs;
+use crate::services::workflow::{execution_artifacts_dir, prune_execution_artifacts};
 use crate::utils::database::Database;
 
 /// Repository for execution data access
@@ -123,6 +128,85 @@ impl ExecutionRepository {
         })
     }
 
+    // =========================================================================
+    // Finished Executions (final node results, used to resume past executions)
+    // =========================================================================
+
+    /// Save the final state of a finished execution
+    pub fn save_finished(&self, execution: &Execution) -> Result<(), String> {
+        let execution_data = serde_json::to_string(execution)
+            .map_err(|e| format!("Failed to serialize execution: {}", e))?;
+        let finished_at = execution
+            .finished_at
+            .clone()
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+
+        self.db.with_connection(|conn| {
+            conn.execute(
+                r#"
+                INSERT OR REPLACE INTO finished_executions
+                (id, workflow_id, execution_data, finished_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![
+                    execution.id,
+                    execution.workflow_id,
+                    execution_data,
+                    finished_at
+                ],
+            )
+            .map_err(|e| format!("Failed to save execution result: {}", e))?;
+
+            Ok(())
+        })
+    }
+
+    /// Get the final state of a finished execution
+    pub fn get_finished(&self, id: &str) -> Result<Option<Execution>, String> {
+        self.db.with_connection(|conn| {
+            let result = conn.query_row(
+                "SELECT execution_data FROM finished_executions WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, String>(0),
+            );
+
+            match result {
+                Ok(json) => {
+                    let execution: Execution = serde_json::from_str(&json)
+                        .map_err(|e| format!("Failed to parse execution: {}", e))?;
+                    Ok(Some(execution))
+                }
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(format!("Failed to get execution result: {}", e)),
+            }
+        })
+    }
+
+    /// List the final states of a workflow's finished executions
+    pub fn list_finished_by_workflow(&self, workflow_id: &str) -> Result<Vec<Execution>, String> {
+        self.db.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT execution_data FROM finished_executions WHERE workflow_id = ?1 ORDER BY finished_at DESC",
+                )
+                .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            let rows = stmt
+                .query_map(params![workflow_id], |row| row.get::<_, String>(0))
+                .map_err(|e| format!("Failed to query execution results: {}", e))?;
+
+            let mut executions = Vec::new();
+            for row in rows {
+                let json = row.map_err(|e| format!("Failed to read row: {}", e))?;
+                let execution: Execution = serde_json::from_str(&json)
+                    .map_err(|e| format!("Failed to parse execution: {}", e))?;
+                executions.push(execution);
+            }
+
+            Ok(executions)
+        })
+    }
+
     // =========================================================================
     // Execution History
     // =========================================================================
@@ -131,13 +215,13 @@ impl ExecutionRepository {
     pub fn list_history(&self, limit: Option<usize>) -> Result<Vec<ExecutionHistoryItem>, String> {
         let limit = limit.unwrap_or(100);
 
-        self.db.with_connection(|conn| {
+        let history = self.db.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
                     r#"
                     SELECT id, workflow_id, workflow_name, status, started_at, finished_at,
                            duration_ms, node_count, completed_node_count, error_message,
-                           output, triggered_by
+                           output, triggered_by, resumed_from_execution_id, revision
                     FROM execution_history
                     ORDER BY started_at DESC
                     LIMIT ?1
@@ -160,6 +244,8 @@ impl ExecutionRepository {
                         error_message: row.get(9)?,
                         output: row.get(10)?,
                         triggered_by: row.get(11)?,
+                        resumed_from_execution_id: row.get(12)?,
+                        revision: row.get(13)?,
                     })
                 })
                 .map_err(|e| format!("Failed to query execution history: {}", e))?;
@@ -171,7 +257,8 @@ impl ExecutionRepository {
             }
 
             Ok(history)
-        })
+        })?;
+        self.attach_artifacts(history)
     }
 
     /// List execution history for a specific workflow
@@ -182,13 +269,13 @@ impl ExecutionRepository {
     ) -> Result<Vec<ExecutionHistoryItem>, String> {
         let limit = limit.unwrap_or(50);
 
-        self.db.with_connection(|conn| {
+        let history = self.db.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
                     r#"
                     SELECT id, workflow_id, workflow_name, status, started_at, finished_at,
                            duration_ms, node_count, completed_node_count, error_message,
-                           output, triggered_by
+                           output, triggered_by, resumed_from_execution_id, revision
                     FROM execution_history
                     WHERE workflow_id = ?1
                     ORDER BY started_at DESC
@@ -212,6 +299,8 @@ impl ExecutionRepository {
                         error_message: row.get(9)?,
                         output: row.get(10)?,
                         triggered_by: row.get(11)?,
+                        resumed_from_execution_id: row.get(12)?,
+                        revision: row.get(13)?,
                     })
                 })
                 .map_err(|e| format!("Failed to query execution history: {}", e))?;
@@ -223,7 +312,20 @@ impl ExecutionRepository {
             }
 
             Ok(history)
-        })
+        })?;
+        self.attach_artifacts(history)
+    }
+
+    /// Fill in the artifacts collected by each execution
+    fn attach_artifacts(
+        &self,
+        mut history: Vec<ExecutionHistoryItem>,
+    ) -> Result<Vec<ExecutionHistoryItem>, String> {
+        let artifact_repo = ArtifactRepository::new(self.db.clone());
+        for item in &mut history {
+            item.artifacts = artifact_repo.list(&item.id)?;
+        }
+        Ok(history)
     }
 
     /// Save execution history entry
@@ -237,8 +339,8 @@ impl ExecutionRepository {
                 INSERT OR REPLACE INTO execution_history
                 (id, workflow_id, workflow_name, status, started_at, finished_at,
                  duration_ms, node_count, completed_node_count, error_message,
-                 output, triggered_by)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 output, triggered_by, resumed_from_execution_id, revision)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
                 "#,
                 params![
                     history.id,
@@ -253,6 +355,8 @@ impl ExecutionRepository {
                     history.error_message,
                     output_json,
                     history.triggered_by,
+                    history.resumed_from_execution_id,
+                    history.revision,
                 ],
             )
             .map_err(|e| format!("Failed to save execution history: {}", e))?;
@@ -267,6 +371,8 @@ impl ExecutionRepository {
             let rows_affected = conn
                 .execute("DELETE FROM execution_history WHERE id = ?1", params![id])
                 .map_err(|e| format!("Failed to delete execution history: {}", e))?;
+            conn.execute("DELETE FROM finished_executions WHERE id = ?1", params![id])
+                .map_err(|e| format!("Failed to delete execution result: {}", e))?;
 
             Ok(rows_affected > 0)
         })
@@ -281,6 +387,11 @@ impl ExecutionRepository {
                     params![workflow_id],
                 )
                 .map_err(|e| format!("Failed to clear workflow history: {}", e))?;
+            conn.execute(
+                "DELETE FROM finished_executions WHERE workflow_id = ?1",
+                params![workflow_id],
+            )
+            .map_err(|e| format!("Failed to clear workflow history: {}", e))?;
 
             Ok(rows_affected)
         })
@@ -304,10 +415,14 @@ impl ExecutionRepository {
         Ok(grouped)
     }
 
-    /// Delete old execution history (keep last N entries per workflow)
-    pub fn prune_history(&self, keep_per_workflow: usize) -> Result<usize, String> {
-        self.db.with_connection(|conn| {
-            let rows_affected = conn
+    /// Apply the history retention settings to execution history, persisted logs and
+    /// artifacts: keep the last N entries per workflow, drop entries older than the
+    /// retention period and the oldest logs and artifacts beyond their storage limits.
+    /// Returns the number of history entries, logs and artifact stores deleted.
+    pub fn prune_history(&self, settings: &ExecutionHistorySettings) -> Result<usize, String> {
+        let keep_per_workflow = settings.max_history_per_workflow;
+        let pruned_history = self.db.with_connection(|conn| {
+            let mut rows_affected = conn
                 .execute(
                     r#"
                     DELETE FROM execution_history
@@ -326,8 +441,69 @@ impl ExecutionRepository {
                 )
                 .map_err(|e| format!("Failed to prune execution history: {}", e))?;
 
+            if settings.retention_days > 0 {
+                let cutoff = (chrono::Utc::now()
+                    - chrono::Duration::days(settings.retention_days as i64))
+                .to_rfc3339();
+                rows_affected += conn
+                    .execute(
+                        "DELETE FROM execution_history WHERE started_at < ?1",
+                        params![cutoff],
+                    )
+                    .map_err(|e| format!("Failed to prune execution history: {}", e))?;
+            }
+
+            // Finished execution results follow the same limits
+            conn.execute(
+                r#"
+                DELETE FROM finished_executions
+                WHERE id NOT IN (
+                    SELECT id FROM (
+                        SELECT id, ROW_NUMBER() OVER (
+                            PARTITION BY workflow_id
+                            ORDER BY finished_at DESC
+                        ) as rn
+                        FROM finished_executions
+                    )
+                    WHERE rn <= ?1
+                )
+                "#,
+                params![keep_per_workflow as i64],
+            )
+            .map_err(|e| format!("Failed to prune execution results: {}", e))?;
+            if settings.retention_days > 0 {
+                let cutoff = (chrono::Utc::now()
+                    - chrono::Duration::days(settings.retention_days as i64))
+                .to_rfc3339();
+                conn.execute(
+                    "DELETE FROM finished_executions WHERE finished_at < ?1",
+                    params![cutoff],
+                )
+                .map_err(|e| format!("Failed to prune execution results: {}", e))?;
+            }
+
             Ok(rows_affected)
-        })
+        })?;
+
+        let pruned_logs = prune_execution_logs(
+            &self.db,
+            &LogRetentionPolicy {
+                keep_per_workflow,
+                retention_days: settings.retention_days,
+                max_total_bytes: settings.max_log_storage_mb * 1024 * 1024,
+            },
+        )?;
+        let pruned_artifacts = prune_execution_artifacts(
+            &self.db,
+            &execution_artifacts_dir()?,
+            &LogRetentionPolicy {
+                keep_per_workflow,
+                retention_days: settings.retention_days,
+                max_total_bytes: settings.max_artifact_storage_mb * 1024 * 1024,
+            },
+        )?;
+
+        Ok(pruned_history + pruned_logs + pruned_artifacts)
     }
 }
 
@@ -345,6 +521,8 @@ struct HistoryRow {
     error_message: Option<String>,
     output: Option<String>,
     triggered_by: String,
+    resumed_from_execution_id: Option<String>,
+    revision: Option<u32>,
 }
 
 impl HistoryRow {
@@ -368,6 +546,9 @@ impl HistoryRow {
             error_message: self.error_message,
             output,
             triggered_by: self.triggered_by,
+            resumed_from_execution_id: self.resumed_from_execution_id,
+            revision: self.revision,
+            artifacts: Vec::new(),
         })
     }
 }
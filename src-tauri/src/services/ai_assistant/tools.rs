@@ -377,6 +377,10 @@ impl MCPToolHandler {
                         "workflow_id": {
                             "type": "string",
                             "description": "The workflow ID - use actual ID from list_workflows, not a placeholder"
+                        },
+                        "params": {
+                            "type": "object",
+                            "description": "Values for the workflow's declared parameters (omitted ones use their defaults)"
                         }
                     },
                     "required": ["workflow_id"]
@@ -2741,7 +2745,11 @@ impl MCPToolHandler {
             workflow.description = description.map(|s| s.to_string());
             workflow.project_id = project_id.map(|s| s.to_string());
 
-            match crate::repositories::WorkflowRepository::new(db.clone()).save(&workflow) {
+            match crate::repositories::WorkflowRepository::new(db.clone()).save_with_revision(
+                &workflow,
+                crate::models::RevisionSource::AiAssistant,
+                Some(tool_call.name.as_str()),
+            ) {
                 Ok(_) => {
                     let output = serde_json::json!({
                         "success": true,
@@ -2904,6 +2912,7 @@ impl MCPToolHandler {
                     config,
                     order,
                     position: None,
+                    depends_on: Vec::new(),
                 };
 
                 workflow.nodes.push(node);
@@ -2917,7 +2926,11 @@ impl MCPToolHandler {
             }
 
             // Save workflow with all steps atomically
-            match crate::repositories::WorkflowRepository::new(db.clone()).save(&workflow) {
+            match crate::repositories::WorkflowRepository::new(db.clone()).save_with_revision(
+                &workflow,
+                crate::models::RevisionSource::AiAssistant,
+                Some(tool_call.name.as_str()),
+            ) {
                 Ok(_) => {
                     let output = serde_json::json!({
                         "success": true,
@@ -3022,7 +3035,11 @@ impl MCPToolHandler {
                     workflow.nodes.push(node);
 
                     // Save the updated workflow
-                    match repo.save(&workflow) {
+                    match repo.save_with_revision(
+                        &workflow,
+                        crate::models::RevisionSource::AiAssistant,
+                        Some(tool_call.name.as_str()),
+                    ) {
                         Ok(_) => {
                             let output = serde_json::json!({
                                 "success": true,
@@ -3183,6 +3200,7 @@ impl MCPToolHandler {
                             config,
                             order,
                             position: None,
+                            depends_on: Vec::new(),
                         };
 
                         workflow.nodes.push(node);
@@ -3196,7 +3214,11 @@ impl MCPToolHandler {
                     }
 
                     // Save the updated workflow
-                    match repo.save(&workflow) {
+                    match repo.save_with_revision(
+                        &workflow,
+                        crate::models::RevisionSource::AiAssistant,
+                        Some(tool_call.name.as_str()),
+                    ) {
                         Ok(_) => {
                             let output = serde_json::json!({
                                 "success": true,
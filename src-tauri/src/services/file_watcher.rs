@@ -239,6 +239,7 @@ impl DatabaseWatcher {
             "get_git_diff" => "Retrieved git diff",
             "list_workflows" => "Listed workflows",
             "get_workflow" => "Queried workflow details",
+            "plan_workflow" => "Planned workflow run",
             "list_step_templates" => "Listed step templates",
             _ => "Updated data",
         };
@@ -501,20 +502,7 @@ impl LockfileWatcherManager {
 
     /// Detect which lockfile exists in a project
     fn detect_lockfile(project_path: &Path) -> Option<(LockfileType, PathBuf)> {
-        let lockfile_checks = [
-            (LockfileType::Pnpm, "pnpm-lock.yaml"),
-            (LockfileType::Npm, "package-lock.json"),
-            (LockfileType::Yarn, "yarn.lock"),
-            (LockfileType::Bun, "bun.lockb"),
-        ];
-
-        for (lockfile_type, filename) in lockfile_checks {
-            let path = project_path.join(filename);
-            if path.exists() {
-                return Some((lockfile_type, path));
-            }
-        }
-        None
+        LockfileType::detect(project_path)
     }
 
     /// Compute SHA-256 hash of a file
@@ -638,7 +626,7 @@ impl LockfileWatcherManager {
                                             .path()
                                             .app_data_dir()
                                             .unwrap_or_else(|_| PathBuf::from("."));
-                                        let storage = SnapshotStorage::new(storage_base.join("snapshots"));
+                                        let storage = SnapshotStorage::new(storage_base.join("time-machine"));
                                         let capture_service = SnapshotCaptureService::new(storage, db_clone.clone());
 
                                         match capture_service.capture_lockfile_change_snapshot(&project_path_owned) {
@@ -789,7 +777,7 @@ impl LockfileWatcherManager {
             .path()
             .app_data_dir()
             .unwrap_or_else(|_| PathBuf::from("."));
-        let storage = SnapshotStorage::new(storage_base.join("snapshots"));
+        let storage = SnapshotStorage::new(storage_base.join("time-machine"));
         let capture_service = SnapshotCaptureService::new(storage, db.clone());
 
         let snapshot = capture_service.capture_lockfile_change_snapshot(project_path)?;
@@ -826,3 +814,288 @@ impl LockfileWatcherManager {
         Ok(Some(snapshot.id))
     }
 }
+
+// =============================================================================
+// Workflow File Triggers
+// =============================================================================
+
+use crate::models::WorkflowFileTrigger;
+use crate::repositories::{ProjectRepository, WorkflowRepository};
+use crate::services::workflow::{FileTriggerMatcher, RetriggerGuard, RETRIGGER_COOLDOWN_MS};
+use std::collections::HashSet;
+use std::time::Instant;
+
+/// Event payload sent to frontend when a file change starts a workflow
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowFileTriggeredPayload {
+    pub workflow_id: String,
+    pub execution_id: Option<String>,
+    /// Changed paths relative to the project root
+    pub changed_files: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Active file trigger of one workflow
+struct WorkflowTriggerWatcher {
+    project_path: String,
+    trigger: WorkflowFileTrigger,
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+/// Manages file-change triggers of workflows
+/// Watches the directories named by each workflow's glob patterns and starts the
+/// workflow when matching files change, ignoring changes while it runs
+pub struct WorkflowFileTriggerManager {
+    /// Map of workflow ID -> watcher
+    watchers: Arc<Mutex<HashMap<String, WorkflowTriggerWatcher>>>,
+    /// Self-retrigger protection shared with the watcher callbacks
+    guard: Arc<Mutex<RetriggerGuard>>,
+}
+
+impl Default for WorkflowFileTriggerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkflowFileTriggerManager {
+    pub fn new() -> Self {
+        Self {
+            watchers: Arc::new(Mutex::new(HashMap::new())),
+            guard: Arc::new(Mutex::new(RetriggerGuard::default())),
+        }
+    }
+
+    /// Start, restart or stop trigger watchers to match the saved workflows
+    pub fn sync(&self, app_handle: &AppHandle, db: &Database) -> Result<(), String> {
+        let workflows = WorkflowRepository::new(db.clone()).list()?;
+        let projects = ProjectRepository::new(db.clone()).list()?;
+        let mut watchers = self.watchers.lock().map_err(|e| e.to_string())?;
+        let mut active = HashSet::new();
+
+        for workflow in workflows {
+            let trigger = match workflow.file_trigger {
+                Some(trigger) if trigger.enabled => trigger,
+                _ => continue,
+            };
+            let project_path = match workflow
+                .project_id
+                .as_ref()
+                .and_then(|id| projects.iter().find(|p| &p.id == id))
+            {
+                Some(project) => project.path.clone(),
+                None => {
+                    log::warn!(
+                        "[WorkflowTrigger] Workflow {} has a file trigger but no project",
+                        workflow.id
+                    );
+                    continue;
+                }
+            };
+            active.insert(workflow.id.clone());
+
+            // Unchanged trigger keeps its watcher
+            if let Some(existing) = watchers.get(&workflow.id) {
+                if existing.project_path == project_path && existing.trigger == trigger {
+                    continue;
+                }
+            }
+
+            match self.watch_workflow(app_handle, db, &workflow.id, &project_path, &trigger) {
+                Ok(debouncer) => {
+                    watchers.insert(
+                        workflow.id.clone(),
+                        WorkflowTriggerWatcher {
+                            project_path,
+                            trigger,
+                            _debouncer: debouncer,
+                        },
+                    );
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[WorkflowTrigger] Failed to watch files for workflow {}: {}",
+                        workflow.id,
+                        e
+                    );
+                    watchers.remove(&workflow.id);
+                }
+            }
+        }
+
+        let removed: Vec<String> = watchers
+            .keys()
+            .filter(|id| !active.contains(*id))
+            .cloned()
+            .collect();
+        if let Ok(mut guard) = self.guard.lock() {
+            for workflow_id in &removed {
+                guard.remove(workflow_id);
+            }
+        }
+        for workflow_id in removed {
+            watchers.remove(&workflow_id);
+            log::info!(
+                "[WorkflowTrigger] Stopped watching files for workflow {}",
+                workflow_id
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Record that a workflow run finished; its own trailing writes are ignored
+    /// for a short cooldown
+    pub fn execution_finished(&self, workflow_id: &str) {
+        let debounce_ms = match self.watchers.lock() {
+            Ok(watchers) => match watchers.get(workflow_id) {
+                Some(watcher) => watcher.trigger.debounce_ms,
+                None => return,
+            },
+            Err(_) => return,
+        };
+        if let Ok(mut guard) = self.guard.lock() {
+            guard.finished(
+                workflow_id,
+                Instant::now(),
+                Duration::from_millis(debounce_ms + RETRIGGER_COOLDOWN_MS),
+            );
+        }
+    }
+
+    /// Get IDs of workflows with an active file trigger
+    pub fn get_watched_workflows(&self) -> Result<Vec<String>, String> {
+        let watchers = self.watchers.lock().map_err(|e| e.to_string())?;
+        Ok(watchers.keys().cloned().collect())
+    }
+
+    fn watch_workflow(
+        &self,
+        app_handle: &AppHandle,
+        db: &Database,
+        workflow_id: &str,
+        project_path: &str,
+        trigger: &WorkflowFileTrigger,
+    ) -> Result<Debouncer<RecommendedWatcher>, String> {
+        let matcher = FileTriggerMatcher::new(Path::new(project_path), trigger)?;
+        let roots = matcher.watch_roots();
+
+        let app_handle = app_handle.clone();
+        let db = db.clone();
+        let guard = self.guard.clone();
+        let workflow_id_owned = workflow_id.to_string();
+
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(trigger.debounce_ms),
+            move |res: Result<Vec<DebouncedEvent>, notify::Error>| {
+                let events = match res {
+                    Ok(events) => events,
+                    Err(e) => {
+                        log::error!("[WorkflowTrigger] Watch error: {:?}", e);
+                        return;
+                    }
+                };
+
+                let mut changed_files: Vec<String> = events
+                    .iter()
+                    .filter_map(|event| matcher.matches(&event.path))
+                    .collect();
+                if changed_files.is_empty() {
+                    return;
+                }
+                changed_files.sort();
+                changed_files.dedup();
+
+                // Changes made while the workflow runs (or right after) are its own writes
+                {
+                    let Ok(mut guard) = guard.lock() else { return };
+                    if !guard.can_fire(&workflow_id_owned, Instant::now()) {
+                        log::debug!(
+                            "[WorkflowTrigger] Ignoring changes during run of workflow {}: {:?}",
+                            workflow_id_owned,
+                            changed_files
+                        );
+                        return;
+                    }
+                    guard.started(&workflow_id_owned);
+                }
+
+                log::info!(
+                    "[WorkflowTrigger] Files changed, starting workflow {}: {:?}",
+                    workflow_id_owned,
+                    changed_files
+                );
+
+                let app_handle = app_handle.clone();
+                let db = db.clone();
+                let guard = guard.clone();
+                let workflow_id = workflow_id_owned.clone();
+                tauri::async_runtime::spawn(async move {
+                    let result = crate::commands::workflow::execute_workflow_internal(
+                        app_handle.clone(),
+                        db,
+                        workflow_id.clone(),
+                        None,
+                        None,
+                        None,
+                    )
+                    .await;
+
+                    if let Err(ref e) = result {
+                        log::warn!(
+                            "[WorkflowTrigger] Failed to start workflow {}: {}",
+                            workflow_id,
+                            e
+                        );
+                        if let Ok(mut guard) = guard.lock() {
+                            guard.finished(&workflow_id, Instant::now(), Duration::ZERO);
+                        }
+                    }
+
+                    let payload = WorkflowFileTriggeredPayload {
+                        workflow_id,
+                        execution_id: result.as_ref().ok().cloned(),
+                        changed_files,
+                        error: result.err(),
+                    };
+                    if let Err(e) = app_handle.emit("workflow_file_triggered", payload) {
+                        log::error!("[WorkflowTrigger] Failed to emit event: {}", e);
+                    }
+                });
+            },
+        )
+        .map_err(|e| format!("Failed to create debouncer: {}", e))?;
+
+        let mut watched = 0;
+        for (dir, recursive) in roots {
+            if !dir.is_dir() {
+                log::debug!(
+                    "[WorkflowTrigger] Skipping missing directory: {}",
+                    dir.display()
+                );
+                continue;
+            }
+            let mode = if recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            debouncer
+                .watcher()
+                .watch(&dir, mode)
+                .map_err(|e| format!("Failed to watch path: {}", e))?;
+            watched += 1;
+        }
+        if watched == 0 {
+            return Err("None of the trigger directories exist".to_string());
+        }
+
+        log::info!(
+            "[WorkflowTrigger] Started watching files for workflow {} in {}",
+            workflow_id,
+            project_path
+        );
+        Ok(debouncer)
+    }
+}
@@ -0,0 +1,342 @@
+// Git Trigger Service
+// Starts workflows on git events in registered projects. Events come from the
+// app's own git commands (commit, branch switch, push) and from the thin git
+// hooks installed by `install_git_hooks`, which drop event files into a spool
+// directory watched here.
+
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebouncedEvent, Debouncer};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::git::exec_git;
+use crate::commands::workflow::wait_for_execution;
+use crate::models::{ExecutionStatus, GitTriggerEvent};
+use crate::repositories::{ProjectRepository, WorkflowRepository};
+use crate::services::workflow::git_trigger::HOOK_EVENT_EXTENSION;
+use crate::services::workflow::{git_trigger_matches, GitEvent, GitHookEvent};
+use crate::utils::database::get_database_path;
+use crate::DatabaseState;
+
+/// How long a push waits for its pre-push workflows
+const PRE_PUSH_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Spool directory (next to the database) that installed hooks write events into
+pub fn git_events_dir() -> Result<PathBuf, String> {
+    get_database_path()?
+        .parent()
+        .map(|dir| dir.join("git-events"))
+        .ok_or_else(|| "Could not determine application data directory".to_string())
+}
+
+/// Event payload sent to frontend when a git event starts a workflow
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowGitTriggeredPayload {
+    pub workflow_id: String,
+    pub execution_id: Option<String>,
+    pub project_path: String,
+    /// Hook name of the event (e.g. "post-checkout")
+    pub event: String,
+    pub branch: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Start the workflows whose git trigger matches an event in the repository at `repo_path`.
+/// `checkout` is the previous and new HEAD of a branch switch.
+/// Returns the started execution IDs.
+pub async fn fire_git_event(
+    app: &AppHandle,
+    repo_path: &Path,
+    event: GitTriggerEvent,
+    checkout: Option<(String, String)>,
+) -> Vec<String> {
+    let repo_root = match exec_git(repo_path, &["rev-parse", "--show-toplevel"]) {
+        Ok(root) => canonical(Path::new(&root)),
+        Err(e) => {
+            log::debug!(
+                "[GitTrigger] Not a git repository {}: {}",
+                repo_path.display(),
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    let branch = exec_git(&repo_root, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .ok()
+        .filter(|b| b != "HEAD");
+
+    // Files changed by the event, relative to the repository root
+    let changed_files: Option<Vec<String>> = match (event, &checkout) {
+        (GitTriggerEvent::PostCheckout, Some((previous, head))) => {
+            exec_git(&repo_root, &["diff", "--name-only", previous, head]).ok()
+        }
+        (GitTriggerEvent::PostCommit, _) => exec_git(
+            &repo_root,
+            &[
+                "diff-tree",
+                "--no-commit-id",
+                "--name-only",
+                "-r",
+                "--root",
+                "HEAD",
+            ],
+        )
+        .ok(),
+        _ => None,
+    }
+    .map(|out| out.lines().map(str::to_string).collect());
+
+    let db = app.state::<DatabaseState>().0.as_ref().clone();
+    let projects = match ProjectRepository::new(db.clone()).list() {
+        Ok(projects) => projects,
+        Err(e) => {
+            log::warn!("[GitTrigger] Failed to list projects: {}", e);
+            return Vec::new();
+        }
+    };
+    let workflow_repo = WorkflowRepository::new(db.clone());
+    let mut execution_ids = Vec::new();
+
+    // Every registered project inside the repository sees the event,
+    // with changed files made relative to the project
+    for project in projects {
+        let project_path = canonical(Path::new(&project.path));
+        let Ok(prefix) = project_path.strip_prefix(&repo_root) else {
+            continue;
+        };
+        let prefix = prefix.to_string_lossy().replace('\\', "/");
+        let git_event = GitEvent {
+            event,
+            branch: branch.clone(),
+            changed_files: changed_files.as_ref().map(|files| {
+                files
+                    .iter()
+                    .filter_map(|f| {
+                        if prefix.is_empty() {
+                            Some(f.clone())
+                        } else {
+                            f.strip_prefix(&format!("{}/", prefix)).map(str::to_string)
+                        }
+                    })
+                    .collect()
+            }),
+        };
+
+        let workflows = match workflow_repo.list_by_project(&project.id) {
+            Ok(workflows) => workflows,
+            Err(e) => {
+                log::warn!(
+                    "[GitTrigger] Failed to list workflows for {}: {}",
+                    project.path,
+                    e
+                );
+                continue;
+            }
+        };
+
+        for workflow in workflows {
+            let matches = workflow
+                .git_trigger
+                .as_ref()
+                .is_some_and(|t| git_trigger_matches(t, &git_event));
+            if !matches {
+                continue;
+            }
+
+            log::info!(
+                "[GitTrigger] {} in {}, starting workflow {}",
+                event.hook_name(),
+                project.path,
+                workflow.id
+            );
+            let result = crate::commands::workflow::execute_workflow_internal(
+                app.clone(),
+                db.clone(),
+                workflow.id.clone(),
+                None,
+                None,
+                None,
+            )
+            .await;
+
+            match &result {
+                Ok(execution_id) => execution_ids.push(execution_id.clone()),
+                Err(e) => log::warn!(
+                    "[GitTrigger] Failed to start workflow {}: {}",
+                    workflow.id,
+                    e
+                ),
+            }
+
+            let payload = WorkflowGitTriggeredPayload {
+                workflow_id: workflow.id,
+                execution_id: result.as_ref().ok().cloned(),
+                project_path: project.path.clone(),
+                event: event.hook_name().to_string(),
+                branch: branch.clone(),
+                error: result.err(),
+            };
+            if let Err(e) = app.emit("workflow_git_triggered", payload) {
+                log::error!("[GitTrigger] Failed to emit event: {}", e);
+            }
+        }
+    }
+
+    execution_ids
+}
+
+/// Fire a git event from a git command run by the app, without waiting for the workflows
+pub fn spawn_git_event(
+    app: &AppHandle,
+    repo_path: &Path,
+    event: GitTriggerEvent,
+    checkout: Option<(String, String)>,
+) {
+    let app = app.clone();
+    let repo_path = repo_path.to_path_buf();
+    tauri::async_runtime::spawn(async move {
+        fire_git_event(&app, &repo_path, event, checkout).await;
+    });
+}
+
+/// Run the pre-push workflows of the repository at `repo_path` and wait for them.
+/// Fails when one of them fails, is cancelled or is still running after
+/// `PRE_PUSH_TIMEOUT`, so that the push can be aborted.
+pub async fn run_pre_push_workflows(app: &AppHandle, repo_path: &Path) -> Result<(), String> {
+    let execution_ids = fire_git_event(app, repo_path, GitTriggerEvent::PrePush, None).await;
+    let deadline = Instant::now() + PRE_PUSH_TIMEOUT;
+
+    for execution_id in execution_ids {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match wait_for_execution(app, &execution_id, remaining).await {
+            Some(ExecutionStatus::Completed) => {}
+            Some(ExecutionStatus::Failed) => {
+                return Err(format!("Pre-push workflow run {} failed", execution_id));
+            }
+            Some(_) => {
+                return Err(format!(
+                    "Pre-push workflow run {} was cancelled",
+                    execution_id
+                ));
+            }
+            None => {
+                return Err(format!(
+                    "Pre-push workflow run {} did not finish within {} minutes",
+                    execution_id,
+                    PRE_PUSH_TIMEOUT.as_secs() / 60
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Watches the spool directory that installed git hooks write events into
+pub struct GitHookWatcher {
+    watcher: Arc<Mutex<Option<Debouncer<RecommendedWatcher>>>>,
+}
+
+impl Default for GitHookWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHookWatcher {
+    pub fn new() -> Self {
+        Self {
+            watcher: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Start watching the spool directory. Events written while the app was
+    /// closed are dropped rather than replayed.
+    pub fn start_watching(&self, app_handle: &AppHandle) -> Result<(), String> {
+        let mut watcher_guard = self.watcher.lock().map_err(|e| e.to_string())?;
+        if watcher_guard.is_some() {
+            return Ok(());
+        }
+
+        let spool_dir = git_events_dir()?;
+        std::fs::create_dir_all(&spool_dir)
+            .map_err(|e| format!("Failed to create git events directory: {}", e))?;
+        if let Ok(entries) = std::fs::read_dir(&spool_dir) {
+            for entry in entries.flatten() {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+
+        let app_handle = app_handle.clone();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(200),
+            move |res: Result<Vec<DebouncedEvent>, notify::Error>| {
+                let events = match res {
+                    Ok(events) => events,
+                    Err(e) => {
+                        log::error!("[GitTrigger] Watch error: {:?}", e);
+                        return;
+                    }
+                };
+
+                for event in events {
+                    let path = event.path;
+                    if path.extension().and_then(|e| e.to_str()) != Some(HOOK_EVENT_EXTENSION) {
+                        continue;
+                    }
+                    // Claim the file; a missing file was already handled
+                    let Ok(content) = std::fs::read_to_string(&path) else {
+                        continue;
+                    };
+                    let _ = std::fs::remove_file(&path);
+
+                    let Some(hook_event) = GitHookEvent::parse(&content) else {
+                        log::warn!(
+                            "[GitTrigger] Ignoring malformed hook event: {}",
+                            path.display()
+                        );
+                        continue;
+                    };
+                    let checkout = hook_event
+                        .checkout_heads()
+                        .map(|(previous, head)| (previous.to_string(), head.to_string()));
+                    // File checkouts (`git checkout -- file`) are not branch switches
+                    if hook_event.event == GitTriggerEvent::PostCheckout && checkout.is_none() {
+                        continue;
+                    }
+
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        fire_git_event(
+                            &app_handle,
+                            Path::new(&hook_event.project_path),
+                            hook_event.event,
+                            checkout,
+                        )
+                        .await;
+                    });
+                }
+            },
+        )
+        .map_err(|e| format!("Failed to create git hook watcher: {}", e))?;
+
+        debouncer
+            .watcher()
+            .watch(&spool_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch git events directory: {}", e))?;
+
+        log::info!(
+            "[GitTrigger] Watching git hook events in {}",
+            spool_dir.display()
+        );
+        *watcher_guard = Some(debouncer);
+        Ok(())
+    }
+}
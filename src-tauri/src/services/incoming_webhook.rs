@@ -433,6 +433,7 @@ async fn handle_webhook_trigger(
         workflow_id.clone(),
         None,
         None,
+        trigger_params_from_body(&body),
     )
     .await
     {
@@ -499,6 +500,13 @@ async fn handle_webhook_trigger(
     }
 }
 
+/// Workflow parameters from a JSON request body: `{"params": {"env": "staging"}}`
+/// Bodies without a `params` object trigger the workflow with its defaults
+fn trigger_params_from_body(body: &Bytes) -> Option<HashMap<String, serde_json::Value>> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    serde_json::from_value(value.get("params")?.clone()).ok()
+}
+
 /// Authenticate webhook request using signature or token
 fn authenticate_request(
     state: &WorkflowWebhookServerState,
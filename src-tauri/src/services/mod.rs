@@ -4,9 +4,11 @@
 // Re-export shared services from packageflow-lib
 pub use packageflow_lib::services::crypto;
 pub use packageflow_lib::services::crypto::*;
+pub use packageflow_lib::services::execution_log;
 pub use packageflow_lib::services::mcp_action;
 pub use packageflow_lib::services::security_guardian;
 pub use packageflow_lib::services::snapshot;
+pub use packageflow_lib::services::workflow;
 
 // Tauri-dependent services (local)
 pub mod ai;
@@ -15,9 +17,12 @@ pub mod ai_cli;
 pub mod audit;
 pub mod deploy;
 pub mod file_watcher;
+pub mod git_trigger;
 pub mod incoming_webhook;
 pub mod notification;
+pub mod outgoing_http;
 pub mod webhook_security;
+pub mod workflow_scheduler;
 
 pub use file_watcher::*;
 pub use incoming_webhook::*;
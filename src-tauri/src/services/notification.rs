@@ -46,6 +46,12 @@ pub enum NotificationType {
         workflow_name: String,
         error: String,
     },
+    /// Workflow is waiting at an approval node
+    WorkflowApprovalRequested {
+        workflow_name: String,
+        node_name: String,
+        message: Option<String>,
+    },
 
     // Git operation notifications
     /// Git push succeeded
@@ -83,7 +89,8 @@ impl NotificationType {
             | NotificationType::WebhookOutgoingFailure { .. } => "webhooks",
 
             NotificationType::WorkflowCompleted { .. }
-            | NotificationType::WorkflowFailed { .. } => "workflow_execution",
+            | NotificationType::WorkflowFailed { .. }
+            | NotificationType::WorkflowApprovalRequested { .. } => "workflow_execution",
 
             NotificationType::GitPushSuccess { .. } | NotificationType::GitPushFailed { .. } => {
                 "git_operations"
@@ -104,6 +111,7 @@ impl NotificationType {
             NotificationType::WebhookOutgoingFailure { .. } => "webhook_outgoing_failure",
             NotificationType::WorkflowCompleted { .. } => "workflow_completed",
             NotificationType::WorkflowFailed { .. } => "workflow_failed",
+            NotificationType::WorkflowApprovalRequested { .. } => "workflow_approval_requested",
             NotificationType::GitPushSuccess { .. } => "git_push_success",
             NotificationType::GitPushFailed { .. } => "git_push_failed",
             NotificationType::SecurityScanCompleted { .. } => "security_scan_completed",
@@ -133,6 +141,11 @@ impl NotificationType {
                 workflow_name,
                 error,
             } => Some(serde_json::json!({ "workflowName": workflow_name, "error": error })),
+            NotificationType::WorkflowApprovalRequested {
+                workflow_name,
+                node_name,
+                ..
+            } => Some(serde_json::json!({ "workflowName": workflow_name, "nodeName": node_name })),
             NotificationType::GitPushSuccess {
                 project_name,
                 branch,
@@ -199,6 +212,17 @@ impl NotificationType {
                 "Workflow Failed".to_string(),
                 format!("\"{}\" failed: {}", workflow_name, error),
             ),
+            NotificationType::WorkflowApprovalRequested {
+                workflow_name,
+                node_name,
+                message,
+            } => (
+                "Approval Required".to_string(),
+                match message {
+                    Some(message) => format!("\"{}\" › {}: {}", workflow_name, node_name, message),
+                    None => format!("\"{}\" is waiting for approval at {}", workflow_name, node_name),
+                },
+            ),
 
             // Git notifications
             NotificationType::GitPushSuccess {
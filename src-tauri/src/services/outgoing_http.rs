@@ -0,0 +1,73 @@
+// Outgoing HTTP
+// Shared client, header handling and HMAC signing for outgoing webhooks and
+// workflow HTTP nodes
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::webhook_security::SignatureVerifier;
+
+/// Header carrying the HMAC-SHA256 signature of a signed request body.
+/// Same header incoming webhooks verify, so PackageFlow instances can call each other.
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Client shared by all outgoing requests (timeouts are set per request)
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .user_agent(concat!("PackageFlow/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// Build a request on the shared client.
+/// Bodies are sent as JSON unless `headers` sets a Content-Type; with a signing secret
+/// the body is signed into the `X-Webhook-Signature` header.
+pub fn build_request(
+    method: reqwest::Method,
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    body: Option<String>,
+    signing_secret: Option<&str>,
+    timeout: Duration,
+) -> reqwest::RequestBuilder {
+    let mut request = CLIENT.request(method, url).timeout(timeout);
+
+    let has_content_type = headers
+        .map(|h| h.keys().any(|k| k.eq_ignore_ascii_case("content-type")))
+        .unwrap_or(false);
+    if body.is_some() && !has_content_type {
+        request = request.header("Content-Type", "application/json");
+    }
+
+    // Add custom headers
+    if let Some(headers) = headers {
+        for (key, value) in headers {
+            request = request.header(key, value);
+        }
+    }
+
+    if let Some(secret) = signing_secret {
+        let payload = body.as_deref().unwrap_or_default();
+        request = request.header(
+            SIGNATURE_HEADER,
+            SignatureVerifier::new(secret).sign(payload.as_bytes()),
+        );
+    }
+
+    match body {
+        Some(body) => request.body(body),
+        None => request,
+    }
+}
+
+/// User-facing message for a request that got no response
+pub fn describe_send_error(error: &reqwest::Error, timeout: Duration) -> String {
+    if error.is_timeout() {
+        format!("Request timed out ({}s)", timeout.as_secs_f64())
+    } else if error.is_connect() {
+        "Connection failed".to_string()
+    } else {
+        error.to_string()
+    }
+}
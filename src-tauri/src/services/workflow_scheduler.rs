@@ -0,0 +1,197 @@
+// Workflow Scheduler Service
+// Background loop that starts workflows on their cron schedule
+// Next-run / last-run state is persisted in workflow_schedule_state so missed
+// runs (app closed, machine asleep) can be detected after a restart
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::models::{Workflow, WorkflowSchedule, WorkflowScheduleState};
+use crate::repositories::{ScheduleRepository, WorkflowRepository};
+use crate::services::workflow::{evaluate_due_run, CronSchedule};
+use crate::utils::database::Database;
+use crate::DatabaseState;
+
+/// How often schedules are checked
+const SCHEDULER_TICK_SECS: u64 = 30;
+
+/// Event payload sent to frontend when a schedule starts a workflow
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledRunPayload {
+    pub workflow_id: String,
+    pub execution_id: Option<String>,
+    /// Time the run was due
+    pub scheduled_at: String,
+    /// The due time passed while the app was closed or asleep
+    pub missed: bool,
+    pub error: Option<String>,
+}
+
+/// Start the background scheduler loop
+pub fn start_workflow_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        log::info!("[scheduler] Workflow scheduler started");
+        loop {
+            let db = app.state::<DatabaseState>().0.as_ref().clone();
+            if let Err(e) = run_due_schedules(&app, &db).await {
+                log::warn!("[scheduler] Failed to check workflow schedules: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+        }
+    });
+}
+
+/// Check every scheduled workflow once and start the ones that are due
+pub async fn run_due_schedules(app: &AppHandle, db: &Database) -> Result<(), String> {
+    let workflow_repo = WorkflowRepository::new(db.clone());
+    let schedule_repo = ScheduleRepository::new(db.clone());
+
+    let mut states: HashMap<String, WorkflowScheduleState> = schedule_repo
+        .list_states()?
+        .into_iter()
+        .map(|s| (s.workflow_id.clone(), s))
+        .collect();
+
+    for workflow in workflow_repo.list()? {
+        let state = states.remove(&workflow.id);
+        match &workflow.schedule {
+            Some(schedule) if schedule.enabled => {
+                if let Err(e) = run_schedule(app, db, &workflow, schedule, state).await {
+                    log::warn!(
+                        "[scheduler] Failed to update schedule for workflow {}: {}",
+                        workflow.id,
+                        e
+                    );
+                }
+            }
+            _ => {
+                if state.is_some() {
+                    schedule_repo.delete_state(&workflow.id)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_schedule(
+    app: &AppHandle,
+    db: &Database,
+    workflow: &Workflow,
+    schedule: &WorkflowSchedule,
+    state: Option<WorkflowScheduleState>,
+) -> Result<(), String> {
+    let schedule_repo = ScheduleRepository::new(db.clone());
+    let now = Local::now();
+
+    let mut state = state.unwrap_or_else(|| WorkflowScheduleState {
+        workflow_id: workflow.id.clone(),
+        cron: schedule.cron.clone(),
+        next_run_at: None,
+        last_run_at: None,
+        last_execution_id: None,
+        last_error: None,
+        updated_at: Utc::now().to_rfc3339(),
+    });
+
+    let cron = match CronSchedule::parse(&schedule.cron) {
+        Ok(cron) => cron,
+        Err(e) => {
+            if state.last_error.as_deref() != Some(e.as_str()) {
+                state.cron = schedule.cron.clone();
+                state.next_run_at = None;
+                state.last_error = Some(e);
+                state.updated_at = Utc::now().to_rfc3339();
+                schedule_repo.save_state(&state)?;
+            }
+            return Ok(());
+        }
+    };
+
+    let next_run_at = state
+        .next_run_at
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Local));
+
+    // New or edited schedule: only compute the first run, never run immediately
+    let next_run_at = match next_run_at {
+        Some(next) if state.cron == schedule.cron => next,
+        _ => {
+            state.cron = schedule.cron.clone();
+            state.next_run_at = cron.next_after(&now).map(|t| t.to_rfc3339());
+            state.last_error = None;
+            state.updated_at = Utc::now().to_rfc3339();
+            return schedule_repo.save_state(&state);
+        }
+    };
+
+    let due = evaluate_due_run(&cron, schedule.missed_run_policy, &next_run_at, &now);
+    if !due.run && !due.missed {
+        return Ok(());
+    }
+
+    state.next_run_at = due.next_run_at.map(|t| t.to_rfc3339());
+    state.updated_at = Utc::now().to_rfc3339();
+
+    if !due.run {
+        log::info!(
+            "[scheduler] Skipping missed run of workflow {} (was due {})",
+            workflow.id,
+            next_run_at.to_rfc3339()
+        );
+        return schedule_repo.save_state(&state);
+    }
+
+    log::info!(
+        "[scheduler] Starting workflow {} (due {}, missed: {})",
+        workflow.id,
+        next_run_at.to_rfc3339(),
+        due.missed
+    );
+
+    let result = crate::commands::workflow::execute_workflow_internal(
+        app.clone(),
+        db.clone(),
+        workflow.id.clone(),
+        None,
+        None,
+        None,
+    )
+    .await;
+
+    state.last_run_at = Some(now.to_rfc3339());
+    match &result {
+        Ok(execution_id) => {
+            state.last_execution_id = Some(execution_id.clone());
+            state.last_error = None;
+        }
+        Err(e) => {
+            log::warn!(
+                "[scheduler] Failed to start workflow {}: {}",
+                workflow.id,
+                e
+            );
+            state.last_error = Some(e.clone());
+        }
+    }
+    schedule_repo.save_state(&state)?;
+
+    let _ = app.emit(
+        "workflow_schedule_triggered",
+        ScheduledRunPayload {
+            workflow_id: workflow.id.clone(),
+            execution_id: result.as_ref().ok().cloned(),
+            scheduled_at: next_run_at.to_rfc3339(),
+            missed: due.missed,
+            error: result.err(),
+        },
+    );
+
+    Ok(())
+}
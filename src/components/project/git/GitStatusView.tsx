@@ -89,6 +89,9 @@ export function GitStatusView({
     if (trimmed === 'REMOTE_REQUIRED') {
       return 'Remote is required for this operation.';
     }
+    if (trimmed.startsWith('PRE_PUSH_FAILED: ')) {
+      return `Push blocked: ${trimmed.slice('PRE_PUSH_FAILED: '.length)}.`;
+    }
 
     // Fallback: parse raw git error strings
     const withoutPrefix = trimmed.startsWith('GIT_ERROR: ')
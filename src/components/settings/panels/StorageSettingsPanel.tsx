@@ -34,7 +34,32 @@ import { Skeleton } from '../../ui/Skeleton';
 import { cn } from '../../../lib/utils';
 import { useSettings } from '../../../contexts/SettingsContext';
 import type { StorePathInfo } from '../../../types/tauri';
-import type { SnapshotStorageStats, TimeMachineSettings } from '../../../types/snapshot';
+import type {
+  SnapshotRetentionPolicy,
+  SnapshotStorageStats,
+  TimeMachineSettings,
+} from '../../../types/snapshot';
+
+const DEFAULT_RETENTION: SnapshotRetentionPolicy = {
+  enabled: false,
+  keepRecent: 10,
+  keepDaily: 7,
+  keepWeekly: 4,
+  keepMonthly: 6,
+};
+
+const RETENTION_FIELDS: { key: 'keepRecent' | 'keepDaily' | 'keepWeekly' | 'keepMonthly'; label: string }[] = [
+  { key: 'keepRecent', label: 'Recent' },
+  { key: 'keepDaily', label: 'Daily' },
+  { key: 'keepWeekly', label: 'Weekly' },
+  { key: 'keepMonthly', label: 'Monthly' },
+];
+
+function formatBytes(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
 
 export const StorageSettingsPanel: React.FC = () => {
   const { formatPath } = useSettings();
@@ -45,10 +70,13 @@ export const StorageSettingsPanel: React.FC = () => {
   // Time Machine storage state
   const [snapshotStats, setSnapshotStats] = useState<SnapshotStorageStats | null>(null);
   const [isLoadingStats, setIsLoadingStats] = useState(true);
-  const [keepPerProject, setKeepPerProject] = useState(10);
   const [isPruning, setIsPruning] = useState(false);
   const [isCleaningUp, setIsCleaningUp] = useState(false);
-  const [pruneResult, setPruneResult] = useState<{ count: number; type: string } | null>(null);
+  const [pruneResult, setPruneResult] = useState<{
+    count: number;
+    type: string;
+    reclaimedBytes?: number;
+  } | null>(null);
 
   // Time Machine auto-watch settings
   const [timeMachineSettings, setTimeMachineSettings] = useState<TimeMachineSettings | null>(null);
@@ -156,21 +184,46 @@ export const StorageSettingsPanel: React.FC = () => {
     }
   }, [timeMachineSettings]);
 
-  // Handle prune snapshots
+  const retention = timeMachineSettings?.retention ?? DEFAULT_RETENTION;
+
+  // Handle retention policy changes
+  const handleUpdateRetention = useCallback(async (changes: Partial<SnapshotRetentionPolicy>) => {
+    if (!timeMachineSettings) return;
+    try {
+      setIsSavingTMSettings(true);
+      const newSettings: TimeMachineSettings = {
+        ...timeMachineSettings,
+        retention: { ...(timeMachineSettings.retention ?? DEFAULT_RETENTION), ...changes },
+        updatedAt: new Date().toISOString(),
+      };
+      await snapshotAPI.updateTimeMachineSettings(newSettings);
+      setTimeMachineSettings(newSettings);
+    } catch (error) {
+      console.error('Failed to update retention policy:', error);
+    } finally {
+      setIsSavingTMSettings(false);
+    }
+  }, [timeMachineSettings]);
+
+  // Handle apply retention now
   const handlePruneSnapshots = useCallback(async () => {
     try {
       setIsPruning(true);
       setPruneResult(null);
-      const deleted = await snapshotAPI.pruneSnapshots(keepPerProject);
-      setPruneResult({ count: deleted, type: 'prune' });
+      const result = await snapshotAPI.applySnapshotRetention();
+      setPruneResult({
+        count: result.deletedSnapshotIds.length,
+        type: 'prune',
+        reclaimedBytes: result.reclaimedBytes,
+      });
       // Reload stats after pruning
       await loadSnapshotStats();
     } catch (error) {
-      console.error('Failed to prune snapshots:', error);
+      console.error('Failed to apply snapshot retention:', error);
     } finally {
       setIsPruning(false);
     }
-  }, [keepPerProject, loadSnapshotStats]);
+  }, [loadSnapshotStats]);
 
   // Handle cleanup orphaned storage
   const handleCleanupOrphaned = useCallback(async () => {
@@ -468,21 +521,54 @@ export const StorageSettingsPanel: React.FC = () => {
                   <div>
                     <h4 className="text-sm font-medium text-foreground">Snapshot Retention</h4>
                     <p className="text-xs text-muted-foreground mt-0.5">
-                      Keep the most recent snapshots per project
+                      Keep recent snapshots plus daily, weekly and monthly ones per project.
+                      Pinned and deployed snapshots are always kept.
                     </p>
                   </div>
-                  <div className="flex items-center gap-2">
-                    <span className="text-xs text-muted-foreground">Keep</span>
+                  <Button
+                    variant={retention.enabled ? 'default' : 'outline'}
+                    size="sm"
+                    onClick={() => handleUpdateRetention({ enabled: !retention.enabled })}
+                    disabled={!timeMachineSettings || isSavingTMSettings}
+                    title="Apply the policy after every capture"
+                  >
+                    {retention.enabled ? 'Auto' : 'Manual'}
+                  </Button>
+                </div>
+                <div className="grid grid-cols-5 gap-2 mb-3">
+                  {RETENTION_FIELDS.map(({ key, label }) => (
+                    <label key={key} className="flex flex-col gap-1">
+                      <span className="text-xs text-muted-foreground">{label}</span>
+                      <input
+                        type="number"
+                        min="0"
+                        max="1000"
+                        value={retention[key]}
+                        onChange={(e) => {
+                          const changes: Partial<SnapshotRetentionPolicy> = {};
+                          changes[key] = Math.max(0, parseInt(e.target.value) || 0);
+                          handleUpdateRetention(changes);
+                        }}
+                        disabled={!timeMachineSettings}
+                        className="w-full px-2 py-1 text-sm text-center rounded border border-border bg-background text-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+                      />
+                    </label>
+                  ))}
+                  <label className="flex flex-col gap-1">
+                    <span className="text-xs text-muted-foreground">Quota (MB)</span>
                     <input
                       type="number"
                       min="1"
-                      max="100"
-                      value={keepPerProject}
-                      onChange={(e) => setKeepPerProject(Math.max(1, parseInt(e.target.value) || 1))}
-                      className="w-16 px-2 py-1 text-sm text-center rounded border border-border bg-background text-foreground focus:outline-none focus:ring-2 focus:ring-ring"
+                      placeholder="None"
+                      value={retention.maxProjectStorageMb ?? ''}
+                      onChange={(e) => {
+                        const value = parseInt(e.target.value);
+                        handleUpdateRetention({ maxProjectStorageMb: value > 0 ? value : undefined });
+                      }}
+                      disabled={!timeMachineSettings}
+                      className="w-full px-2 py-1 text-sm text-center rounded border border-border bg-background text-foreground focus:outline-none focus:ring-2 focus:ring-ring"
                     />
-                    <span className="text-xs text-muted-foreground">per project</span>
-                  </div>
+                  </label>
                 </div>
                 <Button
                   variant="outline"
@@ -498,7 +584,7 @@ export const StorageSettingsPanel: React.FC = () => {
                   ) : (
                     <>
                       <Trash2 className="w-4 h-4 mr-2" />
-                      Prune Old Snapshots
+                      Apply Retention Now
                     </>
                   )}
                 </Button>
@@ -544,7 +630,14 @@ export const StorageSettingsPanel: React.FC = () => {
                 >
                   {pruneResult.type === 'prune' ? (
                     pruneResult.count > 0 ? (
-                      <>Deleted {pruneResult.count} old snapshots</>
+                      <>
+                        Deleted {pruneResult.count} old snapshots
+                        {pruneResult.reclaimedBytes
+                          ? `, reclaimed ${formatBytes(pruneResult.reclaimedBytes)}`
+                          : ''}
+                      </>
+                    ) : pruneResult.reclaimedBytes ? (
+                      <>Reclaimed {formatBytes(pruneResult.reclaimedBytes)} from duplicate lockfiles</>
                     ) : (
                       <>No snapshots to prune</>
                     )
@@ -0,0 +1,207 @@
+// Snapshot Bundle Dialog
+// Export a snapshot as a signed bundle, or import a teammate's bundle for replay
+
+import { useEffect, useState } from 'react';
+import { Share2, Download, XCircle, AlertTriangle, CheckCircle, Loader2, KeyRound } from 'lucide-react';
+import { save, open } from '@tauri-apps/plugin-dialog';
+import { snapshotAPI } from '../../lib/tauri-api';
+import type { SnapshotBundleExport, SnapshotBundleImport } from '../../types/snapshot';
+
+const BUNDLE_EXTENSION = 'pfsnap';
+
+interface Props {
+  mode: 'export' | 'import';
+  isOpen: boolean;
+  onClose: () => void;
+  /** Snapshot to export (export mode) */
+  snapshotId?: string;
+  /** Project the bundle is imported into (import mode) */
+  projectPath?: string;
+  /** Called with the imported snapshot so the caller can refresh or open replay */
+  onImported?: (result: SnapshotBundleImport) => void;
+}
+
+export function SnapshotBundleDialog({
+  mode,
+  isOpen,
+  onClose,
+  snapshotId,
+  projectPath,
+  onImported,
+}: Props) {
+  const [secret, setSecret] = useState('');
+  const [isWorking, setIsWorking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [exported, setExported] = useState<SnapshotBundleExport | null>(null);
+  const [imported, setImported] = useState<SnapshotBundleImport | null>(null);
+
+  useEffect(() => {
+    if (isOpen) {
+      setError(null);
+      setExported(null);
+      setImported(null);
+    }
+  }, [isOpen, mode]);
+
+  const handleExport = async () => {
+    if (!snapshotId) return;
+    const outputPath = await save({
+      title: 'Export Snapshot Bundle',
+      defaultPath: `snapshot-${snapshotId.slice(0, 8)}.${BUNDLE_EXTENSION}`,
+      filters: [{ name: 'Snapshot Bundle', extensions: [BUNDLE_EXTENSION] }],
+    });
+    if (!outputPath) return;
+
+    setIsWorking(true);
+    setError(null);
+    try {
+      setExported(await snapshotAPI.exportSnapshotBundle(snapshotId, outputPath, secret));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsWorking(false);
+    }
+  };
+
+  const handleImport = async () => {
+    if (!projectPath) return;
+    const bundlePath = await open({
+      title: 'Import Snapshot Bundle',
+      multiple: false,
+      directory: false,
+      filters: [{ name: 'Snapshot Bundle', extensions: [BUNDLE_EXTENSION] }],
+    });
+    if (!bundlePath || typeof bundlePath !== 'string') return;
+
+    setIsWorking(true);
+    setError(null);
+    try {
+      const result = await snapshotAPI.importSnapshotBundle(bundlePath, projectPath, secret);
+      setImported(result);
+      onImported?.(result);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsWorking(false);
+    }
+  };
+
+  if (!isOpen) return null;
+
+  const isExport = mode === 'export';
+  const isDone = isExport ? exported !== null : imported !== null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/60 backdrop-blur-sm">
+      <div className="w-full max-w-lg rounded-xl border border-zinc-700 bg-zinc-900 shadow-2xl">
+        {/* Header */}
+        <div className="flex items-center justify-between border-b border-zinc-700 px-6 py-4">
+          <div className="flex items-center gap-3">
+            {isExport ? (
+              <Share2 className="h-5 w-5 text-cyan-400" />
+            ) : (
+              <Download className="h-5 w-5 text-cyan-400" />
+            )}
+            <h2 className="text-lg font-semibold text-zinc-100">
+              {isExport ? 'Share Snapshot Bundle' : 'Import Snapshot Bundle'}
+            </h2>
+          </div>
+          <button onClick={onClose} className="text-zinc-400 hover:text-zinc-200 transition-colors">
+            <XCircle className="h-5 w-5" />
+          </button>
+        </div>
+
+        {/* Content */}
+        <div className="px-6 py-4 space-y-4">
+          <p className="text-sm text-zinc-400">
+            {isExport
+              ? 'Bundles the lockfile, package.json, dependency tree, postinstall scripts and security insights into one signed file.'
+              : 'Imports a teammate\'s bundle as a pinned snapshot of this project, ready for replay or lockfile restore.'}
+          </p>
+
+          {!isDone && (
+            <label className="block space-y-1.5">
+              <span className="flex items-center gap-2 text-sm font-medium text-zinc-300">
+                <KeyRound className="h-4 w-4 text-zinc-400" />
+                Team signing secret
+              </span>
+              <input
+                type="password"
+                value={secret}
+                onChange={(e) => setSecret(e.target.value)}
+                placeholder="Shared with everyone who exchanges bundles"
+                className="w-full rounded-lg border border-zinc-700 bg-zinc-800 px-3 py-2 text-sm text-zinc-100 placeholder:text-zinc-500 focus:border-cyan-500 focus:outline-none"
+              />
+            </label>
+          )}
+
+          {error && (
+            <div className="rounded-lg border border-red-500/30 bg-red-500/10 p-4">
+              <div className="flex items-center gap-3">
+                <AlertTriangle className="h-5 w-5 text-red-400" />
+                <div>
+                  <p className="font-medium text-red-300">
+                    {isExport ? 'Export Failed' : 'Import Failed'}
+                  </p>
+                  <p className="text-sm text-zinc-400 mt-1">{error}</p>
+                </div>
+              </div>
+            </div>
+          )}
+
+          {exported && (
+            <div className="rounded-lg border border-green-500/30 bg-green-500/10 p-4">
+              <div className="flex items-center gap-3">
+                <CheckCircle className="h-5 w-5 text-green-400" />
+                <div className="min-w-0">
+                  <p className="font-medium text-green-300">Bundle Exported</p>
+                  <p className="text-sm text-zinc-400 mt-1 truncate" title={exported.path}>
+                    {exported.path} ({(exported.sizeBytes / 1024).toFixed(1)} KB)
+                  </p>
+                </div>
+              </div>
+            </div>
+          )}
+
+          {imported && (
+            <div className="rounded-lg border border-green-500/30 bg-green-500/10 p-4">
+              <div className="flex items-center gap-3">
+                <CheckCircle className="h-5 w-5 text-green-400" />
+                <div className="min-w-0">
+                  <p className="font-medium text-green-300">Bundle Imported</p>
+                  <p className="text-sm text-zinc-400 mt-1">
+                    {imported.dependencyCount} dependencies, {imported.insightCount} insights from{' '}
+                    <span className="font-mono" title={imported.originalProjectPath}>
+                      {imported.originalSnapshotId.slice(0, 8)}
+                    </span>
+                    . Open it in the timeline to replay.
+                  </p>
+                </div>
+              </div>
+            </div>
+          )}
+        </div>
+
+        {/* Footer */}
+        <div className="flex justify-end gap-3 border-t border-zinc-700 px-6 py-4">
+          <button
+            onClick={onClose}
+            className="px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
+          >
+            {isDone ? 'Close' : 'Cancel'}
+          </button>
+          {!isDone && (
+            <button
+              onClick={isExport ? handleExport : handleImport}
+              disabled={isWorking || !secret}
+              className="flex items-center gap-2 px-4 py-2 text-sm bg-cyan-600 hover:bg-cyan-500 text-white rounded-lg font-medium transition-colors disabled:opacity-50"
+            >
+              {isWorking && <Loader2 className="h-4 w-4 animate-spin" />}
+              {isExport ? 'Export Bundle...' : 'Choose Bundle...'}
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
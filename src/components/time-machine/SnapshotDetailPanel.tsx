@@ -17,9 +17,11 @@ import {
   Hash,
   ChevronDown,
   Info,
+  Share2,
 } from 'lucide-react';
 import { Button } from '../ui/Button';
 import { SecurityBadge, SeverityBadge } from './SecurityBadge';
+import { SnapshotBundleDialog } from './SnapshotBundleDialog';
 import { cn } from '../../lib/utils';
 import { snapshotAPI } from '../../lib/tauri-api';
 import { getInsightTypeConfig, getSeverityColors } from '../../lib/insight-helpers';
@@ -59,6 +61,9 @@ export function SnapshotDetailPanel({
   // Compare state
   const [selectedCompareId, setSelectedCompareId] = useState<string>('');
 
+  // Bundle export dialog
+  const [showBundleDialog, setShowBundleDialog] = useState(false);
+
   // Security insights state
   const [insights, setInsights] = useState<SecurityInsight[]>([]);
   const [insightsLoading, setInsightsLoading] = useState(false);
@@ -260,6 +265,26 @@ export function SnapshotDetailPanel({
               </div>
             </Button>
           </li>
+
+          {/* Share as bundle - only completed snapshots carry a lockfile */}
+          {snapshot.status === 'completed' && (
+            <li>
+              <Button
+                variant="ghost"
+                onClick={() => setShowBundleDialog(true)}
+                className={cn(
+                  'w-full flex items-center gap-2 px-3 py-2.5 text-left h-auto justify-start rounded-none border-l-2',
+                  'hover:bg-accent text-muted-foreground border-transparent'
+                )}
+              >
+                <Share2 className="w-4 h-4 flex-shrink-0" />
+                <div className="flex-1 min-w-0">
+                  <div className="text-sm font-medium">Share</div>
+                  <div className="text-xs text-muted-foreground">Export signed bundle</div>
+                </div>
+              </Button>
+            </li>
+          )}
         </ul>
       </div>
 
@@ -311,6 +336,13 @@ export function SnapshotDetailPanel({
           />
         </div>
       </div>
+
+      <SnapshotBundleDialog
+        mode="export"
+        isOpen={showBundleDialog}
+        onClose={() => setShowBundleDialog(false)}
+        snapshotId={snapshot.id}
+      />
     </div>
   );
 }
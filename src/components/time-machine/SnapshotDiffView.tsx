@@ -636,6 +636,14 @@ function DependencyRow({
           dev
         </span>
       )}
+      {change.introducedBy.length > 0 && (
+        <span
+          className="text-xs text-muted-foreground truncate"
+          title={`Pulled in by ${change.introducedBy.join(', ')}`}
+        >
+          via {change.introducedBy.join(', ')}
+        </span>
+      )}
       {change.postinstallChanged && (
         <span
           className={cn(
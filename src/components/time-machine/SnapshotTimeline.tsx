@@ -129,6 +129,13 @@ export function SnapshotTimeline({
 
   const getTriggerLabel = (triggerSource?: TriggerSource, lockfileType?: string) => {
     const lockfileLabel = lockfileType?.toUpperCase() || '';
+    if (triggerSource === 'import') {
+      return (
+        <span className="text-cyan-600 dark:text-cyan-400" title="Imported from a snapshot bundle">
+          Imported
+        </span>
+      );
+    }
     if (triggerSource === 'manual') {
       return (
         <span className="text-purple-600 dark:text-purple-400" title="Manually captured">
@@ -3,11 +3,21 @@
 // Feature 025 redesign: Project-level lockfile change detection
 
 import { useState, useCallback, useEffect } from 'react';
-import { Clock, GitCompare, RefreshCw, Settings2, ArrowLeft, Camera, AlertTriangle } from 'lucide-react';
+import {
+  Clock,
+  GitCompare,
+  RefreshCw,
+  Settings2,
+  ArrowLeft,
+  Camera,
+  AlertTriangle,
+  Download,
+} from 'lucide-react';
 import { listen } from '@tauri-apps/api/event';
 import { SnapshotTimeline } from './SnapshotTimeline';
 import { SnapshotDetailPanel } from './SnapshotDetailPanel';
 import { SnapshotDiffView } from './SnapshotDiffView';
+import { SnapshotBundleDialog } from './SnapshotBundleDialog';
 import { ConfirmDialog } from '../ui/ConfirmDialog';
 import { useSnapshots, useSnapshot, useSnapshotDiff, useProjectSnapshots } from '../../hooks/useSnapshots';
 import type { SnapshotListItem, TriggerSource } from '../../types/snapshot';
@@ -49,6 +59,7 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
   const [pruneDialogOpen, setPruneDialogOpen] = useState(false);
   const [isDeleting, setIsDeleting] = useState(false);
   const [isPruning, setIsPruning] = useState(false);
+  const [importDialogOpen, setImportDialogOpen] = useState(false);
 
   // Reset view when project changes
   useEffect(() => {
@@ -114,7 +125,7 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
       }
 
       // Don't handle if dialogs are open
-      if (deleteDialogOpen || pruneDialogOpen) {
+      if (deleteDialogOpen || pruneDialogOpen || importDialogOpen) {
         return;
       }
 
@@ -156,7 +167,7 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
 
     window.addEventListener('keydown', handleKeyDown);
     return () => window.removeEventListener('keydown', handleKeyDown);
-  }, [viewMode, compareMode, deleteDialogOpen, pruneDialogOpen, refetchSnapshots]);
+  }, [viewMode, compareMode, deleteDialogOpen, pruneDialogOpen, importDialogOpen, refetchSnapshots]);
 
   // Handle snapshot selection
   const handleSelectSnapshot = useCallback((snapshot: SnapshotListItem) => {
@@ -278,6 +289,24 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
                   <Camera size={14} className="transition-transform duration-200 group-hover:scale-110" />
                   Capture
                 </button>
+                {/* Import a teammate's snapshot bundle */}
+                <button
+                  onClick={() => setImportDialogOpen(true)}
+                  className={cn(
+                    'group flex items-center gap-1.5 px-3 py-1.5 text-xs font-medium rounded-lg',
+                    'transition-all duration-200',
+                    'border backdrop-blur-sm',
+                    'bg-cyan-500/10 dark:bg-cyan-500/15',
+                    'border-cyan-500/30 dark:border-cyan-500/40',
+                    'text-cyan-600 dark:text-cyan-400',
+                    'hover:bg-cyan-500/20 dark:hover:bg-cyan-500/25',
+                    'hover:border-cyan-500/50 dark:hover:border-cyan-500/60'
+                  )}
+                  title="Import snapshot bundle"
+                >
+                  <Download size={14} className="transition-transform duration-200 group-hover:scale-110" />
+                  Import
+                </button>
                 <button
                   onClick={toggleCompareMode}
                   className={cn(
@@ -368,6 +397,24 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
                   <Camera size={14} className="transition-transform duration-200 group-hover:scale-110" />
                   Capture
                 </button>
+                {/* Import a teammate's snapshot bundle */}
+                <button
+                  onClick={() => setImportDialogOpen(true)}
+                  className={cn(
+                    'group flex items-center gap-1.5 px-3 py-1.5 text-xs font-medium rounded-lg',
+                    'transition-all duration-200',
+                    'border backdrop-blur-sm',
+                    'bg-cyan-500/10 dark:bg-cyan-500/15',
+                    'border-cyan-500/30 dark:border-cyan-500/40',
+                    'text-cyan-600 dark:text-cyan-400',
+                    'hover:bg-cyan-500/20 dark:hover:bg-cyan-500/25',
+                    'hover:border-cyan-500/50 dark:hover:border-cyan-500/60'
+                  )}
+                  title="Import snapshot bundle"
+                >
+                  <Download size={14} className="transition-transform duration-200 group-hover:scale-110" />
+                  Import
+                </button>
                 <button
                   onClick={toggleCompareMode}
                   className={cn(
@@ -502,6 +549,15 @@ export function TimeMachinePanel({ projectPath, showHeader = true, className }:
         isLoading={isDeleting}
       />
 
+      {/* Snapshot Bundle Import Dialog */}
+      <SnapshotBundleDialog
+        mode="import"
+        isOpen={importDialogOpen}
+        onClose={() => setImportDialogOpen(false)}
+        projectPath={projectPath}
+        onImported={() => refetchSnapshots()}
+      />
+
       {/* Prune Confirmation Dialog */}
       <ConfirmDialog
         open={pruneDialogOpen}
@@ -9,6 +9,7 @@ export { SnapshotDiffView } from './SnapshotDiffView';
 export { SecurityBadge } from './SecurityBadge';
 export { DependencyIntegrityPanel } from './DependencyIntegrityPanel';
 export { ReplayDialog } from './ReplayDialog';
+export { SnapshotBundleDialog } from './SnapshotBundleDialog';
 export { SnapshotSearchPanel } from './SnapshotSearchPanel';
 export { ExecutionTimeline } from './ExecutionTimeline';
 export { AuditReportPanel } from './AuditReportPanel';
@@ -30,6 +30,8 @@ const defaultSettings: ExecutionHistorySettings = {
   maxHistoryPerWorkflow: 50,
   retentionDays: 30,
   maxOutputLines: 500,
+  maxLogStorageMb: 500,
+  maxArtifactStorageMb: 2048,
 };
 
 export function useExecutionHistory(): UseExecutionHistoryReturn {
@@ -4,7 +4,27 @@
 import { invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type { AppSettings, StoreData, StorePathInfo } from '../types/tauri';
-import type { Project, Workflow, WorkspacePackage } from '../types';
+import type {
+  ExecutionArtifact,
+  ExecutionLogInfo,
+  ExecutionLogPage,
+  ExecutionLogSearchHit,
+  GitTriggerEvent,
+  Project,
+  Workflow,
+  WorkflowConcurrencySettings,
+  WorkflowFileFormat,
+  WorkflowFileStatus,
+  WorkflowFileSyncResult,
+  WorkflowParameterValues,
+  WorkflowPlan,
+  WorkflowRevision,
+  WorkflowRevisionDiff,
+  WorkflowScheduleState,
+  WorkflowSecretInfo,
+  WorkflowStats,
+  WorkspacePackage,
+} from '../types';
 import type { ScanProjectResponse, RefreshProjectResponse } from '../types/project';
 
 // Re-export plugin APIs
@@ -317,6 +337,12 @@ export interface ExecutionHistoryItem {
   errorMessage?: string;
   output: WorkflowOutputLine[];
   triggeredBy: string;
+  /** Execution this run resumed (retry from a node / run a node range) */
+  resumedFromExecutionId?: string;
+  /** Revision of the workflow that was run */
+  revision?: number;
+  /** Files collected from artifact globs */
+  artifacts?: ExecutionArtifact[];
 }
 
 // Execution history settings
@@ -324,6 +350,10 @@ export interface ExecutionHistorySettings {
   maxHistoryPerWorkflow: number;
   retentionDays: number;
   maxOutputLines: number;
+  /** Size limit of all persisted execution logs */
+  maxLogStorageMb?: number;
+  /** Size limit of all collected artifacts */
+  maxArtifactStorageMb?: number;
 }
 
 // Execution history store data
@@ -340,8 +370,64 @@ export const workflowAPI = {
 
   deleteWorkflow: (workflowId: string): Promise<void> => invoke('delete_workflow', { workflowId }),
 
-  executeWorkflow: (workflowId: string): Promise<string> =>
-    invoke<string>('execute_workflow', { workflowId }),
+  /** Saved revisions of a workflow, newest first */
+  listWorkflowRevisions: (workflowId: string): Promise<WorkflowRevision[]> =>
+    invoke<WorkflowRevision[]>('list_workflow_revisions', { workflowId }),
+
+  /** Compare two revisions (toRevision defaults to the latest) */
+  diffWorkflowRevisions: (
+    workflowId: string,
+    fromRevision: number,
+    toRevision?: number
+  ): Promise<WorkflowRevisionDiff> =>
+    invoke<WorkflowRevisionDiff>('diff_workflow_revisions', {
+      workflowId,
+      fromRevision,
+      toRevision,
+    }),
+
+  /** Restore an earlier revision; resolves to the revision number of the rollback */
+  rollbackWorkflow: (workflowId: string, revision: number): Promise<number> =>
+    invoke<number>('rollback_workflow', { workflowId, revision }),
+
+  listWorkflowSecrets: (): Promise<WorkflowSecretInfo[]> =>
+    invoke<WorkflowSecretInfo[]>('list_workflow_secrets'),
+
+  saveWorkflowSecret: (name: string, value: string): Promise<void> =>
+    invoke('save_workflow_secret', { name, value }),
+
+  deleteWorkflowSecret: (name: string): Promise<boolean> =>
+    invoke<boolean>('delete_workflow_secret', { name }),
+
+  getWorkflowScheduleStates: (): Promise<WorkflowScheduleState[]> =>
+    invoke<WorkflowScheduleState[]>('get_workflow_schedule_states'),
+
+  /** Next run times (local, RFC 3339) of a cron expression; rejects invalid expressions */
+  previewWorkflowSchedule: (cron: string, count?: number): Promise<string[]> =>
+    invoke<string[]>('preview_workflow_schedule', { cron, count }),
+
+  /** Compare `.packageflow/workflows` files of a project with its stored workflows */
+  getWorkflowFilesStatus: (projectId: string): Promise<WorkflowFileStatus[]> =>
+    invoke<WorkflowFileStatus[]>('get_workflow_files_status', { projectId }),
+
+  /** Write a project workflow to its workflow file; resolves to the file path */
+  exportWorkflowToFile: (workflowId: string, format?: WorkflowFileFormat): Promise<string> =>
+    invoke<string>('export_workflow_to_file', { workflowId, format }),
+
+  /** Import one workflow file; rejects with validation errors (one per line) */
+  importWorkflowFromFile: (projectId: string, filePath: string): Promise<Workflow> =>
+    invoke<Workflow>('import_workflow_from_file', { projectId, filePath }),
+
+  /** Import every new or changed workflow file of a project */
+  syncWorkflowFiles: (projectId: string): Promise<WorkflowFileSyncResult> =>
+    invoke<WorkflowFileSyncResult>('sync_workflow_files', { projectId }),
+
+  /** Dry run: resolved commands, cwd, env and child workflows without running anything */
+  getWorkflowPlan: (workflowId: string, params?: WorkflowParameterValues): Promise<WorkflowPlan> =>
+    invoke<WorkflowPlan>('get_workflow_plan', { workflowId, params }),
+
+  executeWorkflow: (workflowId: string, params?: WorkflowParameterValues): Promise<string> =>
+    invoke<string>('execute_workflow', { workflowId, params }),
 
   cancelExecution: (executionId: string): Promise<void> =>
     invoke('cancel_execution', { executionId }),
@@ -349,6 +435,11 @@ export const workflowAPI = {
   continueExecution: (executionId: string): Promise<void> =>
     invoke('continue_execution', { executionId }),
 
+  /** Re-run a finished execution from a node (or only the nodes fromNodeId..toNodeId) */
+  resumeExecution: (executionId: string, fromNodeId: string, toNodeId?: string): Promise<string> =>
+    invoke<string>('resume_execution', { executionId, fromNodeId, toNodeId }),
+
+  /** Running and queued executions (queued ones carry their queuePosition) */
   getRunningExecutions: (): Promise<Record<string, Execution>> =>
     invoke<Record<string, Execution>>('get_running_executions'),
 
@@ -393,6 +484,52 @@ export const workflowAPI = {
 
   updateExecutionHistorySettings: (settings: ExecutionHistorySettings): Promise<void> =>
     invoke('update_execution_history_settings', { settings }),
+
+  // Concurrency settings
+  getWorkflowConcurrencySettings: (): Promise<WorkflowConcurrencySettings> =>
+    invoke<WorkflowConcurrencySettings>('get_workflow_concurrency_settings'),
+
+  updateWorkflowConcurrencySettings: (settings: WorkflowConcurrencySettings): Promise<void> =>
+    invoke('update_workflow_concurrency_settings', { settings }),
+
+  // Persisted execution logs
+  listExecutionLogs: (workflowId?: string, limit?: number): Promise<ExecutionLogInfo[]> =>
+    invoke<ExecutionLogInfo[]>('list_execution_logs', { workflowId, limit }),
+
+  readExecutionLog: (
+    executionId: string,
+    offset?: number,
+    limit?: number
+  ): Promise<ExecutionLogPage> =>
+    invoke<ExecutionLogPage>('read_execution_log', { executionId, offset, limit }),
+
+  /** Full-text search across persisted logs (query of at least 3 characters) */
+  searchExecutionLogs: (
+    query: string,
+    workflowId?: string,
+    limit?: number
+  ): Promise<ExecutionLogSearchHit[]> =>
+    invoke<ExecutionLogSearchHit[]>('search_execution_logs', { query, workflowId, limit }),
+
+  // Execution artifacts
+  listExecutionArtifacts: (executionId: string): Promise<ExecutionArtifact[]> =>
+    invoke<ExecutionArtifact[]>('list_execution_artifacts', { executionId }),
+
+  /** Copy artifacts (optionally of one node) into a directory; resolves to the file count */
+  exportExecutionArtifacts: (
+    executionId: string,
+    destination: string,
+    nodeId?: string
+  ): Promise<number> =>
+    invoke<number>('export_execution_artifacts', { executionId, destination, nodeId }),
+
+  /** Open a stored artifact, or reveal it in the file manager */
+  openExecutionArtifact: (executionId: string, filePath: string, reveal?: boolean): Promise<void> =>
+    invoke('open_execution_artifact', { executionId, filePath, reveal }),
+
+  /** Success rate, node timings, daily timeline and flaky nodes of the most recent runs */
+  getWorkflowStats: (workflowId: string, limit?: number): Promise<WorkflowStats> =>
+    invoke<WorkflowStats>('get_workflow_stats', { workflowId, limit }),
 };
 
 // ============================================================================
@@ -1184,6 +1321,31 @@ export interface ChildExecutionCompletedPayload {
   finishedAt: string;
 }
 
+export interface ScheduledRunPayload {
+  workflowId: string;
+  executionId?: string;
+  scheduledAt: string;
+  missed: boolean;
+  error?: string;
+}
+
+export interface WorkflowFileTriggeredPayload {
+  workflowId: string;
+  executionId?: string;
+  /** Changed paths relative to the project root */
+  changedFiles: string[];
+  error?: string;
+}
+
+export interface WorkflowGitTriggeredPayload {
+  workflowId: string;
+  executionId?: string;
+  projectPath: string;
+  event: GitTriggerEvent;
+  branch?: string;
+  error?: string;
+}
+
 export const tauriEvents = {
   // Script events (Phase 6 - US3)
   onScriptOutput: (callback: (data: ScriptOutputPayload) => void): Promise<UnlistenFn> =>
@@ -1208,6 +1370,25 @@ export const tauriEvents = {
   onWorkflowPaused: (callback: (data: ExecutionPausedPayload) => void): Promise<UnlistenFn> =>
     listen<ExecutionPausedPayload>('execution_paused', (event) => callback(event.payload)),
 
+  onWorkflowScheduleTriggered: (
+    callback: (data: ScheduledRunPayload) => void
+  ): Promise<UnlistenFn> =>
+    listen<ScheduledRunPayload>('workflow_schedule_triggered', (event) => callback(event.payload)),
+
+  onWorkflowFileTriggered: (
+    callback: (data: WorkflowFileTriggeredPayload) => void
+  ): Promise<UnlistenFn> =>
+    listen<WorkflowFileTriggeredPayload>('workflow_file_triggered', (event) =>
+      callback(event.payload)
+    ),
+
+  onWorkflowGitTriggered: (
+    callback: (data: WorkflowGitTriggeredPayload) => void
+  ): Promise<UnlistenFn> =>
+    listen<WorkflowGitTriggeredPayload>('workflow_git_triggered', (event) =>
+      callback(event.payload)
+    ),
+
   // Feature 013: Child execution events (T028-T030)
   onChildExecutionStarted: (
     callback: (data: ChildExecutionStartedPayload) => void
@@ -1465,6 +1646,7 @@ import type {
   DiffHunk,
   DiffLine,
   GetFileDiffResponse,
+  GitHooksResponse,
 } from '../types/git';
 
 export type {
@@ -1503,6 +1685,7 @@ export type {
   DiffHunk,
   DiffLine,
   GetFileDiffResponse,
+  GitHooksResponse,
 };
 
 export const gitAPI = {
@@ -1681,6 +1864,16 @@ export const gitAPI = {
     staged: boolean
   ): Promise<GetFileDiffResponse> =>
     invoke<GetFileDiffResponse>('get_file_diff', { projectPath, filePath, staged }),
+
+  // Git hooks for workflow git triggers
+  installGitHooks: (projectPath: string, events?: GitTriggerEvent[]): Promise<GitHooksResponse> =>
+    invoke<GitHooksResponse>('install_git_hooks', { projectPath, events }),
+
+  uninstallGitHooks: (projectPath: string): Promise<GitHooksResponse> =>
+    invoke<GitHooksResponse>('uninstall_git_hooks', { projectPath }),
+
+  getGitHooksStatus: (projectPath: string): Promise<GitHooksResponse> =>
+    invoke<GitHooksResponse>('get_git_hooks_status', { projectPath }),
 };
 
 // ============================================================================
@@ -2665,6 +2858,10 @@ import type {
   SnapshotFilter,
   SnapshotDiff,
   SnapshotStorageStats,
+  SnapshotRetentionResult,
+  SnapshotBundleExport,
+  SnapshotBundleImport,
+  InstallReason,
   SecurityInsight,
   InsightSummary,
   AIAnalysisRequest,
@@ -2709,6 +2906,10 @@ export const snapshotAPI = {
   deleteSnapshot: (snapshotId: string): Promise<boolean> =>
     invoke<boolean>('delete_snapshot', { snapshotId }),
 
+  /** Pin or unpin a snapshot so retention never removes it */
+  setSnapshotPinned: (snapshotId: string, pinned: boolean): Promise<boolean> =>
+    invoke<boolean>('set_snapshot_pinned', { snapshotId, pinned }),
+
   /** Prune old snapshots (keep snapshots newer than N days) - Feature 025 redesign */
   pruneSnapshots: (keepDays?: number): Promise<number> =>
     invoke<number>('prune_snapshots', { keepDays }),
@@ -2752,6 +2953,10 @@ export const snapshotAPI = {
   /** Cleanup orphaned storage */
   cleanupOrphanedStorage: (): Promise<number> => invoke<number>('cleanup_orphaned_storage'),
 
+  /** Apply the saved retention policy to one project, or all projects */
+  applySnapshotRetention: (projectPath?: string): Promise<SnapshotRetentionResult> =>
+    invoke<SnapshotRetentionResult>('apply_snapshot_retention', { projectPath }),
+
   // AI Analysis
   /** Request AI analysis of snapshot diff */
   requestAiAnalysis: (request: AIAnalysisRequest): Promise<AIAnalysisResponse> =>
@@ -2765,6 +2970,19 @@ export const snapshotAPI = {
   ): Promise<PatternAnalysisResult> =>
     invoke<PatternAnalysisResult>('analyze_diff_patterns', { snapshotAId, snapshotBId }),
 
+  // Dependency Graph
+  /** Explain why a package (optionally a specific version) is in a snapshot */
+  whyInstalled: (
+    snapshotId: string,
+    packageName: string,
+    version?: string
+  ): Promise<InstallReason[]> =>
+    invoke<InstallReason[]>('why_installed', { snapshotId, packageName, version }),
+
+  /** Explain what pulled in each package with a postinstall script */
+  getPostinstallOrigins: (snapshotId: string): Promise<InstallReason[]> =>
+    invoke<InstallReason[]>('get_postinstall_origins', { snapshotId }),
+
   // Dependency Integrity (US3 - Security Guardian)
   /** Check dependency integrity against reference snapshot - Feature 025: removed workflowId */
   checkDependencyIntegrity: (projectPath: string): Promise<IntegrityCheckResult> =>
@@ -2787,6 +3005,31 @@ export const snapshotAPI = {
   restoreLockfile: (snapshotId: string): Promise<boolean> =>
     invoke<boolean>('restore_lockfile', { snapshotId }),
 
+  // Snapshot Bundles
+  /** Export a snapshot as a signed bundle file for teammates */
+  exportSnapshotBundle: (
+    snapshotId: string,
+    outputPath: string,
+    signingSecret: string
+  ): Promise<SnapshotBundleExport> =>
+    invoke<SnapshotBundleExport>('export_snapshot_bundle', {
+      snapshotId,
+      outputPath,
+      signingSecret,
+    }),
+
+  /** Import a signed bundle as a pinned snapshot of a local project */
+  importSnapshotBundle: (
+    bundlePath: string,
+    projectPath: string,
+    signingSecret: string
+  ): Promise<SnapshotBundleImport> =>
+    invoke<SnapshotBundleImport>('import_snapshot_bundle', {
+      bundlePath,
+      projectPath,
+      signingSecret,
+    }),
+
   // Security Insights Dashboard (US5)
   /** Get project security overview with risk score */
   getProjectSecurityOverview: (projectPath: string): Promise<ProjectSecurityOverview> =>
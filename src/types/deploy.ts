@@ -118,6 +118,8 @@ export interface Deployment {
   siteName?: string;
   /** Unique preview URL for this specific deploy */
   previewUrl?: string;
+  /** Time Machine snapshot of the project when the deploy started */
+  snapshotId?: string;
 }
 
 export interface OAuthFlowResult {
@@ -1,6 +1,8 @@
 // Git types for 009-git-integration feature
 // TypeScript types matching Rust models in src-tauri/src/models/git.rs
 
+import type { GitTriggerEvent } from './workflow';
+
 /**
  * Git file status types
  */
@@ -419,6 +421,7 @@ export type GitErrorCode =
   | 'FILE_NOT_FOUND'
   | 'HAS_UNCOMMITTED_CHANGES'
   | 'REJECTED_NON_FAST_FORWARD'
+  | 'PRE_PUSH_FAILED'
   // Remote errors
   | 'REMOTE_EXISTS'
   | 'REMOTE_NOT_FOUND'
@@ -551,3 +554,15 @@ export interface UnstageLinesResponse {
   unstagedCount?: number;
   error?: string;
 }
+
+/**
+ * Response for install_git_hooks, uninstall_git_hooks and get_git_hooks_status
+ */
+export interface GitHooksResponse {
+  success: boolean;
+  /** Resolved hooks directory (respects core.hooksPath) */
+  hooksDir?: string;
+  /** Hooks written by PackageFlow that are currently installed */
+  installed: GitTriggerEvent[];
+  error?: string;
+}
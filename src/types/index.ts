@@ -4,12 +4,57 @@
 export type {
   Workflow,
   WorkflowNode,
+  WorkflowParameter,
+  WorkflowParameterValues,
+  WorkflowSecretInfo,
+  WorkflowSchedule,
+  WorkflowFileTrigger,
+  WorkflowGitTrigger,
+  GitTriggerEvent,
+  ConcurrencyPolicy,
+  WorkflowConcurrencySettings,
+  WorkflowScheduleState,
+  WorkflowFileFormat,
+  WorkflowFileError,
+  WorkflowFileDiff,
+  WorkflowFileState,
+  WorkflowFileStatus,
+  WorkflowFileSyncResult,
+  MissedRunPolicy,
+  ParameterType,
   ScriptNodeConfig,
+  ScriptShell,
+  ApprovalNodeConfig,
+  HttpMethod,
+  HttpNodeConfig,
+  NodeMatrix,
   NodePosition,
   Execution,
   ExecutionStatus,
+  ResumeOrigin,
   NodeResult,
+  MatrixCellResult,
+  ApprovalStatus,
+  NodeApproval,
   NodeStatus,
+  ExecutionLogKind,
+  ExecutionArtifact,
+  ExecutionLogInfo,
+  ExecutionLogLine,
+  ExecutionLogPage,
+  ExecutionLogSearchHit,
+  WorkflowStats,
+  NodeTimingStats,
+  StatsTimelinePoint,
+  FlakyNode,
+  WorkflowPlan,
+  NodePlan,
+  PlannedInvocation,
+  RevisionSource,
+  WorkflowRevision,
+  WorkflowFieldChange,
+  WorkflowNodeChange,
+  WorkflowRevisionDiff,
   WorkflowStore,
   UserSettings,
   NodeStartedEvent,
@@ -197,9 +242,13 @@ export type {
   LockfileType,
   SnapshotStatus,
   DependencyChangeType,
+  DependencyKind,
   InsightType,
   InsightSeverity,
   SnapshotDependency,
+  SnapshotDependencyEdge,
+  DependencyPathStep,
+  InstallReason,
   ExecutionSnapshot,
   SnapshotListItem,
   SnapshotWithDependencies,
@@ -216,6 +265,10 @@ export type {
   CreateSnapshotRequest,
   SnapshotFilter,
   SnapshotStorageStats,
+  SnapshotRetentionPolicy,
+  SnapshotRetentionResult,
+  SnapshotBundleExport,
+  SnapshotBundleImport,
   FrequentUpdater,
   HealthFactor,
   DependencyHealth,
@@ -2,6 +2,8 @@
 // Types for MCP action execution system
 // @see specs/021-mcp-actions/data-model.md
 
+import type { ScriptShell } from './workflow';
+
 // ============================================================================
 // Enums
 // ============================================================================
@@ -70,6 +72,8 @@ export interface ScriptConfig {
   env?: Record<string, string>;
   timeoutMs?: number;
   useVolta?: boolean;
+  /** Shell for the command line (same rules as workflow script nodes) */
+  shell?: ScriptShell;
 }
 
 export interface MCPWebhookConfig {
@@ -332,6 +332,11 @@ export const MCP_TOOL_DEFINITIONS: McpToolWithPermission[] = [
     description: 'Get detailed workflow info including all steps',
     category: 'read',
   },
+  {
+    name: 'plan_workflow',
+    description: 'Show what a workflow run would do without running it',
+    category: 'read',
+  },
   { name: 'get_workflow_execution_details', description: 'Get execution logs', category: 'read' },
   {
     name: 'list_step_templates',
@@ -608,6 +613,12 @@ export const TOOL_DEFINITIONS_WITH_PERMISSIONS: ToolDefinitionWithPermissions[]
     category: 'read',
     applicablePermissions: ['read'],
   },
+  {
+    name: 'plan_workflow',
+    description: 'Preview a workflow run',
+    category: 'read',
+    applicablePermissions: ['read'],
+  },
   {
     name: 'list_step_templates',
     description: 'List step templates',
@@ -129,6 +129,7 @@ export type NotificationTypeName =
   | 'webhook_outgoing_failure'
   | 'workflow_completed'
   | 'workflow_failed'
+  | 'workflow_approval_requested'
   | 'git_push_success'
   | 'git_push_failed'
   | 'security_scan_completed'
@@ -140,6 +141,7 @@ export type NotificationTypeName =
  */
 export interface NotificationMetadata {
   workflowName?: string;
+  nodeName?: string;
   projectName?: string;
   url?: string;
   error?: string;
@@ -10,10 +10,12 @@ export type LockfileType = 'npm' | 'pnpm' | 'yarn' | 'bun';
 export type SnapshotStatus = 'capturing' | 'completed' | 'failed';
 
 /** Trigger source for snapshots - Feature 025 redesign */
-export type TriggerSource = 'lockfile_change' | 'manual';
+export type TriggerSource = 'lockfile_change' | 'manual' | 'import';
 
 export type DependencyChangeType = 'added' | 'removed' | 'updated' | 'unchanged';
 
+export type DependencyKind = 'prod' | 'dev' | 'optional' | 'peer';
+
 export type InsightType =
   | 'new_dependency'
   | 'removed_dependency'
@@ -72,6 +74,8 @@ export interface ExecutionSnapshot {
   compressedSize?: number;
   errorMessage?: string;
   createdAt: string;
+  /** Pinned snapshots are never removed by retention */
+  isPinned?: boolean;
 }
 
 export interface SnapshotListItem {
@@ -85,6 +89,34 @@ export interface SnapshotListItem {
   securityScore?: number;
   postinstallCount: number;
   createdAt: string;
+  isPinned?: boolean;
+}
+
+export interface SnapshotDependencyEdge {
+  snapshotId: string;
+  /** Absent when the project's package.json declares the child */
+  parentName?: string;
+  parentVersion?: string;
+  childName: string;
+  childVersion: string;
+  kind: DependencyKind;
+}
+
+export interface DependencyPathStep {
+  name: string;
+  version: string;
+  kind: DependencyKind;
+}
+
+/** Why a package is installed: paths from direct dependencies down to it */
+export interface InstallReason {
+  name: string;
+  version: string;
+  isDirect: boolean;
+  hasPostinstall: boolean;
+  directDependencies: string[];
+  paths: DependencyPathStep[][];
+  truncated: boolean;
 }
 
 export interface SnapshotWithDependencies {
@@ -164,6 +196,8 @@ export interface DependencyChange {
   postinstallChanged: boolean;
   oldPostinstall?: string;
   newPostinstall?: string;
+  /** Changed direct dependencies that pulled in this transitive change */
+  introducedBy: string[];
 }
 
 export interface PostinstallChange {
@@ -227,10 +261,57 @@ export interface LockfileState {
   updatedAt: string;
 }
 
+/**
+ * Which snapshots of a project survive pruning. A snapshot is kept when any rule
+ * selects it; pinned, deployed and the latest snapshots are always kept.
+ */
+export interface SnapshotRetentionPolicy {
+  /** Apply the policy after every capture */
+  enabled: boolean;
+  keepRecent: number;
+  /** Newest snapshot of each of the last N days with snapshots */
+  keepDaily: number;
+  /** Newest snapshot of each of the last N ISO weeks with snapshots */
+  keepWeekly: number;
+  /** Newest snapshot of each of the last N months with snapshots */
+  keepMonthly: number;
+  /** Per-project disk quota; oldest unprotected snapshots go first */
+  maxProjectStorageMb?: number;
+}
+
+export interface SnapshotRetentionResult {
+  deletedSnapshotIds: string[];
+  keptCount: number;
+  reclaimedBytes: number;
+  /** Lockfiles moved into the shared content-addressed store */
+  deduplicatedLockfiles: number;
+  /** Projects still above their quota once only protected snapshots remain */
+  overQuotaProjects: string[];
+}
+
+/** A snapshot written to a signed bundle file */
+export interface SnapshotBundleExport {
+  snapshotId: string;
+  path: string;
+  sizeBytes: number;
+}
+
+/** A snapshot bundle imported for replay against a local checkout */
+export interface SnapshotBundleImport {
+  /** The new local snapshot, pointing at the importing project */
+  snapshot: ExecutionSnapshot;
+  originalSnapshotId: string;
+  originalProjectPath: string;
+  exportedAt: string;
+  dependencyCount: number;
+  insightCount: number;
+}
+
 /** Time Machine global settings */
 export interface TimeMachineSettings {
   autoWatchEnabled: boolean;
   debounceMs: number;
+  retention?: SnapshotRetentionPolicy;
   updatedAt: string;
 }
 
@@ -18,20 +18,191 @@ export interface Workflow {
   lastExecutedAt?: string;
   webhook?: WebhookConfig;
   incomingWebhook?: IncomingWebhookConfig;
+  /** Maximum number of nodes executed concurrently (engine default when unset) */
+  maxParallelism?: number;
+  /** Declared inputs, referenced as `{{params.<name>}}` in node commands */
+  parameters?: WorkflowParameter[];
+  /** Environment variables for every script node; values may reference `{{secrets.<name>}}` */
+  env?: Record<string, string>;
+  /** Cron schedule run by the background scheduler */
+  schedule?: WorkflowSchedule;
+  /** Run when files matching glob patterns change in the workflow's project */
+  fileTrigger?: WorkflowFileTrigger;
+  /** Run on commits, branch switches or pushes in the workflow's project */
+  gitTrigger?: WorkflowGitTrigger;
+  /** What happens when the workflow is started while a run is active (default `allow`) */
+  concurrency?: ConcurrencyPolicy;
 }
 
+/**
+ * allow: runs execute side by side; queue: wait for active runs;
+ * cancel-previous: cancel active runs first; reject: refuse to start
+ */
+export type ConcurrencyPolicy = 'allow' | 'queue' | 'cancel-previous' | 'reject';
+
+/** App-wide limits on workflow executions (shared with the MCP server) */
+export interface WorkflowConcurrencySettings {
+  /** Executions that may run at once; further starts are queued (0 = unlimited) */
+  maxConcurrentExecutions: number;
+}
+
+/** File-change trigger; patterns are relative to the project root */
+export interface WorkflowFileTrigger {
+  enabled: boolean;
+  /** Glob patterns that start the workflow (e.g. `src/**/*.graphql`) */
+  patterns: string[];
+  /** Additional glob patterns to ignore */
+  ignore?: string[];
+  /** Quiet period before a burst of changes starts the workflow (default 1000) */
+  debounceMs?: number;
+  /** Skip paths ignored by the project's root `.gitignore` (default true) */
+  respectGitignore?: boolean;
+}
+
+/** Git event named after the git hook that reports it */
+export type GitTriggerEvent = 'post-commit' | 'post-checkout' | 'pre-push';
+
+/** Git-event trigger; fires from in-app git actions and, once installed, git hooks */
+export interface WorkflowGitTrigger {
+  enabled: boolean;
+  events: GitTriggerEvent[];
+  /** Branch glob patterns (empty = any branch) */
+  branches?: string[];
+  /** Only fire when a changed file matches one of these globs (e.g. `pnpm-lock.yaml`) */
+  paths?: string[];
+}
+
+/** What to do when a scheduled run was missed (app closed or machine asleep) */
+export type MissedRunPolicy = 'skip' | 'runOnce';
+
+export interface WorkflowSchedule {
+  enabled: boolean;
+  /** 5-field cron expression in local time (e.g. `0 3 * * *`) or a macro such as `@daily` */
+  cron: string;
+  missedRunPolicy?: MissedRunPolicy;
+}
+
+/** Persisted scheduler state for a scheduled workflow */
+export interface WorkflowScheduleState {
+  workflowId: string;
+  cron: string;
+  nextRunAt?: string;
+  lastRunAt?: string;
+  lastExecutionId?: string;
+  lastError?: string;
+  updatedAt: string;
+}
+
+/** Format of a committed workflow file under `.packageflow/workflows` */
+export type WorkflowFileFormat = 'yaml' | 'json';
+
+/** Problem in a workflow file; node errors carry the node's index and ID */
+export interface WorkflowFileError {
+  nodeIndex?: number;
+  nodeId?: string;
+  message: string;
+}
+
+/** What importing a workflow file would change in the stored workflow */
+export interface WorkflowFileDiff {
+  changedFields: string[];
+  addedNodes: string[];
+  removedNodes: string[];
+  modifiedNodes: string[];
+}
+
+export type WorkflowFileState = 'new' | 'modified' | 'unchanged' | 'invalid' | 'notExported';
+
+export interface WorkflowFileStatus {
+  /** Relative to the project root; absent for `notExported` */
+  path?: string;
+  workflowId?: string;
+  name?: string;
+  state: WorkflowFileState;
+  diff?: WorkflowFileDiff;
+  errors?: WorkflowFileError[];
+}
+
+export interface WorkflowFileSyncResult {
+  /** IDs of workflows created or updated from files */
+  imported: string[];
+  /** File statuses before the sync */
+  statuses: WorkflowFileStatus[];
+}
+
+export type ParameterType = 'string' | 'number' | 'boolean' | 'choice';
+
+export interface WorkflowParameter {
+  name: string;
+  type?: ParameterType;
+  description?: string;
+  default?: string | number | boolean;
+  required?: boolean;
+  /** Allowed values for 'choice' parameters */
+  options?: string[];
+}
+
+/** Stored secret; the value is write-only and never returned */
+export interface WorkflowSecretInfo {
+  name: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** Values passed when starting a workflow, keyed by parameter name */
+export type WorkflowParameterValues = Record<string, string | number | boolean>;
+
 export interface NodePosition {
   x: number;
   y: number;
 }
 
-export type NodeType = 'script' | 'trigger-workflow';
+export type NodeType = 'script' | 'trigger-workflow' | 'condition' | 'approval' | 'http';
 export type OnChildFailure = 'fail' | 'continue';
 
+export type RetryBackoff = 'fixed' | 'exponential';
+
+/** Failure that should be retried */
+export type RetryOn =
+  | { kind: 'exitCode'; codes: number[] }
+  | { kind: 'outputMatches'; pattern: string };
+
+/** Shell a script command runs in; `none` executes the program directly */
+export type ScriptShell = 'sh' | 'bash' | 'zsh' | 'none';
+
 export interface ScriptNodeConfig {
   command: string;
   cwd?: string;
   timeout?: number;
+  /** Environment variables for this node (override workflow `env`) */
+  env?: Record<string, string>;
+  /** Number of retries after the first attempt (0 = never retry) */
+  retries?: number;
+  /** Delay before the first retry */
+  retryDelayMs?: number;
+  retryBackoff?: RetryBackoff;
+  /** Only retry failures matching one of these (empty = any failure) */
+  retryOn?: RetryOn[];
+  /** Run the command once per matrix combination */
+  matrix?: NodeMatrix;
+  /** Unset runs through `sh` (`cmd` on Windows) only when the command uses shell syntax */
+  shell?: ScriptShell;
+  /** Globs relative to the working directory (e.g. `dist/**`) kept after the node succeeds */
+  artifacts?: string[];
+}
+
+/** Dimensions a script node fans out over; values are available as `{{matrix.<name>}}` */
+export interface NodeMatrix {
+  /** Node.js versions pinned with `volta run --node` (`{{matrix.node}}`) */
+  nodeVersions?: string[];
+  /** Glob patterns of workspace package names; cells run in the package directory */
+  packages?: string[];
+  /** Additional dimensions: name -> values */
+  values?: Record<string, string[]>;
+  /** Cells run at the same time (default 1) */
+  maxParallel?: number;
+  /** Keep running the remaining cells after a cell fails */
+  continueOnFailure?: boolean;
 }
 
 export interface TriggerWorkflowConfig {
@@ -40,7 +211,70 @@ export interface TriggerWorkflowConfig {
   onChildFailure: OnChildFailure;
 }
 
-export type NodeConfig = ScriptNodeConfig | TriggerWorkflowConfig;
+/** What a condition rule inspects */
+export type ConditionCheck =
+  | { kind: 'exitCode'; nodeId: string; equals?: number }
+  | { kind: 'outputMatches'; nodeId: string; pattern: string }
+  | { kind: 'envVar'; name: string; equals?: string }
+  | { kind: 'gitBranch'; pattern: string };
+
+export type ConditionRule = ConditionCheck & {
+  /** Invert the result of the check */
+  negate?: boolean;
+};
+
+export interface ConditionBranch {
+  label?: string;
+  /** Rules that must all match; an empty list always matches */
+  rules: ConditionRule[];
+  /** Node IDs to run when this branch is taken */
+  targets: string[];
+}
+
+export interface ConditionNodeConfig {
+  /** Evaluated in order; the first matching branch is taken */
+  branches: ConditionBranch[];
+  /** Node IDs to run when no branch matches */
+  otherwise?: string[];
+}
+
+/** Manual gate: execution waits until the pending request is approved or rejected */
+export interface ApprovalNodeConfig {
+  /** Shown in the approval request; supports `{{...}}` placeholders */
+  message?: string;
+  /** Reject automatically after this many minutes (absent = wait indefinitely) */
+  timeoutMinutes?: number;
+}
+
+export type HttpMethod = 'GET' | 'POST' | 'PUT' | 'PATCH' | 'DELETE' | 'HEAD';
+
+/**
+ * HTTP request node. URL, header values, body and signing secret support `{{params.*}}`,
+ * `{{steps.*}}` and `{{secrets.*}}` placeholders.
+ */
+export interface HttpNodeConfig {
+  /** Default: GET */
+  method?: HttpMethod;
+  url: string;
+  headers?: Record<string, string>;
+  /** Request body template; sent as JSON unless a Content-Type header is given */
+  body?: string;
+  /** Accepted response status codes (absent = any 2xx) */
+  expectedStatus?: number[];
+  /** Step outputs read from the JSON response: output name -> JSON path (e.g. `$.data.id`) */
+  extract?: Record<string, string>;
+  /** `{{secrets.<name>}}` reference; signs the body into the `X-Webhook-Signature` header */
+  signingSecret?: string;
+  /** Request timeout (default: 30000) */
+  timeoutMs?: number;
+}
+
+export type NodeConfig =
+  | ScriptNodeConfig
+  | TriggerWorkflowConfig
+  | ConditionNodeConfig
+  | ApprovalNodeConfig
+  | HttpNodeConfig;
 
 export interface WorkflowNode {
   id: string;
@@ -49,6 +283,8 @@ export interface WorkflowNode {
   config: NodeConfig;
   order: number;
   position?: NodePosition;
+  /** IDs of nodes that must complete first; workflows without any run sequentially by order */
+  dependsOn?: string[];
 }
 
 export function isScriptNodeConfig(config: NodeConfig): config is ScriptNodeConfig {
@@ -59,6 +295,10 @@ export function isTriggerWorkflowConfig(config: NodeConfig): config is TriggerWo
   return 'targetWorkflowId' in config;
 }
 
+export function isConditionNodeConfig(config: NodeConfig): config is ConditionNodeConfig {
+  return 'branches' in config;
+}
+
 export interface Execution {
   id: string;
   workflowId: string;
@@ -69,6 +309,24 @@ export interface Execution {
   parentExecutionId?: string;
   parentNodeId?: string;
   depth: number;
+  /** Resolved workflow parameters (defaults applied) */
+  parameters?: Record<string, string>;
+  /** Past execution this one re-runs part of */
+  resumedFrom?: ResumeOrigin;
+  /** Commit checked out in the project when the execution started */
+  gitCommit?: string;
+  /** Revision of the workflow that was run */
+  revision?: number;
+  /** 1-based position in the run queue while the execution is queued */
+  queuePosition?: number;
+}
+
+export interface ResumeOrigin {
+  executionId: string;
+  /** First node that was run again */
+  fromNodeId: string;
+  /** Last node of a "run X..Y" range (absent = everything after fromNodeId) */
+  toNodeId?: string;
 }
 
 export type ExecutionStatus =
@@ -77,7 +335,8 @@ export type ExecutionStatus =
   | 'completed'
   | 'failed'
   | 'paused'
-  | 'cancelled';
+  | 'cancelled'
+  | 'queued';
 
 export interface ChildExecutionResult {
   childExecutionId: string;
@@ -88,19 +347,272 @@ export interface ChildExecutionResult {
   errorMessage?: string;
 }
 
+export type SkipReason = 'condition' | 'dependencyFailed' | 'cancelled' | 'notInRun';
+
 export interface NodeResult {
   nodeId: string;
   status: NodeStatus;
+  /** Captured stdout, available to later nodes as `{{steps.<id>.output}}` */
   output: string;
+  /** `output` stopped at its size limit */
+  outputTruncated?: boolean;
   errorMessage?: string;
+  /** Why the node was skipped (status `skipped` only) */
+  skipReason?: SkipReason;
   startedAt: string;
   finishedAt?: string;
   exitCode?: number;
   childExecutionResult?: ChildExecutionResult;
+  /** Every attempt of the node, oldest first (nodes with a retry policy only) */
+  attempts?: NodeAttempt[];
+  /** Key/values exported with `::set-output name=<key>::<value>` lines */
+  outputs?: Record<string, string>;
+  /** Per-cell results of a matrix node */
+  matrixCells?: MatrixCellResult[];
+  /** Approval request and decision of an approval node */
+  approval?: NodeApproval;
+}
+
+export type ApprovalStatus = 'pending' | 'approved' | 'rejected' | 'timedOut' | 'cancelled';
+
+export interface NodeApproval {
+  /** Pending action request answered with `respond_to_action_request` */
+  requestId: string;
+  status: ApprovalStatus;
+  message?: string;
+  /** Comment given with the decision */
+  comment?: string;
+  requestedAt: string;
+  decidedAt?: string;
+}
+
+export interface MatrixCellResult {
+  index: number;
+  /** e.g. `node=20, package=@acme/core` */
+  label: string;
+  values: Record<string, string>;
+  status: NodeStatus;
+  exitCode?: number;
+  errorMessage?: string;
+  startedAt?: string;
+  finishedAt?: string;
+  attempts?: NodeAttempt[];
+}
+
+export interface NodeAttempt {
+  /** 1 = first run */
+  attempt: number;
+  status: NodeStatus;
+  exitCode?: number;
+  errorMessage?: string;
+  startedAt: string;
+  finishedAt?: string;
 }
 
 export type NodeStatus = 'pending' | 'running' | 'completed' | 'failed' | 'skipped';
 
+/** File a script node produced, copied into the artifact store of its execution */
+export interface ExecutionArtifact {
+  executionId: string;
+  workflowId: string;
+  nodeId: string;
+  nodeName: string;
+  /** Matrix cell the file was collected from */
+  cellIndex?: number;
+  /** Path relative to the node's working directory */
+  path: string;
+  sizeBytes: number;
+  sha256: string;
+  /** Stored copy */
+  filePath: string;
+  collectedAt: string;
+}
+
+// Persisted execution logs
+export type ExecutionLogKind = 'workflow' | 'script';
+
+export interface ExecutionLogInfo {
+  executionId: string;
+  kind: ExecutionLogKind;
+  workflowId?: string;
+  /** Workflow name or script command */
+  name: string;
+  projectPath?: string;
+  /** 'running' until the execution finishes, then its final status ('interrupted' if the app quit mid-run) */
+  status: string;
+  startedAt: string;
+  finishedAt?: string;
+  lineCount: number;
+  sizeBytes: number;
+  compressedBytes: number;
+  filePath: string;
+}
+
+export interface ExecutionLogLine {
+  lineNo: number;
+  nodeId?: string;
+  nodeName?: string;
+  stream: 'stdout' | 'stderr';
+  timestamp: string;
+  content: string;
+}
+
+export interface ExecutionLogPage {
+  info: ExecutionLogInfo;
+  lines: ExecutionLogLine[];
+  totalLines: number;
+}
+
+export interface ExecutionLogSearchHit {
+  log: ExecutionLogInfo;
+  line: ExecutionLogLine;
+}
+
+/** Timing and reliability statistics of a workflow over its execution history */
+export interface WorkflowStats {
+  workflowId: string;
+  runCount: number;
+  completedCount: number;
+  failedCount: number;
+  cancelledCount: number;
+  /** completed / (completed + failed), 0..1 */
+  successRate?: number;
+  /** Duration percentiles of completed runs */
+  p50DurationMs?: number;
+  p95DurationMs?: number;
+  /** Slowest median first */
+  nodes: NodeTimingStats[];
+  /** One point per day with runs, oldest first */
+  timeline: StatsTimelinePoint[];
+  /** Nodes that both passed and failed on the same commit */
+  flakyNodes: FlakyNode[];
+}
+
+export interface NodeTimingStats {
+  nodeId: string;
+  nodeName: string;
+  runCount: number;
+  failedCount: number;
+  successRate?: number;
+  p50Ms?: number;
+  p95Ms?: number;
+  maxMs?: number;
+  /** Median of the latest runs and of the runs before them */
+  recentP50Ms?: number;
+  previousP50Ms?: number;
+  /** Recent median is well above the previous one */
+  slowedDown: boolean;
+}
+
+export interface StatsTimelinePoint {
+  /** YYYY-MM-DD (UTC) */
+  date: string;
+  runCount: number;
+  successRate?: number;
+  p50DurationMs?: number;
+  slowestNodeId?: string;
+  slowestNodeP50Ms?: number;
+}
+
+export interface FlakyNode {
+  nodeId: string;
+  nodeName: string;
+  gitCommit: string;
+  passedCount: number;
+  failedCount: number;
+  /** Status changes between consecutive runs on the commit */
+  flipCount: number;
+  lastRunAt: string;
+}
+
+/** What a workflow run would do, resolved without running anything */
+export interface WorkflowPlan {
+  workflowId: string;
+  workflowName: string;
+  /** Executor the plan was resolved for */
+  executor: 'app' | 'mcp';
+  defaultCwd?: string;
+  /** Parameter values with defaults applied */
+  parameters?: Record<string, string>;
+  /** Nodes in the order they would start */
+  nodes: NodePlan[];
+}
+
+export interface NodePlan {
+  nodeId: string;
+  nodeName: string;
+  nodeType: NodeType;
+  dependsOn?: string[];
+  /** Processes a script node would spawn: one, or one per matrix cell */
+  invocations?: PlannedInvocation[];
+  /** Plan of the workflow a trigger-workflow node would start */
+  childWorkflow?: WorkflowPlan;
+  /** Condition branches, approval gate, HTTP request, retries, artifacts */
+  notes?: string[];
+  /** Why the node would be skipped */
+  skipped?: string;
+  /** Why the node would fail before running anything */
+  error?: string;
+}
+
+export interface PlannedInvocation {
+  /** Matrix cell label */
+  cell?: string;
+  cwd?: string;
+  /** Variables set for the process; secret values are masked */
+  env?: Record<string, string>;
+  /** Command line with placeholders substituted ({{steps.*}} stay until the step ran) */
+  command: string;
+  /** Program and arguments after shell selection and Volta/Corepack wrapping */
+  program: string;
+  args: string[];
+  viaShell: boolean;
+  /** Set when rm is intercepted: paths moved to the Trash instead */
+  trashPaths?: string[];
+}
+
+/** What saved a workflow revision */
+export type RevisionSource = 'ui' | 'mcp' | 'ai-assistant' | 'file' | 'unknown';
+
+/** Immutable copy of a workflow as it was saved */
+export interface WorkflowRevision {
+  workflowId: string;
+  /** 1-based, increasing with every saved change */
+  revision: number;
+  source: RevisionSource;
+  /** What changed it, e.g. the tool name or "Rolled back to revision 3" */
+  summary?: string;
+  createdAt: string;
+  workflow: Workflow;
+}
+
+export interface WorkflowFieldChange {
+  /** Field name, e.g. `name`, `schedule` or `config.command` */
+  field: string;
+  /** null when the field was not set */
+  before: unknown;
+  after: unknown;
+}
+
+export interface WorkflowNodeChange {
+  nodeId: string;
+  /** Name in the newer revision */
+  nodeName: string;
+  changes: WorkflowFieldChange[];
+}
+
+/** Differences between two revisions of a workflow */
+export interface WorkflowRevisionDiff {
+  workflowId: string;
+  fromRevision: number;
+  toRevision: number;
+  /** Changed workflow-level fields (everything except nodes) */
+  changes: WorkflowFieldChange[];
+  addedNodes: WorkflowNode[];
+  removedNodes: WorkflowNode[];
+  changedNodes: WorkflowNodeChange[];
+}
+
 export interface WorkflowStore {
   version: string;
   workflows: Workflow[];